@@ -6,6 +6,7 @@ in vec3       v_pos;
 in vec2       v_uv;
 in vec3  v_normal;
 in vec4       v_color;
+in vec4       v_tint;
 
 layout(location = 0) out vec4 gAlbedo;
 layout(location = 1) out vec4 gNormalMetalness;
@@ -20,6 +21,7 @@ uniform float     uMetallicFactor;
 uniform float     uRoughnessFactor;
 uniform float     uOpacity;
 uniform float     uAlphaCutoff;
+uniform float     uMosaicT;  // 0 = true colours, 1 = every fragment tinted
 
 void main() {
     vec4 albedo;
@@ -33,6 +35,16 @@ void main() {
         albedo = vec4(albedoConst, 1.0);
     }
 
+    // Mosaic tint: a fragment shows its own hue, shaded by the material's
+    // brightness, and lets go of it once uMosaicT drops past its release
+    // order, so fragments turn back to their true colour one by one.
+    if (uMosaicT > 0.0 && v_tint.a > 0.0) {
+        float release = v_tint.a * 0.6;
+        float w = smoothstep(release, release + 0.4, uMosaicT);
+        float luma = dot(albedo.rgb, vec3(0.299, 0.587, 0.114));
+        albedo.rgb = mix(albedo.rgb, v_tint.rgb * (0.45 + 0.55 * luma), w);
+    }
+
     albedo.a *= uOpacity;
     if (uAlphaCutoff > 0.0 && albedo.a < uAlphaCutoff) discard;
 
@@ -8,19 +8,24 @@ layout (location = 2) in vec3 norm;
 layout (location = 3) in vec4 boneIndices;  // stored as float; cast to int before use
 layout (location = 4) in vec4 boneWeights;
 layout (location = 5) in vec4 vertColor;
+layout (location = 6) in vec3 scrambleOffset;
+layout (location = 7) in vec4 fragmentTint;  // rgb = hue, a = release order (0 = never tinted)
 
 out vec3 v_pos;
 out vec2 v_uv;
 out vec3 v_normal;
 out vec4 v_color;
+out vec4 v_tint;
 
 uniform mat4 uVP;
 uniform mat4 uModel;
 uniform int uBoneCount;
 uniform mat4 uBones[64];
+uniform float uScrambleT;  // 0 = rest pose, 1 = fully scrambled
 
 void main() {
-    vec4 skinnedPos = vec4(pos, 1.0);
+    vec3 displaced = pos + uScrambleT * scrambleOffset;
+    vec4 skinnedPos = vec4(displaced, 1.0);
     vec3 skinnedNorm = norm;
 
     if (uBoneCount > 0) {
@@ -32,7 +37,7 @@ void main() {
             float weight = boneWeights[i];
             if (boneIdx >= 0 && weight > 0.0) {
                 mat4 bone = uBones[boneIdx];
-                blendedPos += weight * (bone * vec4(pos, 1.0));
+                blendedPos += weight * (bone * vec4(displaced, 1.0));
                 blendedNorm += weight * mat3(bone) * norm;
                 hasInfluence = true;
             }
@@ -50,4 +55,5 @@ void main() {
     v_uv     = uv;
     v_normal = transpose(inverse(mat3(uModel))) * skinnedNorm;
     v_color  = vertColor;
+    v_tint   = fragmentTint;
 }
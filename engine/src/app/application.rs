@@ -12,6 +12,9 @@ pub trait Application {
     fn on_init(&mut self, context: &mut SceneContext);
     fn on_update(&mut self, delta_time: f32, context: &mut SceneContext);
     fn on_event(&mut self, event: &Event, context: &mut SceneContext);
+    /// Called before the app is suspended or closed, while the scene is
+    /// still alive — the last chance to persist state.
+    fn on_suspend(&mut self, _context: &mut SceneContext) {}
 
     // ── Render bridge ─────────────────────────────────────────────────────
     /// Write all per-frame render state into `SceneContext` before
@@ -166,6 +166,12 @@ impl<A: Application + 'static> GameEngine<A> {
         Ok(())
     }
 
+    fn notify_suspend(&mut self) {
+        if let (Some(app), Some(ctx)) = (self.app.as_mut(), &self.scene_context) {
+            app.on_suspend(&mut ctx.borrow_mut());
+        }
+    }
+
     fn cleanup_gl(&mut self) {
         self.pipeline = None; // drops ImguiGlRenderer which owns imgui
         self.gl_context = None;
@@ -209,6 +215,7 @@ impl<A: Application + 'static> ApplicationHandler for GameEngine<A> {
 
     fn suspended(&mut self, _: &ActiveEventLoop) {
         log::info!("App suspended, cleaning up OpenGL...");
+        self.notify_suspend();
         self.cleanup_gl();
         self.gl_initialized = false;
     }
@@ -226,7 +233,10 @@ impl<A: Application + 'static> ApplicationHandler for GameEngine<A> {
         }
 
         match event {
-            WindowEvent::CloseRequested => event_loop.exit(),
+            WindowEvent::CloseRequested => {
+                self.notify_suspend();
+                event_loop.exit();
+            }
 
             WindowEvent::Resized(size) => {
                 if let (Some(surf), Some(ctx)) = (&self.gl_surface, &self.gl_context) {
@@ -385,6 +395,8 @@ impl<A: Application + 'static> ApplicationHandler for GameEngine<A> {
                                 Key::Character(ref s) if s.as_str() == "r" => EngineKey::R,
                                 Key::Character(ref s) if s.as_str() == "n" => EngineKey::N,
                                 Key::Character(ref s) if s.as_str() == "k" => EngineKey::K,
+                                Key::Character(ref s) if s.as_str() == "q" => EngineKey::Q,
+                                Key::Character(ref s) if s.as_str() == "e" => EngineKey::E,
                                 Key::Named(NamedKey::Space) => EngineKey::Space,
                                 _ => EngineKey::Other,
                             };
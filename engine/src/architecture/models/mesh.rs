@@ -12,6 +12,13 @@ use std::rc::Rc;
 pub struct Mesh {
     positions: Vec<f32>,
     pos_buffer: Option<Rc<DataBuffer>>,
+    /// Per-vertex scramble offsets; drawn as `pos + uScrambleT * offset`.
+    /// Empty until `upload_scramble_offsets` (the GPU buffer holds zeros).
+    scramble_offsets: Vec<f32>,
+    offset_buffer: Option<Rc<DataBuffer>>,
+    /// Per-vertex fragment tints (rgba), faded in by `uMosaicT`.  Zeros
+    /// (never tinted) until `upload_fragment_tints`.
+    tint_buffer: Option<Rc<DataBuffer>>,
     vert: Vec<f32>,
     attributes: Vec<Attribute>,
     vao: Vao,
@@ -134,6 +141,28 @@ impl Mesh {
             &[Attribute::of(0, 3, DataType::Float, false)],
         );
 
+        // location 6: scramble offsets (dynamic — uploaded once per scramble,
+        // then driven by the uScrambleT uniform).  Zeros until uploaded.
+        let mut offset_buf = DataBuffer::new(VboUsage::DynamicDraw);
+        offset_buf.allocate_float(flat_pos.len());
+        offset_buf.store_float(0, &vec![0.0f32; flat_pos.len()]);
+        let offset_buffer = Rc::new(offset_buf);
+        vao.load_data_buffer(
+            offset_buffer.clone(),
+            &[Attribute::of(6, 3, DataType::Float, false)],
+        );
+
+        // location 7: fragment tints (dynamic — uploaded once per scramble,
+        // then faded by the uMosaicT uniform).  Zeros until uploaded.
+        let mut tint_buf = DataBuffer::new(VboUsage::DynamicDraw);
+        tint_buf.allocate_float(vert_count * 4);
+        tint_buf.store_float(0, &vec![0.0f32; vert_count * 4]);
+        let tint_buffer = Rc::new(tint_buf);
+        vao.load_data_buffer(
+            tint_buffer.clone(),
+            &[Attribute::of(7, 4, DataType::Float, false)],
+        );
+
         // location 1: UVs
         if !flat_tex.is_empty() {
             let mut buf = DataBuffer::new(VboUsage::StaticDraw);
@@ -212,6 +241,9 @@ impl Mesh {
         Self {
             positions: flat_pos,
             pos_buffer: Some(pos_buffer),
+            scramble_offsets: Vec::new(),
+            offset_buffer: Some(offset_buffer),
+            tint_buffer: Some(tint_buffer),
             vert: Vec::new(),
             attributes: vec![],
             vao,
@@ -228,6 +260,9 @@ impl Mesh {
         Self {
             positions: vec![],
             pos_buffer: None,
+            scramble_offsets: vec![],
+            offset_buffer: None,
+            tint_buffer: None,
             vert: vec![],
             attributes: vec![],
             vao,
@@ -246,6 +281,36 @@ impl Mesh {
         }
     }
 
+    /// Replace the scramble offsets (same layout as the positions).  An
+    /// empty or mismatched array clears them.
+    pub fn upload_scramble_offsets(&mut self, offsets: Vec<f32>) {
+        self.scramble_offsets = if offsets.len() == self.positions.len() {
+            offsets
+        } else {
+            Vec::new()
+        };
+        if let Some(buf) = &self.offset_buffer {
+            if self.scramble_offsets.is_empty() {
+                buf.store_float_shared(0, &vec![0.0f32; self.positions.len()]);
+            } else {
+                buf.store_float_shared(0, &self.scramble_offsets);
+            }
+        }
+    }
+
+    /// Replace the fragment tints (rgba, 4 floats per vertex).  An empty or
+    /// mismatched array clears them.
+    pub fn upload_fragment_tints(&mut self, tints: Vec<f32>) {
+        let len = self.positions.len() / 3 * 4;
+        if let Some(buf) = &self.tint_buffer {
+            if tints.len() == len {
+                buf.store_float_shared(0, &tints);
+            } else {
+                buf.store_float_shared(0, &vec![0.0f32; len]);
+            }
+        }
+    }
+
     pub fn lowest(&self) -> f32 {
         let mut ret = f32::INFINITY;
         let stride = self
@@ -271,6 +336,10 @@ impl Mesh {
     pub fn positions(&self) -> &[f32] {
         &self.positions
     }
+    /// Scramble offsets, or empty if none were uploaded.
+    pub fn scramble_offsets(&self) -> &[f32] {
+        &self.scramble_offsets
+    }
     pub fn has_vertex_colors(&self) -> bool {
         self.has_vertex_colors
     }
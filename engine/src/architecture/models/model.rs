@@ -51,6 +51,18 @@ pub trait Model {
         &[]
     }
 
+    /// Lerp factor for the meshes' scramble offsets (`uScrambleT`):
+    /// 0 draws the rest positions.
+    fn scramble_t(&self) -> f32 {
+        0.0
+    }
+
+    /// How strongly the meshes' fragment tints show (`uMosaicT`): 0 draws
+    /// the true colours.
+    fn mosaic_t(&self) -> f32 {
+        0.0
+    }
+
     /// Advance per-frame animation state.
     fn update_animation(&mut self, _dt: f32) {}
 
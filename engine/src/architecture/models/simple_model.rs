@@ -25,6 +25,10 @@ pub struct SimpleModel {
     /// Cached AABB center.  Set whenever bone matrices or vertex positions
     /// change.  `visual_center()` returns this or falls back to `centroid`.
     cached_visual_center: Option<Vector3<f32>>,
+    /// Lerp factor for the meshes' scramble offsets (`uScrambleT`).
+    scramble_t: f32,
+    /// Strength of the meshes' fragment tints (`uMosaicT`).
+    mosaic_t: f32,
 }
 
 impl SimpleModel {
@@ -53,6 +57,8 @@ impl SimpleModel {
             player: AnimationPlayer::new(),
             bone_matrices: vec![Vec::new(); mesh_count],
             cached_visual_center: None,
+            scramble_t: 0.0,
+            mosaic_t: 0.0,
         })
     }
 
@@ -72,6 +78,8 @@ impl SimpleModel {
             player: AnimationPlayer::new(),
             bone_matrices: Vec::new(),
             cached_visual_center: None,
+            scramble_t: 0.0,
+            mosaic_t: 0.0,
         })
     }
 
@@ -95,6 +103,8 @@ impl SimpleModel {
             player: AnimationPlayer::new(),
             bone_matrices: Vec::new(),
             cached_visual_center: None,
+            scramble_t: 0.0,
+            mosaic_t: 0.0,
         })
     }
 
@@ -159,10 +169,12 @@ impl SimpleModel {
             let bone_indices = mesh.bone_indices();
             let bone_weights = mesh.bone_weights();
 
+            let offsets = mesh.scramble_offsets();
+
             let mut vertex_idx = 0usize;
             let mut i = 0usize;
             while i + 2 < pos.len() {
-                let local = Vector4::new(pos[i], pos[i + 1], pos[i + 2], 1.0);
+                let local = self.drawn_position(pos, offsets, i);
 
                 let skinned = if mesh.is_skinned() {
                     let mut blended = Vector4::new(0.0, 0.0, 0.0, 0.0);
@@ -211,6 +223,18 @@ impl SimpleModel {
         };
     }
 
+    /// Model-space vertex at float index `i`, as the vertex shader places it
+    /// before skinning: the rest position plus `scramble_t` of its offset.
+    fn drawn_position(&self, pos: &[f32], offsets: &[f32], i: usize) -> Vector4<f32> {
+        let mut p = Vector4::new(pos[i], pos[i + 1], pos[i + 2], 1.0);
+        if self.scramble_t != 0.0 && i + 2 < offsets.len() {
+            p.x += offsets[i] * self.scramble_t;
+            p.y += offsets[i + 1] * self.scramble_t;
+            p.z += offsets[i + 2] * self.scramble_t;
+        }
+        p
+    }
+
     pub fn visual_center(&self) -> Option<Vector3<f32>> {
         self.cached_visual_center.or(self.centroid)
     }
@@ -225,6 +249,45 @@ impl SimpleModel {
         }
     }
 
+    /// Upload per-vertex scramble offsets for a mesh (same layout as its
+    /// positions).  The vertex shader draws `pos + scramble_t * offset`, so
+    /// moving along the offsets afterwards only costs `set_scramble_t`.
+    pub fn upload_scramble_offsets(&mut self, mesh_idx: usize, offsets: Vec<f32>) {
+        if let Some(mesh) = self.meshes.get_mut(mesh_idx) {
+            mesh.upload_scramble_offsets(offsets);
+        }
+    }
+
+    /// Set the lerp factor along the scramble offsets: 0 = rest positions,
+    /// 1 = fully displaced.  Costs one uniform per draw.  Bounds queries
+    /// (`get_lowest`, the visual center when it is refreshed) use the
+    /// displaced positions, exactly as if they had been uploaded.
+    pub fn set_scramble_t(&mut self, t: f32) {
+        self.scramble_t = t;
+    }
+
+    pub fn scramble_t(&self) -> f32 {
+        self.scramble_t
+    }
+
+    /// Upload per-vertex fragment tints for a mesh (rgba, 4 floats per
+    /// vertex).  They show in proportion to `set_mosaic_t`.
+    pub fn upload_fragment_tints(&mut self, mesh_idx: usize, tints: Vec<f32>) {
+        if let Some(mesh) = self.meshes.get_mut(mesh_idx) {
+            mesh.upload_fragment_tints(tints);
+        }
+    }
+
+    /// Set how strongly the fragment tints show: 0 = true colours, 1 =
+    /// every fragment tinted.  Costs one uniform per draw.
+    pub fn set_mosaic_t(&mut self, t: f32) {
+        self.mosaic_t = t;
+    }
+
+    pub fn mosaic_t(&self) -> f32 {
+        self.mosaic_t
+    }
+
     pub fn play_animation(&mut self, index: usize) {
         if index >= self.animations.len() {
             return;
@@ -282,10 +345,12 @@ impl Model for SimpleModel {
             let bone_weights = mesh.bone_weights();
             let is_skinned = mesh.is_skinned() && !bones.is_empty();
 
+            let offsets = mesh.scramble_offsets();
+
             let mut vertex_idx = 0usize;
             let mut i = 0;
             while i + 2 < pos.len() {
-                let local = Vector4::new(pos[i], pos[i + 1], pos[i + 2], 1.0);
+                let local = self.drawn_position(pos, offsets, i);
                 let skinned = if is_skinned {
                     let mut blended = Vector4::new(0.0, 0.0, 0.0, 0.0);
                     let mut has_influence = false;
@@ -340,6 +405,14 @@ impl Model for SimpleModel {
         self.bone_matrices_for_mesh(mesh_idx)
     }
 
+    fn scramble_t(&self) -> f32 {
+        self.scramble_t
+    }
+
+    fn mosaic_t(&self) -> f32 {
+        self.mosaic_t
+    }
+
     fn update_animation(&mut self, dt: f32) {
         if let Some(ref mut skel) = self.skeleton {
             for mesh_idx in 0..self.meshes.len() {
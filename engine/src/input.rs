@@ -6,6 +6,8 @@ pub enum Key {
     N,
     K,
     L,
+    Q,
+    E,
     Space,
     Other,
 }
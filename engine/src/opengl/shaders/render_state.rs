@@ -17,6 +17,10 @@ pub struct ModelRenderData {
     pub is_skinned: bool,
     pub bone_matrices: Vec<Matrix4<f32>>,
     pub bone_count: i32,
+    /// Lerp factor for the mesh's scramble offsets (`Model::scramble_t`).
+    pub scramble_t: f32,
+    /// Strength of the mesh's fragment tints (`Model::mosaic_t`).
+    pub mosaic_t: f32,
 }
 
 pub struct RenderState<'a, T: Processable> {
@@ -147,4 +151,10 @@ impl<'a, T: Processable> RenderState<'a, T> {
     pub fn bone_count(&self) -> i32 {
         self.model_data.map(|d| d.bone_count).unwrap_or(0)
     }
+    pub fn scramble_t(&self) -> f32 {
+        self.model_data.map(|d| d.scramble_t).unwrap_or(0.0)
+    }
+    pub fn mosaic_t(&self) -> f32 {
+        self.model_data.map(|d| d.mosaic_t).unwrap_or(0.0)
+    }
 }
@@ -2,7 +2,10 @@ use crate::{
     architecture::scene::node::transform::Transform,
     rendering::instances::camera::camera_controller::CameraController,
 };
-use cgmath::{InnerSpace, Vector3};
+use cgmath::{InnerSpace, Quaternion, Rad, Rotation3, Vector3};
+
+/// Roll applied per Q / E key press.
+pub const TWIST_STEP_RAD: f32 = 5.0 * std::f32::consts::PI / 180.0;
 
 pub struct OrbitController {
     pub target: Vector3<f32>,
@@ -17,6 +20,14 @@ pub struct OrbitController {
     // store mouse deltas until `control()` is called
     delta_x: f32,
     delta_y: f32,
+
+    /// Camera roll about the view axis (radians; positive turns the scene
+    /// clockwise on screen).  Kept across frames; `look_at` alone would
+    /// reset it.
+    roll: f32,
+    delta_roll: f32,
+    /// Active touches `(id, x, y)` — two at once form a twist gesture.
+    touches: Vec<(u64, f32, f32)>,
 }
 
 impl OrbitController {
@@ -31,6 +42,9 @@ impl OrbitController {
             last_y: 0.0,
             delta_x: 0.0,
             delta_y: 0.0,
+            roll: 0.0,
+            delta_roll: 0.0,
+            touches: Vec::new(),
         }
     }
 
@@ -68,29 +82,81 @@ impl OrbitController {
         self.target = target;
         transform.position = self.target + offset;
         self.distance = offset.magnitude().max(0.001);
-        transform.look_at(self.target, Vector3::unit_y());
+        self.look_at_target(transform);
     }
 
     /// Instantly place the camera so it looks along `dir` (dir = FROM camera TOWARD target).
     /// Clears pending deltas so the snap sticks on the next control() call.
+    /// The current roll is kept.
     pub fn snap_to_direction(&mut self, transform: &mut Transform, dir: Vector3<f32>) {
         let d = dir.normalize();
         transform.position = self.target - d * self.distance;
-        transform.look_at(self.target, Vector3::unit_y());
+        self.look_at_target(transform);
         self.delta_x = 0.0;
         self.delta_y = 0.0;
     }
 
+    /// Current camera roll in radians.
+    pub fn roll(&self) -> f32 {
+        self.roll
+    }
+
+    /// Set the camera roll; takes effect on the next `control()` call.
+    pub fn set_roll(&mut self, roll: f32) {
+        self.roll = roll;
+        self.delta_roll = 0.0;
+    }
+
+    /// Look at the target with world up, then roll about the view axis.
+    fn look_at_target(&self, transform: &mut Transform) {
+        transform.look_at(self.target, Vector3::unit_y());
+        transform.rotation = transform.rotation * Quaternion::from_angle_z(Rad(self.roll));
+    }
+
     pub fn handle_event(&mut self, event: &crate::input::Event, width: f32, height: f32) {
         match event {
-            crate::input::Event::MouseDown { x, y, .. }
-            | crate::input::Event::TouchDown { x, y, .. } => {
+            crate::input::Event::TouchDown { id, x, y, .. } => {
+                self.touches.retain(|t| t.0 != *id);
+                self.touches.push((*id, *x, *y));
+                // A second finger turns the drag into a twist.
+                self.dragging = self.touches.len() == 1;
+                self.last_x = *x;
+                self.last_y = *y;
+            }
+
+            crate::input::Event::TouchUp { id } => {
+                self.touches.retain(|t| t.0 != *id);
+                self.dragging = false;
+            }
+
+            crate::input::Event::TouchMove { id, x, y, .. } if self.touches.len() >= 2 => {
+                let before = self.twist_angle();
+                if let Some(t) = self.touches.iter_mut().find(|t| t.0 == *id) {
+                    t.1 = *x;
+                    t.2 = *y;
+                }
+                let turn = self.twist_angle() - before;
+                // Wrap so crossing ±π doesn't spin the camera a full turn.
+                self.delta_roll += (turn + std::f32::consts::PI)
+                    .rem_euclid(2.0 * std::f32::consts::PI)
+                    - std::f32::consts::PI;
+            }
+
+            crate::input::Event::KeyDown {
+                key: crate::input::Key::Q,
+            } => self.delta_roll -= TWIST_STEP_RAD,
+
+            crate::input::Event::KeyDown {
+                key: crate::input::Key::E,
+            } => self.delta_roll += TWIST_STEP_RAD,
+
+            crate::input::Event::MouseDown { x, y, .. } => {
                 self.dragging = true;
                 self.last_x = *x;
                 self.last_y = *y;
             }
 
-            crate::input::Event::MouseUp { .. } | crate::input::Event::TouchUp { .. } => {
+            crate::input::Event::MouseUp { .. } => {
                 self.dragging = false;
             }
 
@@ -113,6 +179,15 @@ impl OrbitController {
         }
     }
 
+    /// Screen angle of the line between the first two touches (y down, so
+    /// a clockwise twist increases it).
+    fn twist_angle(&self) -> f32 {
+        match self.touches.as_slice() {
+            [a, b, ..] => (b.2 - a.2).atan2(b.1 - a.1),
+            _ => 0.0,
+        }
+    }
+
     fn apply_rotation(&mut self, transform: &mut Transform) {
         if let Some(target_provider) = &self.target_provider {
             let target = target_provider();
@@ -152,12 +227,14 @@ impl OrbitController {
         // Update camera transform
         transform.position = self.target + offset;
 
-        // Look at target using world up
-        transform.look_at(self.target, Vector3::unit_y());
+        // Look at target using world up, then apply the roll
+        self.roll += self.delta_roll;
+        self.look_at_target(transform);
 
         // Reset deltas
         self.delta_x = 0.0;
         self.delta_y = 0.0;
+        self.delta_roll = 0.0;
     }
 }
 
@@ -147,6 +147,18 @@ impl<T: SceneObject + 'static> EntityRenderer<T> {
             extractor: Box::new(|state: &RenderState<T>| state.bone_matrices().to_vec()),
         }));
 
+        // uScrambleT: lerp along the per-vertex scramble offsets (location 6).
+        shader_program.add_per_instance_uniform(Box::new(UniformAdapter {
+            uniform: UniformFloat::new("uScrambleT"),
+            extractor: Box::new(|state: &RenderState<T>| state.scramble_t()),
+        }));
+
+        // uMosaicT: fade in the per-vertex fragment tints (location 7).
+        shader_program.add_per_instance_uniform(Box::new(UniformAdapter {
+            uniform: UniformFloat::new("uMosaicT"),
+            extractor: Box::new(|state: &RenderState<T>| state.mosaic_t()),
+        }));
+
         log::info!("EntityRenderer initialized successfully");
         Ok(Self { shader_program })
     }
@@ -228,6 +240,8 @@ impl<T: SceneObject + 'static> IRenderer for EntityRenderer<T> {
                             Vec::new()
                         };
                         let bone_count = bone_matrices.len() as i32;
+                        let scramble_t = model_ref.scramble_t();
+                        let mosaic_t = model_ref.mosaic_t();
                         draw_calls.push(DrawCall {
                             node: Rc::clone(node),
                             mesh_idx: i,
@@ -237,6 +251,8 @@ impl<T: SceneObject + 'static> IRenderer for EntityRenderer<T> {
                                 is_skinned,
                                 bone_matrices,
                                 bone_count,
+                                scramble_t,
+                                mosaic_t,
                             },
                         });
                     }
@@ -9,6 +9,8 @@ fn key_enum_variants_exist() {
         Key::N,
         Key::K,
         Key::L,
+        Key::Q,
+        Key::E,
         Key::Space,
         Key::Other,
     ];
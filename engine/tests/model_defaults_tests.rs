@@ -49,4 +49,5 @@ fn model_default_mesh_helpers_are_stable() {
     assert!(!model.has_vertex_colors(0));
     assert!(model.centroid().is_none());
     assert!(model.mesh_transform(0).is_none());
+    assert_eq!(model.scramble_t(), 0.0);
 }
@@ -1,7 +1,9 @@
 use cgmath::{InnerSpace, Vector3};
 use formosaic_engine::input::{Event, Key};
 use formosaic_engine::rendering::instances::camera::camera_controller::CameraController;
-use formosaic_engine::rendering::instances::camera::orbit_controller::OrbitController;
+use formosaic_engine::rendering::instances::camera::orbit_controller::{
+    OrbitController, TWIST_STEP_RAD,
+};
 
 fn assert_vec3_approx(a: Vector3<f32>, b: Vector3<f32>, eps: f32) {
     let diff = a - b;
@@ -349,3 +351,100 @@ fn orbit_controller_retarget_preserves_camera_offset() {
     assert_vec3_approx(transform.position, Vector3::new(4.0, 5.0, 11.0), 1e-6);
     assert!((ctrl.distance - 5.0).abs() < 1e-6);
 }
+
+#[test]
+fn orbit_controller_twist_keys_roll_the_camera() {
+    let mut ctrl = OrbitController::new(Vector3::new(0.0, 0.0, 0.0), 10.0);
+    let mut transform = formosaic_engine::architecture::scene::node::transform::Transform::new();
+    transform.position = Vector3::new(0.0, 0.0, 10.0);
+    ctrl.set_initial_position(transform.position);
+
+    ctrl.handle_event(&Event::KeyDown { key: Key::E }, 800.0, 600.0);
+    CameraController::control(&mut ctrl, &mut transform);
+    assert!((ctrl.roll() - TWIST_STEP_RAD).abs() < 1e-6);
+    // Positive roll turns the scene clockwise: the camera's up tilts left.
+    let up = transform.up();
+    assert!(up.x < 0.0, "expected up to tilt left, got {up:?}");
+    assert!((transform.forward().dot(-Vector3::unit_z()) - 1.0).abs() < 1e-5);
+
+    ctrl.handle_event(&Event::KeyDown { key: Key::Q }, 800.0, 600.0);
+    ctrl.handle_event(&Event::KeyDown { key: Key::Q }, 800.0, 600.0);
+    CameraController::control(&mut ctrl, &mut transform);
+    assert!((ctrl.roll() + TWIST_STEP_RAD).abs() < 1e-6);
+}
+
+#[test]
+fn orbit_controller_roll_survives_orbiting() {
+    let mut ctrl = OrbitController::new(Vector3::new(0.0, 0.0, 0.0), 10.0);
+    let mut transform = formosaic_engine::architecture::scene::node::transform::Transform::new();
+    transform.position = Vector3::new(0.0, 0.0, 10.0);
+    ctrl.set_initial_position(transform.position);
+    ctrl.set_roll(0.5);
+
+    ctrl.handle_event(
+        &Event::MouseDown {
+            x: 400.0,
+            y: 300.0,
+            width: 800.0,
+            height: 600.0,
+        },
+        800.0,
+        600.0,
+    );
+    ctrl.handle_event(
+        &Event::MouseMove {
+            x: 480.0,
+            y: 330.0,
+            width: 800.0,
+            height: 600.0,
+        },
+        800.0,
+        600.0,
+    );
+    CameraController::control(&mut ctrl, &mut transform);
+
+    let fwd = transform.forward();
+    let to_target = (ctrl.target - transform.position).normalize();
+    assert!((fwd.dot(to_target) - 1.0).abs() < 1e-4);
+    // The up vector is the upright one turned by the roll.
+    let upright = (Vector3::unit_y() - fwd * fwd.y).normalize();
+    assert!((transform.up().dot(upright) - 0.5f32.cos()).abs() < 1e-4);
+}
+
+#[test]
+fn orbit_controller_two_finger_twist_rolls_without_orbiting() {
+    let mut ctrl = OrbitController::new(Vector3::new(0.0, 0.0, 0.0), 10.0);
+    let mut transform = formosaic_engine::architecture::scene::node::transform::Transform::new();
+    transform.position = Vector3::new(0.0, 0.0, 10.0);
+    ctrl.set_initial_position(transform.position);
+
+    let touch = |id, x, y| Event::TouchDown {
+        id,
+        x,
+        y,
+        width: 800.0,
+        height: 600.0,
+    };
+    ctrl.handle_event(&touch(0, 300.0, 300.0), 800.0, 600.0);
+    ctrl.handle_event(&touch(1, 500.0, 300.0), 800.0, 600.0);
+    // Turn the second finger 30° clockwise around the first.
+    let a = 30f32.to_radians();
+    ctrl.handle_event(
+        &Event::TouchMove {
+            id: 1,
+            x: 300.0 + 200.0 * a.cos(),
+            y: 300.0 + 200.0 * a.sin(),
+            width: 800.0,
+            height: 600.0,
+        },
+        800.0,
+        600.0,
+    );
+    CameraController::control(&mut ctrl, &mut transform);
+
+    assert!((ctrl.roll() - a).abs() < 1e-4, "roll {}", ctrl.roll());
+    assert_vec3_approx(transform.position, Vector3::new(0.0, 0.0, 10.0), 1e-4);
+
+    ctrl.handle_event(&Event::TouchUp { id: 1 }, 800.0, 600.0);
+    ctrl.handle_event(&Event::TouchUp { id: 0 }, 800.0, 600.0);
+}
@@ -13,6 +13,8 @@ fn dummy_model_data() -> ModelRenderData {
         is_skinned: false,
         bone_matrices: Vec::new(),
         bone_count: 0,
+        scramble_t: 0.0,
+        mosaic_t: 0.0,
     }
 }
 
@@ -238,3 +240,37 @@ fn render_state_mesh_returns_none_by_default() {
 
     assert!(state.mesh().is_none());
 }
+
+#[test]
+fn render_state_reads_scramble_t_from_model_data() {
+    let renderer = DummyRenderer;
+    let camera = Camera::new();
+    let instance = DummyInstance::new(DummyModel::new());
+
+    let md = ModelRenderData {
+        scramble_t: 0.25,
+        ..dummy_model_data()
+    };
+    let state = RenderState::new_preresolved(&renderer, &instance, &camera, 0, None, false, &md);
+    assert_eq!(state.scramble_t(), 0.25);
+
+    let screenspace = RenderState::<DummyInstance>::new_screenspace(&renderer);
+    assert_eq!(screenspace.scramble_t(), 0.0);
+}
+
+#[test]
+fn render_state_reads_mosaic_t_from_model_data() {
+    let renderer = DummyRenderer;
+    let camera = Camera::new();
+    let instance = DummyInstance::new(DummyModel::new());
+
+    let md = ModelRenderData {
+        mosaic_t: 0.6,
+        ..dummy_model_data()
+    };
+    let state = RenderState::new_preresolved(&renderer, &instance, &camera, 0, None, false, &md);
+    assert_eq!(state.mosaic_t(), 0.6);
+
+    let screenspace = RenderState::<DummyInstance>::new_screenspace(&renderer);
+    assert_eq!(screenspace.mosaic_t(), 0.0);
+}
@@ -75,6 +75,8 @@ fn render_state_flags_are_consistent() {
         is_skinned: false,
         bone_matrices: Vec::new(),
         bone_count: 0,
+        scramble_t: 0.0,
+        mosaic_t: 0.0,
     };
     let state = RenderState::new_preresolved(
         &renderer,
@@ -5,6 +5,8 @@ in  vec2 v_uv;
 out vec4 fragColor;
 
 uniform vec3  uWarmthColor;
+uniform vec3  uColdColor;   // ends of the player's warmth palette
+uniform vec3  uHotColor;
 uniform float uWarmth;      // 0=cold, 1=hot
 uniform float uHintTier;    // 0/1/2/3
 uniform float uTime;
@@ -38,8 +40,8 @@ void main() {
     float dist_from_center = length(uv);
     float vignette_strength = 0.12 * uWarmth;
 
-    vec3 warm_tint = vec3(0.8, 0.15, 0.1);
-    vec3 cold_tint = vec3(0.1, 0.2, 0.8);
+    vec3 warm_tint = uHotColor;
+    vec3 cold_tint = uColdColor;
     vec3 tint = mix(cold_tint, warm_tint, uWarmth);
 
     // Edge vignette: glow at screen edge, colour depends on warmth
@@ -60,8 +62,8 @@ void main() {
     float fill_r   = widget_r * 0.72;
     float fill_d   = sdCircle(wp, fill_r * uWarmth);
     float fill_mask = aa(fill_d) * aa(-sdCircle(wp, fill_r));
-    // Colour the fill from cold blue to hot red
-    vec3 fill_col = mix(vec3(0.12, 0.25, 0.9), vec3(0.9, 0.15, 0.1), uWarmth);
+    // Colour the fill from cold to hot
+    vec3 fill_col = mix(cold_tint, warm_tint, uWarmth);
 
     // Pulsing outer glow when very warm
     float pulse = 0.0;
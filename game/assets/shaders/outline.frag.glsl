@@ -0,0 +1,13 @@
+#version 300 es
+precision mediump float;
+
+out vec4 fragColor;
+
+uniform vec3  uColor;
+uniform float uTime;
+
+void main() {
+    // Slow breathing so the outline reads as a hint, not as geometry.
+    float alpha = 0.55 + 0.2 * sin(uTime * 2.4);
+    fragColor = vec4(uColor, alpha);
+}
@@ -0,0 +1,9 @@
+#version 300 es
+precision mediump float;
+
+// Outline segment end points, already projected to NDC on the CPU.
+layout(location = 0) in vec2 pos;
+
+void main() {
+    gl_Position = vec4(pos, 0.0, 1.0);
+}
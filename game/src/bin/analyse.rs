@@ -0,0 +1,340 @@
+//! analyse — headless puzzle analysis for a directory of models.
+//!
+//! Parses every .glb / .gltf / .fbx / .obj under a directory, runs the same
+//! puzzle params and entropy search the game runs on load, and prints one
+//! row per model.  No window or GL context is created.
+//!
+//!   cargo analyse <dir> [--json] [--stages N] [--strategy S] [--seed N]
+//!
+//! * `--json`      one JSON object per line instead of CSV
+//! * `--stages`    stage count, as the menu setting (default 1)
+//! * `--strategy`  `triangle`, `mesh`, `cluster` or `cluster:N`
+//!                 (default `triangle`)
+//! * `--seed`      raw seed; each model's seed also mixes in its path, so
+//!                 rows are reproducible (default 0)
+//!
+//! Exits with 1 if any model failed to parse or made a degenerate puzzle,
+//! 2 on bad arguments.
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process,
+};
+
+use cgmath::{Vector3, Vector4};
+use formosaic::formosaic::analyse_load_data;
+use formosaic::puzzle::entropy::difficulty_label;
+use formosaic::puzzle::puzzle_params::ScrambleStrategy;
+use formosaic::puzzle::scrambler::StageSplit;
+use formosaic::puzzle::search::SearchHandle;
+use formosaic::puzzle::seed::PuzzleSeed;
+use formosaic_engine::architecture::models::model_loader::{ModelLoadData, ModelLoader};
+
+const MODEL_EXTENSIONS: [&str; 4] = ["glb", "gltf", "fbx", "obj"];
+
+/// Below this peak alignment score the solved view never clearly snaps.
+const MIN_PEAK_SCORE: f32 = 0.5;
+
+const CSV_HEADER: &str = "file,triangles,min_x,min_y,min_z,max_x,max_y,max_z,\
+axis_x,axis_y,axis_z,entropy_bits,isolation_deg,peak_score,difficulty,label,error";
+
+struct Options {
+    dir: PathBuf,
+    json: bool,
+    stages: usize,
+    strategy: ScrambleStrategy,
+    seed: u64,
+}
+
+fn main() {
+    env_logger::Builder::from_env(env_logger::Env::default().default_filter_or("warn")).init();
+    let options = parse_args().unwrap_or_else(|e| {
+        eprintln!("{e}");
+        eprintln!(
+            "Usage: analyse <dir> [--json] [--stages N] [--strategy triangle|mesh|cluster[:N]] [--seed N]"
+        );
+        process::exit(2);
+    });
+
+    let mut files = Vec::new();
+    collect_models(&options.dir, &mut files);
+    files.sort();
+    if files.is_empty() {
+        eprintln!("No models found under {}", options.dir.display());
+        process::exit(2);
+    }
+
+    if !options.json {
+        println!("{CSV_HEADER}");
+    }
+    let mut failures = 0;
+    for path in &files {
+        let name = path
+            .strip_prefix(&options.dir)
+            .unwrap_or(path)
+            .to_string_lossy()
+            .replace('\\', "/");
+        let row = analyse_file(path, &name, &options);
+        if row.error.is_some() {
+            failures += 1;
+        }
+        println!(
+            "{}",
+            if options.json {
+                row.to_json()
+            } else {
+                row.to_csv()
+            }
+        );
+    }
+
+    eprintln!("{} models, {failures} failed", files.len());
+    if failures > 0 {
+        process::exit(1);
+    }
+}
+
+fn parse_args() -> Result<Options, String> {
+    let mut args = env::args().skip(1);
+    let mut options = Options {
+        dir: PathBuf::new(),
+        json: false,
+        stages: 1,
+        strategy: ScrambleStrategy::PerTriangle,
+        seed: 0,
+    };
+    let mut dir = None;
+    while let Some(arg) = args.next() {
+        let mut value = |flag: &str| args.next().ok_or(format!("{flag} needs a value"));
+        match arg.as_str() {
+            "--json" => options.json = true,
+            "--stages" => {
+                options.stages = value("--stages")?
+                    .parse()
+                    .map_err(|_| "--stages must be a number".to_string())?;
+            }
+            "--strategy" => {
+                let s = value("--strategy")?;
+                options.strategy =
+                    ScrambleStrategy::from_meta_str(&s).ok_or(format!("Unknown strategy '{s}'"))?;
+            }
+            "--seed" => {
+                options.seed = value("--seed")?
+                    .parse()
+                    .map_err(|_| "--seed must be a number".to_string())?;
+            }
+            flag if flag.starts_with("--") => return Err(format!("Unknown option {flag}")),
+            _ if dir.is_none() => dir = Some(PathBuf::from(arg)),
+            _ => return Err(format!("Unexpected argument {arg}")),
+        }
+    }
+    options.dir = dir.ok_or("Missing model directory")?;
+    if !options.dir.is_dir() {
+        return Err(format!("{} is not a directory", options.dir.display()));
+    }
+    Ok(options)
+}
+
+fn collect_models(dir: &Path, out: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            collect_models(&path, out);
+        } else if path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| MODEL_EXTENSIONS.contains(&e.to_ascii_lowercase().as_str()))
+        {
+            out.push(path);
+        }
+    }
+}
+
+// ─── Analysis ─────────────────────────────────────────────────────────────────
+
+#[derive(Default)]
+struct Row {
+    file: String,
+    triangles: usize,
+    bounds: Option<(Vector3<f32>, Vector3<f32>)>,
+    /// First stage's axis; `None` until the search has run.
+    axis: Option<Vector3<f32>>,
+    entropy_bits: f32,
+    isolation_deg: f32,
+    peak_score: f32,
+    difficulty: f32,
+    error: Option<String>,
+}
+
+fn analyse_file(path: &Path, name: &str, options: &Options) -> Row {
+    let mut row = Row {
+        file: name.to_string(),
+        ..Row::default()
+    };
+    let data = match fs::read(path)
+        .map_err(|e| format!("read failed: {e}"))
+        .and_then(|bytes| {
+            ModelLoader::prepare_from_bytes_with_path(&path.to_string_lossy(), &bytes)
+        }) {
+        Ok(data) => data,
+        Err(e) => {
+            row.error = Some(e);
+            return row;
+        }
+    };
+
+    row.triangles = data.meshes.iter().map(|m| m.indices.len() / 3).sum();
+    row.bounds = world_bounds(&data);
+    let extent = row.bounds.map_or(0.0, |(lo, hi)| {
+        (hi.x - lo.x).max(hi.y - lo.y).max(hi.z - lo.z)
+    });
+    if row.triangles == 0 || extent <= 1e-6 {
+        row.error = Some("degenerate: no geometry".to_string());
+        return row;
+    }
+
+    let seed = PuzzleSeed::for_level(name, options.seed);
+    let Some((_, search)) = analyse_load_data(
+        &data,
+        &StageSplit::Bands(options.stages.max(1)),
+        options.strategy,
+        false,
+        0.0,
+        seed,
+        &SearchHandle::new(),
+    ) else {
+        row.error = Some("search cancelled".to_string());
+        return row;
+    };
+    let report = search.combined;
+    row.axis = search.axes().first().copied();
+    row.entropy_bits = report.entropy_bits;
+    row.isolation_deg = report.solution_isolation_rad.to_degrees();
+    row.peak_score = report.peak_score;
+    row.difficulty = report.difficulty;
+
+    if ![report.entropy_bits, report.peak_score, report.difficulty]
+        .iter()
+        .all(|v| v.is_finite())
+    {
+        row.error = Some("degenerate: non-finite report".to_string());
+    } else if report.peak_score < MIN_PEAK_SCORE {
+        row.error = Some(format!(
+            "degenerate: peak score {:.2} < {MIN_PEAK_SCORE}",
+            report.peak_score
+        ));
+    }
+    row
+}
+
+/// World-space bounding box of every mesh under its node transform.
+fn world_bounds(data: &ModelLoadData) -> Option<(Vector3<f32>, Vector3<f32>)> {
+    let mut lo = Vector3::from([f32::INFINITY; 3]);
+    let mut hi = Vector3::from([f32::NEG_INFINITY; 3]);
+    for (i, mesh) in data.meshes.iter().enumerate() {
+        let xform = data
+            .mesh_transforms
+            .get(i)
+            .copied()
+            .unwrap_or_else(|| cgmath::Matrix4::from_scale(1.0));
+        for p in mesh.positions.chunks_exact(3) {
+            let w = xform * Vector4::new(p[0], p[1], p[2], 1.0);
+            lo = Vector3::new(lo.x.min(w.x), lo.y.min(w.y), lo.z.min(w.z));
+            hi = Vector3::new(hi.x.max(w.x), hi.y.max(w.y), hi.z.max(w.z));
+        }
+    }
+    (lo.x <= hi.x).then_some((lo, hi))
+}
+
+// ─── Output ───────────────────────────────────────────────────────────────────
+
+impl Row {
+    fn to_csv(&self) -> String {
+        let vec3 = |v: Option<Vector3<f32>>| match v {
+            Some(v) => format!("{:.4},{:.4},{:.4}", v.x, v.y, v.z),
+            None => ",,".to_string(),
+        };
+        let analysed = self.axis.is_some();
+        let num = |v: f32, prec: usize| {
+            if analysed {
+                format!("{v:.prec$}")
+            } else {
+                String::new()
+            }
+        };
+        format!(
+            "{},{},{},{},{},{},{},{},{},{},{}",
+            csv_field(&self.file),
+            self.triangles,
+            vec3(self.bounds.map(|b| b.0)),
+            vec3(self.bounds.map(|b| b.1)),
+            vec3(self.axis),
+            num(self.entropy_bits, 3),
+            num(self.isolation_deg, 1),
+            num(self.peak_score, 3),
+            num(self.difficulty, 3),
+            if analysed {
+                difficulty_label(self.difficulty)
+            } else {
+                ""
+            },
+            csv_field(self.error.as_deref().unwrap_or("")),
+        )
+    }
+
+    fn to_json(&self) -> String {
+        let vec3 = |v: Option<Vector3<f32>>| match v {
+            Some(v) => format!("[{:.4},{:.4},{:.4}]", v.x, v.y, v.z),
+            None => "null".to_string(),
+        };
+        let mut out = format!(
+            r#"{{"file":"{}","triangles":{},"bounds_min":{},"bounds_max":{}"#,
+            json_escape(&self.file),
+            self.triangles,
+            vec3(self.bounds.map(|b| b.0)),
+            vec3(self.bounds.map(|b| b.1)),
+        );
+        if self.axis.is_some() {
+            out += &format!(
+                r#","axis":{},"entropy_bits":{:.3},"isolation_deg":{:.1},"peak_score":{:.3},"difficulty":{:.3},"label":"{}""#,
+                vec3(self.axis),
+                self.entropy_bits,
+                self.isolation_deg,
+                self.peak_score,
+                self.difficulty,
+                difficulty_label(self.difficulty),
+            );
+        }
+        match &self.error {
+            Some(e) => out += &format!(r#","error":"{}"}}"#, json_escape(e)),
+            None => out += r#","error":null}"#,
+        }
+        out
+    }
+}
+
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+fn json_escape(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            c if c.is_control() => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out
+}
@@ -13,12 +13,79 @@
 //! pick the one whose viewpoint-entropy is lowest — meaning there is exactly
 //! one "obvious" viewing direction and all others look clearly wrong.
 //!
-//! # Hints (3-tier progressive)
+//! In anamorphic mode (toggled from the menu) the fragments slide along the
+//! rays from an eye point instead, so the solution is a camera position on
+//! the orbit and the solved view has no perspective parallax seams.
+//!
+//! With the rotation scramble (toggled from the menu) the fragments are also
+//! turned about the view axis, and the camera roll (Q / E or a two-finger
+//! twist) has to match as well.
+//!
+//! With the mosaic tint (toggled from the menu) every fragment is drawn in
+//! its own hue, and fragments turn back to their true colours one by one as
+//! the camera nears the solution (`puzzle::mosaic`).
+//!
+//! A skinned model is scrambled in an animation pose, held while it is
+//! played (`puzzle::pose`).  With motion on (toggled from the menu) its
+//! clip plays during the puzzle instead and settles into the pose as the
+//! camera nears the solution.
+//!
+//! In multi-stage mode (cycled from the menu) the model is split into bands,
+//! each scrambled along its own axis; every view snaps one band into place
+//! and the last one solves the puzzle.
+//!
+//! A composite scene (`level::scene`) places two or three models side by
+//! side and splits the stages by model instead: each model has its own
+//! axis, the HUD marks the ones already found, and finding the last one
+//! completes the scene.
+//!
+//! A stage snaps once its fragments line up on screen to within
+//! `reprojection::SNAP_TOLERANCE_PX` (RMS), measured from the live camera,
+//! so the tolerance follows the model, its fragment count and the viewport.
+//!
+//! Every solve and every give-up (leaving a puzzle for the menu) is logged
+//! with the puzzle's entropy features, and the difficulty model is refit
+//! from that log (`puzzle::calibration`); shown and stored difficulties use
+//! the calibrated model once there is one.
+//!
+//! Marathon chains a fixed number of random saved levels on one cumulative
+//! timer; time attack plays as many as fit in a time budget
+//! (`level::session`).  While one session level is played the next is
+//! already loading in the background, and finished sessions go to a local
+//! leaderboard.
+//!
+//! Each solve is scored from its time, the hint tiers reached and the
+//! level's difficulty, and rated 1–3 stars (`puzzle::scoring`); the level
+//! keeps its best of each.
+//!
+//! Every puzzle's camera path is recorded (`level::replay`), and a saved
+//! level keeps the recording of its best solve.  From the level-complete
+//! screen the best solve can be watched, with the recording driving the
+//! camera, or raced: the same puzzle is played again with the recorded
+//! camera drawn as a ghost frustum.
+//!
+//! A puzzle left unfinished when the app is closed or suspended is saved
+//! (`level::resume`) and offered as "Continue" in the main menu, which
+//! rebuilds the same scramble and puts back the stage, camera, clock and
+//! hints.
+//!
+//! Every puzzle has a share code (`level::share`) naming its level, seed and
+//! mode.  Entered in the menu it plays the identical puzzle, downloading the
+//! level from poly.pizza first when it is not saved locally.
+//!
+//! A saved level can be opened in the puzzle editor, which maps how good a
+//! puzzle every direction would make as the scramble axis, lets the
+//! designer pick the axis on that map and set the displacement range, and
+//! previews the scramble live.  A saved edit is stored in the level (`puzzle::authoring`) and
+//! replaces the entropy search for every later play.
+//!
+//! # Hints (4-tier progressive)
 //!
 //! Press **H** to cycle:
 //!   Tier 1 → warm/cold compass (colour shows proximity to solution)
 //!   Tier 2 → translucent axis-plane disc in 3D space
-//!   Tier 3 → ghost snap (model slowly un-scrambles over ~5 s)
+//!   Tier 3 → silhouette outline of the solved view, drawn in the current view
+//!   Tier 4 → ghost snap (model slowly un-scrambles over ~5 s)
 //!
 //! # Controls
 //!
@@ -32,18 +99,20 @@
 
 // ─── Per-frame game data (passed to pipeline / renderers each frame) ──────────
 
-use cgmath::{Deg, InnerSpace, Quaternion, Rotation3, Vector3};
+use cgmath::{Deg, InnerSpace, Matrix4, Quaternion, Rad, Rotation3, Vector3};
 use std::{
-    cell::RefCell,
+    cell::{Cell, RefCell},
     path::PathBuf,
     rc::Rc,
     sync::mpsc::{channel, Receiver, Sender},
-    time::Instant,
+    time::{Duration, Instant},
 };
 
+use crate::rendering::{GameRenderData, HintRenderState, OutlineRenderState};
 use formosaic_engine::{
     architecture::{
         models::{
+            model::Model,
             model_loader::{IncrementalModelBuilder, ModelLoadData, ModelLoader},
             simple_model::SimpleModel,
         },
@@ -56,21 +125,49 @@ use formosaic_engine::{
     platform::PlatformInfo,
     rendering::{instances::camera::orbit_controller::OrbitController, render_state::LightConfig},
 };
-use crate::rendering::{GameRenderData, HintRenderState};
 use imgui;
 
-use crate::ui::state_machine::{UiContext, UiInput, UiScreen, UiStateMachine, UiTransition};
+use crate::ui::state_machine::{
+    EditorEdit, UiContext, UiInput, UiScreen, UiStateMachine, UiTransition,
+};
 
 use crate::{
     level::{
+        daily::{DailyChallenge, DailyLog, UtcDate},
+        outcomes::{load_calibration, save_calibration, OutcomeLog, PlayOutcome},
         poly_pizza::{ModelDownload, ModelSummary, PolyPizzaClient},
+        replay::{frustum_segments, Recorder, Recording, ReplayController, ReplayPuzzle},
+        resume::SavedPuzzle,
+        scene::{part_mesh_transforms, side_by_side, Bounds, ScenePart},
+        session::{Leaderboard, LeaderboardEntry, Session, SessionKind},
+        settings::Settings,
+        share::ShareCode,
         storage::{LevelMeta, LevelRegistry},
     },
     puzzle::{
-        entropy::{best_scramble_axis_from_offsets, difficulty_label, EntropyReport},
+        authoring::{AuthoredPuzzle, AxisHeatmap},
+        calibration::{DifficultyFeatures, DifficultyModel},
+        clusters::{cluster_count, flat_clusters},
+        entropy::{
+            analyse_fixed_axis, best_stage_axes_with, difficulty_label, AnalysisBackend,
+            EntropyReport, StagedSearchResult,
+        },
         hints::{HintOutput, HintSystem, HintTier},
-        puzzle_params::PuzzleParams,
-        scrambler::{apply_displacement, compute_model_offsets, make_scrambled_orbit, ScrambleState},
+        mosaic::{fragment_tints, mosaic_t, upload_tints},
+        outline::{silhouette_edges, Segment},
+        palette::WarmthPalette,
+        pose::{hold_pose, solve_clip, ModelPose, PoseLoop},
+        puzzle_params::{PuzzleParams, ScrambleProjection, ScrambleStrategy},
+        reprojection::{Misalignment, ScreenView, SeamSamples},
+        scoring::{self, Score},
+        scramble_math::unweld,
+        scrambler::{
+            apply_displacement, camera_roll, compute_model_stages, make_scrambled_orbit,
+            make_scrambled_orbit_from_eye, solution_roll_up, upload_stage, upright_up,
+            ScrambleStage, ScrambleState, ScrambleTarget, StageSplit,
+        },
+        search::{SearchHandle, SearchOptions},
+        seed::PuzzleSeed,
     },
 };
 
@@ -84,6 +181,42 @@ pub use formosaic_engine::app::application::Application;
 // Formosaic fills in each frame inside `populate_scene_context` — before the
 // engine calls the UiNodes for that frame.
 
+/// A best-solve replay, as the HUD shows it.
+#[derive(Debug, Clone, Copy)]
+pub struct ReplayInfo {
+    /// Watched rather than raced.
+    pub watching: bool,
+    /// Time of the recorded solve.
+    pub time_secs: f32,
+}
+
+/// The puzzle editor, as its panel shows it.
+#[derive(Clone)]
+pub struct EditorInfo {
+    /// Scramble axis, in model space.
+    pub axis: Vector3<f32>,
+    pub min_disp: f32,
+    pub max_disp: f32,
+    /// Largest displacement the sliders offer.
+    pub disp_limit: f32,
+    /// How far the preview is scrambled, 0 = solved.
+    pub preview_t: f32,
+    /// Camera view direction, in model space.
+    pub view_dir: Option<Vector3<f32>>,
+    /// How good each axis would be with these displacements.
+    pub heatmap: Option<Rc<AxisHeatmap>>,
+    /// Whether a new heatmap is being computed.
+    pub heatmap_pending: bool,
+    /// Report for the edited axis.
+    pub report: Option<EntropyReport>,
+    /// `report`'s difficulty after calibration, as play will show it.
+    pub difficulty: Option<f32>,
+    /// Whether the level has an authored puzzle saved.
+    pub authored: bool,
+    /// Whether the edit differs from what is saved.
+    pub dirty: bool,
+}
+
 #[derive(Clone)]
 pub struct UiState {
     pub elapsed_secs: f32,
@@ -91,6 +224,10 @@ pub struct UiState {
     pub hint_count: u32,
     pub hint_tier: HintTier,
     pub hint_warmth: f32,
+    /// Roll still to undo on an upright level (`HintOutput::roll_cue`).
+    pub roll_cue: Option<f32>,
+    /// RMS on-screen misalignment of the stage being solved, in pixels.
+    pub misalignment_px: Option<f32>,
     pub is_solved: bool,
     pub is_downloading: bool,
     pub is_loading: bool,
@@ -99,6 +236,55 @@ pub struct UiState {
     pub levels: Vec<LevelMeta>,
     pub current_level: Option<LevelMeta>,
     pub screen: UiScreen,
+    /// Consecutive days with a completed daily challenge.
+    pub daily_streak: u32,
+    /// Whether today's daily challenge has already been completed.
+    pub daily_done: bool,
+    /// Stage being solved and total stages of the current puzzle.
+    pub stage: usize,
+    pub stage_count: usize,
+    /// Difficulty of the current stage on its own (multi-stage puzzles only).
+    pub stage_difficulty: Option<f32>,
+    /// Whether the puzzle is a composite scene, whose stages are its models.
+    pub scene: bool,
+    /// Stage count new puzzles are started with (menu setting).
+    pub stage_mode: usize,
+    /// Whether new puzzles use the anamorphic (eye point) scramble.
+    pub anamorphic_mode: bool,
+    /// Whether new puzzles also rotate fragments (roll must match).
+    pub roll_mode: bool,
+    /// Whether puzzles are drawn with the mosaic tint.
+    pub mosaic_mode: bool,
+    /// Whether posed models play their clip during the puzzle.
+    pub motion_mode: bool,
+    /// Colours of the warm/cold hint (persisted setting).
+    pub warmth_palette: WarmthPalette,
+    /// Score of the solve just finished (credits panel).
+    pub score: Option<Score>,
+    /// Whether `score` beat the level's previous best.
+    pub new_best: bool,
+    /// Marathon or time attack being played.
+    pub session: Option<SessionKind>,
+    /// Puzzles solved and session clock (seconds) of `session`.
+    pub session_solved: u32,
+    pub session_secs: f32,
+    /// Result of the session just finished, with its leaderboard rank
+    /// (`None` if it did not place).
+    pub session_result: Option<(LeaderboardEntry, Option<usize>)>,
+    /// Best results of the finished session's mode, best first.
+    pub leaderboard: Vec<LeaderboardEntry>,
+    /// Whether the current level has a best-solve recording to replay.
+    pub has_replay: bool,
+    /// Best solve being watched or raced.
+    pub replay: Option<ReplayInfo>,
+    /// Whether an unfinished puzzle can be continued.
+    pub has_saved_puzzle: bool,
+    /// Share code of the current puzzle.
+    pub share_code: Option<String>,
+    /// Why the last share code entered could not be played.
+    pub share_error: Option<String>,
+    /// Puzzle editor, while a level is being edited.
+    pub editor: Option<EditorInfo>,
 }
 
 impl Default for UiState {
@@ -109,6 +295,8 @@ impl Default for UiState {
             hint_count: 0,
             hint_tier: HintTier::None,
             hint_warmth: 0.5,
+            roll_cue: None,
+            misalignment_px: None,
             is_solved: false,
             is_downloading: false,
             is_loading: false,
@@ -117,6 +305,31 @@ impl Default for UiState {
             levels: Vec::new(),
             current_level: None,
             screen: UiScreen::MainMenu,
+            daily_streak: 0,
+            daily_done: false,
+            stage: 0,
+            stage_count: 1,
+            stage_difficulty: None,
+            scene: false,
+            stage_mode: 1,
+            anamorphic_mode: false,
+            roll_mode: false,
+            mosaic_mode: false,
+            motion_mode: false,
+            warmth_palette: WarmthPalette::Standard,
+            score: None,
+            new_best: false,
+            session: None,
+            session_solved: 0,
+            session_secs: 0.0,
+            session_result: None,
+            leaderboard: Vec::new(),
+            has_replay: false,
+            replay: None,
+            has_saved_puzzle: false,
+            share_code: None,
+            share_error: None,
+            editor: None,
         }
     }
 }
@@ -125,11 +338,36 @@ impl Default for UiState {
 
 const TARGET_WORLD_RADIUS: f32 = 1.0;
 const CAMERA_FOV: f32 = 75.0 * std::f32::consts::PI / 180.0;
-const SNAP_THRESHOLD_DOT: f32 = 0.996;
 const CAMERA_FADE_DOT: f32 = 0.966; // cos(15°) — un-scramble starts within this angle of solution
 const RESTORE_DURATION: f32 = 1.8;
 /// Number of candidate axes tested in the entropy search.
 const ENTROPY_CANDIDATES: usize = 32;
+/// How the entropy search scores viewpoints.  `Silhouette` rasterises every
+/// candidate on the load thread — better judged, but far slower.
+const ENTROPY_BACKEND: AnalysisBackend = AnalysisBackend::Heuristic;
+/// Largest stage count offered by the menu's multi-stage setting.
+const MAX_STAGES: usize = 3;
+/// Largest fragment rotation of the rotation scramble.
+const ROLL_SCRAMBLE_RAD: f32 = 60.0 * std::f32::consts::PI / 180.0;
+/// Levels in a marathon.
+const MARATHON_LEVELS: usize = 5;
+/// Time budget of a time attack.
+const TIME_ATTACK_SECS: f32 = 300.0;
+/// Ghost camera frustum depth, as a fraction of the orbit distance.
+const GHOST_DEPTH: f32 = 0.2;
+/// Ghost camera colour.
+const GHOST_COLOR: [f32; 3] = [0.55, 0.75, 1.0];
+
+/// Seconds the solved model stays on screen before a session moves on.
+const SESSION_NEXT_DELAY: f32 = 2.0;
+
+/// Axis heatmap resolution of the puzzle editor.
+const HEATMAP_COLS: usize = 36;
+const HEATMAP_ROWS: usize = 18;
+/// Seconds without edits before the editor's puzzle is analysed again.
+const EDITOR_ANALYSIS_DELAY: f32 = 0.3;
+/// Largest displacement the editor offers, as a fraction of the model radius.
+const EDITOR_DISP_LIMIT: f32 = 0.5;
 
 // ─── State machine ────────────────────────────────────────────────────────────
 
@@ -141,8 +379,16 @@ enum GameState {
         cam_start: Vector3<f32>,
         cam_end: Vector3<f32>,
         target: Vector3<f32>,
+        /// Camera roll at the solve, eased back to upright.
+        roll_start: f32,
     },
     Solved,
+    /// A time attack ran out mid-puzzle; the puzzle stays frozen under the
+    /// results.
+    TimeUp,
+    /// The puzzle editor: the scramble follows the editor's preview and the
+    /// puzzle never solves.
+    Editing,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -156,25 +402,131 @@ enum AppMode {
 }
 
 /// Result sent from the background load thread to the main thread.
-/// Includes the CPU-parsed model data PLUS the pre-computed entropy axis,
-/// entropy report, and puzzle params — so the main thread never runs
+/// Includes the CPU-parsed model data PLUS the pre-computed per-stage axes,
+/// entropy reports, and puzzle params — so the main thread never runs
 /// `best_scramble_axis` or `compute_puzzle_params`.
 struct LoadResult {
     request_id: u64,
     level_id: String,
     data: ModelLoadData,
-    axis: Vector3<f32>,
-    report: EntropyReport,
+    /// Model of a composite scene every mesh belongs to; empty for a
+    /// single-model level.
+    mesh_parts: Vec<usize>,
+    search: StagedSearchResult,
+    params: PuzzleParams,
+    seed: PuzzleSeed,
+    /// `LoadJob::authored`.
+    authored: Option<AuthoredPuzzle>,
+}
+
+/// Everything a background load needs besides the model itself.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LoadJob {
+    pub request_id: u64,
+    pub level_id: String,
+    pub seed: PuzzleSeed,
+    pub stages: usize,
+    pub strategy: ScrambleStrategy,
+    pub upright: bool,
+    pub max_roll: f32,
+    /// Puzzle made in the editor, built instead of searching for one.
+    pub authored: Option<AuthoredPuzzle>,
+    /// Model files and placements of a composite scene's other models.
+    pub parts: Vec<(PathBuf, ScenePart)>,
+}
+
+impl LoadJob {
+    /// The load that rebuilds the puzzle `code` names.  Everything about
+    /// the puzzle comes from the code, so the recipient's copy of the
+    /// level cannot change it; only a composite scene's other models
+    /// (`parts`) are found locally.
+    pub fn from_share_code(
+        request_id: u64,
+        code: &ShareCode,
+        parts: Vec<(PathBuf, ScenePart)>,
+    ) -> Self {
+        LoadJob {
+            request_id,
+            level_id: code.level_id.clone(),
+            seed: code.seed,
+            stages: code.stages,
+            strategy: code.strategy,
+            upright: code.upright,
+            max_roll: if code.roll { ROLL_SCRAMBLE_RAD } else { 0.0 },
+            authored: code.authored,
+            parts,
+        }
+    }
+}
+
+/// The next session level, loading on its own thread while the current one
+/// is played.  Handed to the load pipeline when the session moves on.
+struct Prefetch {
+    level_id: String,
+    rx: Receiver<LoadResult>,
+    handle: SearchHandle,
+}
+
+/// How a level's recorded best solve is being shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ReplayMode {
+    /// The recording drives the camera; the player only watches.
+    Watch,
+    /// The player solves the recorded puzzle again, with the recorded
+    /// camera drawn as a ghost.
+    Race,
+}
+
+/// A best-solve recording being watched or raced.
+struct Playback {
+    recording: Rc<Recording>,
+    /// Seconds into the recording; read by `ReplayController`.
+    clock: Rc<Cell<f32>>,
+    mode: ReplayMode,
+}
+
+/// The puzzle editor's working copy of a level's puzzle.
+struct Editor {
+    level_id: String,
+    puzzle: AuthoredPuzzle,
+    /// Largest displacement the sliders offer, in model units.
+    disp_limit: f32,
+    /// How far the preview is scrambled, 0 = solved.
+    preview_t: f32,
+    /// Whether the level has an authored puzzle saved.
+    authored: bool,
+    /// Whether `puzzle` differs from what is saved.
+    dirty: bool,
+    /// Flat model-space positions and clusters the analysis runs on.
+    positions: Vec<f32>,
+    clusters: Vec<usize>,
     params: PuzzleParams,
+    heatmap: Option<Rc<AxisHeatmap>>,
+    /// The heatmap being computed for the current displacements.
+    heatmap_job: Option<HeatmapJob>,
+    /// Whether the displacements changed since the last heatmap started.
+    heatmap_stale: bool,
+    report: Option<EntropyReport>,
+    /// Seconds until the puzzle is analysed again; `None` when up to date.
+    analysis_due: Option<f32>,
+}
+
+/// An editor heatmap computing on its own thread.  Nothing is sent if it
+/// is cancelled.
+struct HeatmapJob {
+    rx: Receiver<AxisHeatmap>,
+    handle: SearchHandle,
 }
 
 struct PendingLoad {
     request_id: u64,
     level_id: String,
     data: ModelLoadData,
-    axis: Vector3<f32>,
-    report: EntropyReport,
+    mesh_parts: Vec<usize>,
+    search: StagedSearchResult,
     params: PuzzleParams,
+    seed: PuzzleSeed,
+    authored: Option<AuthoredPuzzle>,
 }
 
 // ─── Main struct ──────────────────────────────────────────────────────────────
@@ -189,14 +541,86 @@ pub struct Formosaic {
     hints: HintSystem,
     /// Cached output from the last hints.update() call — read by the renderer.
     last_hint_output: Option<HintOutput>,
+    /// This frame's screen-space misalignment of the current stage.
+    misalignment: Option<Misalignment>,
+    /// Triangles the misalignment is measured on, with the stage they were
+    /// picked for.  Picked on first use per stage.
+    seam_samples: Option<(usize, SeamSamples)>,
+    /// Score of the solve just finished, and whether it beat the level's
+    /// best.  Cleared when a new level starts.
+    last_score: Option<(Score, bool)>,
+    /// Solved-view contour edges for the outline hint, with the stage they
+    /// were built for.  Built on first use per stage.
+    outline: Option<(usize, Rc<Vec<Segment>>)>,
     elapsed_secs: f32,
     level_start: Option<Instant>,
     registry: LevelRegistry,
+    daily_log: DailyLog,
+    /// Settings kept across sessions.
+    settings: Settings,
+    /// Solves and give-ups, for difficulty calibration.
+    outcomes: OutcomeLog,
+    /// Difficulty model refit from `outcomes`; `None` = the hand-tuned blend.
+    calibration: Option<DifficultyModel>,
+    /// The daily challenge being played, if the current level is one.
+    daily: Option<DailyChallenge>,
+    /// Marathon or time attack being played.
+    session: Option<Session>,
+    /// Next session level, loading in the background.
+    prefetch: Option<Prefetch>,
+    /// Result of the last finished session and its leaderboard rank.
+    /// Cleared when returning to the menu.
+    session_result: Option<(LeaderboardEntry, Option<usize>)>,
+    /// Finished sessions, best first per mode.
+    leaderboard: Leaderboard,
+    /// Camera recording of the puzzle being played.
+    recorder: Option<Recorder>,
+    /// Recording of the solve just made, kept until it is known whether it
+    /// is the level's best.
+    solve_recording: Option<Recording>,
+    /// Best solve being watched or raced.
+    replay: Option<Playback>,
+    /// Whether the current level has a best-solve recording.
+    has_replay: bool,
+    /// Saved puzzle the load in flight resumes.
+    resume: Option<SavedPuzzle>,
+    /// Whether `resume.json` holds a puzzle to continue.
+    has_saved_puzzle: bool,
+    /// Share code being played, until its level is loaded (and downloaded
+    /// first, if need be).
+    shared: Option<ShareCode>,
+    /// Share code of the current puzzle.
+    share_code: Option<String>,
+    /// Why the last share code entered could not be played.
+    share_error: Option<String>,
+    /// Open the load in flight in the puzzle editor.
+    edit_level: bool,
+    /// Puzzle editor, while a level is being edited.
+    editor: Option<Editor>,
+    /// Stage count for new puzzles (1 = classic single-axis puzzle).
+    stage_count: usize,
+    /// Scramble projection for new puzzles (menu setting).
+    projection: ScrambleProjection,
+    /// Whether new puzzles use the rotation scramble (menu setting).
+    roll_scramble: bool,
+    /// Whether puzzles are drawn with the mosaic tint (menu setting).
+    mosaic: bool,
+    /// Fragment tints of the current puzzle, when it is drawn with them.
+    mosaic_tints: Option<Vec<Vec<f32>>>,
+    /// Whether posed models play their clip during the puzzle (menu
+    /// setting).
+    motion: bool,
+    /// Clip of the current posed puzzle, when it plays during the puzzle.
+    pose_loop: Option<PoseLoop>,
+    /// Per-stage entropy reports of the current puzzle.
+    stage_reports: Vec<EntropyReport>,
     client: PolyPizzaClient,
     load_seq: u64,
     loading_frames: u32,
     loading_started: Option<Instant>,
     loading_progress: f32,
+    /// Axis search of the load in flight; cancelled when it is abandoned.
+    search_handle: Option<SearchHandle>,
     latest_download_progress: Option<crate::level::poly_pizza::DownloadProgress>,
     load_tx: Sender<LoadResult>,
     load_rx: Receiver<LoadResult>,
@@ -205,11 +629,17 @@ pub struct Formosaic {
     /// Builder whose work is fully done — finalization deferred to next frame
     /// so it never compounds with the last mesh/texture upload.
     pending_finalize_builder: Option<IncrementalModelBuilder>,
-    /// Pre-computed entropy axis/report/params from the background thread,
+    /// Pre-computed entropy search/params from the background thread,
     /// stored here while the incremental builder runs.
-    pending_axis: Option<Vector3<f32>>,
-    pending_report: Option<EntropyReport>,
+    pending_search: Option<StagedSearchResult>,
     pending_params: Option<PuzzleParams>,
+    pending_seed: Option<PuzzleSeed>,
+    /// Model of every mesh of the composite scene being built or played
+    /// (`LoadResult::mesh_parts`); empty for a single-model level.
+    scene_parts: Vec<usize>,
+    /// Authored puzzle being built or played (`LoadJob::authored`); `None`
+    /// for a searched one.
+    authored_puzzle: Option<AuthoredPuzzle>,
     mode: AppMode,
     ui_machine: UiStateMachine,
     solved_timer: f32, // seconds since solve; transitions to menu after 5s
@@ -223,6 +653,20 @@ impl Formosaic {
     pub fn new() -> Self {
         let data_dir = LevelRegistry::default_data_dir();
         let registry = LevelRegistry::load(&data_dir);
+        let daily_log = DailyLog::load(&data_dir);
+        let outcomes = OutcomeLog::load(&data_dir);
+        let calibration = load_calibration(&data_dir);
+        let leaderboard = Leaderboard::load(&data_dir);
+        let has_saved_puzzle = SavedPuzzle::exists(&data_dir);
+        let settings = Settings::load(&data_dir);
+        let mut hints = HintSystem::new();
+        hints.set_palette(settings.warmth_palette);
+        if let Some(model) = &calibration {
+            log::info!(
+                "[Formosaic] Calibrated difficulty model ({} outcomes)",
+                model.samples
+            );
+        }
         let (load_tx, load_rx) = channel();
         log::info!(
             "[Formosaic] Data dir: {}  ({} saved levels)",
@@ -236,25 +680,60 @@ impl Formosaic {
             scramble_state: None,
             entropy_report: None,
             game_state: GameState::Playing,
-            hints: HintSystem::new(),
+            hints,
             last_hint_output: None,
+            misalignment: None,
+            seam_samples: None,
+            last_score: None,
+            outline: None,
             elapsed_secs: 0.0,
             level_start: None,
             registry,
+            daily_log,
+            settings,
+            outcomes,
+            calibration,
+            daily: None,
+            session: None,
+            prefetch: None,
+            session_result: None,
+            leaderboard,
+            recorder: None,
+            solve_recording: None,
+            replay: None,
+            has_replay: false,
+            resume: None,
+            has_saved_puzzle,
+            shared: None,
+            share_code: None,
+            share_error: None,
+            edit_level: false,
+            editor: None,
+            stage_count: 1,
+            projection: ScrambleProjection::Parallel,
+            roll_scramble: false,
+            mosaic: false,
+            mosaic_tints: None,
+            motion: false,
+            pose_loop: None,
+            stage_reports: Vec::new(),
             client: PolyPizzaClient::new(),
             load_seq: 0,
             loading_frames: 0,
             loading_started: None,
             loading_progress: 0.0,
+            search_handle: None,
             latest_download_progress: None,
             load_tx,
             load_rx,
             pending_load: None,
             incremental_builder: None,
             pending_finalize_builder: None,
-            pending_axis: None,
-            pending_report: None,
+            pending_search: None,
             pending_params: None,
+            pending_seed: None,
+            scene_parts: Vec::new(),
+            authored_puzzle: None,
             mode: AppMode::LevelSelect,
             ui_machine: UiStateMachine::new(),
             solved_timer: 0.0,
@@ -278,12 +757,15 @@ impl Formosaic {
         self.entity = None;
         self.orbit = None;
         self.hints.reset_full(); // New level: reset per-level hint count
+        self.last_score = None;
         self.loading_started = None;
         self.incremental_builder = None;
         self.pending_finalize_builder = None;
-        self.pending_axis = None;
-        self.pending_report = None;
+        self.pending_search = None;
         self.pending_params = None;
+        self.pending_seed = None;
+        self.authored_puzzle = None;
+        let seed = Self::fresh_seed(level_id);
 
         let bytes = if let Some(b) = preloaded {
             b.to_vec()
@@ -320,76 +802,232 @@ impl Formosaic {
         };
 
         // Compute entropy and puzzle params from raw CPU data (no GPU needed).
-        // This runs before building so the ~3.8 s search never compounds with
+        // This runs before building so the axis search never compounds with
         // texture uploads on the main thread.
-        let pos_slices: Vec<&[f32]> = data.meshes.iter().map(|m| m.positions.as_slice()).collect();
-        let params = PuzzleParams::from_raw_positions(
-            &pos_slices,
-            &data.mesh_transforms,
-            TARGET_WORLD_RADIUS,
-            CAMERA_FOV,
-        );
-        let flat_positions: Vec<f32> = data
-            .meshes
-            .iter()
-            .flat_map(|m| m.positions.iter().copied())
-            .collect();
-        let search = best_scramble_axis_from_offsets(
-            &flat_positions,
-            params.min_disp,
-            params.max_disp,
-            ENTROPY_CANDIDATES,
-        );
+        let strategy = self.level_strategy(level_id);
+        let upright = self.level_upright(level_id);
+        let max_roll = self.scramble_roll();
+        let Some((params, search)) = analyse_load_data(
+            &data,
+            &StageSplit::Bands(self.stage_count),
+            strategy,
+            upright,
+            max_roll,
+            seed,
+            &SearchHandle::new(),
+        ) else {
+            return;
+        };
 
         let mut builder = IncrementalModelBuilder::new(data);
         while !builder.build_next() {}
-        self.finalize_loaded_model(
-            level_id.to_string(),
-            builder,
-            search.axis,
-            search.report,
-            params,
-            ctx,
+        self.finalize_loaded_model(level_id.to_string(), builder, search, params, seed, ctx);
+    }
+
+    /// Seed for a new play of `level_id`.  Logged at finalization so any
+    /// scramble can be reproduced from a bug report.
+    fn fresh_seed(level_id: &str) -> PuzzleSeed {
+        PuzzleSeed::for_level(level_id, PuzzleSeed::random().value())
+    }
+
+    /// World matrix of every mesh of the puzzle model: the entity's
+    /// transform times the mesh's node transform.
+    fn model_matrices(&self) -> Option<Vec<Matrix4<f32>>> {
+        let sc = self.scramble_state.as_ref()?;
+        let model = self.model.as_ref()?;
+        let entity_matrix = self.entity.as_ref()?.borrow().transform().get_matrix();
+        Some(
+            (0..sc.original_positions.len())
+                .map(|i| {
+                    entity_matrix
+                        * model
+                            .borrow()
+                            .mesh_transform(i)
+                            .unwrap_or_else(|| Matrix4::from_scale(1.0))
+                })
+                .collect(),
+        )
+    }
+
+    /// On-screen misalignment of the stage being solved, measured from the
+    /// current camera.  `None` until a puzzle and a viewport exist.
+    fn stage_misalignment(&mut self, ctx: &SceneContext) -> Option<Misalignment> {
+        let sc = self.scramble_state.as_ref()?;
+        let stage = sc.current_stage;
+        if self.seam_samples.as_ref().map(|(s, _)| *s) != Some(stage) {
+            self.seam_samples = Some((stage, sc.seam_samples()));
+        }
+        let (_, samples) = self.seam_samples.as_ref()?;
+        let model_matrices = self.model_matrices()?;
+        let camera = ctx.camera().borrow();
+        if camera.resolution.y == 0 {
+            return None;
+        }
+        let view = ScreenView::new(
+            camera.transform.position,
+            camera.transform.forward(),
+            camera.transform.up(),
+            self.orbit_target(camera.transform.position),
+            camera.get_fov(),
+            camera.resolution.y as f32,
         );
+        Some(sc.misalignment(samples, &model_matrices, &view))
+    }
+
+    /// Difficulty of a puzzle with `stages` stages: the calibrated model's
+    /// when there is one, else the report's hand-tuned blend.
+    fn calibrated_difficulty(&self, report: &EntropyReport, stages: usize) -> f32 {
+        match &self.calibration {
+            Some(model) => model.difficulty(&DifficultyFeatures::from_report(report, stages)),
+            None => report.difficulty,
+        }
+    }
+
+    /// Contour edges of the current stage's solved view, for the outline
+    /// hint.  Cached until the stage changes.
+    fn stage_outline(&mut self) -> Option<Rc<Vec<Segment>>> {
+        let sc = self.scramble_state.as_ref()?;
+        let stage = sc.current_stage;
+        if let Some((built_for, segments)) = &self.outline {
+            if *built_for == stage {
+                return Some(Rc::clone(segments));
+            }
+        }
+        let target = match sc.solution_eye() {
+            Some(eye) => ScrambleTarget::Eye(eye),
+            None => ScrambleTarget::Axis(sc.solution_dir()),
+        };
+        let segments = Rc::new(silhouette_edges(
+            &sc.original_positions,
+            &self.model_matrices()?,
+            target,
+        ));
+        self.outline = Some((stage, Rc::clone(&segments)));
+        Some(segments)
+    }
+
+    /// Current orbit centre — the entity's centroid, falling back to the
+    /// orbit target (or the camera itself) before there is an entity.
+    fn orbit_target(&self, cam_pos: Vector3<f32>) -> Vector3<f32> {
+        self.entity
+            .as_ref()
+            .map(|e| e.borrow().centroid())
+            .or_else(|| self.orbit.as_ref().map(|o| o.target))
+            .unwrap_or(cam_pos)
+    }
+
+    /// Scramble strategy chosen by the level's metadata (per-triangle for
+    /// levels that are not in the registry).
+    fn level_strategy(&self, level_id: &str) -> ScrambleStrategy {
+        self.registry
+            .levels
+            .iter()
+            .find(|m| m.id == level_id)
+            .map(|m| m.scramble_strategy)
+            .unwrap_or_default()
+    }
+
+    /// Whether the level's metadata asks for an upright solve (false for
+    /// levels that are not in the registry).
+    fn level_upright(&self, level_id: &str) -> bool {
+        self.registry
+            .levels
+            .iter()
+            .any(|m| m.id == level_id && m.upright)
+    }
+
+    /// Fragment rotation for a new puzzle: `ROLL_SCRAMBLE_RAD` when the
+    /// rotation scramble is on, 0 otherwise.  The daily is the same puzzle
+    /// for everyone, so it ignores the setting.
+    fn scramble_roll(&self) -> f32 {
+        if self.roll_scramble && self.daily.is_none() {
+            ROLL_SCRAMBLE_RAD
+        } else {
+            0.0
+        }
     }
 
     fn finalize_loaded_model(
         &mut self,
         level_id: String,
         builder: IncrementalModelBuilder,
-        axis: Vector3<f32>,
-        report: EntropyReport,
+        search: StagedSearchResult,
         params: PuzzleParams,
+        seed: PuzzleSeed,
         ctx: &mut SceneContext,
     ) {
         let model = builder.finish();
+        // The daily is the same puzzle for everyone, so it ignores the
+        // setting; a replay, a resumed puzzle or a share code is the puzzle
+        // it was.  The editor previews the parallel scramble its analysis
+        // scores.
+        let projection = if self.edit_level {
+            ScrambleProjection::Parallel
+        } else if let Some(saved) = &self.resume {
+            saved.puzzle.projection
+        } else if let Some(code) = &self.shared {
+            code.projection
+        } else if let Some(playback) = &self.replay {
+            playback.recording.puzzle.projection
+        } else if self.daily.is_some() {
+            ScrambleProjection::Parallel
+        } else {
+            self.projection
+        };
+        let params = params.with_projection(projection);
 
+        let report = search.combined;
         self.entropy_report = Some(report);
+        self.outline = None;
+        self.seam_samples = None;
+        self.stage_reports = search.stage_reports();
+        let difficulty = self.calibrated_difficulty(&report, search.stages.len());
         log::info!(
-            "[Formosaic] difficulty={} ({:.2})  entropy={:.2} bits  isolation={:.1}°",
-            difficulty_label(report.difficulty),
-            report.difficulty,
+            "[Formosaic] level='{}' seed={} stages={}  difficulty={} ({:.2})  entropy={:.2} bits  isolation={:.1}°",
+            level_id,
+            seed,
+            search.stages.len(),
+            difficulty_label(difficulty),
+            difficulty,
             report.entropy_bits,
             report.solution_isolation_rad.to_degrees(),
         );
 
         if let Some(m) = self.registry.levels.iter_mut().find(|m| m.id == level_id) {
-            m.difficulty = report.difficulty;
+            m.difficulty = difficulty;
         }
 
-        let original_positions: Vec<Vec<f32>> = model
-            .borrow()
-            .meshes()
-            .iter()
-            .map(|m| m.positions().to_vec())
-            .collect();
-        let scramble_offsets =
-            compute_model_offsets(&model.borrow(), axis, params.min_disp, params.max_disp);
-        for (mesh_idx, offsets) in scramble_offsets.iter().enumerate() {
-            let orig = &original_positions[mesh_idx];
-            let displaced: Vec<f32> = orig.iter().zip(offsets.iter()).map(|(p, o)| p + o).collect();
-            model.borrow_mut().upload_mesh_positions(mesh_idx, displaced);
-        }
+        // A skinned model is scrambled in the pose it was analysed in.
+        let pose = ModelPose::of_model(&model.borrow());
+        let original_positions: Vec<Vec<f32>> = match &pose {
+            Some(pose) => pose.posed_positions().to_vec(),
+            None => model
+                .borrow()
+                .meshes()
+                .iter()
+                .map(|m| m.positions().to_vec())
+                .collect(),
+        };
+        let axes = search.axes();
+        let split = if self.scene_parts.is_empty() {
+            StageSplit::Bands(axes.len())
+        } else {
+            StageSplit::Parts(self.scene_parts.clone())
+        };
+        let (_, stage_offsets) = compute_model_stages(
+            &model.borrow(),
+            &original_positions,
+            &axes,
+            &split,
+            &params,
+            seed,
+        );
+        let recorded_puzzle = ReplayPuzzle {
+            seed,
+            stages: axes.len(),
+            max_roll: params.max_roll,
+            projection: params.projection,
+        };
 
         if let Some(scene) = ctx.scene() {
             let entity = Rc::new(RefCell::new(SimpleEntity::new(model.clone())));
@@ -409,46 +1047,174 @@ impl Formosaic {
                 .rotate_around_world(pivot, Quaternion::from_angle_y(Deg(180.0)));
 
             let entity_rot = entity.borrow().transform().rotation;
-            let solution_dir = (entity_rot * axis).normalize();
-            self.scramble_state = Some(ScrambleState {
-                solution_dir,
+            let centroid = entity.borrow().centroid();
+            let dist = params.orbit_distance;
+            let stages: Vec<ScrambleStage> = axes
+                .iter()
+                .zip(stage_offsets)
+                .enumerate()
+                .map(|(k, (&axis, scramble_offsets))| {
+                    let dir = (entity_rot * axis).normalize();
+                    // The model-space eye maps onto the orbit sphere: the
+                    // entity's uniform scale turns eye_distance into `dist`.
+                    let solution_eye = match params.projection {
+                        ScrambleProjection::Parallel => None,
+                        ScrambleProjection::Anamorphic => Some(centroid + dir * dist),
+                    };
+                    // Looking from the eye towards the model.
+                    let solution_dir = if solution_eye.is_some() { -dir } else { dir };
+                    ScrambleStage {
+                        solution_dir,
+                        solution_eye,
+                        solution_up: (params.max_roll > 0.0).then(|| {
+                            // An upright level is solved with the camera
+                            // upright, so it gets no solution roll.
+                            if params.upright {
+                                upright_up(solution_dir)
+                            } else {
+                                solution_roll_up(solution_dir, seed.stage(k))
+                            }
+                        }),
+                        scramble_offsets,
+                    }
+                })
+                .collect();
+            let solution_dir = stages[0].solution_dir;
+            let solution_eye = stages[0].solution_eye;
+            self.hints.set_stage_count(stages.len());
+            let mut state = ScrambleState {
+                stages,
+                current_stage: 0,
                 params,
                 original_positions,
-                scramble_offsets,
+                seed,
+                pose,
+            };
+            // A resumed puzzle picks up on the stage it was left on.
+            if let Some(saved) = &self.resume {
+                while state.current_stage < saved.stage && state.advance_stage() {}
+                self.hints.restore(
+                    state.current_stage,
+                    saved.hint_tier,
+                    saved.hint_count,
+                    saved.tier_uses,
+                );
+            }
+            // The editor previews the plain scramble.
+            self.mosaic_tints = (self.mosaic && !self.edit_level).then(|| {
+                let slices: Vec<&[f32]> =
+                    state.original_positions.iter().map(Vec::as_slice).collect();
+                fragment_tints(&slices, state.params.strategy, seed)
             });
+            if let Some(pose) = &state.pose {
+                hold_pose(&mut model.borrow_mut(), pose.pose);
+                self.pose_loop = PoseLoop::new(pose.pose, model.borrow().animations())
+                    .filter(|_| self.motion && !self.edit_level);
+            }
+            upload_stage(&model, &state);
+            upload_tints(&model, &state, self.mosaic_tints.as_deref());
+            apply_displacement(&model, 1.0);
+            let tint = if self.mosaic_tints.is_some() {
+                1.0
+            } else {
+                0.0
+            };
+            model.borrow_mut().set_mosaic_t(tint);
+            self.scramble_state = Some(state);
             self.entity = Some(entity.clone());
 
             let camera = ctx.camera();
-            let centroid = entity.borrow().centroid();
-            let dist = params.orbit_distance;
-            let (mut ctrl, sp) = make_scrambled_orbit(centroid, dist, solution_dir);
+            let (mut ctrl, sp) = match solution_eye {
+                Some(eye) => make_scrambled_orbit_from_eye(centroid, dist, eye, seed),
+                None => make_scrambled_orbit(centroid, dist, solution_dir, seed),
+            };
             let target_entity = entity.clone();
             ctrl.set_target_provider(Some(Box::new(move || target_entity.borrow().centroid())));
-            camera.borrow_mut().transform.position = sp;
+            let (start, roll) = match &self.resume {
+                Some(saved) => (saved.camera_position, saved.camera_roll),
+                None => (sp, 0.0),
+            };
+            camera.borrow_mut().transform.position = start;
             camera
                 .borrow_mut()
                 .transform
                 .look_at(centroid, Vector3::unit_y());
+            if self.resume.is_some() {
+                ctrl.set_initial_position(start);
+                ctrl.set_roll(roll);
+                let mut cam = camera.borrow_mut();
+                cam.transform.rotation =
+                    cam.transform.rotation * Quaternion::from_angle_z(Rad(roll));
+            }
             self.orbit = Some(OrbitController::new(centroid, dist));
-            camera.borrow_mut().set_controller(Some(Box::new(ctrl)));
+            match self.replay.as_ref().filter(|p| p.mode == ReplayMode::Watch) {
+                Some(playback) => {
+                    let replay = ReplayController::new(
+                        Rc::clone(&playback.recording),
+                        Rc::clone(&playback.clock),
+                    );
+                    camera.borrow_mut().set_controller(Some(Box::new(replay)));
+                }
+                None => camera.borrow_mut().set_controller(Some(Box::new(ctrl))),
+            }
 
             if !self.is_in_menu() {
                 scene.add_node(entity.clone());
             }
         }
 
+        // A watched replay or an edit is not a play of its own, and a
+        // resumed puzzle's camera path is only known from the resume on.
+        let resumed = self.resume.take();
+        let editing = std::mem::take(&mut self.edit_level);
+        self.recorder = (!editing && !self.watching_replay() && resumed.is_none())
+            .then(|| Recorder::new(recorded_puzzle));
+        self.has_replay = Recording::exists(&self.registry.level_dir(&level_id));
+        // Whatever was saved is superseded by the puzzle now started.
+        if self.has_saved_puzzle {
+            SavedPuzzle::clear(&LevelRegistry::default_data_dir());
+            self.has_saved_puzzle = false;
+        }
+        let elapsed = resumed.map_or(0.0, |saved| saved.elapsed_secs);
+        self.shared = None;
+        self.share_code = ShareCode {
+            level_id: level_id.clone(),
+            seed,
+            stages: recorded_puzzle.stages,
+            projection: recorded_puzzle.projection,
+            roll: recorded_puzzle.max_roll > 0.0,
+            strategy: params.strategy,
+            upright: params.upright,
+            authored: self.authored_puzzle,
+        }
+        .encode();
+        if let Some(code) = &self.share_code {
+            log::info!("[Formosaic] Share code: {code}");
+        }
+
         self.model = Some(model);
         self.game_state = GameState::Playing;
+        if editing {
+            self.enter_editor(level_id.clone(), axes[0]);
+        }
         self.ui_machine = UiStateMachine::new();
-        let _ = self
-            .ui_machine
-            .handle(UiInput::PlayLevel(level_id.clone()), &UiContext::default());
-        self.level_start = Some(Instant::now());
-        self.elapsed_secs = 0.0;
+        let input = if editing {
+            UiInput::EditLevel(level_id.clone())
+        } else {
+            UiInput::PlayLevel(level_id.clone())
+        };
+        let _ = self.ui_machine.handle(input, &UiContext::default());
+        let now = Instant::now();
+        self.level_start = Some(
+            now.checked_sub(Duration::from_secs_f32(elapsed))
+                .unwrap_or(now),
+        );
+        self.elapsed_secs = elapsed;
         self.mode = AppMode::InGame { level_id };
         self.sync_scenegraph(ctx);
         self.solved_timer = 0.0;
         self.loading_started = None;
+        self.prefetch_next_session_level();
     }
 
     /// Ensure the builtin cactus level exists in the registry so it shows up
@@ -456,7 +1222,12 @@ impl Formosaic {
     /// if the level is already saved.
     fn seed_builtin_levels(&mut self) {
         let id = "cactus_builtin";
-        if self.registry.levels.iter().any(|l| l.id == id) {
+        // A scene of three cacti: the level's own model plus two parts,
+        // each scrambled along its own axis.
+        let garden_id = "cactus_garden_builtin";
+        let has = |id: &str| self.registry.levels.iter().any(|l| l.id == id);
+        let (has_cactus, has_garden) = (has(id), has(garden_id));
+        if has_cactus && has_garden {
             return;
         }
 
@@ -468,8 +1239,14 @@ impl Formosaic {
             source_url: "https://poly.pizza/m/7S5Snphkam".to_string(),
             model_file: "cactus.fbx".to_string(),
             best_time_secs: None,
+            best_score: None,
+            stars: 0,
             play_count: 0,
             difficulty: 0.3,
+            scramble_strategy: ScrambleStrategy::default(),
+            upright: false,
+            authored: None,
+            parts: Vec::new(),
         };
 
         let bytes = match crate::asset_loader::load_3d_asset("models/Cactus/cactus.fbx") {
@@ -479,119 +1256,870 @@ impl Formosaic {
                 return;
             }
         };
+        if !has_garden {
+            let garden = LevelMeta {
+                id: garden_id.to_string(),
+                name: "Cactus Garden".to_string(),
+                difficulty: 0.5,
+                parts: side_by_side(&["cactus.fbx", "cactus.fbx"]),
+                ..meta.clone()
+            };
+            let parts = [bytes.as_slice(), bytes.as_slice()];
+            if let Err(e) = self.registry.save_scene(garden, &bytes, &parts) {
+                log::warn!("[Formosaic] Failed to seed builtin cactus garden: {e}");
+            }
+        }
+        if has_cactus {
+            return;
+        }
         if let Err(e) = self.registry.save_level(meta, &bytes) {
             log::warn!("[Formosaic] Failed to seed builtin cactus: {e}");
         }
     }
 
-    fn load_random_saved(&mut self, ctx: &mut SceneContext) {
-        if let Some(meta) = self.registry.random_level() {
-            let path = self.registry.model_path(meta);
-            let id = meta.id.clone();
-            if path.exists() {
-                self.begin_saved_level_load(id, path, ctx);
-                return;
-            }
+    fn load_random_saved(&mut self, ctx: &mut SceneContext) {
+        self.daily = None;
+        self.end_session();
+        if let Some(meta) = self.registry.random_level() {
+            let path = self.registry.model_path(meta);
+            let id = meta.id.clone();
+            if path.exists() {
+                let seed = Self::fresh_seed(&id);
+                self.begin_saved_level_load(id, path, seed, self.stage_count, ctx);
+                return;
+            }
+        }
+        // No saved levels — fetch one online instead
+        log::info!("[Formosaic] No saved levels for random — fetching online");
+        self.fetch_online_level(ctx);
+    }
+
+    /// Start today's daily challenge.  Level and seed depend only on the UTC
+    /// date and the local level library, never on the network.
+    fn load_daily(&mut self, ctx: &mut SceneContext) {
+        let found = match DailyChallenge::for_date(UtcDate::today(), &self.registry.levels) {
+            None => Err("no levels are available".to_string()),
+            Some(c) => match self.registry.levels.iter().find(|m| m.id == c.level_id) {
+                None => Err(format!("level {} is not saved", c.level_id)),
+                Some(meta) => {
+                    let path = self.registry.model_path(meta);
+                    if path.exists() {
+                        Ok((c, path))
+                    } else {
+                        Err(format!("level {} is missing its model file", c.level_id))
+                    }
+                }
+            },
+        };
+        // The menu already switched to the game screen; send it back
+        // rather than leave the player on an empty one.
+        let (challenge, path) = match found {
+            Ok(found) => found,
+            Err(reason) => {
+                log::warn!("[Formosaic] Cannot start the daily challenge: {reason}");
+                self.ui_machine = UiStateMachine::new();
+                self.sync_scenegraph(ctx);
+                return;
+            }
+        };
+        log::info!(
+            "[Formosaic] Daily {} → level={}",
+            challenge.date,
+            challenge.level_id
+        );
+        // Everyone plays the same daily, so it ignores the stage setting.
+        // Set before loading so `scramble_roll` sees it.
+        let (level_id, seed) = (challenge.level_id.clone(), challenge.seed);
+        self.end_session();
+        self.daily = Some(challenge);
+        self.begin_saved_level_load(level_id, path, seed, 1, ctx);
+    }
+
+    fn fetch_online_level(&mut self, ctx: &mut SceneContext) {
+        if !self.client.is_explore_pending() {
+            self.daily = None;
+            self.end_session();
+            self.mode = AppMode::FetchingOnline;
+            self.loading_started = Some(Instant::now());
+            self.loading_frames = 0;
+            self.loading_progress = 0.0;
+            self.latest_download_progress = None;
+            // Page 0 is fetched first to discover total, then a random valid
+            // page is chosen — all handled inside fetch_explore.
+            self.client.fetch_explore_page(0, 20);
+            log::info!("[Formosaic] Fetching poly.pizza random page");
+            self.sync_scenegraph(ctx);
+        }
+    }
+
+    /// Cancel the axis search of any load in flight and start tracking a
+    /// new one.  Returns the handle for the load thread.
+    fn start_search(&mut self) -> SearchHandle {
+        self.cancel_search();
+        let handle = SearchHandle::new();
+        self.search_handle = Some(handle.clone());
+        handle
+    }
+
+    /// Stop the axis search of an abandoned load between candidates.  Its
+    /// result would be discarded by the `load_seq` check anyway.
+    fn cancel_search(&mut self) {
+        if let Some(handle) = self.search_handle.take() {
+            handle.cancel();
+        }
+    }
+
+    fn begin_saved_level_load(
+        &mut self,
+        level_id: String,
+        path: PathBuf,
+        seed: PuzzleSeed,
+        stages: usize,
+        ctx: &mut SceneContext,
+    ) {
+        let request_id = self.begin_load(&level_id, ctx);
+        let job = self.load_job(request_id, level_id, seed, stages);
+        let handle = self.start_search();
+        spawn_load(job, path, None, handle, self.load_tx.clone());
+    }
+
+    /// Reset the puzzle for a new load of `level_id` and show the loading
+    /// screen.  Returns the load's request id; results of older loads are
+    /// discarded from now on.
+    fn begin_load(&mut self, level_id: &str, ctx: &mut SceneContext) -> u64 {
+        self.load_seq = self.load_seq.wrapping_add(1);
+        self.incremental_builder = None;
+        self.pending_finalize_builder = None;
+        self.pending_search = None;
+        self.pending_params = None;
+        self.pending_seed = None;
+        self.scene_parts.clear();
+        self.authored_puzzle = None;
+        self.mode = AppMode::Loading {
+            level_id: level_id.to_string(),
+        };
+        self.loading_frames = 0;
+        self.loading_started = Some(Instant::now());
+        self.loading_progress = 0.0;
+        self.latest_download_progress = None;
+        self.model = None;
+        self.entity = None;
+        self.orbit = None;
+        self.scramble_state = None;
+        self.mosaic_tints = None;
+        self.pose_loop = None;
+        self.entropy_report = None;
+        self.stage_reports.clear();
+        self.hints.reset_full(); // New level: reset per-level hint count
+        self.last_score = None;
+        self.recorder = None;
+        self.solve_recording = None;
+        self.replay = None;
+        self.resume = None;
+        self.shared = None;
+        self.share_code = None;
+        self.edit_level = false;
+        self.close_editor();
+        self.sync_scenegraph(ctx);
+        self.load_seq
+    }
+
+    /// What a background load of `level_id` needs, under the current menu
+    /// settings.  A level with an authored puzzle builds that puzzle, with
+    /// its own seed and a single stage; the daily ignores it, since it is
+    /// the same puzzle for everyone.  A composite scene has one stage per
+    /// model, whatever `stages` says.
+    fn load_job(
+        &self,
+        request_id: u64,
+        level_id: String,
+        seed: PuzzleSeed,
+        stages: usize,
+    ) -> LoadJob {
+        let meta = self.registry.levels.iter().find(|m| m.id == level_id);
+        let parts = self.scene_part_files(&level_id);
+        let authored = meta
+            .and_then(|m| m.authored)
+            .filter(|_| self.daily.is_none() && parts.is_empty());
+        LoadJob {
+            request_id,
+            strategy: self.level_strategy(&level_id),
+            upright: self.level_upright(&level_id),
+            max_roll: self.scramble_roll(),
+            level_id,
+            seed: authored.map_or(seed, |p| p.seed),
+            stages: if authored.is_some() { 1 } else { stages },
+            authored,
+            parts,
+        }
+    }
+
+    /// Model files and placements of the other models of `level_id`'s
+    /// composite scene; empty for a single-model level.
+    fn scene_part_files(&self, level_id: &str) -> Vec<(PathBuf, ScenePart)> {
+        self.registry
+            .levels
+            .iter()
+            .find(|m| m.id == level_id)
+            .map(|m| {
+                self.registry
+                    .part_paths(m)
+                    .into_iter()
+                    .zip(m.parts.clone())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// Model file of a saved level, if the level and its file exist.
+    fn saved_model_path(&self, level_id: &str) -> Option<PathBuf> {
+        let meta = self.registry.levels.iter().find(|m| m.id == level_id)?;
+        let path = self.registry.model_path(meta);
+        path.exists().then_some(path)
+    }
+
+    // ── Replays ────────────────────────────────────────────────────────────
+
+    /// Replay the current level's best solve: `Watch` it with the recording
+    /// driving the camera, or `Race` it on the same puzzle.
+    fn start_replay(&mut self, mode: ReplayMode, ctx: &mut SceneContext) {
+        let AppMode::InGame { level_id } = &self.mode else {
+            return;
+        };
+        let level_id = level_id.clone();
+        let Some(recording) = Recording::load(&self.registry.level_dir(&level_id)) else {
+            log::warn!("[Formosaic] No replay for '{level_id}'");
+            return;
+        };
+        let Some(path) = self.saved_model_path(&level_id) else {
+            return;
+        };
+        self.end_session();
+        self.daily = None;
+        let puzzle = recording.puzzle;
+        let request_id = self.begin_load(&level_id, ctx);
+        let mut job = self.load_job(request_id, level_id, puzzle.seed, puzzle.stages);
+        job.max_roll = puzzle.max_roll;
+        let handle = self.start_search();
+        spawn_load(job, path, None, handle, self.load_tx.clone());
+        log::info!(
+            "[Formosaic] {mode:?} replay: {:.1}s, {} frames",
+            recording.time_secs,
+            recording.frames.len()
+        );
+        self.replay = Some(Playback {
+            recording: Rc::new(recording),
+            clock: Rc::new(Cell::new(0.0)),
+            mode,
+        });
+    }
+
+    fn watching_replay(&self) -> bool {
+        self.replay
+            .as_ref()
+            .is_some_and(|p| p.mode == ReplayMode::Watch)
+    }
+
+    /// Move the hints on a tier and note it in the recording.  A watched
+    /// replay takes its hints from the recording instead.
+    fn advance_hint(&mut self) {
+        if self.watching_replay() {
+            return;
+        }
+        self.hints.advance();
+        if let Some(recorder) = &mut self.recorder {
+            recorder.hint(self.hints.tier().as_u8());
+        }
+    }
+
+    /// The raced recording's camera at the current play time, as ghost
+    /// frustum edges.
+    fn ghost_segments(&self, ctx: &SceneContext) -> Option<Vec<Segment>> {
+        let playback = self
+            .replay
+            .as_ref()
+            .filter(|p| p.mode == ReplayMode::Race)?;
+        let frame = playback.recording.sample(self.elapsed_secs)?;
+        let camera = ctx.get_camera().borrow();
+        let aspect = camera.resolution.x.max(1) as f32 / camera.resolution.y.max(1) as f32;
+        let depth = self.orbit.as_ref().map_or(1.0, |o| o.distance) * GHOST_DEPTH;
+        Some(frustum_segments(&frame, camera.get_fov(), aspect, depth))
+    }
+
+    // ── Resume ─────────────────────────────────────────────────────────────
+
+    /// Save the puzzle being played so "Continue" can pick it up again.
+    /// Watched replays, sessions and levels that are not saved locally are
+    /// left out.
+    fn save_puzzle(&mut self, ctx: &mut SceneContext) {
+        let AppMode::InGame { level_id } = &self.mode else {
+            return;
+        };
+        if self.game_state != GameState::Playing
+            || self.watching_replay()
+            || self.session.is_some()
+            || self.saved_model_path(level_id).is_none()
+        {
+            return;
+        }
+        let Some(sc) = &self.scramble_state else {
+            return;
+        };
+        let camera = ctx.camera();
+        let cam = camera.borrow();
+        let saved = SavedPuzzle {
+            level_id: level_id.clone(),
+            puzzle: ReplayPuzzle {
+                seed: sc.seed,
+                stages: sc.stage_count(),
+                max_roll: sc.params.max_roll,
+                projection: sc.params.projection,
+            },
+            stage: sc.current_stage,
+            camera_position: cam.transform.position,
+            camera_roll: camera_roll(cam.transform.forward(), cam.transform.up()),
+            elapsed_secs: self.elapsed_secs,
+            hint_tier: self.hints.tier(),
+            hint_count: self.hints.hint_count(),
+            tier_uses: self.hints.tier_uses(),
+            daily: self.daily.as_ref().map(|d| d.date),
+        };
+        saved.save(&LevelRegistry::default_data_dir());
+        self.has_saved_puzzle = true;
+        log::info!(
+            "[Formosaic] Saved '{}' at stage {} ({:.1}s)",
+            saved.level_id,
+            saved.stage,
+            saved.elapsed_secs
+        );
+    }
+
+    /// Reload the saved puzzle: same level, seed and scramble settings.
+    /// Stage, camera, clock and hints are put back in `finalize_loaded_model`.
+    fn start_resume(&mut self, ctx: &mut SceneContext) {
+        let data_dir = LevelRegistry::default_data_dir();
+        let Some(saved) = SavedPuzzle::load(&data_dir) else {
+            SavedPuzzle::clear(&data_dir);
+            self.has_saved_puzzle = false;
+            return;
+        };
+        let Some(path) = self.saved_model_path(&saved.level_id) else {
+            log::warn!("[Formosaic] Saved level {} is gone", saved.level_id);
+            SavedPuzzle::clear(&data_dir);
+            self.has_saved_puzzle = false;
+            return;
+        };
+        self.end_session();
+        // A daily still counts as that day's daily once finished.
+        self.daily = saved
+            .daily
+            .and_then(|date| DailyChallenge::for_date(date, &self.registry.levels))
+            .filter(|c| c.level_id == saved.level_id);
+        let puzzle = saved.puzzle;
+        let request_id = self.begin_load(&saved.level_id, ctx);
+        let level_id = saved.level_id.clone();
+        let mut job = self.load_job(request_id, level_id, puzzle.seed, puzzle.stages);
+        job.max_roll = puzzle.max_roll;
+        let handle = self.start_search();
+        spawn_load(job, path, None, handle, self.load_tx.clone());
+        log::info!(
+            "[Formosaic] Continuing '{}' at stage {}",
+            saved.level_id,
+            saved.stage
+        );
+        self.resume = Some(saved);
+    }
+
+    // ── Share codes ────────────────────────────────────────────────────────
+
+    /// Play the puzzle a share code names, downloading its level first when
+    /// it is not saved locally.
+    fn start_shared(&mut self, code: ShareCode, ctx: &mut SceneContext) {
+        self.end_session();
+        self.daily = None;
+        self.share_error = None;
+        if let Some(path) = self.saved_model_path(&code.level_id) {
+            self.begin_shared_load(code, path, None, ctx);
+            return;
+        }
+        if self.client.is_download_pending() {
+            log::warn!("[Formosaic] A download is already running");
+            return;
+        }
+        log::info!("[Formosaic] Fetching shared level {}", code.level_id);
+        self.mode = AppMode::Downloading {
+            summary: ModelSummary {
+                id: code.level_id.clone(),
+                name: code.level_id.clone(),
+                author: String::new(),
+                license: String::new(),
+                thumbnail_url: String::new(),
+                source_url: format!("https://poly.pizza/m/{}", code.level_id),
+                download_url: String::new(),
+            },
+        };
+        self.loading_started = Some(Instant::now());
+        self.loading_frames = 0;
+        self.loading_progress = 0.0;
+        self.latest_download_progress = None;
+        self.client.download_model_by_id(&code.level_id);
+        self.shared = Some(code);
+        self.sync_scenegraph(ctx);
+    }
+
+    /// Load a share code's level from `path` (or the `bytes` just
+    /// downloaded) as the puzzle the code names.
+    fn begin_shared_load(
+        &mut self,
+        code: ShareCode,
+        path: PathBuf,
+        bytes: Option<Vec<u8>>,
+        ctx: &mut SceneContext,
+    ) {
+        let request_id = self.begin_load(&code.level_id, ctx);
+        let parts = self.scene_part_files(&code.level_id);
+        let job = LoadJob::from_share_code(request_id, &code, parts);
+        let handle = self.start_search();
+        spawn_load(job, path, bytes, handle, self.load_tx.clone());
+        self.shared = Some(code);
+    }
+
+    // ── Puzzle editor ──────────────────────────────────────────────────────
+
+    /// Open a saved level in the editor.  Its authored puzzle is loaded if
+    /// it has one, otherwise a searched one to start from.  A composite
+    /// scene cannot be edited: an authored puzzle has a single axis.
+    fn start_editor(&mut self, level_id: String, ctx: &mut SceneContext) {
+        let is_scene = self
+            .registry
+            .levels
+            .iter()
+            .any(|m| m.id == level_id && !m.parts.is_empty());
+        let path = self.saved_model_path(&level_id).filter(|_| !is_scene);
+        let Some(path) = path else {
+            self.ui_machine = UiStateMachine::new();
+            return;
+        };
+        self.end_session();
+        self.daily = None;
+        let request_id = self.begin_load(&level_id, ctx);
+        let seed = Self::fresh_seed(&level_id);
+        let mut job = self.load_job(request_id, level_id, seed, 1);
+        job.max_roll = 0.0;
+        let handle = self.start_search();
+        spawn_load(job, path, None, handle, self.load_tx.clone());
+        self.edit_level = true;
+    }
+
+    /// Hand the puzzle just built to the editor, starting from its `axis`.
+    fn enter_editor(&mut self, level_id: String, axis: Vector3<f32>) {
+        let Some(sc) = &self.scramble_state else {
+            return;
+        };
+        let authored = self
+            .registry
+            .levels
+            .iter()
+            .any(|m| m.id == level_id && m.authored.is_some());
+        let pos_slices: Vec<&[f32]> = sc.original_positions.iter().map(|p| p.as_slice()).collect();
+        let clusters = flat_clusters(&pos_slices, sc.params.strategy);
+        log::info!("[Formosaic] Editing '{level_id}'");
+        self.editor = Some(Editor {
+            level_id,
+            puzzle: AuthoredPuzzle {
+                axis,
+                min_disp: sc.params.min_disp,
+                max_disp: sc.params.max_disp,
+                seed: sc.seed,
+            },
+            disp_limit: (sc.params.model_space_radius * EDITOR_DISP_LIMIT).max(sc.params.max_disp),
+            preview_t: 1.0,
+            authored,
+            dirty: false,
+            positions: pos_slices.concat(),
+            clusters,
+            params: sc.params,
+            heatmap: None,
+            heatmap_job: None,
+            heatmap_stale: true,
+            report: None,
+            analysis_due: Some(0.0),
+        });
+        self.game_state = GameState::Editing;
+    }
+
+    fn apply_editor_edit(&mut self, edit: EditorEdit, ctx: &mut SceneContext) {
+        let Some(editor) = &mut self.editor else {
+            return;
+        };
+        match edit {
+            EditorEdit::Axis(axis) => editor.puzzle = editor.puzzle.with_axis(axis),
+            EditorEdit::Displacement { min, max } => {
+                let limit = editor.disp_limit;
+                editor.puzzle = editor.puzzle.with_disp(min.min(limit), max.min(limit));
+                editor.heatmap_stale = true;
+            }
+            EditorEdit::Reseed => {
+                editor.puzzle.seed = Self::fresh_seed(&editor.level_id);
+                editor.heatmap_stale = true;
+            }
+            EditorEdit::Preview(t) => {
+                editor.preview_t = t.clamp(0.0, 1.0);
+                if let Some(model) = &self.model {
+                    apply_displacement(model, editor.preview_t);
+                }
+                return;
+            }
+            EditorEdit::ViewAxis => {
+                self.view_editor_axis(ctx);
+                return;
+            }
+        }
+        editor.dirty = true;
+        editor.analysis_due = Some(EDITOR_ANALYSIS_DELAY);
+        self.rebuild_editor_preview();
+    }
+
+    /// Rebuild the scramble from the editor's puzzle, the way a play of the
+    /// level will build it.
+    fn rebuild_editor_preview(&mut self) {
+        let (Some(editor), Some(model), Some(entity), Some(sc)) = (
+            &self.editor,
+            &self.model,
+            &self.entity,
+            &mut self.scramble_state,
+        ) else {
+            return;
+        };
+        let puzzle = editor.puzzle;
+        sc.params = puzzle.apply(sc.params);
+        sc.seed = puzzle.seed;
+        let split = StageSplit::Bands(1);
+        let (_, stage_offsets) = compute_model_stages(
+            &model.borrow(),
+            &sc.original_positions,
+            &[puzzle.axis],
+            &split,
+            &sc.params,
+            puzzle.seed,
+        );
+        let Some(offsets) = stage_offsets.into_iter().next() else {
+            return;
+        };
+        let entity_rot = entity.borrow().transform().rotation;
+        sc.stages[0].solution_dir = (entity_rot * puzzle.axis).normalize();
+        sc.stages[0].scramble_offsets = offsets;
+        upload_stage(model, sc);
+        apply_displacement(model, editor.preview_t);
+        self.seam_samples = None;
+    }
+
+    /// Analyse the editor's axis, and restart the heatmap if the
+    /// displacements changed.  The old heatmap stays up until the new one
+    /// is in.
+    fn refresh_editor_analysis(&mut self) {
+        let Some(editor) = &mut self.editor else {
+            return;
+        };
+        let params = editor.puzzle.apply(editor.params);
+        let stage = analyse_fixed_axis(
+            &editor.positions,
+            &editor.clusters,
+            editor.puzzle.axis,
+            &params,
+            editor.puzzle.seed,
+            ENTROPY_BACKEND,
+        );
+        editor.report = Some(stage.report);
+        editor.analysis_due = None;
+        if !std::mem::take(&mut editor.heatmap_stale) {
+            return;
+        }
+        if let Some(stale) = editor.heatmap_job.take() {
+            stale.handle.cancel();
+        }
+        let (positions, clusters) = (editor.positions.clone(), editor.clusters.clone());
+        let (puzzle, params) = (editor.puzzle, editor.params);
+        let handle = SearchHandle::new();
+        let options = SearchOptions {
+            backend: ENTROPY_BACKEND,
+            threads: 0,
+            handle: handle.clone(),
+        };
+        let (tx, rx) = channel();
+        std::thread::spawn(move || {
+            if let Some(map) = AxisHeatmap::new(
+                &positions,
+                &clusters,
+                &puzzle,
+                &params,
+                HEATMAP_COLS,
+                HEATMAP_ROWS,
+                &options,
+            ) {
+                let _ = tx.send(map);
+            }
+        });
+        editor.heatmap_job = Some(HeatmapJob { rx, handle });
+    }
+
+    /// Take the editor's heatmap if its thread has finished.
+    fn poll_editor_heatmap(&mut self) {
+        let Some(editor) = &mut self.editor else {
+            return;
+        };
+        let Some(job) = &editor.heatmap_job else {
+            return;
+        };
+        if let Ok(map) = job.rx.try_recv() {
+            editor.heatmap = Some(Rc::new(map));
+            editor.heatmap_job = None;
+        }
+    }
+
+    /// Leave the editor, stopping its heatmap.
+    fn close_editor(&mut self) {
+        if let Some(job) = self.editor.take().and_then(|e| e.heatmap_job) {
+            job.handle.cancel();
+        }
+    }
+
+    /// Move the camera onto the editor's axis, on the side it is already on.
+    fn view_editor_axis(&mut self, ctx: &mut SceneContext) {
+        let (Some(sc), Some(entity)) = (&self.scramble_state, &self.entity) else {
+            return;
+        };
+        let centroid = entity.borrow().centroid();
+        let dist = sc.params.orbit_distance;
+        let dir = sc.stages[0].solution_dir;
+        let camera = ctx.camera();
+        // Either end of a parallel axis solves.
+        let side = if (camera.borrow().transform.position - centroid).dot(dir) < 0.0 {
+            -1.0
+        } else {
+            1.0
+        };
+        let position = centroid + dir * (side * dist);
+        let mut ctrl = OrbitController::new(centroid, dist);
+        let target_entity = entity.clone();
+        ctrl.set_target_provider(Some(Box::new(move || target_entity.borrow().centroid())));
+        ctrl.set_initial_position(position);
+        let mut cam = camera.borrow_mut();
+        cam.transform.position = position;
+        cam.transform.look_at(centroid, Vector3::unit_y());
+        cam.set_controller(Some(Box::new(ctrl)));
+    }
+
+    /// Store the editor's puzzle in its level, or with `keep` false drop the
+    /// level's authored puzzle so plays search for an axis again.
+    fn save_authored(&mut self, keep: bool) {
+        if matches!(&self.editor, Some(e) if e.analysis_due.is_some()) {
+            self.refresh_editor_analysis();
+        }
+        let Some(editor) = &self.editor else {
+            return;
+        };
+        let (level_id, puzzle) = (editor.level_id.clone(), editor.puzzle);
+        if keep {
+            let difficulty = editor.report.map(|r| self.calibrated_difficulty(&r, 1));
+            if let (Some(m), Some(d)) = (
+                self.registry.levels.iter_mut().find(|m| m.id == level_id),
+                difficulty,
+            ) {
+                m.difficulty = d;
+            }
+        }
+        let puzzle_kept = keep.then_some(puzzle);
+        if !self.registry.set_authored(&level_id, puzzle_kept) {
+            return;
+        }
+        if let Some(editor) = &mut self.editor {
+            editor.authored = keep;
+            editor.dirty = !keep;
+        }
+        if keep {
+            log::info!(
+                "[Formosaic] Saved puzzle for '{level_id}': {}",
+                puzzle.to_meta_str()
+            );
+        } else {
+            log::info!("[Formosaic] Cleared the puzzle of '{level_id}'");
+        }
+    }
+
+    // ── Sessions ───────────────────────────────────────────────────────────
+
+    /// Start a marathon or time attack over the saved levels.
+    fn start_session(&mut self, kind: SessionKind, ctx: &mut SceneContext) {
+        self.end_session();
+        self.daily = None;
+        match Session::new(kind, &self.registry.levels, PuzzleSeed::random()) {
+            Some(session) => {
+                log::info!("[Formosaic] {} started: {:?}", kind.label(), session.kind);
+                self.session = Some(session);
+                self.next_session_level(ctx);
+            }
+            None => log::warn!("[Formosaic] No saved levels for a {}", kind.label()),
+        }
+    }
+
+    /// Move the session on to its next level: straight from the prefetch
+    /// when it holds that level, loaded from scratch otherwise.
+    fn next_session_level(&mut self, ctx: &mut SceneContext) {
+        let Some(level_id) = self.session.as_mut().and_then(|s| s.next_level()) else {
+            return;
+        };
+        match self.prefetch.take() {
+            Some(prefetch) if prefetch.level_id == level_id => {
+                let request_id = self.begin_load(&level_id, ctx);
+                self.cancel_search();
+                self.search_handle = Some(prefetch.handle);
+                let rx = prefetch.rx;
+                let tx = self.load_tx.clone();
+                // Hand the result over as this load's once it is in.
+                std::thread::spawn(move || {
+                    if let Ok(result) = rx.recv() {
+                        let _ = tx.send(LoadResult {
+                            request_id,
+                            ..result
+                        });
+                    }
+                });
+            }
+            other => {
+                if let Some(stale) = other {
+                    stale.handle.cancel();
+                }
+                let Some(path) = self.saved_model_path(&level_id) else {
+                    log::warn!("[Formosaic] Session level {level_id} is missing its model file");
+                    self.end_session();
+                    return;
+                };
+                let seed = Self::fresh_seed(&level_id);
+                self.begin_saved_level_load(level_id, path, seed, self.stage_count, ctx);
+            }
+        }
+    }
+
+    /// Start loading the session's next level in the background, unless it
+    /// already is.
+    fn prefetch_next_session_level(&mut self) {
+        let Some(level_id) = self.session.as_ref().and_then(|s| s.peek_next()) else {
+            return;
+        };
+        if self
+            .prefetch
+            .as_ref()
+            .is_some_and(|p| p.level_id == level_id)
+        {
+            return;
+        }
+        self.cancel_prefetch();
+        let Some(path) = self.saved_model_path(&level_id) else {
+            return;
+        };
+        // The request id is assigned when the session gets there.
+        let seed = Self::fresh_seed(&level_id);
+        let job = self.load_job(0, level_id.clone(), seed, self.stage_count);
+        let handle = SearchHandle::new();
+        let (tx, rx) = channel();
+        spawn_load(job, path, None, handle.clone(), tx);
+        log::info!("[Formosaic] Prefetching session level {level_id}");
+        self.prefetch = Some(Prefetch {
+            level_id,
+            rx,
+            handle,
+        });
+    }
+
+    fn cancel_prefetch(&mut self) {
+        if let Some(prefetch) = self.prefetch.take() {
+            prefetch.handle.cancel();
         }
-        // No saved levels — fetch one online instead
-        log::info!("[Formosaic] No saved levels for random — fetching online");
-        self.fetch_online_level(ctx);
     }
 
-    fn fetch_online_level(&mut self, ctx: &mut SceneContext) {
-        if !self.client.is_explore_pending() {
-            self.mode = AppMode::FetchingOnline;
-            self.loading_started = Some(Instant::now());
-            self.loading_frames = 0;
-            self.loading_progress = 0.0;
-            self.latest_download_progress = None;
-            // Page 0 is fetched first to discover total, then a random valid
-            // page is chosen — all handled inside fetch_explore.
-            self.client.fetch_explore_page(0, 20);
-            log::info!("[Formosaic] Fetching poly.pizza random page");
-            self.sync_scenegraph(ctx);
-        }
+    /// Abandon any session without recording it, and forget the last
+    /// session's result.
+    fn end_session(&mut self) {
+        self.session = None;
+        self.session_result = None;
+        self.cancel_prefetch();
     }
 
-    fn begin_saved_level_load(&mut self, level_id: String, path: PathBuf, ctx: &mut SceneContext) {
-        self.load_seq = self.load_seq.wrapping_add(1);
-        self.incremental_builder = None;
-        self.pending_finalize_builder = None;
-        self.pending_axis = None;
-        self.pending_report = None;
-        self.pending_params = None;
-        let request_id = self.load_seq;
-        self.mode = AppMode::Loading {
-            level_id: level_id.clone(),
+    /// Close the session and put its result on the leaderboard.
+    fn finish_session(&mut self) {
+        let current = self.session_current_secs();
+        let Some(session) = self.session.take() else {
+            return;
         };
-        self.loading_frames = 0;
-        self.loading_started = Some(Instant::now());
-        self.loading_progress = 0.0;
-        self.latest_download_progress = None;
-        self.model = None;
-        self.entity = None;
-        self.orbit = None;
-        self.scramble_state = None;
-        self.entropy_report = None;
-        self.hints.reset_full(); // New level: reset per-level hint count
-        self.sync_scenegraph(ctx);
-        let tx = self.load_tx.clone();
-        std::thread::spawn(move || {
-            let bytes = match std::fs::read(&path) {
-                Ok(b) => b,
-                Err(e) => {
-                    log::error!("[Formosaic] Failed to read model file: {e}");
-                    return;
-                }
-            };
-            let data = match ModelLoader::prepare_from_bytes_with_path(
-                path.to_string_lossy().as_ref(),
-                &bytes,
-            ) {
-                Ok(d) => d,
-                Err(e) => {
-                    log::error!("[Formosaic] Failed to parse model: {e}");
-                    return;
-                }
-            };
-            let pos_slices: Vec<&[f32]> =
-                data.meshes.iter().map(|m| m.positions.as_slice()).collect();
-            let params = PuzzleParams::from_raw_positions(
-                &pos_slices,
-                &data.mesh_transforms,
-                TARGET_WORLD_RADIUS,
-                CAMERA_FOV,
-            );
-            let flat_positions: Vec<f32> = data
-                .meshes
-                .iter()
-                .flat_map(|m| m.positions.iter().copied())
-                .collect();
-            let search = best_scramble_axis_from_offsets(
-                &flat_positions,
-                params.min_disp,
-                params.max_disp,
-                ENTROPY_CANDIDATES,
-            );
-            let _ = tx.send(LoadResult {
-                request_id,
-                level_id,
-                data,
-                axis: search.axis,
-                report: search.report,
-                params,
-            });
-        });
+        self.cancel_prefetch();
+        let entry = session.result(UtcDate::today(), current);
+        let rank = self.leaderboard.record(entry.clone());
+        log::info!(
+            "[Formosaic] {} over: {} solved in {:.1}s, rank {:?}",
+            session.kind.label(),
+            entry.solved,
+            entry.time_secs,
+            rank
+        );
+        self.session_result = Some((entry, rank));
+    }
+
+    /// Leave the puzzle for the session results.
+    fn show_session_results(&mut self, ctx: &mut SceneContext) {
+        let transitions = self
+            .ui_machine
+            .handle(UiInput::SessionOver, &UiContext::default());
+        self.apply_ui_transitions(transitions, ctx);
+    }
+
+    /// The time attack ran out mid-puzzle.
+    fn time_up(&mut self, ctx: &mut SceneContext) {
+        self.finish_session();
+        self.game_state = GameState::TimeUp;
+        self.show_session_results(ctx);
+    }
+
+    /// The running puzzle's share of the session clock: its play time until
+    /// `finish_restore` banks it with the session.
+    fn session_current_secs(&self) -> f32 {
+        match self.game_state {
+            GameState::Playing | GameState::Restoring { .. } => self.elapsed_secs,
+            GameState::Solved | GameState::TimeUp | GameState::Editing => 0.0,
+        }
     }
 
     // ── Puzzle ─────────────────────────────────────────────────────────────
 
+    /// A non-final stage of a multi-stage puzzle was found: lock its band in
+    /// place and hand the player the next axis.
+    fn complete_stage(&mut self) {
+        let Some(sc) = &mut self.scramble_state else {
+            return;
+        };
+        if !sc.advance_stage() {
+            return;
+        }
+        log::info!(
+            "[Formosaic] Stage {}/{} found at {:.1}s",
+            sc.current_stage,
+            sc.stage_count(),
+            self.elapsed_secs
+        );
+        self.hints.next_stage();
+        if let Some(model) = &self.model {
+            upload_stage(model, sc);
+            upload_tints(model, sc, self.mosaic_tints.as_deref());
+            apply_displacement(model, 1.0);
+        }
+    }
+
     fn trigger_solve(&mut self, ctx: &mut SceneContext) {
         if self.game_state != GameState::Playing {
             return;
         }
-        let solution_dir = match &self.scramble_state {
-            Some(s) => s.solution_dir,
+        let (solution_dir, solution_eye) = match &self.scramble_state {
+            Some(s) => (s.solution_dir(), s.solution_eye()),
             None => return,
         };
 
@@ -602,12 +2130,16 @@ impl Formosaic {
         );
 
         // Snap the model to solved immediately so the camera target is based on
-        // the solved visual center, not the scrambled vertex positions.
-        if let (Some(model), Some(sc)) = (&self.model, &self.scramble_state) {
-            for (mesh_idx, offsets) in sc.scramble_offsets.iter().enumerate() {
-                let orig = &sc.original_positions[mesh_idx];
-                let solved: Vec<f32> = orig.iter().zip(offsets.iter()).map(|(p, _)| *p).collect();
-                model.borrow_mut().upload_mesh_positions(mesh_idx, solved);
+        // the solved visual center, not the scrambled vertex positions.  This
+        // is the final stage, so t = 0 draws the original positions.
+        if let Some(model) = &self.model {
+            apply_displacement(model, 0.0);
+            model.borrow_mut().set_mosaic_t(0.0);
+            // A playing clip stops in the pose the scramble lines up in.
+            if self.pose_loop.take().is_some() {
+                if let Some(pose) = self.scramble_state.as_ref().and_then(|s| s.pose.as_ref()) {
+                    hold_pose(&mut model.borrow_mut(), pose.pose);
+                }
             }
         }
 
@@ -626,8 +2158,14 @@ impl Formosaic {
         } else {
             -solution_dir
         };
-        let cam_end = target - dir * dist;
+        // An anamorphic puzzle only looks whole from its eye point.
+        let cam_end = solution_eye.unwrap_or(target - dir * dist);
         let cam_start = cam.transform.position;
+        let roll_start = camera_roll(fwd, cam.transform.up());
+        self.solve_recording = self
+            .recorder
+            .take()
+            .map(|r| r.finish(&cam.transform, self.elapsed_secs));
         drop(cam);
         camera.borrow_mut().set_controller(None);
         self.game_state = GameState::Restoring {
@@ -635,6 +2173,7 @@ impl Formosaic {
             cam_start,
             cam_end,
             target,
+            roll_start,
         };
 
         if let Some(start) = self.level_start {
@@ -643,39 +2182,16 @@ impl Formosaic {
     }
 
     fn pick_solve_animation(model: &Rc<RefCell<SimpleModel>>) {
-        let anims = model.borrow().animations().to_vec();
-        if anims.is_empty() {
-            return;
+        let clip = solve_clip(model.borrow().animations());
+        if let Some(idx) = clip {
+            model.borrow_mut().play_animation(idx);
         }
-        let prefer = [
-            "idle", "interact", "pose", "stand", "celebrat", "victory", "win", "success",
-        ];
-        let avoid = [
-            "run", "walk", "jump", "fall", "hit", "punch", "attack", "roll", "hurt",
-        ];
-        let idx = anims
-            .iter()
-            .enumerate()
-            .filter(|(_, clip)| {
-                let n = clip.name.to_lowercase();
-                !avoid.iter().any(|k| n.contains(k))
-            })
-            .max_by_key(|(_, clip)| {
-                let n = clip.name.to_lowercase();
-                prefer
-                    .iter()
-                    .position(|k| n.contains(k))
-                    .map(|i| 100 - i as i32)
-                    .unwrap_or(0)
-            })
-            .map(|(i, _)| i)
-            .unwrap_or(0);
-        model.borrow_mut().play_animation(idx);
     }
 
     fn finish_restore(&mut self, ctx: &mut SceneContext) {
         let dist = self.orbit.as_ref().map(|o| o.distance).unwrap_or(3.0);
         let pos = ctx.camera().borrow().transform.position;
+        let hints_used = self.hints.hint_count();
         self.game_state = GameState::Solved;
         self.solved_timer = 0.0;
         self.hints.reset();
@@ -702,16 +2218,81 @@ impl Formosaic {
             camera.set_controller(Some(Box::new(post)));
         }
 
+        // A watched replay ends here, leaving the level's records alone.
+        if self
+            .replay
+            .take()
+            .is_some_and(|p| p.mode == ReplayMode::Watch)
+        {
+            self.sync_scenegraph(ctx);
+            return;
+        }
+
         let level_id = match &self.mode {
             AppMode::InGame { level_id } => level_id.clone(),
             _ => return,
         };
-        self.registry
-            .record_completion(&level_id, self.elapsed_secs);
+        let difficulty = self
+            .entropy_report
+            .map(|r| self.calibrated_difficulty(&r, self.stage_reports.len()))
+            .unwrap_or(0.5);
+        let score = scoring::score(
+            self.elapsed_secs,
+            &self.hints.tier_uses(),
+            difficulty,
+            self.stage_reports.len(),
+        );
+        let new_best = self
+            .registry
+            .record_completion(&level_id, self.elapsed_secs, score);
+        log::info!(
+            "[Formosaic] Solved '{}': {} points, {} stars{}",
+            level_id,
+            score.points,
+            score.stars,
+            if new_best { " (new best)" } else { "" }
+        );
+        self.last_score = Some((score, new_best));
+        if let Some(recording) = self.solve_recording.take().filter(|_| new_best) {
+            match recording.save(&self.registry.level_dir(&level_id)) {
+                Ok(()) => self.has_replay = true,
+                Err(e) => log::warn!("[Formosaic] Failed to save the replay of '{level_id}': {e}"),
+            }
+        }
+        self.record_outcome(&level_id, hints_used, false);
+        if let Some(daily) = self.daily.as_ref().filter(|d| d.level_id == level_id) {
+            self.daily_log
+                .record_completion(daily.date, &level_id, self.elapsed_secs, hints_used);
+        }
+        if let Some(session) = &mut self.session {
+            session.record_solve(self.elapsed_secs);
+            if session.is_over(0.0) {
+                self.finish_session();
+            }
+        }
 
         self.sync_scenegraph(ctx);
     }
 
+    /// Log how the current puzzle went and refit the difficulty model.
+    /// Puzzles still loading (no entropy report yet) are not logged.
+    fn record_outcome(&mut self, level_id: &str, hints: u32, gave_up: bool) {
+        let Some(report) = self.entropy_report else {
+            return;
+        };
+        self.outcomes.record(PlayOutcome {
+            level_id: level_id.to_string(),
+            time_secs: self.elapsed_secs,
+            hints,
+            gave_up,
+            features: DifficultyFeatures::from_report(&report, self.stage_reports.len()),
+        });
+        if let Some(model) = self.outcomes.calibrate() {
+            save_calibration(&LevelRegistry::default_data_dir(), &model);
+            self.calibration = Some(model);
+        }
+    }
+
     // ── Download polling ────────────────────────────────────────────────────
 
     // ── Download polling ────────────────────────────────────────────────────
@@ -770,6 +2351,14 @@ impl Formosaic {
         if let Some(result) = self.client.poll_download() {
             match result {
                 Ok(dl) => self.on_download_complete(dl, ctx),
+                Err(e) if self.shared.is_some() => {
+                    let id = self.shared.take().map(|c| c.level_id).unwrap_or_default();
+                    log::warn!("[Formosaic] Shared level {id} unavailable: {e}");
+                    self.share_error = Some(format!("Level {id} could not be downloaded"));
+                    self.mode = AppMode::LevelSelect;
+                    self.ui_machine = UiStateMachine::new();
+                    self.sync_scenegraph(ctx);
+                }
                 Err(e) => {
                     log::warn!("[Formosaic] Download failed: {} — retrying fetch", e);
                     // Re-fetch a new model rather than falling back to cactus
@@ -795,8 +2384,14 @@ impl Formosaic {
             source_url: dl.source_url.clone(),
             model_file: format!("model.{}", dl.file_ext),
             best_time_secs: None,
+            best_score: None,
+            stars: 0,
             play_count: 0,
             difficulty: 0.5, // updated after entropy analysis below
+            scramble_strategy: ScrambleStrategy::default(),
+            upright: false,
+            authored: None,
+            parts: Vec::new(),
         };
 
         if let Err(e) = self.registry.save_level(meta.clone(), &dl.bytes) {
@@ -806,19 +2401,28 @@ impl Formosaic {
         let path = self.registry.model_path(&meta);
         // Pass the bytes we already have in memory — avoids re-reading from disk,
         // which would go through the JNI asset manager on Android and crash.
-        self.begin_preloaded_level_load(
-            meta.id.clone(),
-            path.to_string_lossy().into_owned(),
-            dl.bytes,
-            ctx,
-        );
+        match self.shared.take().filter(|c| c.level_id == dl.id) {
+            Some(code) => self.begin_shared_load(code, path, Some(dl.bytes), ctx),
+            None => {
+                let seed = Self::fresh_seed(&meta.id);
+                self.begin_preloaded_level_load(
+                    meta.id.clone(),
+                    path.to_string_lossy().into_owned(),
+                    dl.bytes,
+                    seed,
+                    self.stage_count,
+                    ctx,
+                );
+            }
+        }
 
         // Persist the entropy-derived difficulty now that analysis has run.
         if let Some(report) = self.entropy_report {
+            let difficulty = self.calibrated_difficulty(&report, self.stage_reports.len());
             let data_dir = LevelRegistry::default_data_dir();
             let meta_path = data_dir.join("levels").join(&dl.id).join("meta.json");
             if let Some(m) = self.registry.levels.iter_mut().find(|m| m.id == dl.id) {
-                m.difficulty = report.difficulty;
+                m.difficulty = difficulty;
                 let _ = std::fs::write(meta_path, m.to_json());
             }
         }
@@ -829,66 +2433,15 @@ impl Formosaic {
         level_id: String,
         path: String,
         bytes: Vec<u8>,
+        seed: PuzzleSeed,
+        stages: usize,
         ctx: &mut SceneContext,
     ) {
-        self.load_seq = self.load_seq.wrapping_add(1);
-        self.incremental_builder = None;
-        self.pending_finalize_builder = None;
-        self.pending_axis = None;
-        self.pending_report = None;
-        self.pending_params = None;
-        let request_id = self.load_seq;
-        self.mode = AppMode::Loading {
-            level_id: level_id.clone(),
-        };
-        self.loading_frames = 0;
-        self.loading_started = Some(Instant::now());
-        self.loading_progress = 0.0;
-        self.latest_download_progress = None;
-        self.model = None;
-        self.entity = None;
-        self.orbit = None;
-        self.scramble_state = None;
-        self.entropy_report = None;
-        self.hints.reset_full(); // New level: reset per-level hint count
-        self.sync_scenegraph(ctx);
+        let request_id = self.begin_load(&level_id, ctx);
+        let job = self.load_job(request_id, level_id, seed, stages);
+        let handle = self.start_search();
         let tx = self.load_tx.clone();
-        std::thread::spawn(move || {
-            let data = match ModelLoader::prepare_from_bytes_with_path(&path, &bytes) {
-                Ok(d) => d,
-                Err(e) => {
-                    log::error!("[Formosaic] Failed to parse preloaded model: {e}");
-                    return;
-                }
-            };
-            let pos_slices: Vec<&[f32]> =
-                data.meshes.iter().map(|m| m.positions.as_slice()).collect();
-            let params = PuzzleParams::from_raw_positions(
-                &pos_slices,
-                &data.mesh_transforms,
-                TARGET_WORLD_RADIUS,
-                CAMERA_FOV,
-            );
-            let flat_positions: Vec<f32> = data
-                .meshes
-                .iter()
-                .flat_map(|m| m.positions.iter().copied())
-                .collect();
-            let search = best_scramble_axis_from_offsets(
-                &flat_positions,
-                params.min_disp,
-                params.max_disp,
-                ENTROPY_CANDIDATES,
-            );
-            let _ = tx.send(LoadResult {
-                request_id,
-                level_id,
-                data,
-                axis: search.axis,
-                report: search.report,
-                params,
-            });
-        });
+        spawn_load(job, PathBuf::from(path), Some(bytes), handle, tx);
     }
 
     // ── Public accessors (read by game_engine / pipeline) ──────────────
@@ -940,7 +2493,7 @@ impl Formosaic {
     }
 
     pub fn solution_dir(&self) -> Option<Vector3<f32>> {
-        self.scramble_state.as_ref().map(|s| s.solution_dir)
+        self.scramble_state.as_ref().map(|s| s.solution_dir())
     }
 
     /// Current scene lighting configuration.
@@ -978,6 +2531,7 @@ impl Formosaic {
         #[cfg(target_os = "android")]
         crate::ui::touch_buttons::register(scene, Rc::clone(&state));
         crate::ui::credits::register(scene, Rc::clone(&state));
+        crate::ui::editor::register(scene, Rc::clone(&state));
         if let Some(entity) = &self.entity {
             scene.add_node(entity.clone());
         }
@@ -998,17 +2552,32 @@ impl Formosaic {
         for transition in transitions {
             match transition {
                 UiTransition::ShowMainMenu => {
+                    self.cancel_search();
+                    if let (AppMode::InGame { level_id }, GameState::Playing, false) =
+                        (&self.mode, &self.game_state, self.watching_replay())
+                    {
+                        let level_id = level_id.clone();
+                        let hints = self.hints.hint_count();
+                        self.record_outcome(&level_id, hints, true);
+                    }
                     self.mode = AppMode::LevelSelect;
                     self.model = None;
                     self.entity = None;
                     self.orbit = None;
                     self.scramble_state = None;
                     self.entropy_report = None;
+                    self.outline = None;
+                    self.seam_samples = None;
                     self.incremental_builder = None;
                     self.pending_finalize_builder = None;
-                    self.pending_axis = None;
-                    self.pending_report = None;
+                    self.pending_search = None;
                     self.pending_params = None;
+                    self.pending_seed = None;
+                    self.daily = None;
+                    self.end_session();
+                    self.recorder = None;
+                    self.replay = None;
+                    self.close_editor();
                     self.sync_scenegraph(ctx);
                 }
                 UiTransition::ShowCredits => {
@@ -1018,13 +2587,73 @@ impl Formosaic {
                     if let Some(meta) = self.registry.levels.iter().find(|m| m.id == id).cloned() {
                         let path = self.registry.model_path(&meta);
                         if path.exists() {
-                            self.begin_saved_level_load(meta.id.clone(), path, ctx);
+                            let seed = Self::fresh_seed(&meta.id);
+                            self.begin_saved_level_load(
+                                meta.id.clone(),
+                                path,
+                                seed,
+                                self.stage_count,
+                                ctx,
+                            );
                         }
                     }
                 }
+                UiTransition::StartDaily => self.load_daily(ctx),
+                UiTransition::WatchReplay => self.start_replay(ReplayMode::Watch, ctx),
+                UiTransition::RaceReplay => self.start_replay(ReplayMode::Race, ctx),
+                UiTransition::StartMarathon => self.start_session(
+                    SessionKind::Marathon {
+                        levels: MARATHON_LEVELS,
+                    },
+                    ctx,
+                ),
+                UiTransition::StartTimeAttack => self.start_session(
+                    SessionKind::TimeAttack {
+                        budget_secs: TIME_ATTACK_SECS,
+                    },
+                    ctx,
+                ),
+                UiTransition::CycleStages => {
+                    self.stage_count = self.stage_count % MAX_STAGES + 1;
+                    log::info!("[Formosaic] Stage mode → {}", self.stage_count);
+                }
+                UiTransition::ToggleProjection => {
+                    self.projection = match self.projection {
+                        ScrambleProjection::Parallel => ScrambleProjection::Anamorphic,
+                        ScrambleProjection::Anamorphic => ScrambleProjection::Parallel,
+                    };
+                    log::info!("[Formosaic] Projection → {:?}", self.projection);
+                }
+                UiTransition::ToggleRoll => {
+                    self.roll_scramble = !self.roll_scramble;
+                    log::info!("[Formosaic] Rotation scramble → {}", self.roll_scramble);
+                }
+                UiTransition::ToggleMosaic => {
+                    self.mosaic = !self.mosaic;
+                    log::info!("[Formosaic] Mosaic tint → {}", self.mosaic);
+                }
+                UiTransition::ToggleMotion => {
+                    self.motion = !self.motion;
+                    log::info!("[Formosaic] Posed motion → {}", self.motion);
+                }
+                UiTransition::CyclePalette => {
+                    self.settings.warmth_palette = self.settings.warmth_palette.next();
+                    self.hints.set_palette(self.settings.warmth_palette);
+                    self.settings.save(&LevelRegistry::default_data_dir());
+                    log::info!(
+                        "[Formosaic] Warmth palette → {:?}",
+                        self.settings.warmth_palette
+                    );
+                }
                 UiTransition::FetchOnline => self.fetch_online_level(ctx),
                 UiTransition::RandomSaved => self.load_random_saved(ctx),
-                UiTransition::AdvanceHint => self.hints.advance(),
+                UiTransition::Continue => self.start_resume(ctx),
+                UiTransition::PlayCode(code) => self.start_shared(code, ctx),
+                UiTransition::StartEditor(id) => self.start_editor(id, ctx),
+                UiTransition::Edit(edit) => self.apply_editor_edit(edit, ctx),
+                UiTransition::SaveAuthored => self.save_authored(true),
+                UiTransition::ClearAuthored => self.save_authored(false),
+                UiTransition::AdvanceHint => self.advance_hint(),
                 UiTransition::OpenArtistLink(url) => {
                     if let Err(e) = webbrowser::open(&url) {
                         log::warn!("Failed to open URL {}: {e}", url);
@@ -1043,6 +2672,240 @@ impl Default for Formosaic {
 
 // ─── Application impl ─────────────────────────────────────────────────────────
 
+/// Every mesh of freshly parsed model data as the triangle soup the built
+/// model draws (`unweld`): the loader welds identical vertices, so its
+/// positions are shared between faces and cannot be scrambled per face.
+/// A skinned model's are posed (`ModelPose`), as its scramble is.
+pub fn face_positions(data: &ModelLoadData) -> Vec<Vec<f32>> {
+    match ModelPose::of_load_data(data) {
+        Some(pose) => pose.into_posed_positions(),
+        None => data
+            .meshes
+            .iter()
+            .map(|m| unweld(&m.positions, &m.indices))
+            .collect(),
+    }
+}
+
+/// Puzzle params and per-stage entropy search for freshly parsed model data.
+/// Pure CPU work — runs on the background load thread.  The axis search
+/// runs on a worker pool and reports through `handle`; `None` if it was
+/// cancelled.
+///
+/// `split` decides which triangles each stage moves.  A model with fewer
+/// clusters than requested stages gets one stage per cluster, since a
+/// cluster never spans two stages.
+///
+/// Also used by the headless `analyse` binary, so its reports match what
+/// the game would build.
+pub fn analyse_load_data(
+    data: &ModelLoadData,
+    split: &StageSplit,
+    strategy: ScrambleStrategy,
+    upright: bool,
+    max_roll: f32,
+    seed: PuzzleSeed,
+    handle: &SearchHandle,
+) -> Option<(PuzzleParams, StagedSearchResult)> {
+    let faces = face_positions(data);
+    let pos_slices: Vec<&[f32]> = faces.iter().map(Vec::as_slice).collect();
+    let clusters = flat_clusters(&pos_slices, strategy);
+    let params = PuzzleParams::from_raw_positions(
+        &pos_slices,
+        &data.mesh_transforms,
+        TARGET_WORLD_RADIUS,
+        CAMERA_FOV,
+    )
+    .with_strategy(strategy)
+    .with_upright(upright)
+    .with_roll(max_roll)
+    .with_stages(split.stage_count().min(cluster_count(&clusters)));
+    let flat_positions: Vec<f32> = faces.concat();
+    let split = match split {
+        StageSplit::Bands(_) => StageSplit::Bands(params.stage_count),
+        StageSplit::Parts(_) => split.clone(),
+    };
+    let groups: Vec<usize> = split
+        .groups(&pos_slices, &data.mesh_transforms, strategy, seed)
+        .concat();
+    let options = SearchOptions {
+        backend: ENTROPY_BACKEND,
+        threads: 0,
+        handle: handle.clone(),
+    };
+    let search = best_stage_axes_with(
+        &flat_positions,
+        &groups,
+        &clusters,
+        &params,
+        ENTROPY_CANDIDATES,
+        seed,
+        &options,
+    )?;
+    Some((params, search))
+}
+
+/// Puzzle params and a one-stage search result for a level's authored
+/// puzzle: the authored axis and displacement range stand in for the axis
+/// search, and the report is the authored axis' own.
+fn authored_load_data(
+    data: &ModelLoadData,
+    puzzle: &AuthoredPuzzle,
+    strategy: ScrambleStrategy,
+    upright: bool,
+    max_roll: f32,
+) -> (PuzzleParams, StagedSearchResult) {
+    let faces = face_positions(data);
+    let pos_slices: Vec<&[f32]> = faces.iter().map(Vec::as_slice).collect();
+    let clusters = flat_clusters(&pos_slices, strategy);
+    let params = puzzle.apply(
+        PuzzleParams::from_raw_positions(
+            &pos_slices,
+            &data.mesh_transforms,
+            TARGET_WORLD_RADIUS,
+            CAMERA_FOV,
+        )
+        .with_strategy(strategy)
+        .with_upright(upright)
+        .with_roll(max_roll),
+    );
+    let stage = analyse_fixed_axis(
+        &faces.concat(),
+        &clusters,
+        puzzle.axis,
+        &params,
+        puzzle.seed,
+        ENTROPY_BACKEND,
+    );
+    let search = StagedSearchResult {
+        stages: vec![stage],
+        combined: stage.report,
+    };
+    (params, search)
+}
+
+/// Read a composite scene's other models and merge them into its main
+/// model's `data`, each placed by its `ScenePart`.  Returns the merged data
+/// and the model every mesh belongs to (0 for the main model); without
+/// parts `data` is returned as it is, with no mesh parts.  A part's
+/// skeleton and animations are dropped, so it is drawn in its rest pose.
+fn merge_scene_parts(
+    mut data: ModelLoadData,
+    parts: &[(PathBuf, ScenePart)],
+) -> Result<(ModelLoadData, Vec<usize>), String> {
+    if parts.is_empty() {
+        return Ok((data, Vec::new()));
+    }
+    let bounds = |d: &ModelLoadData| {
+        let slices: Vec<&[f32]> = d.meshes.iter().map(|m| m.positions.as_slice()).collect();
+        Bounds::of(&slices, &d.mesh_transforms)
+    };
+    let main = bounds(&data).ok_or("the main model has no geometry")?;
+    let identity = Matrix4::from_scale(1.0);
+    data.mesh_transforms = part_mesh_transforms(&data.mesh_transforms, data.meshes.len(), identity);
+    let mut mesh_parts = vec![0; data.meshes.len()];
+    for (i, (path, part)) in parts.iter().enumerate() {
+        let name = path.to_string_lossy();
+        let bytes = std::fs::read(path).map_err(|e| format!("{name}: {e}"))?;
+        let part_data = ModelLoader::prepare_from_bytes_with_path(name.as_ref(), &bytes)?;
+        let part_bounds = bounds(&part_data).ok_or_else(|| format!("{name}: no geometry"))?;
+        let placement = part.placement(main, part_bounds);
+        let mesh_count = part_data.meshes.len();
+        let transforms = part_mesh_transforms(&part_data.mesh_transforms, mesh_count, placement);
+        data.mesh_transforms.extend(transforms);
+        mesh_parts.extend(std::iter::repeat_n(i + 1, mesh_count));
+        let material_base = data.materials.len();
+        for mut mesh in part_data.meshes {
+            mesh.material_index += material_base;
+            mesh.bone_indices.clear();
+            mesh.bone_weights.clear();
+            data.meshes.push(mesh);
+        }
+        data.materials.extend(part_data.materials);
+    }
+    data.centroid = bounds(&data).map(|b| b.center);
+    Ok((data, mesh_parts))
+}
+
+/// Parse and analyse a model on a new thread and send the result to `tx`.
+/// `bytes` is the model file when already in memory; otherwise it is read
+/// from `path`.  A composite scene's other models are read from
+/// `job.parts`.  Nothing is sent if the load fails or is cancelled.
+fn spawn_load(
+    job: LoadJob,
+    path: PathBuf,
+    bytes: Option<Vec<u8>>,
+    handle: SearchHandle,
+    tx: Sender<LoadResult>,
+) {
+    std::thread::spawn(move || {
+        let bytes = match bytes {
+            Some(b) => b,
+            None => match std::fs::read(&path) {
+                Ok(b) => b,
+                Err(e) => {
+                    log::error!("[Formosaic] Failed to read model file: {e}");
+                    return;
+                }
+            },
+        };
+        let data = match ModelLoader::prepare_from_bytes_with_path(
+            path.to_string_lossy().as_ref(),
+            &bytes,
+        ) {
+            Ok(d) => d,
+            Err(e) => {
+                log::error!("[Formosaic] Failed to parse model: {e}");
+                return;
+            }
+        };
+        let (data, mesh_parts) = match merge_scene_parts(data, &job.parts) {
+            Ok(merged) => merged,
+            Err(e) => {
+                log::error!("[Formosaic] Failed to build scene: {e}");
+                return;
+            }
+        };
+        let split = if mesh_parts.is_empty() {
+            StageSplit::Bands(job.stages)
+        } else {
+            StageSplit::Parts(mesh_parts.clone())
+        };
+        let analysed = match &job.authored {
+            Some(puzzle) => Some(authored_load_data(
+                &data,
+                puzzle,
+                job.strategy,
+                job.upright,
+                job.max_roll,
+            )),
+            None => analyse_load_data(
+                &data,
+                &split,
+                job.strategy,
+                job.upright,
+                job.max_roll,
+                job.seed,
+                &handle,
+            ),
+        };
+        let Some((params, search)) = analysed else {
+            log::info!("[Formosaic] Load {} cancelled", job.request_id);
+            return;
+        };
+        let _ = tx.send(LoadResult {
+            request_id: job.request_id,
+            level_id: job.level_id,
+            data,
+            mesh_parts,
+            search,
+            params,
+            seed: job.seed,
+            authored: job.authored,
+        });
+    });
+}
+
 fn smoothstep(t: f32) -> f32 {
     let t = t.clamp(0.0, 1.0);
     t * t * (3.0 - 2.0 * t)
@@ -1074,7 +2937,20 @@ impl Application for Formosaic {
             if let Some(meta) = self.registry.levels.iter().find(|m| m.id == id).cloned() {
                 let path = self.registry.model_path(&meta);
                 if path.exists() {
-                    self.begin_saved_level_load(meta.id.clone(), path, ctx);
+                    // Rebuild the same scramble the player was looking at.
+                    let seed = self
+                        .scramble_state
+                        .as_ref()
+                        .map(|s| s.seed)
+                        .or(self.pending_seed)
+                        .unwrap_or_else(|| Self::fresh_seed(&meta.id));
+                    let stages = self
+                        .scramble_state
+                        .as_ref()
+                        .map(|s| s.stage_count())
+                        .or(self.pending_params.map(|p| p.stage_count))
+                        .unwrap_or(self.stage_count);
+                    self.begin_saved_level_load(meta.id.clone(), path, seed, stages, ctx);
                 }
             }
         }
@@ -1097,9 +2973,11 @@ impl Application for Formosaic {
                 request_id: result.request_id,
                 level_id: result.level_id,
                 data: result.data,
-                axis: result.axis,
-                report: result.report,
+                mesh_parts: result.mesh_parts,
+                search: result.search,
                 params: result.params,
+                seed: result.seed,
+                authored: result.authored,
             });
         }
 
@@ -1109,9 +2987,11 @@ impl Application for Formosaic {
                 self.pending_load = Some(load);
             } else {
                 let level_id = load.level_id.clone();
-                self.pending_axis = Some(load.axis);
-                self.pending_report = Some(load.report);
+                self.pending_search = Some(load.search);
                 self.pending_params = Some(load.params);
+                self.pending_seed = Some(load.seed);
+                self.scene_parts = load.mesh_parts;
+                self.authored_puzzle = load.authored;
                 self.mode = AppMode::Building { level_id };
                 self.incremental_builder = Some(IncrementalModelBuilder::new(load.data));
                 self.loading_frames = 0;
@@ -1133,10 +3013,10 @@ impl Application for Formosaic {
                 AppMode::Building { level_id } => level_id.clone(),
                 _ => unreachable!(),
             };
-            let axis = self.pending_axis.take().unwrap();
-            let report = self.pending_report.take().unwrap();
+            let search = self.pending_search.take().unwrap();
             let params = self.pending_params.take().unwrap();
-            self.finalize_loaded_model(level_id, builder, axis, report, params, ctx);
+            let seed = self.pending_seed.take().unwrap();
+            self.finalize_loaded_model(level_id, builder, search, params, seed, ctx);
             self.loading_frames = 0;
         }
 
@@ -1146,15 +3026,34 @@ impl Application for Formosaic {
         // Skip hint updates once the puzzle is solved — hints are frozen at
         // tier None after finish_restore resets them, and we must not keep
         // calling hints.update() which would keep ghost-snap active.
+        self.misalignment = if matches!(self.game_state, GameState::Playing) {
+            self.stage_misalignment(ctx)
+        } else {
+            None
+        };
         self.last_hint_output = if matches!(self.game_state, GameState::Playing) {
             if let Some(sc) = &self.scramble_state {
                 let camera = ctx.camera();
                 let fwd = camera.borrow().transform.forward().normalize();
-                let output = self.hints.update(delta_time, fwd, sc.solution_dir);
-                // Apply ghost-snap lerp (Tier 3 hint).
+                let up = camera.borrow().transform.up();
+                let cam_pos = camera.borrow().transform.position;
+                self.hints.set_upright_roll(sc.upright_roll(fwd, up));
+                let output = match (self.misalignment, sc.solution_eye()) {
+                    (Some(m), _) => {
+                        self.hints
+                            .update_error(delta_time, m.rms_px, sc.solution_dir())
+                    }
+                    (None, Some(_)) => {
+                        let alignment = sc.alignment(cam_pos, fwd, up, self.orbit_target(cam_pos));
+                        self.hints
+                            .update_point(delta_time, alignment, sc.solution_dir())
+                    }
+                    (None, None) => self.hints.update(delta_time, fwd, sc.solution_dir()),
+                };
+                // Apply ghost-snap lerp (Tier 4 hint).
                 if output.ghost_lerp > 0.0 {
                     if let Some(model) = &self.model {
-                        apply_displacement(model, sc, 1.0 - output.ghost_lerp);
+                        apply_displacement(model, 1.0 - output.ghost_lerp);
                     }
                 }
                 Some(output)
@@ -1165,23 +3064,56 @@ impl Application for Formosaic {
             None
         };
 
-        // Advance per-frame animation (skinning / bone matrices).
+        // Advance per-frame animation (skinning / bone matrices).  A posed
+        // puzzle's clip follows the scramble `t` of the last frame.
         if let Some(model) = &self.model {
+            if let Some(pose_loop) = &mut self.pose_loop {
+                if self.game_state == GameState::Playing {
+                    let t = model.borrow().scramble_t();
+                    let time = pose_loop.advance(delta_time, t);
+                    model.borrow_mut().player.local_time_sec = time as f64;
+                }
+            }
             model.borrow_mut().update_animation(delta_time);
         }
 
         let mut do_solve = false;
+        let mut do_stage_complete = false;
         let mut do_restore_complete = false;
+        let mut do_time_up = false;
+        let mut do_analysis = false;
         let mut cam_pos: Option<Vector3<f32>> = None;
         let mut cam_target: Option<Vector3<f32>> = None;
+        let mut cam_roll = 0.0;
+        let cam_pos_now = ctx.camera().borrow().transform.position;
+        let orbit_target = self.orbit_target(cam_pos_now);
 
         match &mut self.game_state {
             GameState::Playing => {
                 self.elapsed_secs += delta_time;
-                let snap = if let Some(sc) = &self.scramble_state {
+                if let Some(recorder) = &mut self.recorder {
+                    recorder.tick(delta_time, &ctx.camera().borrow().transform);
+                }
+                let watched = self.replay.as_ref().filter(|p| p.mode == ReplayMode::Watch);
+                if let Some(playback) = watched {
+                    let t = playback.clock.get() + delta_time;
+                    playback.clock.set(t);
+                    // Hints come back where they were taken.
+                    while self.hints.hint_count() < playback.recording.hints_at(t) {
+                        self.hints.advance();
+                    }
+                }
+                do_time_up = self
+                    .session
+                    .as_ref()
+                    .is_some_and(|s| s.is_over(self.elapsed_secs));
+                let snap = if do_time_up {
+                    false
+                } else if let Some(sc) = &self.scramble_state {
                     let camera = ctx.camera();
                     let fwd = camera.borrow().transform.forward().normalize();
-                    let dot = fwd.dot(sc.solution_dir.normalize()).abs();
+                    let up = camera.borrow().transform.up();
+                    let dot = sc.alignment(cam_pos_now, fwd, up, orbit_target);
 
                     // Camera-driven un-scramble: when looking from near the
                     // solution direction, smoothly reduce the scramble so
@@ -1190,16 +3122,31 @@ impl Application for Formosaic {
                     // Skip during GhostSnap hint (ghost_lerp handles it).
                     if self.hints.tier() != HintTier::GhostSnap {
                         if let Some(model) = &self.model {
-                            apply_displacement(model, sc, camera_scramble_t(dot));
+                            apply_displacement(model, camera_scramble_t(dot));
                         }
                     }
+                    if let (Some(model), Some(_)) = (&self.model, &self.mosaic_tints) {
+                        model.borrow_mut().set_mosaic_t(mosaic_t(dot));
+                    }
 
-                    dot >= SNAP_THRESHOLD_DOT
+                    // Snap once the fragments line up on screen, and on an
+                    // upright level only with the camera the right way up.
+                    self.misalignment.is_some_and(|m| m.is_solved()) && sc.is_upright(fwd, up)
                 } else {
                     false
                 };
                 if snap {
-                    do_solve = true;
+                    // Earlier stages of a multi-stage puzzle only lock their
+                    // band in place; the final stage solves the puzzle.
+                    let final_stage = self
+                        .scramble_state
+                        .as_ref()
+                        .is_none_or(|sc| sc.is_final_stage());
+                    if final_stage {
+                        do_solve = true;
+                    } else {
+                        do_stage_complete = true;
+                    }
                 }
             }
             GameState::Restoring {
@@ -1207,6 +3154,7 @@ impl Application for Formosaic {
                 cam_start,
                 cam_end,
                 target,
+                roll_start,
             } => {
                 *elapsed += delta_time;
                 let ev = *elapsed;
@@ -1216,10 +3164,18 @@ impl Application for Formosaic {
                 let t = smoothstep((ev / RESTORE_DURATION).min(1.0));
                 cam_pos = Some(*cam_start + (*cam_end - *cam_start) * t);
                 cam_target = Some(*target);
+                cam_roll = *roll_start * (1.0 - t);
             }
             GameState::Solved => {
                 self.solved_timer += delta_time;
             }
+            GameState::TimeUp => {}
+            GameState::Editing => {
+                if let Some(due) = self.editor.as_mut().and_then(|e| e.analysis_due.as_mut()) {
+                    *due -= delta_time;
+                    do_analysis = *due <= 0.0;
+                }
+            }
         }
 
         if let (Some(pos), Some(tgt)) = (cam_pos, cam_target) {
@@ -1227,14 +3183,38 @@ impl Application for Formosaic {
             let mut c = camera.borrow_mut();
             c.transform.position = pos;
             c.transform.look_at(tgt, Vector3::unit_y());
+            c.transform.rotation = c.transform.rotation * Quaternion::from_angle_z(Rad(cam_roll));
         }
 
+        if do_stage_complete {
+            self.complete_stage();
+        }
         if do_solve {
             self.trigger_solve(ctx);
         }
         if do_restore_complete {
             self.finish_restore(ctx);
         }
+        if do_time_up {
+            self.time_up(ctx);
+        }
+        if do_analysis {
+            self.refresh_editor_analysis();
+        }
+        self.poll_editor_heatmap();
+
+        // A session moves on by itself once the solve has been seen.
+        if self.game_state == GameState::Solved
+            && self.solved_timer >= SESSION_NEXT_DELAY
+            && matches!(self.mode, AppMode::InGame { .. })
+            && self.ui_machine.screen() == UiScreen::Game
+        {
+            if self.session.is_some() {
+                self.next_session_level(ctx);
+            } else if self.session_result.is_some() {
+                self.show_session_results(ctx);
+            }
+        }
     }
 
     fn on_event(&mut self, event: &Event, ctx: &mut SceneContext) {
@@ -1249,9 +3229,15 @@ impl Application for Formosaic {
                         | AppMode::FetchingOnline
                 );
                 if !is_loading
-                    && !matches!(self.game_state, GameState::Solved | GameState::Restoring { .. })
+                    && !matches!(
+                        self.game_state,
+                        GameState::Solved
+                            | GameState::Restoring { .. }
+                            | GameState::TimeUp
+                            | GameState::Editing
+                    )
                 {
-                    self.hints.advance();
+                    self.advance_hint();
                     log::info!("[Formosaic] Hint → {:?}", self.hints.tier());
                 }
             }
@@ -1259,11 +3245,11 @@ impl Application for Formosaic {
                 log::info!("[Formosaic] Cheat-solve");
                 self.trigger_solve(ctx);
             }
-            Event::KeyDown { key: Key::R } => {
+            Event::KeyDown { key: Key::R } if self.ui_machine.screen() != UiScreen::Editor => {
                 log::info!("[Formosaic] Random saved level");
                 self.load_random_saved(ctx);
             }
-            Event::KeyDown { key: Key::N } => {
+            Event::KeyDown { key: Key::N } if self.ui_machine.screen() != UiScreen::Editor => {
                 log::info!("[Formosaic] Online random level");
                 self.fetch_online_level(ctx);
             }
@@ -1285,8 +3271,11 @@ impl Application for Formosaic {
                 self.apply_ui_transitions(inputs, ctx);
             }
             _ => {
-                if self.ui_machine.screen() == UiScreen::Game
-                    && matches!(self.game_state, GameState::Playing | GameState::Solved)
+                if matches!(self.ui_machine.screen(), UiScreen::Game | UiScreen::Editor)
+                    && matches!(
+                        self.game_state,
+                        GameState::Playing | GameState::Solved | GameState::Editing
+                    )
                 {
                     let camera = ctx.camera();
                     let (w, h) = {
@@ -1299,6 +3288,10 @@ impl Application for Formosaic {
         }
     }
 
+    fn on_suspend(&mut self, ctx: &mut SceneContext) {
+        self.save_puzzle(ctx);
+    }
+
     fn handle_ui_actions(&mut self, ctx: &mut SceneContext) {
         let ui_ctx = UiContext {
             is_solved: matches!(self.game_state, GameState::Solved | GameState::Restoring { .. }),
@@ -1335,30 +3328,119 @@ impl Application for Formosaic {
         // Generic engine flag: skip geometry/lighting when showing full-screen
         // menu.  The pipeline checks this without knowing anything about menus.
         ctx.render_3d = !self.is_in_menu();
+        let outline = match self.last_hint_output {
+            Some(o) if o.show_outline && !solved => {
+                self.stage_outline().map(|segments| OutlineRenderState {
+                    segments,
+                    color: o.warmth_color,
+                    time: self.elapsed_secs,
+                })
+            }
+            _ => None,
+        };
+        let ghost = match self.game_state {
+            GameState::Playing => self.ghost_segments(ctx).map(|segments| OutlineRenderState {
+                segments: Rc::new(segments),
+                color: GHOST_COLOR,
+                time: self.elapsed_secs,
+            }),
+            _ => None,
+        };
         // Pack game-specific render data into a type-erased slot.
         // Game-side renderers downcast to GameRenderData.
         ctx.game_render_data = Some(Box::new(GameRenderData {
+            outline,
             hints: self.last_hint_output.as_ref().map(|o| HintRenderState {
                 warmth: o.warmth,
                 warmth_color: o.warmth_color,
+                cold_color: self.settings.warmth_palette.cold(),
+                hot_color: self.settings.warmth_palette.hot(),
                 tier: o.tier.as_u8(),
                 time: solved_t,
             }),
             solved_timer: if solved { Some(solved_t) } else { None },
+            ghost,
         }));
 
+        // The editor marks the camera's view on its map, in model space.
+        let view_dir = match (&self.editor, &self.entity) {
+            (Some(_), Some(entity)) => {
+                let fwd = ctx.camera().borrow().transform.forward().normalize();
+                Some(entity.borrow().transform().rotation.conjugate() * fwd)
+            }
+            _ => None,
+        };
+
         // Sync game state into UiState so UiNodes can read it this frame.
         {
             let mut ui = self.ui_state.borrow_mut();
             ui.elapsed_secs = self.elapsed_secs;
-            ui.difficulty = self.entropy_report.map(|r| r.difficulty);
+            ui.difficulty = self
+                .entropy_report
+                .map(|r| self.calibrated_difficulty(&r, self.stage_reports.len()));
             ui.hint_count = self.hints.hint_count() as u32;
+            ui.score = self.last_score.map(|(score, _)| score);
+            ui.new_best = self.last_score.is_some_and(|(_, best)| best);
+            ui.has_replay = self.has_replay && !self.is_in_menu();
+            ui.has_saved_puzzle = self.has_saved_puzzle;
+            ui.share_code.clone_from(&self.share_code);
+            ui.share_error.clone_from(&self.share_error);
+            ui.editor = self.editor.as_ref().map(|e| EditorInfo {
+                axis: e.puzzle.axis,
+                min_disp: e.puzzle.min_disp,
+                max_disp: e.puzzle.max_disp,
+                disp_limit: e.disp_limit,
+                preview_t: e.preview_t,
+                view_dir,
+                heatmap: e.heatmap.clone(),
+                heatmap_pending: e.heatmap_job.is_some() || e.heatmap_stale,
+                report: e.report,
+                difficulty: e.report.map(|r| self.calibrated_difficulty(&r, 1)),
+                authored: e.authored,
+                dirty: e.dirty,
+            });
+            ui.replay = self.replay.as_ref().map(|p| ReplayInfo {
+                watching: p.mode == ReplayMode::Watch,
+                time_secs: p.recording.time_secs,
+            });
+            let current = self.session_current_secs();
+            ui.session = self.session.as_ref().map(|s| s.kind);
+            ui.session_solved = self.session.as_ref().map_or(0, |s| s.solved);
+            ui.session_secs = self.session.as_ref().map_or(0.0, |s| s.total_secs(current));
+            ui.session_result.clone_from(&self.session_result);
+            ui.leaderboard = match &self.session_result {
+                Some((entry, _)) => self
+                    .leaderboard
+                    .top(entry.kind)
+                    .into_iter()
+                    .cloned()
+                    .collect(),
+                None => Vec::new(),
+            };
+            ui.stage = self.hints.stage();
+            ui.stage_count = self.hints.stage_count();
+            ui.scene = !self.scene_parts.is_empty();
+            ui.stage_difficulty = if self.stage_reports.len() > 1 {
+                self.stage_reports
+                    .get(ui.stage)
+                    .map(|r| self.calibrated_difficulty(r, 1))
+            } else {
+                None
+            };
+            ui.stage_mode = self.stage_count;
+            ui.anamorphic_mode = self.projection == ScrambleProjection::Anamorphic;
+            ui.roll_mode = self.roll_scramble;
+            ui.mosaic_mode = self.mosaic;
+            ui.motion_mode = self.motion;
+            ui.warmth_palette = self.settings.warmth_palette;
             ui.hint_tier = self.hints.tier();
             ui.hint_warmth = self
                 .last_hint_output
                 .as_ref()
                 .map(|o| o.warmth)
                 .unwrap_or(0.5);
+            ui.roll_cue = self.last_hint_output.and_then(|o| o.roll_cue);
+            ui.misalignment_px = self.misalignment.map(|m| m.rms_px);
             // Treat Restoring (camera animating post-solve) same as Solved
             // so Credits widget shows immediately when user presses Menu.
             ui.is_solved = matches!(self.game_state, GameState::Solved | GameState::Restoring { .. });
@@ -1373,13 +3455,23 @@ impl Application for Formosaic {
             );
 
             let mut progress = self.loading_progress;
-            if matches!(self.mode, AppMode::Loading { .. } | AppMode::FetchingOnline) {
+            if matches!(self.mode, AppMode::FetchingOnline) {
                 // Move continuously while the background thread works.
                 let t = self
                     .loading_started
                     .map(|start| start.elapsed().as_secs_f32())
                     .unwrap_or(0.0);
                 progress = progress.max((t / 6.0).clamp(0.05, 0.45));
+            } else if matches!(self.mode, AppMode::Loading { .. }) {
+                // Creep while the model parses, then follow the axis search.
+                let t = self
+                    .loading_started
+                    .map(|start| start.elapsed().as_secs_f32())
+                    .unwrap_or(0.0);
+                let search = self.search_handle.as_ref().map_or(0.0, |h| h.progress());
+                progress = progress
+                    .max((t / 6.0).clamp(0.05, 0.10))
+                    .max(0.10 + search * 0.35);
             }
             if let Some(p) = self.latest_download_progress.as_ref() {
                 let phase = if matches!(self.mode, AppMode::Downloading { .. }) {
@@ -1412,6 +3504,9 @@ impl Application for Formosaic {
             ui.screen = self.ui_machine.screen();
             ui.is_touch = PlatformInfo::detect().is_touch();
             ui.levels.clone_from(&self.registry.levels);
+            let today = UtcDate::today();
+            ui.daily_streak = self.daily_log.streak(today);
+            ui.daily_done = self.daily_log.record_for(today).is_some();
             ui.current_level = match &self.mode {
                 AppMode::InGame { level_id }
                 | AppMode::Loading { level_id }
@@ -1436,7 +3531,8 @@ impl Application for Formosaic {
         pipeline: &mut formosaic_engine::rendering::pipeline::Pipeline,
     ) {
         use crate::rendering::{
-            hint_render::HintRenderer, menu_render::MenuRenderer, shine_render::ShineRenderer,
+            hint_render::HintRenderer, menu_render::MenuRenderer, outline_render::OutlineRenderer,
+            shine_render::ShineRenderer,
         };
         match MenuRenderer::new() {
             Ok(r) => pipeline.add_renderer(Box::new(r)),
@@ -1446,6 +3542,14 @@ impl Application for Formosaic {
             Ok(r) => pipeline.add_renderer(Box::new(r)),
             Err(e) => log::warn!("HintRenderer failed to init: {e}"),
         }
+        match OutlineRenderer::new() {
+            Ok(r) => pipeline.add_renderer(Box::new(r)),
+            Err(e) => log::warn!("OutlineRenderer failed to init: {e}"),
+        }
+        match OutlineRenderer::ghost() {
+            Ok(r) => pipeline.add_renderer(Box::new(r)),
+            Err(e) => log::warn!("Ghost OutlineRenderer failed to init: {e}"),
+        }
         match ShineRenderer::new() {
             Ok(r) => pipeline.add_renderer(Box::new(r)),
             Err(e) => log::warn!("ShineRenderer failed to init: {e}"),
@@ -0,0 +1,265 @@
+//! Daily challenge: one level + scramble seed per UTC day.
+//!
+//! The level and seed are a pure function of the UTC date and the set of
+//! locally available levels, so everyone with the same level library plays the
+//! identical puzzle on the same day.  The builtin cactus is always in the
+//! library, so the daily works fully offline on a fresh install.
+//!
+//! Results are kept in `{data_dir}/daily.json`, one record per day:
+//!
+//! ```json
+//! [
+//!   {"date":"2025-03-14","level_id":"cactus_builtin","time_secs":42.17,"hints":1}
+//! ]
+//! ```
+
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::json::{extract, extract_num};
+use super::storage::LevelMeta;
+use crate::puzzle::seed::PuzzleSeed;
+
+// ─── Date ─────────────────────────────────────────────────────────────────────
+
+/// A calendar day in UTC.  Ordered chronologically.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct UtcDate {
+    pub year: i32,
+    pub month: u32,
+    pub day: u32,
+}
+
+impl UtcDate {
+    pub fn new(year: i32, month: u32, day: u32) -> Self {
+        Self { year, month, day }
+    }
+
+    /// Today's date according to the system clock.
+    pub fn today() -> Self {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or(0);
+        Self::from_days_since_epoch(secs.div_euclid(86_400))
+    }
+
+    /// Convert a day count since 1970-01-01 to a civil date (proleptic
+    /// Gregorian, Howard Hinnant's `civil_from_days`).
+    pub fn from_days_since_epoch(days: i64) -> Self {
+        let z = days + 719_468;
+        let era = z.div_euclid(146_097);
+        let doe = z.rem_euclid(146_097);
+        let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+        let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+        let mp = (5 * doy + 2) / 153;
+        let day = (doy - (153 * mp + 2) / 5 + 1) as u32;
+        let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+        let year = (yoe + era * 400 + i64::from(month <= 2)) as i32;
+        Self { year, month, day }
+    }
+
+    /// Inverse of `from_days_since_epoch`.
+    pub fn days_since_epoch(self) -> i64 {
+        let y = i64::from(self.year) - i64::from(self.month <= 2);
+        let m = i64::from(self.month);
+        let era = y.div_euclid(400);
+        let yoe = y.rem_euclid(400);
+        let doy = (153 * (if m > 2 { m - 3 } else { m + 9 }) + 2) / 5 + i64::from(self.day) - 1;
+        let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+        era * 146_097 + doe - 719_468
+    }
+
+    pub fn pred(self) -> Self {
+        Self::from_days_since_epoch(self.days_since_epoch() - 1)
+    }
+
+    /// Parse `YYYY-MM-DD`.
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut parts = s.splitn(3, '-');
+        let year = parts.next()?.parse().ok()?;
+        let month = parts.next()?.parse().ok()?;
+        let day = parts.next()?.parse().ok()?;
+        if !(1..=12).contains(&month) || !(1..=31).contains(&day) {
+            return None;
+        }
+        Some(Self { year, month, day })
+    }
+}
+
+impl fmt::Display for UtcDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}-{:02}-{:02}", self.year, self.month, self.day)
+    }
+}
+
+// ─── Challenge ────────────────────────────────────────────────────────────────
+
+/// The puzzle for one day.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DailyChallenge {
+    pub date: UtcDate,
+    pub level_id: String,
+    pub seed: PuzzleSeed,
+}
+
+impl DailyChallenge {
+    /// Pick the level and seed for `date` from the available levels.
+    ///
+    /// Levels are ordered by id rather than by the registry's difficulty sort
+    /// so that the pick does not drift when a difficulty is re-estimated.
+    /// Returns `None` if there are no levels at all.
+    pub fn for_date(date: UtcDate, levels: &[LevelMeta]) -> Option<Self> {
+        let mut ids: Vec<&str> = levels.iter().map(|l| l.id.as_str()).collect();
+        if ids.is_empty() {
+            return None;
+        }
+        ids.sort_unstable();
+        ids.dedup();
+
+        let day_seed = PuzzleSeed::for_level("daily", date.days_since_epoch() as u64);
+        let level_id = ids[(day_seed.value() % ids.len() as u64) as usize].to_string();
+        let seed = PuzzleSeed::for_level(&level_id, day_seed.value());
+        Some(Self {
+            date,
+            level_id,
+            seed,
+        })
+    }
+}
+
+// ─── Results ──────────────────────────────────────────────────────────────────
+
+/// Result of one completed daily challenge.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DailyRecord {
+    pub date: UtcDate,
+    pub level_id: String,
+    pub time_secs: f32,
+    pub hints: u32,
+}
+
+impl DailyRecord {
+    pub fn to_json(&self) -> String {
+        format!(
+            r#"{{"date":"{date}","level_id":"{id}","time_secs":{t:.2},"hints":{h}}}"#,
+            date = self.date,
+            id = self.level_id,
+            t = self.time_secs,
+            h = self.hints,
+        )
+    }
+
+    /// Parse one record object produced by `to_json`.
+    pub fn from_json(s: &str) -> Option<Self> {
+        Some(DailyRecord {
+            date: UtcDate::parse(extract(s, "date")?)?,
+            level_id: extract(s, "level_id")?.to_string(),
+            time_secs: extract_num(s, "time_secs")?,
+            hints: extract_num(s, "hints").unwrap_or(0.0) as u32,
+        })
+    }
+}
+
+/// Per-day daily-challenge results, persisted in the data dir.
+pub struct DailyLog {
+    /// Sorted by date, at most one record per day.
+    pub records: Vec<DailyRecord>,
+    path: Option<PathBuf>,
+}
+
+impl DailyLog {
+    /// An empty log that is never written to disk.
+    pub fn in_memory() -> Self {
+        Self {
+            records: Vec::new(),
+            path: None,
+        }
+    }
+
+    /// Load `{base_dir}/daily.json`.  A missing or unreadable file gives an
+    /// empty log.
+    pub fn load(base_dir: &Path) -> Self {
+        let path = base_dir.join("daily.json");
+        let records = std::fs::read_to_string(&path)
+            .map(|json| Self::parse(&json))
+            .unwrap_or_default();
+        Self {
+            records,
+            path: Some(path),
+        }
+    }
+
+    fn parse(json: &str) -> Vec<DailyRecord> {
+        let mut records: Vec<DailyRecord> = json
+            .split('}')
+            .filter_map(|obj| DailyRecord::from_json(&format!("{obj}}}")))
+            .collect();
+        records.sort_by_key(|r| r.date);
+        records.dedup_by_key(|r| r.date);
+        records
+    }
+
+    pub fn to_json(&self) -> String {
+        let body: Vec<String> = self.records.iter().map(|r| r.to_json()).collect();
+        format!("[\n  {}\n]\n", body.join(",\n  "))
+    }
+
+    pub fn record_for(&self, date: UtcDate) -> Option<&DailyRecord> {
+        self.records.iter().find(|r| r.date == date)
+    }
+
+    /// Store a completion.  Only the first completion of a day counts —
+    /// replaying the same daily cannot improve its result.  Returns whether
+    /// the record was stored.
+    pub fn record_completion(
+        &mut self,
+        date: UtcDate,
+        level_id: &str,
+        time_secs: f32,
+        hints: u32,
+    ) -> bool {
+        if self.record_for(date).is_some() {
+            return false;
+        }
+        let record = DailyRecord {
+            date,
+            level_id: level_id.to_string(),
+            time_secs,
+            hints,
+        };
+        let idx = self.records.partition_point(|r| r.date < date);
+        self.records.insert(idx, record);
+        self.save();
+        true
+    }
+
+    /// Number of consecutive completed days ending at `today`.  A streak that
+    /// ended yesterday is still alive until today's daily is missed.
+    pub fn streak(&self, today: UtcDate) -> u32 {
+        let mut day = if self.record_for(today).is_some() {
+            today
+        } else {
+            today.pred()
+        };
+        let mut streak = 0;
+        while self.record_for(day).is_some() {
+            streak += 1;
+            day = day.pred();
+        }
+        streak
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        if let Some(dir) = path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        if let Err(e) = std::fs::write(path, self.to_json()) {
+            log::warn!("[Daily] Failed to save {}: {e}", path.display());
+        }
+    }
+}
@@ -0,0 +1,38 @@
+//! Field lookups for the small hand-written JSON files the game keeps.
+//!
+//! These are not a JSON parser: each looks for the first `"key":` in the
+//! text, which is all the flat objects written by the `to_json` methods
+//! need.  Callers parsing a list split it into objects first.
+
+/// The string value of `key`, without its quotes.  Escapes are not
+/// decoded; none of the writers produce any.
+pub fn extract<'a>(json: &'a str, key: &str) -> Option<&'a str> {
+    let needle = format!("\"{}\":\"", key);
+    let start = json.find(needle.as_str())? + needle.len();
+    let end = json[start..].find('"')? + start;
+    Some(&json[start..end])
+}
+
+/// The numeric value of `key`.  Space after the colon (as in the
+/// pretty-printed `calibration.json`) and exponents are accepted.
+pub fn extract_num(json: &str, key: &str) -> Option<f32> {
+    let needle = format!("\"{}\":", key);
+    let start = json.find(needle.as_str())? + needle.len();
+    let rest = json[start..].trim_start();
+    let end = rest
+        .find(|c: char| !c.is_ascii_digit() && !matches!(c, '.' | '-' | 'e' | 'E' | '+'))
+        .unwrap_or(rest.len());
+    rest[..end].parse().ok()
+}
+
+/// The numbers of the array value of `key`.  `None` if any is not a
+/// number.
+pub fn extract_list(json: &str, key: &str) -> Option<Vec<f32>> {
+    let needle = format!("\"{}\":[", key);
+    let start = json.find(needle.as_str())? + needle.len();
+    let end = json[start..].find(']')? + start;
+    json[start..end]
+        .split(',')
+        .map(|v| v.trim().parse().ok())
+        .collect()
+}
@@ -1,2 +1,11 @@
+pub mod daily;
+pub mod json;
+pub mod outcomes;
 pub mod poly_pizza;
+pub mod replay;
+pub mod resume;
+pub mod scene;
+pub mod session;
+pub mod settings;
+pub mod share;
 pub mod storage;
@@ -0,0 +1,175 @@
+//! Per-level play outcomes, the dataset difficulty calibration is fit on.
+//!
+//! Every finished puzzle — solved, or abandoned for the menu — appends one
+//! record to `{data_dir}/outcomes.json` with how it went and the entropy
+//! features of the puzzle that was played:
+//!
+//! ```json
+//! [
+//!   {"level_id":"cactus_builtin","time_secs":42.17,"hints":1,"gave_up":false,
+//!    "stages":1,"heuristic":0.4120,"entropy":0.5310,"weak_peak":0.0210,
+//!    "crowding":0.6400,"roll_sensitivity":0.0000}
+//! ]
+//! ```
+//!
+//! The model refit from it lives next to it in
+//! `{data_dir}/difficulty_model.json` (see `puzzle::calibration`).
+
+use std::path::{Path, PathBuf};
+
+use super::json::{extract, extract_num};
+use crate::puzzle::calibration::{observed_difficulty, DifficultyFeatures, DifficultyModel};
+
+/// Oldest records are dropped past this many.
+pub const MAX_OUTCOMES: usize = 2000;
+
+const OUTCOMES_FILE: &str = "outcomes.json";
+const MODEL_FILE: &str = "difficulty_model.json";
+
+// ─── Records ──────────────────────────────────────────────────────────────────
+
+/// How one play of a level went.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PlayOutcome {
+    pub level_id: String,
+    pub time_secs: f32,
+    pub hints: u32,
+    /// The player left for the menu before solving.
+    pub gave_up: bool,
+    pub features: DifficultyFeatures,
+}
+
+impl PlayOutcome {
+    /// Target difficulty this outcome contributes to the fit.
+    pub fn observed_difficulty(&self) -> f32 {
+        observed_difficulty(self.time_secs, self.hints, self.gave_up)
+    }
+
+    pub fn to_json(&self) -> String {
+        let f = &self.features;
+        format!(
+            r#"{{"level_id":"{id}","time_secs":{t:.2},"hints":{h},"gave_up":{g},"stages":{s},"heuristic":{d:.4},"entropy":{e:.4},"weak_peak":{p:.4},"crowding":{c:.4},"roll_sensitivity":{r:.4}}}"#,
+            id = self.level_id,
+            t = self.time_secs,
+            h = self.hints,
+            g = self.gave_up,
+            s = f.extra_stages as u32 + 1,
+            d = f.heuristic,
+            e = f.entropy,
+            p = f.weak_peak,
+            c = f.crowding,
+            r = f.roll_sensitivity,
+        )
+    }
+
+    /// Parse one record object produced by `to_json`.
+    pub fn from_json(s: &str) -> Option<Self> {
+        let gave_up = s.contains("\"gave_up\":true");
+        Some(PlayOutcome {
+            level_id: extract(s, "level_id")?.to_string(),
+            time_secs: extract_num(s, "time_secs")?,
+            hints: extract_num(s, "hints").unwrap_or(0.0) as u32,
+            gave_up,
+            features: DifficultyFeatures {
+                heuristic: extract_num(s, "heuristic")?,
+                entropy: extract_num(s, "entropy")?,
+                weak_peak: extract_num(s, "weak_peak")?,
+                crowding: extract_num(s, "crowding")?,
+                roll_sensitivity: extract_num(s, "roll_sensitivity").unwrap_or(0.0),
+                extra_stages: (extract_num(s, "stages").unwrap_or(1.0) - 1.0).max(0.0),
+            },
+        })
+    }
+}
+
+// ─── Log ──────────────────────────────────────────────────────────────────────
+
+/// Recorded outcomes, persisted in the data dir, oldest first.
+pub struct OutcomeLog {
+    pub outcomes: Vec<PlayOutcome>,
+    path: Option<PathBuf>,
+}
+
+impl OutcomeLog {
+    /// An empty log that is never written to disk.
+    pub fn in_memory() -> Self {
+        Self {
+            outcomes: Vec::new(),
+            path: None,
+        }
+    }
+
+    /// Load `{base_dir}/outcomes.json`.  A missing or unreadable file gives
+    /// an empty log.
+    pub fn load(base_dir: &Path) -> Self {
+        let path = base_dir.join(OUTCOMES_FILE);
+        let outcomes = std::fs::read_to_string(&path)
+            .map(|json| Self::parse(&json))
+            .unwrap_or_default();
+        Self {
+            outcomes,
+            path: Some(path),
+        }
+    }
+
+    fn parse(json: &str) -> Vec<PlayOutcome> {
+        json.split('}')
+            .filter_map(|obj| PlayOutcome::from_json(&format!("{obj}}}")))
+            .collect()
+    }
+
+    pub fn to_json(&self) -> String {
+        let body: Vec<String> = self.outcomes.iter().map(|o| o.to_json()).collect();
+        format!("[\n  {}\n]\n", body.join(",\n  "))
+    }
+
+    /// Append an outcome, dropping the oldest past `MAX_OUTCOMES`.
+    pub fn record(&mut self, outcome: PlayOutcome) {
+        self.outcomes.push(outcome);
+        if self.outcomes.len() > MAX_OUTCOMES {
+            let excess = self.outcomes.len() - MAX_OUTCOMES;
+            self.outcomes.drain(..excess);
+        }
+        self.save();
+    }
+
+    /// Refit the difficulty model on every recorded outcome.  `None` until
+    /// there are enough of them.
+    pub fn calibrate(&self) -> Option<DifficultyModel> {
+        let samples: Vec<(DifficultyFeatures, f32)> = self
+            .outcomes
+            .iter()
+            .map(|o| (o.features, o.observed_difficulty()))
+            .collect();
+        DifficultyModel::fit(&samples)
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        if let Some(dir) = path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        if let Err(e) = std::fs::write(path, self.to_json()) {
+            log::warn!("[Outcomes] Failed to save {}: {e}", path.display());
+        }
+    }
+}
+
+// ─── Calibrated model ─────────────────────────────────────────────────────────
+
+/// Load `{base_dir}/difficulty_model.json`, if there is a valid one.
+pub fn load_calibration(base_dir: &Path) -> Option<DifficultyModel> {
+    let json = std::fs::read_to_string(base_dir.join(MODEL_FILE)).ok()?;
+    DifficultyModel::from_json(&json)
+}
+
+/// Write `model` to `{base_dir}/difficulty_model.json`.
+pub fn save_calibration(base_dir: &Path, model: &DifficultyModel) {
+    let path = base_dir.join(MODEL_FILE);
+    let _ = std::fs::create_dir_all(base_dir);
+    if let Err(e) = std::fs::write(&path, model.to_json()) {
+        log::warn!("[Outcomes] Failed to save {}: {e}", path.display());
+    }
+}
@@ -161,6 +161,34 @@ impl PolyPizzaClient {
         });
     }
 
+    /// Start an async download of the model with id `id`, looking up its
+    /// name, author and licence first.  Results arrive via `poll_download`.
+    pub fn download_model_by_id(&mut self, id: &str) {
+        if self.download_pending {
+            return;
+        }
+        self.download_pending = true;
+        let progress = Arc::new(AtomicUsize::new(0));
+        self.progress_state = Some(progress.clone());
+        let tx = self.download_tx.clone();
+        let ptx = self.progress_tx.clone();
+        let id = id.to_string();
+        std::thread::spawn(move || {
+            let result = fetch_summary(&id).and_then(|s| {
+                do_download(
+                    &id,
+                    &s.name,
+                    &s.author,
+                    &s.license,
+                    &s.source_url,
+                    &s.download_url,
+                    Some((ptx, progress)),
+                )
+            });
+            let _ = tx.send(result);
+        });
+    }
+
     /// Non-blocking poll — returns `Some(result)` when the download completes.
     pub fn poll_download(&mut self) -> Option<DownloadResult> {
         match self.download_rx.try_recv() {
@@ -289,6 +317,21 @@ fn fetch_explore(_offset: usize, limit: usize) -> ExploreResult {
     }
 }
 
+/// Look up one model by id: GET /model/{id}.
+fn fetch_summary(id: &str) -> Result<ModelSummary, String> {
+    let key = api_key()?;
+    let detail_url = format!("{}/model/{}", API_BASE, id);
+    log::info!("[PolyPizza] GET model detail: {}", detail_url);
+    let body = authed_get_string(&detail_url, key)
+        .map_err(|e| format!("[PolyPizza] model detail fetch failed for '{}': {}", id, e))?;
+    parse_summary_v1(&body).ok_or_else(|| {
+        format!(
+            "[PolyPizza] unreadable model detail for '{}': {:.300}",
+            id, body
+        )
+    })
+}
+
 /// Download a model: use `known_dl_url` if non-empty (it comes from the search
 /// response `Download` field), otherwise fetch the model detail endpoint to get it.
 fn do_download(
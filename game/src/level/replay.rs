@@ -0,0 +1,435 @@
+//! Camera path recordings and ghost replays of solves.
+//!
+//! While a puzzle is played, a `Recorder` samples the camera every
+//! `SAMPLE_INTERVAL_SECS` of play time and notes each hint taken.  The best
+//! solve of a saved level is kept next to it as `levels/{id}/best.replay`
+//! and can be watched two ways:
+//!
+//! * as a **ghost** — the recorded camera drawn as a translucent frustum
+//!   (`frustum_segments`) in the 3D view, in step with the play clock;
+//! * as a full **playback** — `ReplayController` drives the `Camera` along
+//!   the recorded path.
+//!
+//! A recording also keeps the seed and scramble settings of its puzzle
+//! (`ReplayPuzzle`), so a playback reloads exactly the puzzle that was
+//! solved.
+//!
+//! ## Format
+//!
+//! Little-endian binary, versioned by `REPLAY_VERSION`:
+//!
+//! ```text
+//! magic      "FRPL"
+//! version    u8
+//! seed       u64
+//! stages     u8
+//! flags      u8     bit 0: anamorphic projection
+//! max_roll   f32
+//! interval   f32    seconds between frames
+//! time_secs  f32    solve time
+//! frames     u32 count, then per frame:
+//!              position  3 × f32
+//!              rotation  4 × i16  unit quaternion (s, x, y, z) × 32767
+//! events     u16 count, then per event:
+//!              frame     u32    first frame after the hint
+//!              tier      u8     hint tier reached
+//! ```
+//!
+//! A frame is 20 bytes, so ten minutes of play is about 120 KB.  Files of
+//! any other version are ignored rather than misread.
+
+use std::cell::Cell;
+use std::path::Path;
+use std::rc::Rc;
+
+use cgmath::{InnerSpace, Quaternion, Vector3, VectorSpace};
+use formosaic_engine::architecture::scene::node::transform::Transform;
+use formosaic_engine::rendering::instances::camera::camera_controller::CameraController;
+
+use crate::puzzle::outline::Segment;
+use crate::puzzle::puzzle_params::ScrambleProjection;
+use crate::puzzle::seed::PuzzleSeed;
+
+/// Version written by `Recording::to_bytes`.
+pub const REPLAY_VERSION: u8 = 1;
+/// Play time between recorded frames.
+pub const SAMPLE_INTERVAL_SECS: f32 = 0.1;
+/// Frames kept at most (an hour of play); longer solves stop recording.
+pub const MAX_FRAMES: usize = 36_000;
+
+const MAGIC: &[u8; 4] = b"FRPL";
+const FILE_NAME: &str = "best.replay";
+const QUAT_SCALE: f32 = 32767.0;
+
+/// The puzzle a recording was made on: enough to load it again.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReplayPuzzle {
+    pub seed: PuzzleSeed,
+    pub stages: usize,
+    pub max_roll: f32,
+    pub projection: ScrambleProjection,
+}
+
+/// Camera pose at one sample.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReplayFrame {
+    pub position: Vector3<f32>,
+    pub rotation: Quaternion<f32>,
+}
+
+impl ReplayFrame {
+    fn of(camera: &Transform) -> Self {
+        Self {
+            position: camera.position,
+            rotation: camera.rotation.normalize(),
+        }
+    }
+}
+
+/// A hint taken during the solve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReplayEvent {
+    /// Index of the first frame recorded after the hint.
+    pub frame: u32,
+    /// Hint tier reached (`HintTier::as_u8`).
+    pub tier: u8,
+}
+
+// ─── Recording ───────────────────────────────────────────────────────────────
+
+/// A recorded solve.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recording {
+    pub puzzle: ReplayPuzzle,
+    pub interval_secs: f32,
+    /// Solve time on the play clock.
+    pub time_secs: f32,
+    pub frames: Vec<ReplayFrame>,
+    pub events: Vec<ReplayEvent>,
+}
+
+impl Recording {
+    /// Time from the first frame to the last.
+    pub fn duration_secs(&self) -> f32 {
+        self.frames.len().saturating_sub(1) as f32 * self.interval_secs
+    }
+
+    /// Camera pose `t` seconds in, interpolated between frames and held at
+    /// either end.  `None` for an empty recording.
+    pub fn sample(&self, t: f32) -> Option<ReplayFrame> {
+        let last = self.frames.len().checked_sub(1)?;
+        let x = (t / self.interval_secs).clamp(0.0, last as f32);
+        let i = (x.floor() as usize).min(last);
+        let (a, b) = (self.frames[i], self.frames[(i + 1).min(last)]);
+        let f = x - i as f32;
+        // q and -q are the same rotation; blend along the short way.
+        let to = if a.rotation.dot(b.rotation) < 0.0 {
+            -b.rotation
+        } else {
+            b.rotation
+        };
+        Some(ReplayFrame {
+            position: a.position.lerp(b.position, f),
+            rotation: a.rotation.nlerp(to, f),
+        })
+    }
+
+    /// Hints taken in the first `t` seconds.
+    pub fn hints_at(&self, t: f32) -> u32 {
+        self.events
+            .iter()
+            .filter(|e| e.frame as f32 * self.interval_secs <= t)
+            .count() as u32
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::with_capacity(32 + self.frames.len() * 20 + self.events.len() * 5);
+        out.extend_from_slice(MAGIC);
+        out.push(REPLAY_VERSION);
+        out.extend_from_slice(&self.puzzle.seed.value().to_le_bytes());
+        out.push(self.puzzle.stages.min(u8::MAX as usize) as u8);
+        let anamorphic = self.puzzle.projection == ScrambleProjection::Anamorphic;
+        out.push(anamorphic as u8);
+        for v in [self.puzzle.max_roll, self.interval_secs, self.time_secs] {
+            out.extend_from_slice(&v.to_le_bytes());
+        }
+
+        let frames = &self.frames[..self.frames.len().min(MAX_FRAMES)];
+        out.extend_from_slice(&(frames.len() as u32).to_le_bytes());
+        for frame in frames {
+            for v in [frame.position.x, frame.position.y, frame.position.z] {
+                out.extend_from_slice(&v.to_le_bytes());
+            }
+            let q = frame.rotation.normalize();
+            for c in [q.s, q.v.x, q.v.y, q.v.z] {
+                let c = (c.clamp(-1.0, 1.0) * QUAT_SCALE).round() as i16;
+                out.extend_from_slice(&c.to_le_bytes());
+            }
+        }
+
+        let events = &self.events[..self.events.len().min(u16::MAX as usize)];
+        out.extend_from_slice(&(events.len() as u16).to_le_bytes());
+        for event in events {
+            out.extend_from_slice(&event.frame.to_le_bytes());
+            out.push(event.tier);
+        }
+        out
+    }
+
+    /// Parse `to_bytes` output.  `None` for a truncated file, a bad magic
+    /// or another version.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let mut r = Reader(bytes);
+        if r.take(4)? != MAGIC || r.u8()? != REPLAY_VERSION {
+            return None;
+        }
+        let seed = PuzzleSeed::new(r.u64()?);
+        let stages = r.u8()? as usize;
+        let projection = if r.u8()? & 1 != 0 {
+            ScrambleProjection::Anamorphic
+        } else {
+            ScrambleProjection::Parallel
+        };
+        let max_roll = r.f32()?;
+        let interval_secs = r.f32()?;
+        let time_secs = r.f32()?;
+        if !interval_secs.is_finite() || interval_secs <= 0.0 {
+            return None;
+        }
+
+        let frame_count = r.u32()? as usize;
+        if frame_count > MAX_FRAMES {
+            return None;
+        }
+        let mut frames = Vec::with_capacity(frame_count);
+        for _ in 0..frame_count {
+            let position = Vector3::new(r.f32()?, r.f32()?, r.f32()?);
+            let mut q = [0.0; 4];
+            for c in &mut q {
+                *c = r.i16()? as f32 / QUAT_SCALE;
+            }
+            let rotation = Quaternion::new(q[0], q[1], q[2], q[3]);
+            if rotation.magnitude2() < 0.5 {
+                return None;
+            }
+            frames.push(ReplayFrame {
+                position,
+                rotation: rotation.normalize(),
+            });
+        }
+
+        let event_count = r.u16()? as usize;
+        let mut events = Vec::with_capacity(event_count);
+        for _ in 0..event_count {
+            events.push(ReplayEvent {
+                frame: r.u32()?,
+                tier: r.u8()?,
+            });
+        }
+
+        Some(Self {
+            puzzle: ReplayPuzzle {
+                seed,
+                stages,
+                max_roll,
+                projection,
+            },
+            interval_secs,
+            time_secs,
+            frames,
+            events,
+        })
+    }
+
+    /// Whether `level_dir` has a best-solve recording.
+    pub fn exists(level_dir: &Path) -> bool {
+        level_dir.join(FILE_NAME).is_file()
+    }
+
+    /// Load `{level_dir}/best.replay`.  `None` if there is none or it cannot
+    /// be read.
+    pub fn load(level_dir: &Path) -> Option<Self> {
+        let bytes = std::fs::read(level_dir.join(FILE_NAME)).ok()?;
+        let recording = Self::from_bytes(&bytes);
+        if recording.is_none() {
+            log::warn!(
+                "[Replay] Ignoring unreadable {}",
+                level_dir.join(FILE_NAME).display()
+            );
+        }
+        recording
+    }
+
+    /// Write `{level_dir}/best.replay`, replacing any previous best.
+    pub fn save(&self, level_dir: &Path) -> std::io::Result<()> {
+        std::fs::create_dir_all(level_dir)?;
+        std::fs::write(level_dir.join(FILE_NAME), self.to_bytes())
+    }
+}
+
+/// Little-endian cursor over a recording's bytes.
+struct Reader<'a>(&'a [u8]);
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, n: usize) -> Option<&'a [u8]> {
+        if self.0.len() < n {
+            return None;
+        }
+        let (head, rest) = self.0.split_at(n);
+        self.0 = rest;
+        Some(head)
+    }
+
+    fn array<const N: usize>(&mut self) -> Option<[u8; N]> {
+        self.take(N)?.try_into().ok()
+    }
+
+    fn u8(&mut self) -> Option<u8> {
+        Some(self.take(1)?[0])
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        self.array().map(u16::from_le_bytes)
+    }
+
+    fn i16(&mut self) -> Option<i16> {
+        self.array().map(i16::from_le_bytes)
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        self.array().map(u32::from_le_bytes)
+    }
+
+    fn u64(&mut self) -> Option<u64> {
+        self.array().map(u64::from_le_bytes)
+    }
+
+    fn f32(&mut self) -> Option<f32> {
+        self.array().map(f32::from_le_bytes)
+    }
+}
+
+// ─── Recorder ────────────────────────────────────────────────────────────────
+
+/// Records the camera of the puzzle being played.
+pub struct Recorder {
+    puzzle: ReplayPuzzle,
+    frames: Vec<ReplayFrame>,
+    events: Vec<ReplayEvent>,
+    /// Play time since the last frame.
+    since_frame: f32,
+}
+
+impl Recorder {
+    pub fn new(puzzle: ReplayPuzzle) -> Self {
+        Self {
+            puzzle,
+            frames: Vec::new(),
+            events: Vec::new(),
+            since_frame: 0.0,
+        }
+    }
+
+    /// Advance the play clock by `dt` with the camera at `camera`.  The
+    /// first call records frame 0; after that a frame is recorded for every
+    /// `SAMPLE_INTERVAL_SECS` that passes, so a long frame repeats the pose
+    /// rather than shifting the timeline.
+    pub fn tick(&mut self, dt: f32, camera: &Transform) {
+        if self.frames.is_empty() {
+            self.push(camera);
+            return;
+        }
+        self.since_frame += dt.max(0.0);
+        while self.since_frame >= SAMPLE_INTERVAL_SECS {
+            self.since_frame -= SAMPLE_INTERVAL_SECS;
+            self.push(camera);
+        }
+    }
+
+    /// Note that the player moved the hints on to `tier`.
+    pub fn hint(&mut self, tier: u8) {
+        self.events.push(ReplayEvent {
+            frame: self.frames.len() as u32,
+            tier,
+        });
+    }
+
+    /// Close the recording with the camera where it ended and the solve
+    /// time.
+    pub fn finish(mut self, camera: &Transform, time_secs: f32) -> Recording {
+        self.push(camera);
+        Recording {
+            puzzle: self.puzzle,
+            interval_secs: SAMPLE_INTERVAL_SECS,
+            time_secs,
+            frames: self.frames,
+            events: self.events,
+        }
+    }
+
+    fn push(&mut self, camera: &Transform) {
+        if self.frames.len() < MAX_FRAMES {
+            self.frames.push(ReplayFrame::of(camera));
+        }
+    }
+}
+
+// ─── Playback ────────────────────────────────────────────────────────────────
+
+/// Drives the camera along a recording.  The game owns `clock` (seconds
+/// into the replay) and advances it each frame; the controller only reads
+/// it, and ignores input.
+pub struct ReplayController {
+    recording: Rc<Recording>,
+    clock: Rc<Cell<f32>>,
+}
+
+impl ReplayController {
+    pub fn new(recording: Rc<Recording>, clock: Rc<Cell<f32>>) -> Self {
+        Self { recording, clock }
+    }
+}
+
+impl CameraController for ReplayController {
+    fn control(&mut self, transform: &mut Transform) {
+        if let Some(frame) = self.recording.sample(self.clock.get()) {
+            transform.position = frame.position;
+            transform.rotation = frame.rotation;
+        }
+    }
+
+    fn handle_event(&mut self, _event: &formosaic_engine::input::Event, _w: f32, _h: f32) {}
+}
+
+/// World-space edges of a camera frustum at `frame`, `depth` deep, for
+/// drawing a ghost camera: the four edges from the eye, the far rectangle,
+/// and a small triangle on its top edge so the ghost's roll reads.
+pub fn frustum_segments(frame: &ReplayFrame, fov: f32, aspect: f32, depth: f32) -> Vec<Segment> {
+    let camera = Transform {
+        position: frame.position,
+        rotation: frame.rotation,
+        ..Transform::new()
+    };
+    let (eye, fwd, up) = (camera.position, camera.forward(), camera.up());
+    let right = fwd.cross(up).normalize();
+    let half_h = (fov * 0.5).tan() * depth;
+    let (u, r) = (up * half_h, right * half_h * aspect);
+    let centre = eye + fwd * depth;
+    let corners = [
+        centre + r + u,
+        centre - r + u,
+        centre - r - u,
+        centre + r - u,
+    ];
+
+    let mut segments = Vec::with_capacity(11);
+    for (i, &c) in corners.iter().enumerate() {
+        segments.push([eye, c]);
+        segments.push([c, corners[(i + 1) % 4]]);
+    }
+    let tip = centre + u * 1.5;
+    segments.push([centre + u + r * 0.4, tip]);
+    segments.push([tip, centre + u - r * 0.4]);
+    segments.push([centre + u - r * 0.4, centre + u + r * 0.4]);
+    segments
+}
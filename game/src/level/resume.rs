@@ -0,0 +1,171 @@
+//! Save and resume a puzzle in progress.
+//!
+//! Closing the app mid-puzzle, or the system suspending it (Android does so
+//! freely), writes the puzzle to `{data_dir}/resume.json`.  The main menu
+//! then offers "Continue", which reloads the level with the saved seed and
+//! scramble settings — the exact same scramble — and puts back the stage,
+//! camera, clock and hints:
+//!
+//! ```json
+//! {"version":1,"level_id":"abc","seed":"9e3779b97f4a7c15","stages":2,
+//!  "max_roll":0.7853982,"projection":"parallel","stage":1,
+//!  "camera":[0.5,1.25,-3],"roll":0.1,"elapsed_secs":42.50,
+//!  "hint_tier":2,"hint_count":3,"tier_uses":[0,1,1,0,0],"daily":"2025-03-14"}
+//! ```
+//!
+//! `daily` is present when the puzzle is a daily challenge, so its
+//! completion still counts after a resume.  Marathons and time attacks are
+//! not saved.  The file holds one puzzle and is removed as soon as any
+//! puzzle starts, resumed or not.
+
+use std::path::{Path, PathBuf};
+
+use cgmath::Vector3;
+
+use super::daily::UtcDate;
+use super::json::{extract, extract_list, extract_num};
+use super::replay::ReplayPuzzle;
+use crate::puzzle::hints::HintTier;
+use crate::puzzle::puzzle_params::ScrambleProjection;
+use crate::puzzle::seed::PuzzleSeed;
+
+/// Version written by `SavedPuzzle::to_json`; other versions are ignored.
+pub const RESUME_VERSION: u32 = 1;
+
+const FILE_NAME: &str = "resume.json";
+
+/// A puzzle left unfinished.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SavedPuzzle {
+    pub level_id: String,
+    /// Seed and scramble settings the puzzle was generated with.
+    pub puzzle: ReplayPuzzle,
+    /// Stage being solved.
+    pub stage: usize,
+    /// Camera position in world space.
+    pub camera_position: Vector3<f32>,
+    /// Camera roll about the view axis (`scrambler::camera_roll`).
+    pub camera_roll: f32,
+    pub elapsed_secs: f32,
+    pub hint_tier: HintTier,
+    pub hint_count: u32,
+    pub tier_uses: [u32; HintTier::COUNT],
+    /// Date of the daily challenge, if the puzzle is one.
+    pub daily: Option<UtcDate>,
+}
+
+impl SavedPuzzle {
+    pub fn to_json(&self) -> String {
+        let p = self.camera_position;
+        let tier_uses: Vec<String> = self.tier_uses.iter().map(|u| u.to_string()).collect();
+        let projection = match self.puzzle.projection {
+            ScrambleProjection::Parallel => "parallel",
+            ScrambleProjection::Anamorphic => "anamorphic",
+        };
+        let daily = match self.daily {
+            Some(date) => format!(r#","daily":"{date}""#),
+            None => String::new(),
+        };
+        format!(
+            concat!(
+                r#"{{"version":{version},"level_id":"{id}","seed":"{seed}","stages":{stages},"#,
+                r#""max_roll":{max_roll},"projection":"{projection}","stage":{stage},"#,
+                r#""camera":[{x},{y},{z}],"roll":{roll},"elapsed_secs":{t:.2},"#,
+                r#""hint_tier":{tier},"hint_count":{count},"tier_uses":[{uses}]{daily}}}"#,
+            ),
+            version = RESUME_VERSION,
+            id = self.level_id,
+            seed = self.puzzle.seed,
+            stages = self.puzzle.stages,
+            max_roll = self.puzzle.max_roll,
+            projection = projection,
+            stage = self.stage,
+            x = p.x,
+            y = p.y,
+            z = p.z,
+            roll = self.camera_roll,
+            t = self.elapsed_secs,
+            tier = self.hint_tier.as_u8(),
+            count = self.hint_count,
+            uses = tier_uses.join(","),
+            daily = daily,
+        )
+    }
+
+    /// Parse `to_json` output.  `None` if a field is missing or the file is
+    /// of another version.
+    pub fn from_json(s: &str) -> Option<Self> {
+        if extract_num(s, "version")? as u32 != RESUME_VERSION {
+            return None;
+        }
+        let seed = u64::from_str_radix(extract(s, "seed")?, 16).ok()?;
+        let projection = match extract(s, "projection")? {
+            "parallel" => ScrambleProjection::Parallel,
+            "anamorphic" => ScrambleProjection::Anamorphic,
+            _ => return None,
+        };
+        let camera = extract_list(s, "camera")?;
+        let uses = extract_list(s, "tier_uses")?;
+        if camera.len() != 3 || uses.len() != HintTier::COUNT {
+            return None;
+        }
+        let mut tier_uses = [0; HintTier::COUNT];
+        for (dst, src) in tier_uses.iter_mut().zip(uses) {
+            *dst = src as u32;
+        }
+        let daily = match extract(s, "daily") {
+            Some(date) => Some(UtcDate::parse(date)?),
+            None => None,
+        };
+
+        Some(SavedPuzzle {
+            level_id: extract(s, "level_id")?.to_string(),
+            puzzle: ReplayPuzzle {
+                seed: PuzzleSeed::new(seed),
+                stages: (extract_num(s, "stages")? as usize).max(1),
+                max_roll: extract_num(s, "max_roll")?,
+                projection,
+            },
+            stage: extract_num(s, "stage")? as usize,
+            camera_position: Vector3::new(camera[0], camera[1], camera[2]),
+            camera_roll: extract_num(s, "roll")?,
+            elapsed_secs: extract_num(s, "elapsed_secs")?,
+            hint_tier: HintTier::from_u8(extract_num(s, "hint_tier")? as u8)?,
+            hint_count: extract_num(s, "hint_count")? as u32,
+            tier_uses,
+            daily,
+        })
+    }
+
+    /// The saved puzzle in `base_dir`, if there is a readable one.
+    pub fn load(base_dir: &Path) -> Option<Self> {
+        let json = std::fs::read_to_string(path(base_dir)).ok()?;
+        let saved = Self::from_json(&json);
+        if saved.is_none() {
+            log::warn!("[Resume] Ignoring unreadable {}", path(base_dir).display());
+        }
+        saved
+    }
+
+    /// Write `{base_dir}/resume.json`, replacing any saved puzzle.
+    pub fn save(&self, base_dir: &Path) {
+        let _ = std::fs::create_dir_all(base_dir);
+        if let Err(e) = std::fs::write(path(base_dir), self.to_json()) {
+            log::warn!("[Resume] Failed to save {}: {e}", path(base_dir).display());
+        }
+    }
+
+    /// Whether `base_dir` holds a saved puzzle.
+    pub fn exists(base_dir: &Path) -> bool {
+        path(base_dir).is_file()
+    }
+
+    /// Forget the saved puzzle, if any.
+    pub fn clear(base_dir: &Path) {
+        let _ = std::fs::remove_file(path(base_dir));
+    }
+}
+
+fn path(base_dir: &Path) -> PathBuf {
+    base_dir.join(FILE_NAME)
+}
@@ -0,0 +1,162 @@
+//! Composite scenes: levels made of several models placed side by side.
+//!
+//! A composite level's `model_file` is its main model; `LevelMeta::parts`
+//! lists the others, each with a placement relative to the main model.
+//! Placements are measured in the main model's own size, so a scene looks
+//! the same whatever units its models were authored in: `offset` moves
+//! the part's centre that many main-model radii from the main model's
+//! centre, and `scale` makes the part's radius that many main-model radii.
+//!
+//! The load merges the parts into one model (see `part_mesh_transforms`)
+//! and gives every model a stage of its own (`StageSplit::Parts`), so each
+//! is scrambled along its own axis and the player finds them one by one.
+//!
+//! `meta.json` stores the parts as one string (see `parts_to_meta_str`):
+//!
+//! ```text
+//! "parts":"chair.glb@1.5,0,0*0.8;lamp.glb@-1.4,0.2,0.3*0.6"
+//! ```
+
+use cgmath::{InnerSpace, Matrix4, Vector3, Vector4};
+
+/// Most models a composite scene holds, the main model included.
+pub const MAX_SCENE_MODELS: usize = 3;
+
+/// A model placed next to a composite level's main model.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScenePart {
+    /// Filename of the model within the level directory.
+    pub model_file: String,
+    /// Part centre relative to the main model's centre, in main-model radii.
+    pub offset: Vector3<f32>,
+    /// Part radius in main-model radii.
+    pub scale: f32,
+}
+
+/// Centre and radius (half the bounding-box diagonal) of a model.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bounds {
+    pub center: Vector3<f32>,
+    pub radius: f32,
+}
+
+impl Bounds {
+    /// Bounds of meshes' flat positions under their transforms; `None`
+    /// without any vertex.
+    pub fn of(mesh_positions: &[&[f32]], mesh_transforms: &[Matrix4<f32>]) -> Option<Self> {
+        let mut min = Vector3::new(f32::INFINITY, f32::INFINITY, f32::INFINITY);
+        let mut max = -min;
+        for (mesh_idx, pos) in mesh_positions.iter().enumerate() {
+            let transform = mesh_transforms
+                .get(mesh_idx)
+                .copied()
+                .unwrap_or_else(|| Matrix4::from_scale(1.0));
+            for v in pos.chunks_exact(3) {
+                let p = (transform * Vector4::new(v[0], v[1], v[2], 1.0)).truncate();
+                min = Vector3::new(min.x.min(p.x), min.y.min(p.y), min.z.min(p.z));
+                max = Vector3::new(max.x.max(p.x), max.y.max(p.y), max.z.max(p.z));
+            }
+        }
+        (min.x <= max.x).then(|| Self {
+            center: (min + max) * 0.5,
+            radius: ((max - min).magnitude() * 0.5).max(0.001),
+        })
+    }
+}
+
+impl ScenePart {
+    /// Compact form: `file@x,y,z*scale`.
+    pub fn to_meta_str(&self) -> String {
+        format!(
+            "{}@{},{},{}*{}",
+            self.model_file, self.offset.x, self.offset.y, self.offset.z, self.scale
+        )
+    }
+
+    /// Parse `to_meta_str`'s output; `None` for a missing file, a
+    /// non-finite offset or a scale that is not positive.
+    pub fn from_meta_str(s: &str) -> Option<Self> {
+        let (model_file, placement) = s.rsplit_once('@')?;
+        let (offset, scale) = placement.split_once('*')?;
+        let offset: Vec<f32> = offset
+            .split(',')
+            .map(|c| c.parse().ok().filter(|c: &f32| c.is_finite()))
+            .collect::<Option<_>>()?;
+        let scale: f32 = scale.parse().ok()?;
+        if model_file.is_empty() || offset.len() != 3 || !(scale.is_finite() && scale > 0.0) {
+            return None;
+        }
+        Some(Self {
+            model_file: model_file.to_string(),
+            offset: Vector3::new(offset[0], offset[1], offset[2]),
+            scale,
+        })
+    }
+
+    /// Transform taking the part's model space into the main model's: the
+    /// part is centred on its offset and scaled to its size.  `main` and
+    /// `part` are the two models' own bounds.
+    pub fn placement(&self, main: Bounds, part: Bounds) -> Matrix4<f32> {
+        Matrix4::from_translation(main.center + self.offset * main.radius)
+            * Matrix4::from_scale(self.scale * main.radius / part.radius)
+            * Matrix4::from_translation(-part.center)
+    }
+}
+
+/// Default placement of the models `model_files` next to a main model:
+/// alternately to its right and left, each a little smaller than it.
+pub fn side_by_side(model_files: &[&str]) -> Vec<ScenePart> {
+    model_files
+        .iter()
+        .enumerate()
+        .map(|(i, file)| {
+            let side = if i % 2 == 0 { 1.0 } else { -1.0 };
+            ScenePart {
+                model_file: file.to_string(),
+                offset: Vector3::new(side * 1.7, 0.0, 0.0),
+                scale: 0.8,
+            }
+        })
+        .collect()
+}
+
+/// The parts of a scene as one string, `;`-separated.
+pub fn parts_to_meta_str(parts: &[ScenePart]) -> String {
+    parts
+        .iter()
+        .map(ScenePart::to_meta_str)
+        .collect::<Vec<_>>()
+        .join(";")
+}
+
+/// Parse `parts_to_meta_str`'s output; `None` if any part is malformed or
+/// the scene would hold more than `MAX_SCENE_MODELS` models.
+pub fn parts_from_meta_str(s: &str) -> Option<Vec<ScenePart>> {
+    if s.is_empty() {
+        return Some(Vec::new());
+    }
+    let parts: Vec<ScenePart> = s
+        .split(';')
+        .map(ScenePart::from_meta_str)
+        .collect::<Option<_>>()?;
+    (parts.len() < MAX_SCENE_MODELS).then_some(parts)
+}
+
+/// A part's `mesh_count` mesh transforms once merged into the scene:
+/// `placement` applied after each mesh's own transform (missing ones are
+/// the identity).
+pub fn part_mesh_transforms(
+    mesh_transforms: &[Matrix4<f32>],
+    mesh_count: usize,
+    placement: Matrix4<f32>,
+) -> Vec<Matrix4<f32>> {
+    (0..mesh_count)
+        .map(|i| {
+            placement
+                * mesh_transforms
+                    .get(i)
+                    .copied()
+                    .unwrap_or_else(|| Matrix4::from_scale(1.0))
+        })
+        .collect()
+}
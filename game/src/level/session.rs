@@ -0,0 +1,356 @@
+//! Marathon and time-attack sessions, and their local leaderboard.
+//!
+//! A session chains saved levels back to back without the menu in between:
+//!
+//! * **Marathon** — a fixed number of random saved levels, no repeats, on
+//!   one cumulative timer.
+//! * **Time attack** — a fixed time budget; the library is played in a
+//!   shuffled order (reshuffled whenever it runs out) until the clock runs
+//!   out, counting the puzzles solved.
+//!
+//! The clock is play time only: the session banks each solve's time, and
+//! the game adds the running puzzle's time on top.  Loading between levels
+//! does not count, so prefetching the next level is purely a comfort.
+//!
+//! The level order is a pure function of the session seed and the level
+//! library, so `peek_next` can tell the game what to prefetch before the
+//! current puzzle is solved.
+//!
+//! Finished sessions are ranked per mode — more puzzles solved first, then
+//! less time — and the best `LEADERBOARD_SIZE` of each are kept in
+//! `{data_dir}/leaderboard.json`:
+//!
+//! ```json
+//! [
+//!   {"date":"2025-03-14","mode":"marathon","levels":5,"solved":5,"time_secs":312.40},
+//!   {"date":"2025-03-14","mode":"time_attack","budget_secs":300,"solved":7,"time_secs":300.00}
+//! ]
+//! ```
+//!
+//! Abandoned sessions are not recorded.
+
+use std::path::{Path, PathBuf};
+
+use rand::seq::SliceRandom;
+
+use super::daily::UtcDate;
+use super::json::{extract, extract_num};
+use super::storage::LevelMeta;
+use crate::puzzle::seed::PuzzleSeed;
+
+/// Results kept per mode (and per level count or budget).
+pub const LEADERBOARD_SIZE: usize = 10;
+
+// ─── Session ──────────────────────────────────────────────────────────────────
+
+/// Which session is played, with its length.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SessionKind {
+    /// `levels` puzzles back to back, timed.
+    Marathon { levels: usize },
+    /// As many puzzles as fit in `budget_secs`.
+    TimeAttack { budget_secs: f32 },
+}
+
+impl SessionKind {
+    /// Display name.
+    pub fn label(self) -> &'static str {
+        match self {
+            SessionKind::Marathon { .. } => "Marathon",
+            SessionKind::TimeAttack { .. } => "Time Attack",
+        }
+    }
+}
+
+/// A marathon or time attack in progress.
+#[derive(Debug, Clone)]
+pub struct Session {
+    pub kind: SessionKind,
+    /// Level ids, sorted, that the session draws from.
+    library: Vec<String>,
+    seed: PuzzleSeed,
+    /// Shuffled `library` of the current round.
+    order: Vec<String>,
+    /// Reshuffles so far (time attack only).
+    round: u64,
+    /// Index into `order` of the next level to hand out.
+    next: usize,
+    /// Puzzles solved so far.
+    pub solved: u32,
+    /// Play time of the solved puzzles.
+    banked_secs: f32,
+}
+
+impl Session {
+    /// Start a session over `levels`.  A marathon longer than the library
+    /// is cut down to one play of every level.  `None` if there are no
+    /// levels at all.
+    pub fn new(kind: SessionKind, levels: &[LevelMeta], seed: PuzzleSeed) -> Option<Self> {
+        let mut library: Vec<String> = levels.iter().map(|l| l.id.clone()).collect();
+        if library.is_empty() {
+            return None;
+        }
+        // Sorted so the order does not depend on the registry's sort.
+        library.sort_unstable();
+        library.dedup();
+        let kind = match kind {
+            SessionKind::Marathon { levels } => SessionKind::Marathon {
+                levels: levels.clamp(1, library.len()),
+            },
+            other => other,
+        };
+        let order = shuffled(&library, seed, 0, None);
+        Some(Self {
+            kind,
+            library,
+            seed,
+            order,
+            round: 0,
+            next: 0,
+            solved: 0,
+            banked_secs: 0.0,
+        })
+    }
+
+    /// The level `next_level` will hand out, without advancing.
+    pub fn peek_next(&self) -> Option<String> {
+        if let SessionKind::Marathon { levels } = self.kind {
+            if self.next >= levels {
+                return None;
+            }
+        }
+        match self.order.get(self.next) {
+            Some(id) => Some(id.clone()),
+            None => {
+                let last = self.order.last().map(String::as_str);
+                shuffled(&self.library, self.seed, self.round + 1, last)
+                    .into_iter()
+                    .next()
+            }
+        }
+    }
+
+    /// Hand out the next level to play; `None` once a marathon has handed
+    /// out all of its levels.
+    pub fn next_level(&mut self) -> Option<String> {
+        let id = self.peek_next()?;
+        if self.next >= self.order.len() {
+            let last = self.order.last().map(String::as_str);
+            self.order = shuffled(&self.library, self.seed, self.round + 1, last);
+            self.round += 1;
+            self.next = 0;
+        }
+        self.next += 1;
+        Some(id)
+    }
+
+    /// Bank a solve that took `time_secs` of play.
+    pub fn record_solve(&mut self, time_secs: f32) {
+        self.solved += 1;
+        self.banked_secs += time_secs.max(0.0);
+    }
+
+    /// Session clock with the running puzzle at `current_secs`.
+    pub fn total_secs(&self, current_secs: f32) -> f32 {
+        self.banked_secs + current_secs.max(0.0)
+    }
+
+    /// Time left of a time attack; `None` for a marathon.
+    pub fn remaining_secs(&self, current_secs: f32) -> Option<f32> {
+        match self.kind {
+            SessionKind::TimeAttack { budget_secs } => {
+                Some((budget_secs - self.total_secs(current_secs)).max(0.0))
+            }
+            SessionKind::Marathon { .. } => None,
+        }
+    }
+
+    /// Whether the session is finished: every marathon level solved, or
+    /// the time-attack budget spent.
+    pub fn is_over(&self, current_secs: f32) -> bool {
+        match self.kind {
+            SessionKind::Marathon { levels } => self.solved as usize >= levels,
+            SessionKind::TimeAttack { .. } => self.remaining_secs(current_secs) == Some(0.0),
+        }
+    }
+
+    /// Leaderboard entry for the session as it stands.
+    pub fn result(&self, date: UtcDate, current_secs: f32) -> LeaderboardEntry {
+        let total = self.total_secs(current_secs);
+        LeaderboardEntry {
+            date,
+            kind: self.kind,
+            solved: self.solved,
+            time_secs: match self.kind {
+                SessionKind::TimeAttack { budget_secs } => total.min(budget_secs),
+                SessionKind::Marathon { .. } => total,
+            },
+        }
+    }
+}
+
+/// `library` shuffled for `round`.  A round never starts with `avoid` (the
+/// level that ended the previous one), so no level is played twice in a row.
+fn shuffled(library: &[String], seed: PuzzleSeed, round: u64, avoid: Option<&str>) -> Vec<String> {
+    let mut order = library.to_vec();
+    order.shuffle(&mut seed.derive(round).rng());
+    if order.len() > 1 && avoid == Some(order[0].as_str()) {
+        order.swap(0, 1);
+    }
+    order
+}
+
+// ─── Leaderboard ──────────────────────────────────────────────────────────────
+
+/// Result of one finished session.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LeaderboardEntry {
+    pub date: UtcDate,
+    pub kind: SessionKind,
+    pub solved: u32,
+    /// Session clock at the end (the full budget for a time attack).
+    pub time_secs: f32,
+}
+
+impl LeaderboardEntry {
+    /// Whether this result ranks above `other`: more puzzles solved, then
+    /// less time.
+    pub fn beats(&self, other: &LeaderboardEntry) -> bool {
+        self.solved > other.solved
+            || (self.solved == other.solved && self.time_secs < other.time_secs)
+    }
+
+    pub fn to_json(&self) -> String {
+        let (mode, length) = match self.kind {
+            SessionKind::Marathon { levels } => ("marathon", format!(r#""levels":{levels}"#)),
+            SessionKind::TimeAttack { budget_secs } => (
+                "time_attack",
+                format!(r#""budget_secs":{:.0}"#, budget_secs),
+            ),
+        };
+        format!(
+            r#"{{"date":"{date}","mode":"{mode}",{length},"solved":{solved},"time_secs":{t:.2}}}"#,
+            date = self.date,
+            solved = self.solved,
+            t = self.time_secs,
+        )
+    }
+
+    /// Parse one entry object produced by `to_json`.
+    pub fn from_json(s: &str) -> Option<Self> {
+        let kind = match extract(s, "mode")? {
+            "marathon" => SessionKind::Marathon {
+                levels: extract_num(s, "levels")? as usize,
+            },
+            "time_attack" => SessionKind::TimeAttack {
+                budget_secs: extract_num(s, "budget_secs")?,
+            },
+            _ => return None,
+        };
+        Some(LeaderboardEntry {
+            date: UtcDate::parse(extract(s, "date")?)?,
+            kind,
+            solved: extract_num(s, "solved")? as u32,
+            time_secs: extract_num(s, "time_secs")?,
+        })
+    }
+}
+
+/// Best session results, persisted in the data dir.
+pub struct Leaderboard {
+    /// Every mode's results, each mode's in rank order.
+    pub entries: Vec<LeaderboardEntry>,
+    path: Option<PathBuf>,
+}
+
+impl Leaderboard {
+    /// An empty leaderboard that is never written to disk.
+    pub fn in_memory() -> Self {
+        Self {
+            entries: Vec::new(),
+            path: None,
+        }
+    }
+
+    /// Load `{base_dir}/leaderboard.json`.  A missing or unreadable file
+    /// gives an empty leaderboard.
+    pub fn load(base_dir: &Path) -> Self {
+        let path = base_dir.join("leaderboard.json");
+        let entries = std::fs::read_to_string(&path)
+            .map(|json| Self::parse(&json))
+            .unwrap_or_default();
+        Self {
+            entries,
+            path: Some(path),
+        }
+    }
+
+    fn parse(json: &str) -> Vec<LeaderboardEntry> {
+        let mut board = Self::in_memory();
+        for entry in json
+            .split('}')
+            .filter_map(|obj| LeaderboardEntry::from_json(&format!("{obj}}}")))
+        {
+            board.insert(entry);
+        }
+        board.entries
+    }
+
+    pub fn to_json(&self) -> String {
+        let body: Vec<String> = self.entries.iter().map(|e| e.to_json()).collect();
+        format!("[\n  {}\n]\n", body.join(",\n  "))
+    }
+
+    /// Results of `kind`, best first.
+    pub fn top(&self, kind: SessionKind) -> Vec<&LeaderboardEntry> {
+        self.entries.iter().filter(|e| e.kind == kind).collect()
+    }
+
+    /// Store a finished session.  Returns its 1-based rank among results of
+    /// the same kind, or `None` if it did not make the top
+    /// `LEADERBOARD_SIZE` (and was dropped).
+    pub fn record(&mut self, entry: LeaderboardEntry) -> Option<usize> {
+        let rank = self.insert(entry);
+        self.save();
+        rank
+    }
+
+    /// `record` without saving.
+    fn insert(&mut self, entry: LeaderboardEntry) -> Option<usize> {
+        let kind = entry.kind;
+        // Ties keep the older result ahead.
+        let pos = self
+            .entries
+            .iter()
+            .position(|e| e.kind == kind && entry.beats(e))
+            .unwrap_or(self.entries.len());
+        let rank = self.entries[..pos]
+            .iter()
+            .filter(|e| e.kind == kind)
+            .count()
+            + 1;
+        self.entries.insert(pos, entry);
+
+        let mut kept = 0;
+        self.entries.retain(|e| {
+            if e.kind != kind {
+                return true;
+            }
+            kept += 1;
+            kept <= LEADERBOARD_SIZE
+        });
+        (rank <= LEADERBOARD_SIZE).then_some(rank)
+    }
+
+    fn save(&self) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        if let Some(dir) = path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        if let Err(e) = std::fs::write(path, self.to_json()) {
+            log::warn!("[Leaderboard] Failed to save {}: {e}", path.display());
+        }
+    }
+}
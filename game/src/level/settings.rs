@@ -0,0 +1,69 @@
+//! Player settings that outlive a session.
+//!
+//! Kept in `{data_dir}/settings.json`:
+//!
+//! ```json
+//! {"version":1,"warmth_palette":"deuteranopia"}
+//! ```
+//!
+//! A missing or unreadable file, or an unknown value, falls back to the
+//! default for that setting, so an older or newer build never refuses to
+//! start over its settings.
+
+use std::path::{Path, PathBuf};
+
+use super::json::extract;
+use crate::puzzle::palette::WarmthPalette;
+
+/// Version written by `Settings::to_json`.
+pub const SETTINGS_VERSION: u32 = 1;
+
+const FILE_NAME: &str = "settings.json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Settings {
+    /// Colours of the warm/cold hint.
+    pub warmth_palette: WarmthPalette,
+}
+
+impl Settings {
+    pub fn to_json(&self) -> String {
+        format!(
+            r#"{{"version":{version},"warmth_palette":"{palette}"}}"#,
+            version = SETTINGS_VERSION,
+            palette = self.warmth_palette.to_meta_str(),
+        )
+    }
+
+    /// Parse `to_json` output.  Missing or unknown settings keep their
+    /// defaults.
+    pub fn from_json(s: &str) -> Self {
+        let mut settings = Settings::default();
+        if let Some(palette) = extract(s, "warmth_palette").and_then(WarmthPalette::from_meta_str) {
+            settings.warmth_palette = palette;
+        }
+        settings
+    }
+
+    /// The settings in `base_dir`, or the defaults if there are none.
+    pub fn load(base_dir: &Path) -> Self {
+        std::fs::read_to_string(path(base_dir))
+            .map(|json| Self::from_json(&json))
+            .unwrap_or_default()
+    }
+
+    /// Write `{base_dir}/settings.json`.
+    pub fn save(&self, base_dir: &Path) {
+        let _ = std::fs::create_dir_all(base_dir);
+        if let Err(e) = std::fs::write(path(base_dir), self.to_json()) {
+            log::warn!(
+                "[Settings] Failed to save {}: {e}",
+                path(base_dir).display()
+            );
+        }
+    }
+}
+
+fn path(base_dir: &Path) -> PathBuf {
+    base_dir.join(FILE_NAME)
+}
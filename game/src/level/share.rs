@@ -0,0 +1,282 @@
+//! Share codes: a short text code for one exact puzzle.
+//!
+//! A code names the level, the scramble seed and everything else the
+//! puzzle is built from: the mode (stage count, projection, rotation
+//! scramble), the level's scramble strategy and upright constraint, and its
+//! authored puzzle if that is what was played.  Pasting it into the menu
+//! plays the identical puzzle, whatever the recipient's copy of the level
+//! says — the level is fetched from poly.pizza by id when it is not saved
+//! locally.
+//!
+//! The bytes are
+//!
+//! ```text
+//! version u8 | mode u8 | strategy u8 [max triangles u16 LE]
+//!     | seed u64 LE | [authored puzzle] | level id (ASCII) | CRC-16 BE
+//! ```
+//!
+//! where `mode` holds `stages - 1` in bits 0–3, anamorphic in bit 4, the
+//! rotation scramble in bit 5, upright in bit 6 and an authored puzzle in
+//! bit 7.  `strategy` is 0 per triangle, 1 per mesh or 2 per cluster, the
+//! last followed by the cluster size limit.  An authored puzzle is its
+//! axis and displacement range, five `f32 LE`; its seed is the code's.
+//! They are written in Crockford base32, in dash-separated groups of
+//! `GROUP_LEN` characters:
+//!
+//! ```text
+//! 08S0-05BW-99ZV-JY9Q-KRVN-6DAK-DSR6-GTV1-DNY0-R
+//! ```
+//!
+//! Decoding ignores case, dashes and whitespace and reads `O` as `0` and
+//! `I` / `L` as `1`, so a code read out or retyped still works.  The CRC
+//! catches any other typo.
+
+use cgmath::Vector3;
+
+use crate::puzzle::authoring::AuthoredPuzzle;
+use crate::puzzle::puzzle_params::{ScrambleProjection, ScrambleStrategy};
+use crate::puzzle::seed::PuzzleSeed;
+
+/// Version written by `ShareCode::encode`; other versions are rejected.
+pub const SHARE_VERSION: u8 = 2;
+
+/// Most stages a code can hold.
+pub const MAX_SHARE_STAGES: usize = 16;
+
+/// Longest level id a code can hold.
+pub const MAX_ID_LEN: usize = 64;
+
+/// Characters per dash-separated group.
+pub const GROUP_LEN: usize = 4;
+
+const ALPHABET: &[u8; 32] = b"0123456789ABCDEFGHJKMNPQRSTVWXYZ";
+
+const ANAMORPHIC: u8 = 1 << 4;
+const ROLL: u8 = 1 << 5;
+const UPRIGHT: u8 = 1 << 6;
+const AUTHORED: u8 = 1 << 7;
+
+const PER_TRIANGLE: u8 = 0;
+const PER_MESH: u8 = 1;
+const PER_CLUSTER: u8 = 2;
+
+/// Everything needed to rebuild a puzzle on another device.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShareCode {
+    /// Level id — the poly.pizza model id for downloaded levels.
+    pub level_id: String,
+    pub seed: PuzzleSeed,
+    pub stages: usize,
+    pub projection: ScrambleProjection,
+    /// Whether fragments are also rotated (the rotation scramble).
+    pub roll: bool,
+    pub strategy: ScrambleStrategy,
+    /// Whether the solve must also be upright (see `PuzzleParams::upright`).
+    pub upright: bool,
+    /// The level's authored puzzle, when that is what was played.  Its seed
+    /// is `seed`.
+    pub authored: Option<AuthoredPuzzle>,
+}
+
+impl ShareCode {
+    /// The code as text.  `None` if the puzzle cannot be shared: an invalid
+    /// level id, more than `MAX_SHARE_STAGES` stages, a cluster size limit
+    /// past `u16::MAX` or an authored puzzle with a seed of its own.
+    pub fn encode(&self) -> Option<String> {
+        let mut bytes = self.to_bytes()?;
+        bytes.extend_from_slice(&crc16(&bytes).to_be_bytes());
+        let text = to_base32(&bytes);
+        let groups: Vec<&str> = text
+            .as_bytes()
+            .chunks(GROUP_LEN)
+            .map(|g| std::str::from_utf8(g).unwrap_or_default())
+            .collect();
+        Some(groups.join("-"))
+    }
+
+    /// Parse `encode` output.  `None` if the code is mistyped, damaged or
+    /// of another version.
+    pub fn decode(code: &str) -> Option<Self> {
+        let bytes = from_base32(code)?;
+        if bytes.len() < 2 {
+            return None;
+        }
+        let (body, crc) = bytes.split_at(bytes.len() - 2);
+        if crc16(body).to_be_bytes() != crc {
+            return None;
+        }
+        Self::from_bytes(body)
+    }
+
+    /// The code's bytes before the CRC.
+    pub fn to_bytes(&self) -> Option<Vec<u8>> {
+        if !valid_level_id(&self.level_id)
+            || !(1..=MAX_SHARE_STAGES).contains(&self.stages)
+            || self.authored.is_some_and(|p| p.seed != self.seed)
+        {
+            return None;
+        }
+        let mut mode = (self.stages - 1) as u8;
+        if self.projection == ScrambleProjection::Anamorphic {
+            mode |= ANAMORPHIC;
+        }
+        if self.roll {
+            mode |= ROLL;
+        }
+        if self.upright {
+            mode |= UPRIGHT;
+        }
+        if self.authored.is_some() {
+            mode |= AUTHORED;
+        }
+        let mut bytes = vec![SHARE_VERSION, mode];
+        match self.strategy {
+            ScrambleStrategy::PerTriangle => bytes.push(PER_TRIANGLE),
+            ScrambleStrategy::PerMesh => bytes.push(PER_MESH),
+            ScrambleStrategy::PerCluster { max_triangles } => {
+                bytes.push(PER_CLUSTER);
+                bytes.extend_from_slice(&u16::try_from(max_triangles).ok()?.to_le_bytes());
+            }
+        }
+        bytes.extend_from_slice(&self.seed.value().to_le_bytes());
+        if let Some(p) = &self.authored {
+            for v in [p.axis.x, p.axis.y, p.axis.z, p.min_disp, p.max_disp] {
+                bytes.extend_from_slice(&v.to_le_bytes());
+            }
+        }
+        bytes.extend_from_slice(self.level_id.as_bytes());
+        Some(bytes)
+    }
+
+    /// Parse `to_bytes` output.
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let mut reader = Reader(bytes);
+        if reader.take::<1>()? != [SHARE_VERSION] {
+            return None;
+        }
+        let [mode] = reader.take()?;
+        let strategy = match reader.take()? {
+            [PER_TRIANGLE] => ScrambleStrategy::PerTriangle,
+            [PER_MESH] => ScrambleStrategy::PerMesh,
+            [PER_CLUSTER] => ScrambleStrategy::PerCluster {
+                max_triangles: u16::from_le_bytes(reader.take()?) as usize,
+            },
+            _ => return None,
+        };
+        let seed = PuzzleSeed::new(u64::from_le_bytes(reader.take()?));
+        let authored = if mode & AUTHORED != 0 {
+            let mut v = [0.0; 5];
+            for x in &mut v {
+                *x = f32::from_le_bytes(reader.take()?);
+            }
+            Some(AuthoredPuzzle {
+                axis: Vector3::new(v[0], v[1], v[2]),
+                min_disp: v[3],
+                max_disp: v[4],
+                seed,
+            })
+        } else {
+            None
+        };
+        let level_id = std::str::from_utf8(reader.0).ok()?;
+        if !valid_level_id(level_id) {
+            return None;
+        }
+        Some(ShareCode {
+            level_id: level_id.to_string(),
+            seed,
+            stages: (mode & 0x0f) as usize + 1,
+            projection: if mode & ANAMORPHIC != 0 {
+                ScrambleProjection::Anamorphic
+            } else {
+                ScrambleProjection::Parallel
+            },
+            roll: mode & ROLL != 0,
+            strategy,
+            upright: mode & UPRIGHT != 0,
+            authored,
+        })
+    }
+}
+
+/// The unread rest of a code's bytes.
+struct Reader<'a>(&'a [u8]);
+
+impl Reader<'_> {
+    /// The next `N` bytes, or `None` if fewer are left.
+    fn take<const N: usize>(&mut self) -> Option<[u8; N]> {
+        let (head, rest) = self.0.split_first_chunk::<N>()?;
+        self.0 = rest;
+        Some(*head)
+    }
+}
+
+/// Whether `id` can travel in a code.  Level ids name directories, so only
+/// ASCII letters, digits, `_` and `-` are allowed.
+pub fn valid_level_id(id: &str) -> bool {
+    !id.is_empty()
+        && id.len() <= MAX_ID_LEN
+        && id
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'_' || b == b'-')
+}
+
+/// CRC-16/CCITT-FALSE.
+fn crc16(bytes: &[u8]) -> u16 {
+    let mut crc: u16 = 0xffff;
+    for &b in bytes {
+        crc ^= (b as u16) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+fn to_base32(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len() * 8 / 5 + 1);
+    let (mut acc, mut bits) = (0u32, 0);
+    for &b in bytes {
+        acc = (acc << 8) | b as u32;
+        bits += 8;
+        while bits >= 5 {
+            bits -= 5;
+            out.push(ALPHABET[((acc >> bits) & 31) as usize] as char);
+        }
+    }
+    if bits > 0 {
+        out.push(ALPHABET[((acc << (5 - bits)) & 31) as usize] as char);
+    }
+    out
+}
+
+fn from_base32(text: &str) -> Option<Vec<u8>> {
+    let mut out = Vec::with_capacity(text.len() * 5 / 8);
+    let (mut acc, mut bits) = (0u32, 0);
+    for c in text.chars() {
+        if c == '-' || c.is_whitespace() {
+            continue;
+        }
+        let c = match c.to_ascii_uppercase() {
+            'O' => '0',
+            'I' | 'L' => '1',
+            c => c,
+        };
+        let value = ALPHABET.iter().position(|&a| a as char == c)? as u32;
+        acc = (acc << 5) | value;
+        bits += 5;
+        if bits >= 8 {
+            bits -= 8;
+            out.push((acc >> bits) as u8);
+        }
+    }
+    // Leftover bits are padding and must be zero.
+    if acc & ((1 << bits) - 1) != 0 {
+        return None;
+    }
+    Some(out)
+}
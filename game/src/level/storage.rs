@@ -14,6 +14,7 @@
 //! ```text
 //! {data_dir}/levels/{id}/
 //!     model.glb        (or .fbx / .obj)
+//!     part1.glb …      (the other models of a composite scene)
 //!     meta.json        (LevelMeta serialised as JSON)
 //! ```
 //!
@@ -27,13 +28,33 @@
 //!   "source_url": "https://poly.pizza/m/7S5Snphkam",
 //!   "model_file": "model.glb",
 //!   "best_time_secs": null,
+//!   "best_score": null,
+//!   "stars": 0,
 //!   "play_count": 0,
-//!   "difficulty": 0.42
+//!   "difficulty": 0.42,
+//!   "strategy": "cluster:64",
+//!   "upright": false,
+//!   "authored": "0.6,0,0.8/0.02,0.12/9e3779b97f4a7c15",
+//!   "parts":    "part1.glb@1.7,0,0*0.8"
 //! }
 //! ```
+//!
+//! `strategy` is optional (see `ScrambleStrategy::to_meta_str`); levels saved
+//! before it existed scramble per triangle.  `best_score` and `stars` are
+//! optional too and read as never scored, and a missing `upright` leaves
+//! the level without the upright constraint.  `authored` is only present
+//! for a level whose puzzle was made in the editor (see
+//! `AuthoredPuzzle::to_meta_str`); without it every play searches for an
+//! axis of its own.  `parts` is only present for a composite scene (see
+//! `level::scene`); without it the level is the one model.
 
 use std::path::{Path, PathBuf};
 
+use super::scene::{parts_from_meta_str, parts_to_meta_str, ScenePart};
+use crate::puzzle::authoring::AuthoredPuzzle;
+use crate::puzzle::puzzle_params::ScrambleStrategy;
+use crate::puzzle::scoring::Score;
+
 // ─── Types ────────────────────────────────────────────────────────────────────
 
 /// Metadata stored alongside every downloaded level.
@@ -53,10 +74,24 @@ pub struct LevelMeta {
     pub model_file: String,
     /// Best completion time in seconds (None if never completed).
     pub best_time_secs: Option<f32>,
+    /// Best score (None if never completed).
+    pub best_score: Option<u32>,
+    /// Most stars earned, 0–3 (0 if never completed).
+    pub stars: u8,
     /// Number of times this level has been played.
     pub play_count: u32,
     /// Entropy-derived difficulty [0,1].
     pub difficulty: f32,
+    /// What moves as one piece when this level is scrambled.
+    pub scramble_strategy: ScrambleStrategy,
+    /// Roll-locked level: only an upright camera solves it
+    /// (`PuzzleParams::upright`).
+    pub upright: bool,
+    /// Puzzle made in the editor, played instead of a searched one.
+    pub authored: Option<AuthoredPuzzle>,
+    /// The other models of a composite scene, placed around `model_file`
+    /// (empty for a single-model level).
+    pub parts: Vec<ScenePart>,
 }
 
 impl LevelMeta {
@@ -68,14 +103,49 @@ impl LevelMeta {
         )
     }
 
+    /// Number of models in the level: 1, or more for a composite scene.
+    pub fn model_count(&self) -> usize {
+        1 + self.parts.len()
+    }
+
+    /// Fold a completion into the records and count the play.  Best time,
+    /// score and stars are kept independently.  Returns true if `score`
+    /// beats the previous best score.
+    pub fn record_score(&mut self, time_secs: f32, score: Score) -> bool {
+        self.play_count += 1;
+        self.best_time_secs = Some(match self.best_time_secs {
+            Some(prev) => prev.min(time_secs),
+            None => time_secs,
+        });
+        self.stars = self.stars.max(score.stars);
+        let new_best = self.best_score.is_none_or(|prev| score.points > prev);
+        if new_best {
+            self.best_score = Some(score.points);
+        }
+        new_best
+    }
+
     /// Serialise to a simple JSON string (no external crate required).
     pub fn to_json(&self) -> String {
         let best = match self.best_time_secs {
             Some(t) => format!("{:.2}", t),
             None => "null".to_string(),
         };
+        let score = match self.best_score {
+            Some(p) => p.to_string(),
+            None => "null".to_string(),
+        };
+        let authored = match &self.authored {
+            Some(puzzle) => format!(r#","authored":"{}""#, puzzle.to_meta_str()),
+            None => String::new(),
+        };
+        let parts = if self.parts.is_empty() {
+            String::new()
+        } else {
+            format!(r#","parts":"{}""#, parts_to_meta_str(&self.parts))
+        };
         format!(
-            r#"{{"id":"{id}","name":"{name}","author":"{author}","license":"{lic}","source_url":"{url}","model_file":"{mf}","best_time_secs":{best},"play_count":{pc},"difficulty":{diff:.4}}}"#,
+            r#"{{"id":"{id}","name":"{name}","author":"{author}","license":"{lic}","source_url":"{url}","model_file":"{mf}","best_time_secs":{best},"best_score":{score},"stars":{stars},"play_count":{pc},"difficulty":{diff:.4},"strategy":"{strat}","upright":{upright}{authored}{parts}}}"#,
             id = self.id,
             name = self.name,
             author = self.author,
@@ -83,8 +153,14 @@ impl LevelMeta {
             url = self.source_url,
             mf = self.model_file,
             best = best,
+            score = score,
+            stars = self.stars,
             pc = self.play_count,
             diff = self.difficulty,
+            strat = self.scramble_strategy.to_meta_str(),
+            upright = self.upright,
+            authored = authored,
+            parts = parts,
         )
     }
 
@@ -131,8 +207,18 @@ impl LevelMeta {
             source_url: extract(s, "source_url")?.to_string(),
             model_file: extract(s, "model_file")?.to_string(),
             best_time_secs,
+            best_score: extract_num(s, "best_score").map(|p| p as u32),
+            stars: (extract_num(s, "stars").unwrap_or(0.0) as u8).min(3),
             play_count: extract_num(s, "play_count").unwrap_or(0.0) as u32,
             difficulty: extract_num(s, "difficulty").unwrap_or(0.5),
+            scramble_strategy: extract(s, "strategy")
+                .and_then(ScrambleStrategy::from_meta_str)
+                .unwrap_or_default(),
+            upright: s.contains("\"upright\":true"),
+            authored: extract(s, "authored").and_then(AuthoredPuzzle::from_meta_str),
+            parts: extract(s, "parts")
+                .and_then(parts_from_meta_str)
+                .unwrap_or_default(),
         })
     }
 }
@@ -171,12 +257,20 @@ impl LevelRegistry {
         }
     }
 
+    /// Directory holding a level's model, metadata and best replay.
+    pub fn level_dir(&self, id: &str) -> PathBuf {
+        self.base_dir.join("levels").join(id)
+    }
+
     /// Path to the model file for a level.
     pub fn model_path(&self, meta: &LevelMeta) -> PathBuf {
-        self.base_dir
-            .join("levels")
-            .join(&meta.id)
-            .join(&meta.model_file)
+        self.level_dir(&meta.id).join(&meta.model_file)
+    }
+
+    /// Paths to the model files of a composite scene's parts, in order.
+    pub fn part_paths(&self, meta: &LevelMeta) -> Vec<PathBuf> {
+        let dir = self.level_dir(&meta.id);
+        meta.parts.iter().map(|p| dir.join(&p.model_file)).collect()
     }
 
     /// Save a new level (model bytes + meta) to disk.
@@ -203,19 +297,52 @@ impl LevelRegistry {
         Ok(())
     }
 
-    /// Update the best time and increment play count for a level.
-    pub fn record_completion(&mut self, id: &str, time_secs: f32) {
-        if let Some(meta) = self.levels.iter_mut().find(|l| l.id == id) {
-            meta.play_count += 1;
-            meta.best_time_secs = Some(match meta.best_time_secs {
-                Some(prev) => prev.min(time_secs),
-                None => time_secs,
-            });
-            // Persist update.
-            let dir = self.base_dir.join("levels").join(&meta.id);
-            let path = dir.join("meta.json");
-            let _ = std::fs::write(path, meta.to_json());
+    /// Save a new composite scene: `model_bytes` for its main model and
+    /// `part_bytes` for each of `meta.parts`, in order.
+    pub fn save_scene(
+        &mut self,
+        meta: LevelMeta,
+        model_bytes: &[u8],
+        part_bytes: &[&[u8]],
+    ) -> Result<(), std::io::Error> {
+        if part_bytes.len() != meta.parts.len() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "one model file is needed per scene part",
+            ));
+        }
+        let dir = self.level_dir(&meta.id);
+        std::fs::create_dir_all(&dir)?;
+        for (part, bytes) in meta.parts.iter().zip(part_bytes) {
+            std::fs::write(dir.join(&part.model_file), bytes)?;
         }
+        self.save_level(meta, model_bytes)
+    }
+
+    /// Update the best time, score and stars and increment play count for a
+    /// level.  Returns true if `score` beats the level's previous best.
+    pub fn record_completion(&mut self, id: &str, time_secs: f32, score: Score) -> bool {
+        let Some(meta) = self.levels.iter_mut().find(|l| l.id == id) else {
+            return false;
+        };
+        let new_best = meta.record_score(time_secs, score);
+        // Persist update.
+        let dir = self.base_dir.join("levels").join(&meta.id);
+        let path = dir.join("meta.json");
+        let _ = std::fs::write(path, meta.to_json());
+        new_best
+    }
+
+    /// Store (or with `None`, drop) a level's authored puzzle.  Returns
+    /// false if the level is unknown.
+    pub fn set_authored(&mut self, id: &str, puzzle: Option<AuthoredPuzzle>) -> bool {
+        let Some(meta) = self.levels.iter_mut().find(|l| l.id == id) else {
+            return false;
+        };
+        meta.authored = puzzle;
+        let dir = self.base_dir.join("levels").join(&meta.id);
+        let _ = std::fs::write(dir.join("meta.json"), meta.to_json());
+        true
     }
 
     /// Returns a random level meta (for the "Random Level" button).
@@ -0,0 +1,204 @@
+//! Hand-authored puzzles.
+//!
+//! The puzzle editor lets a level designer pick a level's scramble axis and
+//! displacement range instead of leaving them to the entropy search.  The
+//! choice is saved in the level's `meta.json` (`LevelMeta::authored`) and
+//! every later play of the level builds that puzzle: one stage along
+//! `axis`, displaced by `min_disp..max_disp`, drawn from `seed`.  The
+//! projection and the rotation scramble stay the player's settings.
+//!
+//! The editor shows the viewpoint sphere as an equirectangular map
+//! (`direction_at` / `map_position`), coloured by how good a puzzle each
+//! direction would make as the axis (`AxisHeatmap`): every cell is run
+//! through `analyse_fixed_axes` with the editor's displacements and ranked
+//! the way the entropy search ranks its candidates.
+
+use cgmath::{InnerSpace, Vector3};
+use std::f32::consts::PI;
+
+use super::entropy::{analyse_fixed_axes, search_rank};
+use super::puzzle_params::PuzzleParams;
+use super::search::SearchOptions;
+use super::seed::PuzzleSeed;
+
+/// Smallest gap kept between `min_disp` and `max_disp`, in model units;
+/// the displacement range must not be empty.
+pub const MIN_DISP_SPREAD: f32 = 1e-4;
+
+/// A designer's puzzle for one level.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AuthoredPuzzle {
+    /// Scramble axis in model space, unit length.
+    pub axis: Vector3<f32>,
+    /// Displacement range in model units (see `PuzzleParams`).
+    pub min_disp: f32,
+    pub max_disp: f32,
+    /// Seed the per-fragment displacements are drawn from.
+    pub seed: PuzzleSeed,
+}
+
+impl AuthoredPuzzle {
+    /// `self` with a new displacement range: negative values are clamped
+    /// to zero and `max_disp` is kept above `min_disp`.
+    pub fn with_disp(self, min_disp: f32, max_disp: f32) -> Self {
+        let min_disp = min_disp.max(0.0);
+        AuthoredPuzzle {
+            min_disp,
+            max_disp: max_disp.max(min_disp + MIN_DISP_SPREAD),
+            ..self
+        }
+    }
+
+    /// `self` with a new axis, normalised.  Unchanged if `axis` has no
+    /// direction.
+    pub fn with_axis(self, axis: Vector3<f32>) -> Self {
+        if axis.magnitude2() <= 1e-12 {
+            return self;
+        }
+        AuthoredPuzzle {
+            axis: axis.normalize(),
+            ..self
+        }
+    }
+
+    /// `params` for this puzzle: the authored displacement range, one stage.
+    pub fn apply(&self, params: PuzzleParams) -> PuzzleParams {
+        PuzzleParams {
+            min_disp: self.min_disp,
+            max_disp: self.max_disp,
+            ..params
+        }
+        .with_stages(1)
+    }
+
+    /// Compact form stored in `meta.json`: `x,y,z/min,max/seed`, the seed in
+    /// hex.  Floats are written in full so the puzzle is rebuilt exactly.
+    pub fn to_meta_str(&self) -> String {
+        format!(
+            "{},{},{}/{},{}/{}",
+            self.axis.x, self.axis.y, self.axis.z, self.min_disp, self.max_disp, self.seed
+        )
+    }
+
+    /// Inverse of `to_meta_str`.  `None` for a zero axis or an empty or
+    /// negative displacement range.
+    pub fn from_meta_str(s: &str) -> Option<Self> {
+        fn floats<const N: usize>(s: &str) -> Option<[f32; N]> {
+            let values: Vec<f32> = s
+                .split(',')
+                .map(|v| v.trim().parse::<f32>().ok().filter(|v| v.is_finite()))
+                .collect::<Option<_>>()?;
+            values.try_into().ok()
+        }
+
+        let mut parts = s.split('/');
+        let [x, y, z] = floats::<3>(parts.next()?)?;
+        let [min_disp, max_disp] = floats::<2>(parts.next()?)?;
+        let seed = u64::from_str_radix(parts.next()?.trim(), 16).ok()?;
+        if parts.next().is_some() || min_disp < 0.0 || max_disp <= min_disp {
+            return None;
+        }
+        let axis = Vector3::new(x, y, z);
+        if axis.magnitude2() <= 1e-12 {
+            return None;
+        }
+        Some(AuthoredPuzzle {
+            axis: axis.normalize(),
+            min_disp,
+            max_disp,
+            seed: PuzzleSeed::new(seed),
+        })
+    }
+}
+
+// ─── Viewpoint map ───────────────────────────────────────────────────────────
+
+/// Direction at map position `(u, v)`, both in [0,1].  `u` runs once round
+/// the horizon starting at +X; `v` runs from straight up (+Y) at 0 to
+/// straight down at 1.
+pub fn direction_at(u: f32, v: f32) -> Vector3<f32> {
+    let lon = u * 2.0 * PI;
+    let lat = (0.5 - v) * PI;
+    Vector3::new(lat.cos() * lon.cos(), lat.sin(), lat.cos() * lon.sin())
+}
+
+/// Map position of `dir`; inverse of `direction_at`.
+pub fn map_position(dir: Vector3<f32>) -> (f32, f32) {
+    let d = dir.normalize();
+    let lat = d.y.clamp(-1.0, 1.0).asin();
+    let lon = d.z.atan2(d.x).rem_euclid(2.0 * PI);
+    (lon / (2.0 * PI), 0.5 - lat / PI)
+}
+
+/// Axis quality sampled on a `cols` × `rows` grid over the map, rescaled
+/// to the spread between the worst and the best axis (the search's ranks
+/// have no fixed range).
+#[derive(Debug, Clone)]
+pub struct AxisHeatmap {
+    pub cols: usize,
+    pub rows: usize,
+    /// Row-major, top row first; 0 at the worst axis, 1 at the best.
+    pub scores: Vec<f32>,
+}
+
+impl AxisHeatmap {
+    /// Analyse the centre of every cell as the axis of `puzzle`'s
+    /// displacements (`puzzle.axis` itself is ignored).  `positions_flat`
+    /// and `clusters` are as for `analyse_fixed_axes`.  `None` if the
+    /// search was cancelled.
+    pub fn new(
+        positions_flat: &[f32],
+        clusters: &[usize],
+        puzzle: &AuthoredPuzzle,
+        params: &PuzzleParams,
+        cols: usize,
+        rows: usize,
+        options: &SearchOptions,
+    ) -> Option<Self> {
+        let axes: Vec<Vector3<f32>> = (0..rows)
+            .flat_map(|r| {
+                (0..cols).map(move |c| {
+                    direction_at(
+                        (c as f32 + 0.5) / cols as f32,
+                        (r as f32 + 0.5) / rows as f32,
+                    )
+                })
+            })
+            .collect();
+        let params = puzzle.apply(*params);
+        let results = analyse_fixed_axes(
+            positions_flat,
+            clusters,
+            &axes,
+            &params,
+            puzzle.seed,
+            options,
+        )?;
+        // Lower ranks are better puzzles.
+        let ranks: Vec<f32> = results.iter().map(|r| search_rank(&r.report)).collect();
+        let best = ranks.iter().copied().fold(f32::INFINITY, f32::min);
+        let worst = ranks.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+        let spread = worst - best;
+        let scores = ranks
+            .iter()
+            .map(|&rank| {
+                if spread > 0.0 {
+                    (worst - rank) / spread
+                } else {
+                    1.0
+                }
+            })
+            .collect();
+        Some(AxisHeatmap { cols, rows, scores })
+    }
+
+    /// Score of the cell containing map position `(u, v)`.
+    pub fn at(&self, u: f32, v: f32) -> f32 {
+        if self.cols == 0 || self.rows == 0 {
+            return 0.0;
+        }
+        let c = ((u.clamp(0.0, 1.0) * self.cols as f32) as usize).min(self.cols - 1);
+        let r = ((v.clamp(0.0, 1.0) * self.rows as f32) as usize).min(self.rows - 1);
+        self.scores[r * self.cols + c]
+    }
+}
@@ -0,0 +1,279 @@
+//! Difficulty calibration from recorded play.
+//!
+//! `EntropyReport::difficulty` is a hand-tuned blend of the entropy
+//! features.  Once players have solved (or given up on) a few levels, the
+//! outcomes say how hard those puzzles really were, and a `DifficultyModel`
+//! refit on them maps the same features to a difficulty that tracks play.
+//! Everything that shows or sorts by difficulty — the HUD label, the level
+//! list — reads the calibrated value when a model is present.
+//!
+//! # Observed difficulty
+//!
+//! A play outcome is turned into a target in [0,1] by `observed_difficulty`:
+//! solve time plus `HINT_COST_SECS` per hint, as effort `e` in units of
+//! `REFERENCE_SECS`, mapped through `e / (1 + e)`.  A hint-free solve in
+//! `REFERENCE_SECS` is 0.5; giving up is 1.
+//!
+//! # Model
+//!
+//! The model is linear in `DifficultyFeatures`, clamped to [0,1].  `fit`
+//! solves a ridge least-squares problem pulled towards the default model,
+//! which reproduces the hand-tuned difficulty exactly — so a handful of
+//! outcomes nudges the mapping and a large dataset replaces it.
+//!
+//! Fitted models round-trip through `to_json` / `from_json`; an exported
+//! model's weights can be pasted into `DifficultyModel::default` to ship a
+//! tuned default.
+
+use std::f32::consts::PI;
+
+use super::entropy::{EntropyReport, VIEWPOINTS};
+use crate::level::json::extract_num;
+
+/// Seconds of solving that count as 0.5 observed difficulty.
+pub const REFERENCE_SECS: f32 = 120.0;
+
+/// Seconds of solving one hint is worth.
+pub const HINT_COST_SECS: f32 = 30.0;
+
+/// Outcomes needed before `DifficultyModel::fit` returns a model.
+pub const MIN_OUTCOMES: usize = 8;
+
+/// Weight of the default model in the fit, in outcomes.
+const PRIOR_WEIGHT: f32 = 4.0;
+
+const FEATURES: usize = 7;
+
+/// What the model sees of a puzzle.  Every field is in [0,1] except
+/// `extra_stages`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DifficultyFeatures {
+    /// The hand-tuned `EntropyReport::difficulty`.
+    pub heuristic: f32,
+    /// Entropy over its maximum for the stage count.
+    pub entropy: f32,
+    /// `1 - peak_score`.
+    pub weak_peak: f32,
+    /// `1 - isolation / π`: how close the runner-up view is.
+    pub crowding: f32,
+    pub roll_sensitivity: f32,
+    /// Stages beyond the first.
+    pub extra_stages: f32,
+}
+
+impl DifficultyFeatures {
+    /// Features of a puzzle with `stages` stages whose combined report is
+    /// `report`.
+    pub fn from_report(report: &EntropyReport, stages: usize) -> Self {
+        let stages = stages.max(1);
+        let max_entropy = stages as f32 * (VIEWPOINTS as f32).log2();
+        Self {
+            heuristic: report.difficulty.clamp(0.0, 1.0),
+            entropy: (report.entropy_bits / max_entropy).clamp(0.0, 1.0),
+            weak_peak: (1.0 - report.peak_score).clamp(0.0, 1.0),
+            crowding: (1.0 - report.solution_isolation_rad / PI).clamp(0.0, 1.0),
+            roll_sensitivity: report.roll_sensitivity.clamp(0.0, 1.0),
+            extra_stages: (stages - 1) as f32,
+        }
+    }
+
+    /// Regressors in `DifficultyModel` weight order, bias first.
+    fn row(&self) -> [f32; FEATURES] {
+        [
+            1.0,
+            self.heuristic,
+            self.entropy,
+            self.weak_peak,
+            self.crowding,
+            self.roll_sensitivity,
+            self.extra_stages,
+        ]
+    }
+}
+
+/// Observed difficulty of one play, in [0,1] — see the module docs.
+pub fn observed_difficulty(time_secs: f32, hints: u32, gave_up: bool) -> f32 {
+    if gave_up {
+        return 1.0;
+    }
+    let effort = (time_secs.max(0.0) + hints as f32 * HINT_COST_SECS) / REFERENCE_SECS;
+    effort / (1.0 + effort)
+}
+
+/// Linear map from `DifficultyFeatures` to difficulty.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct DifficultyModel {
+    pub bias: f32,
+    pub heuristic: f32,
+    pub entropy: f32,
+    pub weak_peak: f32,
+    pub crowding: f32,
+    pub roll_sensitivity: f32,
+    pub extra_stages: f32,
+    /// Outcomes the model was fitted on (0 = the default).
+    pub samples: u32,
+}
+
+impl Default for DifficultyModel {
+    /// The hand-tuned difficulty, unchanged.
+    fn default() -> Self {
+        Self {
+            bias: 0.0,
+            heuristic: 1.0,
+            entropy: 0.0,
+            weak_peak: 0.0,
+            crowding: 0.0,
+            roll_sensitivity: 0.0,
+            extra_stages: 0.0,
+            samples: 0,
+        }
+    }
+}
+
+impl DifficultyModel {
+    /// Calibrated difficulty in [0,1].
+    pub fn difficulty(&self, features: &DifficultyFeatures) -> f32 {
+        let w = self.weights();
+        let x = features.row();
+        (0..FEATURES)
+            .map(|i| w[i] * x[i])
+            .sum::<f32>()
+            .clamp(0.0, 1.0)
+    }
+
+    /// Refit on `(features, observed difficulty)` pairs.  `None` with fewer
+    /// than `MIN_OUTCOMES` samples.
+    pub fn fit(samples: &[(DifficultyFeatures, f32)]) -> Option<Self> {
+        if samples.len() < MIN_OUTCOMES {
+            return None;
+        }
+        // Normal equations of Σ(y - w·x)² + λ|w - w₀|², accumulated in f64.
+        let prior = Self::default().weights();
+        let mut a = [[0.0f64; FEATURES]; FEATURES];
+        let mut b = [0.0f64; FEATURES];
+        for (features, y) in samples {
+            let x = features.row();
+            for i in 0..FEATURES {
+                b[i] += x[i] as f64 * *y as f64;
+                for j in 0..FEATURES {
+                    a[i][j] += x[i] as f64 * x[j] as f64;
+                }
+            }
+        }
+        for i in 0..FEATURES {
+            a[i][i] += PRIOR_WEIGHT as f64;
+            b[i] += PRIOR_WEIGHT as f64 * prior[i] as f64;
+        }
+        let w = solve(a, b)?;
+        Some(Self {
+            bias: w[0] as f32,
+            heuristic: w[1] as f32,
+            entropy: w[2] as f32,
+            weak_peak: w[3] as f32,
+            crowding: w[4] as f32,
+            roll_sensitivity: w[5] as f32,
+            extra_stages: w[6] as f32,
+            samples: samples.len() as u32,
+        })
+    }
+
+    /// Root-mean-square error of the model on `samples`.
+    pub fn rms_error(&self, samples: &[(DifficultyFeatures, f32)]) -> f32 {
+        if samples.is_empty() {
+            return 0.0;
+        }
+        let sum: f32 = samples
+            .iter()
+            .map(|(f, y)| (self.difficulty(f) - y).powi(2))
+            .sum();
+        (sum / samples.len() as f32).sqrt()
+    }
+
+    fn weights(&self) -> [f32; FEATURES] {
+        [
+            self.bias,
+            self.heuristic,
+            self.entropy,
+            self.weak_peak,
+            self.crowding,
+            self.roll_sensitivity,
+            self.extra_stages,
+        ]
+    }
+
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\n  \"bias\": {:.6},\n  \"heuristic\": {:.6},\n  \"entropy\": {:.6},\n  \
+             \"weak_peak\": {:.6},\n  \"crowding\": {:.6},\n  \"roll_sensitivity\": {:.6},\n  \
+             \"extra_stages\": {:.6},\n  \"samples\": {}\n}}\n",
+            self.bias,
+            self.heuristic,
+            self.entropy,
+            self.weak_peak,
+            self.crowding,
+            self.roll_sensitivity,
+            self.extra_stages,
+            self.samples,
+        )
+    }
+
+    /// Parse a model produced by `to_json`.  Missing weights keep their
+    /// default; a file without any weight is rejected.
+    pub fn from_json(s: &str) -> Option<Self> {
+        let d = Self::default();
+        let keys = [
+            "bias",
+            "heuristic",
+            "entropy",
+            "weak_peak",
+            "crowding",
+            "roll_sensitivity",
+            "extra_stages",
+        ];
+        let parsed: Vec<Option<f32>> = keys.iter().map(|k| extract_num(s, k)).collect();
+        if parsed.iter().all(Option::is_none) {
+            return None;
+        }
+        let w: Vec<f32> = parsed
+            .iter()
+            .zip(d.weights())
+            .map(|(p, default)| p.unwrap_or(default))
+            .collect();
+        Some(Self {
+            bias: w[0],
+            heuristic: w[1],
+            entropy: w[2],
+            weak_peak: w[3],
+            crowding: w[4],
+            roll_sensitivity: w[5],
+            extra_stages: w[6],
+            samples: extract_num(s, "samples").unwrap_or(0.0) as u32,
+        })
+    }
+}
+
+/// Gaussian elimination with partial pivoting.  `None` if `a` is singular.
+fn solve(mut a: [[f64; FEATURES]; FEATURES], mut b: [f64; FEATURES]) -> Option<[f64; FEATURES]> {
+    for col in 0..FEATURES {
+        let pivot = (col..FEATURES).max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))?;
+        if a[pivot][col].abs() < 1e-12 {
+            return None;
+        }
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        for row in col + 1..FEATURES {
+            let f = a[row][col] / a[col][col];
+            let pivot_row = a[col];
+            for (x, p) in a[row][col..].iter_mut().zip(&pivot_row[col..]) {
+                *x -= f * p;
+            }
+            b[row] -= f * b[col];
+        }
+    }
+    let mut x = [0.0f64; FEATURES];
+    for row in (0..FEATURES).rev() {
+        let tail: f64 = (row + 1..FEATURES).map(|k| a[row][k] * x[k]).sum();
+        x[row] = (b[row] - tail) / a[row][row];
+    }
+    Some(x)
+}
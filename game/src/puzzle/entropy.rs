@@ -27,20 +27,71 @@
 //!
 //! `best_scramble_axis` samples N candidate axes, scores each one by running a
 //! candidate scramble, then returns the axis whose entropy is lowest (most
-//! distinctive puzzle).
-
-use cgmath::{InnerSpace, Vector3};
+//! distinctive puzzle).  Candidate `i` draws its axis and its simulated
+//! displacements from `seed.stream(Axis).derive(i)`, so the search result is a
+//! pure function of the positions, displacement range and `PuzzleSeed`.
+//!
+//! ## Multi-stage puzzles
+//!
+//! `best_stage_axes_from_offsets` runs the same search once per triangle
+//! group, using only that group's triangles, and keeps each stage's axis at
+//! least `MIN_STAGE_SEPARATION_RAD` away from the earlier ones (otherwise
+//! finding one view would solve the next for free).  The per-stage reports
+//! are folded into one by `combine_reports`: the player has to find every
+//! view, so the entropies add and the difficulties compound.
+//!
+//! ## Scramble strategies
+//!
+//! The simulation has to move the same pieces the real scramble will.  When
+//! triangles are clustered (`ScrambleStrategy::PerCluster` / `PerMesh`) every
+//! triangle of a cluster gets one shared amount, so a model with few clusters
+//! has few independent depths and scores as far less distinctive than the
+//! same model scrambled per triangle.
+//!
+//! ## Worker pool
+//!
+//! Candidates are scored on a pool of worker threads (see `search`) and
+//! reduced in candidate order, so the result never depends on the thread
+//! count.  `best_stage_axes_with` takes `SearchOptions` whose handle can
+//! cancel the search between candidates and reports its progress.
+//!
+//! ## Analysis backends
+//!
+//! `AnalysisBackend::Silhouette` swaps the offset variance for rendered
+//! views (see `silhouette`): the model is rasterised solved and scrambled
+//! from every viewpoint and scored by silhouette IoU times edge coherence.
+//! It is far slower, but measures whether the solved view is actually
+//! recognisable and whether a wrong view happens to look whole.  The
+//! backend is chosen per call; both produce the same `EntropyReport`.
+//!
+//! ## Rotation scrambles
+//!
+//! Turning fragments about the view axis leaves their depth along it alone,
+//! so the viewpoint entropy is unchanged.  What it adds is a second thing
+//! to find: `roll_sensitivity` scores how badly a wrong camera roll
+//! scatters the fragments, relative to the depth scramble, and raises the
+//! difficulty accordingly.
+
+use cgmath::{InnerSpace, Matrix4, Vector3};
+use std::collections::HashMap;
 use std::f32::consts::PI;
+use std::ops::Range;
 
 use formosaic_engine::architecture::models::model::Model;
 use formosaic_engine::architecture::models::simple_model::SimpleModel;
 
+use super::clusters::cluster_count;
 use super::puzzle_params::PuzzleParams;
+use super::scramble_math::compute_roll_offsets;
+use super::scrambler::ScrambleTarget;
+use super::search::SearchOptions;
+use super::seed::{PuzzleSeed, SeedStream};
+use super::silhouette::SolvedViews;
 
 // ─── Public types ────────────────────────────────────────────────────────────
 
 /// Quality descriptor returned by `analyse_axis`.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub struct EntropyReport {
     /// Shannon entropy (bits) of the viewpoint-score distribution.
     /// Lower = more distinctive / easier to identify the solution.
@@ -53,11 +104,16 @@ pub struct EntropyReport {
     /// Large = the solution is well isolated → better puzzle.
     pub solution_isolation_rad: f32,
 
+    /// How much a wrong camera roll spoils the solved view, in [0,1):
+    /// 0 = roll does not matter (no rotation scramble).
+    pub roll_sensitivity: f32,
+
     /// Estimated puzzle difficulty in [0,1]: 0 = trivial, 1 = very hard.
     pub difficulty: f32,
 }
 
 /// Result of finding the best axis via mutual-information search.
+#[derive(Debug, Clone, Copy)]
 pub struct AxisSearchResult {
     /// The chosen scramble axis.
     pub axis: Vector3<f32>,
@@ -65,6 +121,51 @@ pub struct AxisSearchResult {
     pub report: EntropyReport,
 }
 
+/// Result of a multi-stage search: one axis per triangle group.
+#[derive(Debug, Clone)]
+pub struct StagedSearchResult {
+    pub stages: Vec<AxisSearchResult>,
+    /// The puzzle as a whole — see `combine_reports`.
+    pub combined: EntropyReport,
+}
+
+impl StagedSearchResult {
+    pub fn axes(&self) -> Vec<Vector3<f32>> {
+        self.stages.iter().map(|s| s.axis).collect()
+    }
+
+    pub fn stage_reports(&self) -> Vec<EntropyReport> {
+        self.stages.iter().map(|s| s.report).collect()
+    }
+}
+
+/// How viewpoints are scored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum AnalysisBackend {
+    /// Variance of the projected scramble offsets — cheap.
+    #[default]
+    Heuristic,
+    /// CPU-rasterised silhouette IoU × edge coherence.
+    Silhouette,
+}
+
+/// Number of viewpoints every report is computed over.
+pub const VIEWPOINTS: usize = 64;
+
+/// Minimum angle between the axes of two stages of the same puzzle.
+pub const MIN_STAGE_SEPARATION_RAD: f32 = 45.0 * PI / 180.0;
+
+/// Highest elevation of a solution axis above the horizon.
+pub const MAX_AXIS_ELEVATION_RAD: f32 = 55.0 * PI / 180.0;
+
+/// Highest axis elevation on an upright level.  The upright check measures
+/// roll against the model's up axis, which is undefined looking straight
+/// down it, so these levels keep their solutions closer to the horizon.
+pub const UPRIGHT_AXIS_ELEVATION_RAD: f32 = 30.0 * PI / 180.0;
+
+/// Share of the remaining difficulty a fully roll-sensitive puzzle adds.
+const ROLL_DIFFICULTY_WEIGHT: f32 = 0.3;
+
 // ─── Public API ──────────────────────────────────────────────────────────────
 
 /// Analyse how good `axis` is as a Formosaic puzzle solution axis for `model`.
@@ -72,7 +173,7 @@ pub struct AxisSearchResult {
 /// `offsets_flat` is the flat list of per-vertex displacement offsets
 /// as stored per-vertex in the game's scramble offsets (3 floats per vertex, triangle order).
 pub fn analyse_axis(scramble_offsets_flat: &[f32], axis: Vector3<f32>) -> EntropyReport {
-    let directions = fibonacci_sphere(64);
+    let directions = fibonacci_sphere(VIEWPOINTS);
     let scores: Vec<f32> = directions
         .iter()
         .map(|&d| alignment_score(scramble_offsets_flat, d))
@@ -81,6 +182,21 @@ pub fn analyse_axis(scramble_offsets_flat: &[f32], axis: Vector3<f32>) -> Entrop
     entropy_report(&directions, &scores, axis)
 }
 
+/// `analyse_axis` with a choice of backend.  `positions_flat` are the rest
+/// positions the offsets apply to; the heuristic ignores them.
+pub fn analyse_axis_with(
+    backend: AnalysisBackend,
+    positions_flat: &[f32],
+    scramble_offsets_flat: &[f32],
+    axis: Vector3<f32>,
+) -> EntropyReport {
+    let margin = scramble_offsets_flat
+        .chunks_exact(3)
+        .map(|o| Vector3::new(o[0], o[1], o[2]).magnitude())
+        .fold(0.0, f32::max);
+    Scorer::new(backend, positions_flat, margin).report(scramble_offsets_flat, axis)
+}
+
 /// Search for the scramble axis that produces the most informative puzzle.
 ///
 /// Samples `candidates` random axes, evaluates them via a lightweight score,
@@ -90,11 +206,17 @@ pub fn best_scramble_axis(
     candidates: usize,
     target_world_radius: f32,
     fov_radians: f32,
+    seed: PuzzleSeed,
 ) -> AxisSearchResult {
     let params = PuzzleParams::from_model(model, target_world_radius, fov_radians);
     let positions = model_positions_flat(model);
-    let result =
-        best_scramble_axis_from_offsets(&positions, params.min_disp, params.max_disp, candidates);
+    let result = best_scramble_axis_from_offsets(
+        &positions,
+        params.min_disp,
+        params.max_disp,
+        candidates,
+        seed,
+    );
     log::info!(
         "[Entropy] best axis entropy={:.3} bits  isolation={:.2}°  difficulty={:.2}",
         result.report.entropy_bits,
@@ -116,49 +238,362 @@ pub fn best_scramble_axis_from_offsets(
     min_disp: f32,
     max_disp: f32,
     candidates: usize,
+    seed: PuzzleSeed,
 ) -> AxisSearchResult {
-    use rand::Rng;
-    let mut rng = rand::rng();
+    let clusters: Vec<usize> = (0..positions_flat.len() / 9).collect();
+    let options = SearchOptions::default();
+    let search = CandidateSearch {
+        scorer: Scorer::Heuristic,
+        candidates,
+        max_elevation: MAX_AXIS_ELEVATION_RAD,
+        options: &options,
+    };
+    let result = search_axis(
+        positions_flat,
+        &clusters,
+        min_disp..max_disp,
+        seed,
+        &[],
+        &search,
+    )
+    .expect("a search nobody else holds cannot be cancelled");
+    log::info!(
+        "[Entropy] best axis entropy={:.3} bits  isolation={:.2}°  difficulty={:.2}",
+        result.report.entropy_bits,
+        result.report.solution_isolation_rad.to_degrees(),
+        result.report.difficulty,
+    );
+    result
+}
+
+/// Multi-stage search over `positions_flat`, the meshes' triangle soups
+/// concatenated (indexed meshes go through `scramble_math::unweld` first).
+/// `groups[t]` is the stage of flat triangle `t` (the per-mesh output of
+/// `assign_stage_groups`, concatenated in mesh order) and `clusters[t]` its
+/// cluster (see `clusters::flat_clusters`).
+/// Stage `k` is searched over its own triangles with `seed.stage(k)`, so a
+/// one-stage per-triangle search is identical to
+/// `best_scramble_axis_from_offsets`.
+pub fn best_stage_axes_from_offsets(
+    positions_flat: &[f32],
+    groups: &[usize],
+    clusters: &[usize],
+    params: &PuzzleParams,
+    candidates: usize,
+    seed: PuzzleSeed,
+) -> StagedSearchResult {
+    best_stage_axes_with(
+        positions_flat,
+        groups,
+        clusters,
+        params,
+        candidates,
+        seed,
+        &SearchOptions::default(),
+    )
+    .expect("a search nobody else holds cannot be cancelled")
+}
+
+/// `best_stage_axes_from_offsets` run as `options` say: scored with its
+/// backend, on its worker pool, reporting to and cancellable through its
+/// handle.  The silhouette backend renders each stage's solved views once
+/// and reuses them for every candidate.  `None` if cancelled.
+pub fn best_stage_axes_with(
+    positions_flat: &[f32],
+    groups: &[usize],
+    clusters: &[usize],
+    params: &PuzzleParams,
+    candidates: usize,
+    seed: PuzzleSeed,
+    options: &SearchOptions,
+) -> Option<StagedSearchResult> {
+    let stage_count = params.stage_count.max(1);
+    options.handle.add_work(stage_count * candidates);
+    let mut stages: Vec<AxisSearchResult> = Vec::with_capacity(stage_count);
+    for k in 0..stage_count {
+        let (group_positions, group_clusters): (Vec<f32>, Vec<usize>) = if stage_count == 1 {
+            (positions_flat.to_vec(), clusters.to_vec())
+        } else {
+            // Renumber the stage's clusters densely in order of appearance.
+            let mut pos = Vec::new();
+            let mut ids = Vec::new();
+            let mut dense: HashMap<usize, usize> = HashMap::new();
+            for ((tri, &g), &c) in positions_flat.chunks_exact(9).zip(groups).zip(clusters) {
+                if g == k {
+                    pos.extend_from_slice(tri);
+                    let next = dense.len();
+                    ids.push(*dense.entry(c).or_insert(next));
+                }
+            }
+            (pos, ids)
+        };
+        let avoid: Vec<Vector3<f32>> = stages.iter().map(|s| s.axis).collect();
+        let search = CandidateSearch {
+            scorer: Scorer::new(options.backend, &group_positions, params.max_disp),
+            candidates,
+            max_elevation: if params.upright {
+                UPRIGHT_AXIS_ELEVATION_RAD
+            } else {
+                MAX_AXIS_ELEVATION_RAD
+            },
+            options,
+        };
+        let mut result = search_axis(
+            &group_positions,
+            &group_clusters,
+            params.min_disp..params.max_disp,
+            seed.stage(k),
+            &avoid,
+            &search,
+        )?;
+        if params.max_roll > 0.0 {
+            let sensitivity = roll_sensitivity(
+                &group_positions,
+                &group_clusters,
+                result.axis,
+                params,
+                seed.stage(k),
+            );
+            result.report = with_roll_term(result.report, sensitivity);
+        }
+        log::info!(
+            "[Entropy] stage {}/{} axis entropy={:.3} bits  isolation={:.2}°  difficulty={:.2}",
+            k + 1,
+            stage_count,
+            result.report.entropy_bits,
+            result.report.solution_isolation_rad.to_degrees(),
+            result.report.difficulty,
+        );
+        stages.push(result);
+    }
+    let combined = combine_reports(&stages.iter().map(|s| s.report).collect::<Vec<_>>());
+    Some(StagedSearchResult { stages, combined })
+}
+
+/// Analyse a chosen axis the way the search analyses its candidates: the
+/// scramble is simulated with `params`' displacement range and the stage-0
+/// stream of `seed`, and the roll term is added for a rotation scramble.
+/// Used for hand-authored puzzles, which skip the search.
+pub fn analyse_fixed_axis(
+    positions_flat: &[f32],
+    clusters: &[usize],
+    axis: Vector3<f32>,
+    params: &PuzzleParams,
+    seed: PuzzleSeed,
+    backend: AnalysisBackend,
+) -> AxisSearchResult {
+    let options = SearchOptions {
+        backend,
+        ..SearchOptions::serial()
+    };
+    analyse_fixed_axes(positions_flat, clusters, &[axis], params, seed, &options)
+        .and_then(|results| results.into_iter().next())
+        .expect("a serial search is never cancelled")
+}
+
+/// `analyse_fixed_axis` for every axis in `axes`, on the worker pool.  All
+/// axes share the same displacement draws, so the results differ only by
+/// direction.  `None` if the search was cancelled.
+pub fn analyse_fixed_axes(
+    positions_flat: &[f32],
+    clusters: &[usize],
+    axes: &[Vector3<f32>],
+    params: &PuzzleParams,
+    seed: PuzzleSeed,
+    options: &SearchOptions,
+) -> Option<Vec<AxisSearchResult>> {
+    let scorer = Scorer::new(options.backend, positions_flat, params.max_disp);
+    let stage_seed = seed.stage(0);
+    options.handle.add_work(axes.len());
+    options.map(axes.len(), |i| {
+        let axis = axes[i].normalize();
+        let offsets = simulate_scramble_offsets(
+            positions_flat,
+            clusters,
+            axis,
+            params.min_disp..params.max_disp,
+            stage_seed.stream(SeedStream::Displacement),
+        );
+        let mut report = scorer.report(&offsets, axis);
+        if params.max_roll > 0.0 {
+            let sensitivity = roll_sensitivity(positions_flat, clusters, axis, params, stage_seed);
+            report = with_roll_term(report, sensitivity);
+        }
+        AxisSearchResult { axis, report }
+    })
+}
+
+/// What the axis search minimises: low entropy, and a solution far from
+/// any other good-looking view.
+pub fn search_rank(report: &EntropyReport) -> f32 {
+    report.entropy_bits - report.solution_isolation_rad * 2.0
+}
+
+/// Fold per-stage reports into one for the whole puzzle.
+///
+/// Every stage has to be found, so entropies add, the weakest peak, the
+/// least isolated solution and the most roll-sensitive stage dominate, and
+/// difficulties compound as `1 - Π(1 - dₖ)` — two medium stages make a hard
+/// puzzle.
+pub fn combine_reports(stages: &[EntropyReport]) -> EntropyReport {
+    match stages {
+        [] => EntropyReport {
+            entropy_bits: 0.0,
+            peak_score: 0.0,
+            solution_isolation_rad: 0.0,
+            roll_sensitivity: 0.0,
+            difficulty: 0.5,
+        },
+        [only] => *only,
+        _ => EntropyReport {
+            entropy_bits: stages.iter().map(|r| r.entropy_bits).sum(),
+            peak_score: stages
+                .iter()
+                .map(|r| r.peak_score)
+                .fold(f32::INFINITY, f32::min),
+            solution_isolation_rad: stages
+                .iter()
+                .map(|r| r.solution_isolation_rad)
+                .fold(f32::INFINITY, f32::min),
+            roll_sensitivity: stages
+                .iter()
+                .map(|r| r.roll_sensitivity)
+                .fold(0.0, f32::max),
+            difficulty: 1.0
+                - stages
+                    .iter()
+                    .map(|r| 1.0 - r.difficulty.clamp(0.0, 1.0))
+                    .product::<f32>(),
+        },
+    }
+}
+
+/// One stage's candidate search: how candidates are scored and where
+/// they run.
+struct CandidateSearch<'a> {
+    scorer: Scorer<'a>,
+    candidates: usize,
+    /// Highest candidate axis elevation above the horizon.
+    max_elevation: f32,
+    options: &'a SearchOptions,
+}
+
+/// Candidate search shared by the single- and multi-stage entry points.
+/// Candidates within `MIN_STAGE_SEPARATION_RAD` of an `avoid` axis are
+/// redrawn from the same candidate RNG, so results stay reproducible, and
+/// dropped if every redraw is too close; should all of them be dropped,
+/// `farthest_axis` is scored instead.  Candidates are scored on the worker
+/// pool and reduced in candidate order; `None` if the search was
+/// cancelled.
+fn search_axis(
+    positions_flat: &[f32],
+    clusters: &[usize],
+    disp: Range<f32>,
+    seed: PuzzleSeed,
+    avoid: &[Vector3<f32>],
+    search: &CandidateSearch,
+) -> Option<AxisSearchResult> {
+    let axis_seed = seed.stream(SeedStream::Axis);
+    let score = |axis: Vector3<f32>, candidate_seed: PuzzleSeed| {
+        let offsets = simulate_scramble_offsets(
+            positions_flat,
+            clusters,
+            axis,
+            disp.clone(),
+            candidate_seed.stream(SeedStream::Displacement),
+        );
+        (axis, search.scorer.report(&offsets, axis))
+    };
+    let reports = search.options.map(search.candidates, |i| {
+        let candidate_seed = axis_seed.derive(i as u64);
+        let axis = candidate_axis(candidate_seed, search.max_elevation, avoid)?;
+        Some(score(axis, candidate_seed))
+    })?;
+    let mut reports: Vec<_> = reports.into_iter().flatten().collect();
+    if reports.is_empty() {
+        let axis = farthest_axis(search.max_elevation, avoid);
+        reports.push(score(axis, axis_seed.derive(search.candidates as u64)));
+    }
 
     let mut best_axis = Vector3::new(0.0, 1.0, 0.0);
     let mut best_report = EntropyReport {
         entropy_bits: f32::INFINITY,
         peak_score: 0.0,
         solution_isolation_rad: 0.0,
+        roll_sensitivity: 0.0,
         difficulty: 1.0,
     };
+    for (axis, report) in reports {
+        if search_rank(&report) < search_rank(&best_report) {
+            best_axis = axis;
+            best_report = report;
+        }
+    }
 
-    const MAX_ELEV: f32 = 55.0 * PI / 180.0;
+    Some(AxisSearchResult {
+        axis: best_axis,
+        report: best_report,
+    })
+}
 
-    for _ in 0..candidates {
-        let theta: f32 = rng.random_range(0.0..2.0 * PI);
-        let phi: f32 = rng.random_range(-MAX_ELEV..MAX_ELEV);
-        let axis =
-            Vector3::new(phi.cos() * theta.cos(), phi.sin(), phi.cos() * theta.sin()).normalize();
+/// Draw one candidate axis, at most `max_elev` from the horizon and clear
+/// of `avoid`.  `None` if no draw cleared it.
+fn candidate_axis(
+    candidate_seed: PuzzleSeed,
+    max_elev: f32,
+    avoid: &[Vector3<f32>],
+) -> Option<Vector3<f32>> {
+    use rand::Rng;
 
-        let offsets = simulate_scramble_offsets(positions_flat, axis, min_disp, max_disp);
-        let report = analyse_axis(&offsets, axis);
+    const MAX_REDRAWS: usize = 16;
 
-        let score = report.entropy_bits - report.solution_isolation_rad * 2.0;
-        let best_score = best_report.entropy_bits - best_report.solution_isolation_rad * 2.0;
+    let mut rng = candidate_seed.rng();
+    for _ in 0..MAX_REDRAWS {
+        let theta: f32 = rng.random_range(0.0..2.0 * PI);
+        let phi: f32 = rng.random_range(-max_elev..max_elev);
+        let axis = axis_at(theta, phi);
+        if is_clear_of(axis, avoid) {
+            return Some(axis);
+        }
+    }
+    None
+}
 
-        if score < best_score {
-            best_axis = axis;
-            best_report = report;
+/// The axis at most `max_elev` from the horizon that is farthest from its
+/// nearest `avoid` axis, from a fixed grid.  Two earlier stages always
+/// leave room on the horizon for an axis clear of both.
+fn farthest_axis(max_elev: f32, avoid: &[Vector3<f32>]) -> Vector3<f32> {
+    const AZIMUTHS: usize = 36;
+    const ELEVATIONS: usize = 7;
+
+    let mut best = (f32::NEG_INFINITY, Vector3::unit_x());
+    for i in 0..AZIMUTHS {
+        let theta = PI * i as f32 / AZIMUTHS as f32;
+        for j in 0..ELEVATIONS {
+            let phi = max_elev * (2.0 * j as f32 / (ELEVATIONS - 1) as f32 - 1.0);
+            let axis = axis_at(theta, phi);
+            let clearance = avoid
+                .iter()
+                .map(|a| 1.0 - axis.dot(*a).abs())
+                .fold(f32::INFINITY, f32::min);
+            if clearance > best.0 {
+                best = (clearance, axis);
+            }
         }
     }
+    best.1
+}
 
-    log::info!(
-        "[Entropy] best axis entropy={:.3} bits  isolation={:.2}°  difficulty={:.2}",
-        best_report.entropy_bits,
-        best_report.solution_isolation_rad.to_degrees(),
-        best_report.difficulty,
-    );
+/// Unit axis at azimuth `theta` and elevation `phi` above the horizon.
+fn axis_at(theta: f32, phi: f32) -> Vector3<f32> {
+    Vector3::new(phi.cos() * theta.cos(), phi.sin(), phi.cos() * theta.sin()).normalize()
+}
 
-    AxisSearchResult {
-        axis: best_axis,
-        report: best_report,
-    }
+/// At least `MIN_STAGE_SEPARATION_RAD` from every `avoid` axis.
+fn is_clear_of(axis: Vector3<f32>, avoid: &[Vector3<f32>]) -> bool {
+    avoid
+        .iter()
+        .all(|a| axis.dot(*a).abs() < MIN_STAGE_SEPARATION_RAD.cos())
 }
 
 /// Difficulty label for UI display.
@@ -173,9 +608,49 @@ pub fn difficulty_label(difficulty: f32) -> &'static str {
 
 // ─── Internal helpers ─────────────────────────────────────────────────────────
 
+/// A backend ready to score candidate offsets over the standard viewpoints.
+enum Scorer<'a> {
+    Heuristic,
+    Silhouette(SolvedViews<'a>),
+}
+
+impl<'a> Scorer<'a> {
+    /// `margin` is the largest displacement the scored offsets will use.
+    fn new(backend: AnalysisBackend, positions_flat: &'a [f32], margin: f32) -> Self {
+        match backend {
+            AnalysisBackend::Heuristic => Scorer::Heuristic,
+            AnalysisBackend::Silhouette => Scorer::Silhouette(SolvedViews::new(
+                positions_flat,
+                &fibonacci_sphere(VIEWPOINTS),
+                margin,
+            )),
+        }
+    }
+
+    fn report(&self, offsets_flat: &[f32], axis: Vector3<f32>) -> EntropyReport {
+        match self {
+            Scorer::Heuristic => analyse_axis(offsets_flat, axis),
+            Scorer::Silhouette(views) => {
+                // The rendered score peaks sharply, so the candidate's own
+                // axis is scored too — the sphere alone would miss the peak.
+                let mut directions = fibonacci_sphere(VIEWPOINTS);
+                directions.push(axis);
+                let mut scores: Vec<f32> = views
+                    .view_scores(offsets_flat)
+                    .iter()
+                    .map(|v| v.score())
+                    .collect();
+                scores.push(views.view_score(offsets_flat, axis).score());
+                entropy_report(&directions, &scores, axis)
+            }
+        }
+    }
+}
+
 /// Alignment score for camera direction `d`.
 ///
-/// Projects every triangle's scramble offset onto `d`.
+/// Projects every triangle's scramble offset onto `d`; `offsets_flat` is
+/// per-vertex, over a triangle soup.
 /// Returns 1/(1 + variance) — high score = looks aligned from this direction.
 fn alignment_score(offsets_flat: &[f32], d: Vector3<f32>) -> f32 {
     if offsets_flat.is_empty() {
@@ -221,6 +696,7 @@ fn entropy_report(
             entropy_bits: 0.0,
             peak_score: 0.0,
             solution_isolation_rad: 0.0,
+            roll_sensitivity: 0.0,
             difficulty: 0.5,
         };
     }
@@ -265,10 +741,49 @@ fn entropy_report(
         entropy_bits,
         peak_score,
         solution_isolation_rad,
+        roll_sensitivity: 0.0,
         difficulty,
     }
 }
 
+/// Roll sensitivity of a rotation scramble about `axis`: simulates the
+/// fragment rotations and compares their RMS in-plane displacement with
+/// `max_disp`, the scale of the depth scramble, as `1 - 1/(1 + s²)`.
+fn roll_sensitivity(
+    positions_flat: &[f32],
+    clusters: &[usize],
+    axis: Vector3<f32>,
+    params: &PuzzleParams,
+    seed: PuzzleSeed,
+) -> f32 {
+    let offsets = compute_roll_offsets(
+        positions_flat,
+        clusters,
+        ScrambleTarget::Axis(axis),
+        params.max_roll,
+        Matrix4::from_scale(1.0),
+        seed.stream(SeedStream::Roll),
+    );
+    let vertex_count = offsets.len() / 3;
+    if vertex_count == 0 || params.max_disp <= 0.0 {
+        return 0.0;
+    }
+    let mean_sq = offsets.iter().map(|o| o * o).sum::<f32>() / vertex_count as f32;
+    let s2 = mean_sq / (params.max_disp * params.max_disp);
+    1.0 - 1.0 / (1.0 + s2)
+}
+
+/// Fold a roll-sensitivity term into a direction-only report.
+fn with_roll_term(report: EntropyReport, roll_sensitivity: f32) -> EntropyReport {
+    let keep = (1.0 - report.difficulty.clamp(0.0, 1.0))
+        * (1.0 - roll_sensitivity * ROLL_DIFFICULTY_WEIGHT);
+    EntropyReport {
+        roll_sensitivity,
+        difficulty: 1.0 - keep,
+        ..report
+    }
+}
+
 /// Generate N points roughly evenly distributed on a unit sphere
 /// using the Fibonacci / golden-angle spiral method.
 fn fibonacci_sphere(n: usize) -> Vec<Vector3<f32>> {
@@ -292,24 +807,30 @@ fn model_positions_flat(model: &SimpleModel) -> Vec<f32> {
 }
 
 /// Simulate what the scramble offsets would look like for a given axis,
-/// without touching any GPU state.  Uses the same distribution as `compute_scramble_offsets`.
+/// without touching any GPU state.  Uses the same distribution as
+/// `compute_cluster_offsets`: one amount per cluster, shared by its triangles.
+/// `positions_flat` is a triangle soup, so a vertex is never shared between
+/// two triangles that move apart.
 fn simulate_scramble_offsets(
     positions_flat: &[f32],
+    clusters: &[usize],
     axis: Vector3<f32>,
-    min_disp: f32,
-    max_disp: f32,
+    disp: Range<f32>,
+    seed: PuzzleSeed,
 ) -> Vec<f32> {
     use rand::Rng;
-    let mut rng = rand::rng();
+    let mut rng = seed.rng();
 
     let n = positions_flat.len();
-    let tri_count = n / 9;
+    let tri_count = (n / 9).min(clusters.len());
     let mut offsets = vec![0.0f32; n];
+    let amounts: Vec<f32> = (0..cluster_count(clusters))
+        .map(|_| rng.random_range(disp.clone()))
+        .collect();
 
     for tri in 0..tri_count {
-        let amount: f32 = rng.random_range(min_disp..max_disp);
         // Use positive-only displacement — must match `scramble_along_axis` exactly.
-        let disp = axis * amount;
+        let disp = axis * amounts[clusters[tri]];
 
         let base = tri * 9;
         for corner in 0..3 {
@@ -1,4 +1,4 @@
-//! Progressive 3-tier hint system for Formosaic.
+//! Progressive 4-tier hint system for Formosaic.
 //!
 //! # Hint tiers
 //!
@@ -7,13 +7,16 @@
 //! | 0    | Nothing (default)    | –                  |
 //! | 1    | Warm/cold compass    | H pressed once     |
 //! | 2    | Axis-plane indicator | H pressed twice    |
-//! | 3    | Ghost model overlay  | H pressed 3 times  |
+//! | 3    | Silhouette outline   | H pressed 3 times  |
+//! | 4    | Ghost model overlay  | H pressed 4 times  |
 //!
 //! ## Tier 1 – Warm/Cold
 //!
 //! The HUD shows a directional arrow that gets "warmer" (red → orange → yellow)
 //! as the camera approaches the solution hemisphere, and "cooler" (blue → teal)
 //! as it moves away.  No axis is revealed — just a colour temperature.
+//! The colours come from the player's `WarmthPalette` (`set_palette`), which
+//! offers colour-blind-safe and monochrome alternatives to red/blue.
 //!
 //! ## Tier 2 – Axis-Plane Indicator
 //!
@@ -22,14 +25,57 @@
 //! is always visible but gives away neither which of the two valid directions
 //! (±axis) to look from.
 //!
-//! ## Tier 3 – Ghost Snap
+//! ## Tier 3 – Silhouette Outline
+//!
+//! The solved model's outline as seen from the solution (see `outline`) is
+//! drawn over the scene, projected into the current view.  From the
+//! solution it traces the model's silhouette; anywhere else it shows how
+//! far the shape still has to turn, without moving any fragment.
+//!
+//! ## Tier 4 – Ghost Snap
 //!
 //! The scramble lerp is driven toward `t = 0` by a fraction each frame, so the
 //! model slowly "un-scrambles" over ~5 s.  This gives away the solution
 //! immediately — it is a last resort.
+//!
+//! ## Anamorphic puzzles
+//!
+//! A point-based (anamorphic) stage has one solution, the eye point, so
+//! `update_point` warms only towards it instead of towards either pole of
+//! an axis.  The disc is drawn perpendicular to the eye's direction.
+//!
+//! ## Screen-space warmth
+//!
+//! In play the warmth comes from `update_error`, fed with the stage's
+//! on-screen misalignment (see `reprojection`): the same pixel measure
+//! that decides the solve, so "hot" always means "about to snap".
+//! `update` and `update_point` remain for callers without a camera.
+//!
+//! ## Upright levels
+//!
+//! On a roll-locked level the camera also has to be upright.  The game
+//! feeds the camera's roll in through `set_upright_roll` every frame, and
+//! from tier 1 on the output carries a `roll_cue` whenever that roll is
+//! outside `UPRIGHT_TOLERANCE_RAD`, so the HUD can tell the player which
+//! way to twist.
+//!
+//! ## Multi-stage puzzles
+//!
+//! Hints always point at the stage being solved.  `next_stage` drops the
+//! tier back to None when a stage snaps into place, so a ghost snap bought
+//! for one stage does not un-scramble the next; hints already used still
+//! count towards the total.
 
 use cgmath::{InnerSpace, Vector3};
 
+use super::palette::WarmthPalette;
+use super::reprojection::SNAP_TOLERANCE_PX;
+use super::scrambler::UPRIGHT_TOLERANCE_RAD;
+
+/// Misalignment, in pixels above the snap tolerance, at which the
+/// warm/cold indicator reads half warm.
+pub const WARMTH_HALF_PX: f32 = 16.0;
+
 // ─── Types ────────────────────────────────────────────────────────────────────
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -38,15 +84,20 @@ pub enum HintTier {
     None = 0,
     WarmCold = 1,
     AxisPlane = 2,
-    GhostSnap = 3,
+    Outline = 3,
+    GhostSnap = 4,
 }
 
 impl HintTier {
+    /// Number of tiers, `None` included.
+    pub const COUNT: usize = 5;
+
     pub fn next(self) -> Self {
         match self {
             HintTier::None => HintTier::WarmCold,
             HintTier::WarmCold => HintTier::AxisPlane,
-            HintTier::AxisPlane => HintTier::GhostSnap,
+            HintTier::AxisPlane => HintTier::Outline,
+            HintTier::Outline => HintTier::GhostSnap,
             HintTier::GhostSnap => HintTier::GhostSnap, // stays at max
         }
     }
@@ -54,6 +105,18 @@ impl HintTier {
     pub fn as_u8(self) -> u8 {
         self as u8
     }
+
+    /// Inverse of `as_u8`.
+    pub fn from_u8(v: u8) -> Option<Self> {
+        match v {
+            0 => Some(HintTier::None),
+            1 => Some(HintTier::WarmCold),
+            2 => Some(HintTier::AxisPlane),
+            3 => Some(HintTier::Outline),
+            4 => Some(HintTier::GhostSnap),
+            _ => None,
+        }
+    }
 }
 
 /// Output of `HintSystem::update` — everything the renderer needs each frame.
@@ -63,23 +126,40 @@ pub struct HintOutput {
     pub tier: HintTier,
     /// [0,1] warmth of the warm/cold indicator.  1 = facing solution, 0 = facing away.
     pub warmth: f32,
-    /// RGB colour for the warm/cold arrow.
+    /// RGB colour for the warm/cold arrow, in the system's palette.
     pub warmth_color: [f32; 3],
     /// Whether to draw the axis-plane disc.
     pub show_disc: bool,
     /// Normal of the disc (= solution axis).
     pub disc_normal: Vector3<f32>,
+    /// Whether to draw the solved silhouette outline.
+    pub show_outline: bool,
+    /// Camera roll (radians, `scrambler::camera_roll`) still to undo on an
+    /// upright level; `None` when the camera is upright enough, the level
+    /// is not roll-locked, or no hint is showing.
+    pub roll_cue: Option<f32>,
     /// How much to un-scramble the model (0 = fully scrambled, 1 = solved).
     /// Only >0 for GhostSnap tier.
     pub ghost_lerp: f32,
     /// Hint penalty to add to the score (increments each time a hint is used).
     pub hint_count: u32,
+    /// Index of the stage the hints refer to.
+    pub stage: usize,
+    /// Total number of stages in the puzzle (1 for a classic puzzle).
+    pub stage_count: usize,
 }
 
 pub struct HintSystem {
     tier: HintTier,
     ghost_lerp: f32,
     hint_count: u32,
+    /// Times each tier has been reached, indexed by `HintTier`.
+    tier_uses: [u32; HintTier::COUNT],
+    stage: usize,
+    stage_count: usize,
+    /// Camera roll away from upright, on an upright level.
+    upright_roll: Option<f32>,
+    palette: WarmthPalette,
 }
 
 impl HintSystem {
@@ -88,12 +168,21 @@ impl HintSystem {
             tier: HintTier::None,
             ghost_lerp: 0.0,
             hint_count: 0,
+            tier_uses: [0; HintTier::COUNT],
+            stage: 0,
+            stage_count: 1,
+            upright_roll: None,
+            palette: WarmthPalette::Standard,
         }
     }
 
     /// Called when the player presses the hint key.
     pub fn advance(&mut self) {
-        self.tier = self.tier.next();
+        let next = self.tier.next();
+        if next != self.tier {
+            self.tier_uses[next as usize] += 1;
+        }
+        self.tier = next;
         self.hint_count += 1;
         log::info!("[Hint] advanced to tier {:?}", self.tier);
     }
@@ -109,6 +198,45 @@ impl HintSystem {
         self.tier = HintTier::None;
         self.ghost_lerp = 0.0;
         self.hint_count = 0;
+        self.tier_uses = [0; HintTier::COUNT];
+        self.stage = 0;
+        self.stage_count = 1;
+        self.upright_roll = None;
+    }
+
+    /// Declare how many stages the new puzzle has.  Call after `reset_full`.
+    pub fn set_stage_count(&mut self, stage_count: usize) {
+        self.stage = 0;
+        self.stage_count = stage_count.max(1);
+    }
+
+    /// Put the hints back where a saved puzzle left them.  Call after
+    /// `set_stage_count`.  A ghost snap in progress starts over.
+    pub fn restore(
+        &mut self,
+        stage: usize,
+        tier: HintTier,
+        hint_count: u32,
+        tier_uses: [u32; HintTier::COUNT],
+    ) {
+        self.stage = stage.min(self.stage_count - 1);
+        self.tier = tier;
+        self.ghost_lerp = 0.0;
+        self.hint_count = hint_count;
+        self.tier_uses = tier_uses;
+    }
+
+    /// A stage was solved: hints restart at None for the next one.
+    pub fn next_stage(&mut self) {
+        self.reset();
+        self.stage = (self.stage + 1).min(self.stage_count - 1);
+    }
+
+    /// Camera roll away from upright for the next `update`
+    /// (`ScrambleState::upright_roll`); `None` on a level without the
+    /// upright constraint.
+    pub fn set_upright_roll(&mut self, roll: Option<f32>) {
+        self.upright_roll = roll;
     }
 
     /// Update each frame.  `delta_time` in seconds, `camera_fwd` and `solution_dir` in world space.
@@ -123,34 +251,79 @@ impl HintSystem {
         // 0 means facing directly away.  We take the absolute value so *either*
         // pole of the solution axis counts as "warm" (the puzzle is rotationally
         // symmetric around the axis).
-        // Apply a power curve so the indicator sharpens as the camera approaches
-        // the target hemisphere — small angles (close to solution) produce warmth
-        // values near 1.0 much more distinctly than with a linear mapping.
         let linear = (dot.abs() + 1.0) * 0.5; // [0.5, 1.0] — abs keeps both poles warm
-        let warmth = linear.powf(3.0).clamp(0.0, 1.0); // sharpen near solution
+        self.output(delta_time, sharpen(linear), solution_dir)
+    }
+
+    /// `update` for an anamorphic stage.  `alignment` is
+    /// `scrambler::point_alignment` of the camera with the eye; only the eye
+    /// itself is warm, the far side of the orbit is fully cold.
+    pub fn update_point(
+        &mut self,
+        delta_time: f32,
+        alignment: f32,
+        eye_dir: Vector3<f32>,
+    ) -> HintOutput {
+        let linear = (alignment.clamp(-1.0, 1.0) + 1.0) * 0.5;
+        self.output(delta_time, sharpen(linear), eye_dir)
+    }
+
+    /// `update` from the stage's screen-space misalignment in pixels
+    /// (`Misalignment::rms_px`).  Fully warm at the snap tolerance, half
+    /// warm `WARMTH_HALF_PX` beyond it.
+    pub fn update_error(
+        &mut self,
+        delta_time: f32,
+        error_px: f32,
+        disc_normal: Vector3<f32>,
+    ) -> HintOutput {
+        let excess = (error_px - SNAP_TOLERANCE_PX).max(0.0);
+        let warmth = WARMTH_HALF_PX / (WARMTH_HALF_PX + excess);
+        self.output(delta_time, warmth, disc_normal)
+    }
+
+    /// Shared tail of the `update` variants.
+    fn output(&mut self, delta_time: f32, warmth: f32, disc_normal: Vector3<f32>) -> HintOutput {
+        let warmth = warmth.clamp(0.0, 1.0);
 
         // Ghost snap: slowly un-scramble over ~5 s.
         if self.tier == HintTier::GhostSnap {
             self.ghost_lerp = (self.ghost_lerp + delta_time / 5.0).min(1.0);
         }
 
-        let warmth_color = warmth_to_rgb(warmth);
+        let warmth_color = self.palette.color(warmth);
 
         HintOutput {
             tier: self.tier,
             warmth,
             warmth_color,
             show_disc: self.tier as u8 >= HintTier::AxisPlane as u8,
-            disc_normal: solution_dir,
+            disc_normal,
+            show_outline: self.tier as u8 >= HintTier::Outline as u8,
+            roll_cue: self
+                .upright_roll
+                .filter(|roll| self.tier != HintTier::None && roll.abs() > UPRIGHT_TOLERANCE_RAD),
             ghost_lerp: if self.tier == HintTier::GhostSnap {
                 self.ghost_lerp
             } else {
                 0.0
             },
             hint_count: self.hint_count,
+            stage: self.stage,
+            stage_count: self.stage_count,
         }
     }
 
+    /// Colour `warmth_color` with `palette` from now on.  A player setting,
+    /// so the resets leave it alone.
+    pub fn set_palette(&mut self, palette: WarmthPalette) {
+        self.palette = palette;
+    }
+
+    pub fn palette(&self) -> WarmthPalette {
+        self.palette
+    }
+
     pub fn tier(&self) -> HintTier {
         self.tier
     }
@@ -158,6 +331,21 @@ impl HintSystem {
     pub fn hint_count(&self) -> u32 {
         self.hint_count
     }
+
+    /// Times each tier has been reached since `reset_full`, indexed by
+    /// `HintTier`.  Pressing H again at the ghost snap counts towards
+    /// `hint_count` but reaches no new tier.
+    pub fn tier_uses(&self) -> [u32; HintTier::COUNT] {
+        self.tier_uses
+    }
+
+    pub fn stage(&self) -> usize {
+        self.stage
+    }
+
+    pub fn stage_count(&self) -> usize {
+        self.stage_count
+    }
 }
 
 impl Default for HintSystem {
@@ -168,17 +356,9 @@ impl Default for HintSystem {
 
 // ─── Colour helpers ───────────────────────────────────────────────────────────
 
-/// Map a [0,1] warmth value to an RGB colour.
-/// 0 = cold blue, 0.5 = neutral white, 1 = hot red.
-fn warmth_to_rgb(w: f32) -> [f32; 3] {
-    let w = w.clamp(0.0, 1.0);
-    if w < 0.5 {
-        // cold: blue → white
-        let t = w * 2.0;
-        [t, t, 1.0]
-    } else {
-        // warm: white → red/orange/yellow
-        let t = (w - 0.5) * 2.0;
-        [1.0, 1.0 - t * 0.6, 1.0 - t]
-    }
+/// Apply a power curve so the indicator sharpens as the camera approaches
+/// the target hemisphere — small angles (close to solution) produce warmth
+/// values near 1.0 much more distinctly than with a linear mapping.
+fn sharpen(linear: f32) -> f32 {
+    linear.powf(3.0)
 }
@@ -1,5 +1,17 @@
+pub mod authoring;
+pub mod calibration;
+pub mod clusters;
 pub mod entropy;
 pub mod hints;
+pub mod mosaic;
+pub mod outline;
+pub mod palette;
+pub mod pose;
 pub mod puzzle_params;
+pub mod reprojection;
+pub mod scoring;
 pub mod scramble_math;
 pub mod scrambler;
+pub mod search;
+pub mod seed;
+pub mod silhouette;
@@ -0,0 +1,127 @@
+//! Mosaic tint: an optional presentation mode for the scramble.
+//!
+//! Every fragment (a triangle or cluster, as the level scrambles them) gets
+//! its own hue, uploaded as a per-vertex tint next to the scramble offsets;
+//! the tint's alpha is the fragment's release order.  Each frame the game
+//! sets the model's `mosaic_t` from how close the camera is to the
+//! solution, and the shader fades a fragment back to its true colour once
+//! `mosaic_t` falls past its release order.  Far from the solution the
+//! model is a patchwork of colours; near it, it assembles into the real
+//! thing piece by piece, a progress cue that needs no compass hint.
+//!
+//! Hues and orders come from the puzzle seed (`SeedStream::Tint`), so a
+//! replayed or shared puzzle looks the same.  The tint is cosmetic: it
+//! never changes the scramble, the solve check or the score.
+
+use rand::Rng;
+use std::cell::RefCell;
+use std::f32::consts::PI;
+use std::rc::Rc;
+
+use formosaic_engine::architecture::models::simple_model::SimpleModel;
+
+use super::clusters::{cluster_count, flat_clusters};
+use super::puzzle_params::ScrambleStrategy;
+use super::scrambler::ScrambleState;
+use super::seed::{PuzzleSeed, SeedStream};
+
+/// Angle from the solution (radians) at and beyond which every fragment
+/// is fully tinted.
+pub const MOSAIC_FULL_TINT_RAD: f32 = 75.0 * PI / 180.0;
+
+/// Smallest release order a fragment is given; 0 marks an untinted vertex.
+const MIN_RELEASE: f32 = 0.02;
+
+/// Golden-ratio hue step: consecutive fragments get well-separated hues.
+const HUE_STEP: f32 = 0.618_034;
+
+/// Tint of every vertex of every mesh (rgba, 4 floats per vertex): the hue
+/// of the vertex's fragment and, in alpha, the fragment's release order in
+/// (0,1].  `mesh_positions` are the meshes' flat triangle-soup positions.
+pub fn fragment_tints(
+    mesh_positions: &[&[f32]],
+    strategy: ScrambleStrategy,
+    seed: PuzzleSeed,
+) -> Vec<Vec<f32>> {
+    let clusters = flat_clusters(mesh_positions, strategy);
+    let mut rng = seed.stream(SeedStream::Tint).rng();
+    let base_hue: f32 = rng.random_range(0.0..1.0);
+    let fragments: Vec<[f32; 4]> = (0..cluster_count(&clusters))
+        .map(|i| {
+            let [r, g, b] = hue_rgb((base_hue + i as f32 * HUE_STEP).fract());
+            [r, g, b, rng.random_range(MIN_RELEASE..=1.0)]
+        })
+        .collect();
+
+    let mut out = Vec::with_capacity(mesh_positions.len());
+    let mut tri = 0;
+    for pos in mesh_positions {
+        let tri_count = pos.len() / 9;
+        let mut tints = Vec::with_capacity(tri_count * 12);
+        for &cluster in &clusters[tri..tri + tri_count] {
+            for _ in 0..3 {
+                tints.extend_from_slice(&fragments[cluster]);
+            }
+        }
+        tri += tri_count;
+        out.push(tints);
+    }
+    out
+}
+
+/// One mesh's `tints` for the stage being solved: vertices that neither
+/// the current nor a later stage displaces are settled and left untinted.
+pub fn stage_tints(state: &ScrambleState, mesh_idx: usize, tints: &[f32]) -> Vec<f32> {
+    let pending: Vec<&[f32]> = state.stages[state.current_stage..]
+        .iter()
+        .filter_map(|s| s.scramble_offsets.get(mesh_idx).map(Vec::as_slice))
+        .collect();
+    let mut out = tints.to_vec();
+    for (v, tint) in out.chunks_exact_mut(4).enumerate() {
+        let moves = pending.iter().any(|offsets| {
+            offsets
+                .get(v * 3..v * 3 + 3)
+                .is_some_and(|o| o.iter().any(|&x| x != 0.0))
+        });
+        if !moves {
+            tint[3] = 0.0;
+        }
+    }
+    out
+}
+
+/// Upload `tints` for the stage being solved (see `stage_tints`), or clear
+/// every tint when `tints` is `None`.  Call with `upload_stage`.
+pub fn upload_tints(
+    model: &Rc<RefCell<SimpleModel>>,
+    state: &ScrambleState,
+    tints: Option<&[Vec<f32>]>,
+) {
+    let mut model = model.borrow_mut();
+    for mesh_idx in 0..state.original_positions.len() {
+        let mesh_tints = tints
+            .and_then(|t| t.get(mesh_idx))
+            .map(|t| stage_tints(state, mesh_idx, t))
+            .unwrap_or_default();
+        model.upload_fragment_tints(mesh_idx, mesh_tints);
+    }
+}
+
+/// Tint strength for a camera `alignment` (the cosine from
+/// `ScrambleState::alignment`): 0 at the solution, rising with the angle
+/// to 1 at `MOSAIC_FULL_TINT_RAD`.
+pub fn mosaic_t(alignment: f32) -> f32 {
+    let angle = alignment.clamp(-1.0, 1.0).acos();
+    (angle / MOSAIC_FULL_TINT_RAD).min(1.0)
+}
+
+/// Saturated, bright colour of `hue` in [0,1).
+fn hue_rgb(hue: f32) -> [f32; 3] {
+    const SATURATION: f32 = 0.7;
+    const VALUE: f32 = 0.95;
+    let channel = |n: f32| {
+        let k = (n + hue * 6.0) % 6.0;
+        VALUE - VALUE * SATURATION * k.min(4.0 - k).clamp(0.0, 1.0)
+    };
+    [channel(5.0), channel(3.0), channel(1.0)]
+}
@@ -0,0 +1,152 @@
+//! Silhouette outline of the solved model, for the outline hint tier.
+//!
+//! The outline is the set of contour edges of the rest pose as seen from
+//! the solution: edges where a triangle facing the viewer meets one facing
+//! away, plus the open boundary edges of the mesh.  The edges are kept in
+//! world space, so drawing them with the live camera shows the solved
+//! shape's outline in the current view — it traces the model's silhouette
+//! exactly when the camera reaches the solution.
+//!
+//! Models arrive as triangle soup, so vertices are welded by position
+//! (across meshes too) before edges are matched up.
+
+use std::collections::HashMap;
+
+use cgmath::{InnerSpace, Matrix4, Vector3, Vector4};
+
+use super::scrambler::ScrambleTarget;
+
+/// Welding grid, as a fraction of the model's bounding-box diagonal.
+const WELD_TOLERANCE: f32 = 1e-5;
+
+/// One outline segment, world space.
+pub type Segment = [Vector3<f32>; 2];
+
+/// Contour edges of the rest pose seen from `target` (world space: a view
+/// direction, or an eye point).  `original_positions` are per mesh in
+/// triangle order; `model_matrices` place each mesh in the world.
+pub fn silhouette_edges(
+    original_positions: &[Vec<f32>],
+    model_matrices: &[Matrix4<f32>],
+    target: ScrambleTarget,
+) -> Vec<Segment> {
+    let triangles = world_triangles(original_positions, model_matrices);
+    let Some(cell) = weld_cell(&triangles) else {
+        return Vec::new();
+    };
+
+    // Welded vertex ids, and every edge's facing triangles.
+    let mut ids: HashMap<[i64; 3], usize> = HashMap::new();
+    let mut points: Vec<Vector3<f32>> = Vec::new();
+    let mut edges: HashMap<(usize, usize), (u32, u32)> = HashMap::new();
+    let mut order: Vec<(usize, usize)> = Vec::new();
+    for tri in &triangles {
+        let normal = (tri[1] - tri[0]).cross(tri[2] - tri[0]);
+        if normal.magnitude2() < 1e-20 {
+            continue;
+        }
+        let view = match target {
+            ScrambleTarget::Axis(dir) => dir,
+            ScrambleTarget::Eye(eye) => (tri[0] + tri[1] + tri[2]) / 3.0 - eye,
+        };
+        let front = normal.dot(view) < 0.0;
+
+        let v = tri.map(|p| {
+            let key = [
+                (p.x / cell).round() as i64,
+                (p.y / cell).round() as i64,
+                (p.z / cell).round() as i64,
+            ];
+            *ids.entry(key).or_insert_with(|| {
+                points.push(p);
+                points.len() - 1
+            })
+        });
+        for (a, b) in [(v[0], v[1]), (v[1], v[2]), (v[2], v[0])] {
+            if a == b {
+                continue;
+            }
+            let key = (a.min(b), a.max(b));
+            let counts = edges.entry(key).or_insert_with(|| {
+                order.push(key);
+                (0, 0)
+            });
+            if front {
+                counts.0 += 1;
+            } else {
+                counts.1 += 1;
+            }
+        }
+    }
+
+    order
+        .into_iter()
+        .filter(|key| {
+            let (front, back) = edges[key];
+            let boundary = front + back == 1;
+            boundary || (front > 0 && back > 0)
+        })
+        .map(|(a, b)| [points[a], points[b]])
+        .collect()
+}
+
+/// Project `segments` through `view_proj` into NDC, two `[x, y]` points per
+/// segment.  Segments are clipped to the near plane; ones entirely behind
+/// the camera are dropped.
+pub fn project_segments(segments: &[Segment], view_proj: Matrix4<f32>) -> Vec<f32> {
+    const NEAR_W: f32 = 1e-4;
+    let mut out = Vec::with_capacity(segments.len() * 4);
+    for [a, b] in segments {
+        let mut ca = view_proj * a.extend(1.0);
+        let mut cb = view_proj * b.extend(1.0);
+        if ca.w < NEAR_W && cb.w < NEAR_W {
+            continue;
+        }
+        let clip = |inside: Vector4<f32>, outside: Vector4<f32>| {
+            let t = (inside.w - NEAR_W) / (inside.w - outside.w);
+            inside + (outside - inside) * t
+        };
+        if ca.w < NEAR_W {
+            ca = clip(cb, ca);
+        } else if cb.w < NEAR_W {
+            cb = clip(ca, cb);
+        }
+        out.extend_from_slice(&[ca.x / ca.w, ca.y / ca.w, cb.x / cb.w, cb.y / cb.w]);
+    }
+    out
+}
+
+/// Every triangle of every mesh, in world space.
+fn world_triangles(
+    original_positions: &[Vec<f32>],
+    model_matrices: &[Matrix4<f32>],
+) -> Vec<[Vector3<f32>; 3]> {
+    let mut triangles = Vec::new();
+    for (mesh_idx, positions) in original_positions.iter().enumerate() {
+        let m = model_matrices
+            .get(mesh_idx)
+            .copied()
+            .unwrap_or_else(|| Matrix4::from_scale(1.0));
+        let world = |i: usize| {
+            let p = m * Vector4::new(positions[i], positions[i + 1], positions[i + 2], 1.0);
+            p.truncate()
+        };
+        for tri in 0..positions.len() / 9 {
+            let base = tri * 9;
+            triangles.push([world(base), world(base + 3), world(base + 6)]);
+        }
+    }
+    triangles
+}
+
+/// Welding grid cell size; `None` for an empty or point-sized model.
+fn weld_cell(triangles: &[[Vector3<f32>; 3]]) -> Option<f32> {
+    let mut lo = Vector3::from([f32::INFINITY; 3]);
+    let mut hi = Vector3::from([f32::NEG_INFINITY; 3]);
+    for p in triangles.iter().flatten() {
+        lo = Vector3::new(lo.x.min(p.x), lo.y.min(p.y), lo.z.min(p.z));
+        hi = Vector3::new(hi.x.max(p.x), hi.y.max(p.y), hi.z.max(p.z));
+    }
+    let diagonal = (hi - lo).magnitude();
+    (diagonal.is_finite() && diagonal > 0.0).then_some(diagonal * WELD_TOLERANCE)
+}
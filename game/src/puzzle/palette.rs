@@ -0,0 +1,133 @@
+//! Warmth palettes: the colours the Tier 1 compass is drawn in.
+//!
+//! The standard palette runs from cold blue through white to hot red, which
+//! players with a colour vision deficiency may not be able to tell apart.
+//! The other palettes pick a cold/hot pair each deficiency still separates,
+//! and ramp straight from one to the other so warmth also reads as
+//! brightness: hot is always clearly lighter than cold.  `Monochrome` drops
+//! hue altogether and is luminance only.
+//!
+//! The palette is a player setting (see `level::settings`).  `HintSystem`
+//! colours its output with it, the HUD and the hint overlay take their cold
+//! and hot tints from it, and none of them hard-code a colour of their own.
+//! The HUD also spells the warmth out as a number and a bar, so the
+//! compass can be read without telling colours apart at all.
+
+/// Colour scheme of the warm/cold indicator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WarmthPalette {
+    /// Blue → white → red.
+    #[default]
+    Standard,
+    /// Blue → orange, for red-green (green-weak) colour blindness.
+    Deuteranopia,
+    /// Blue → yellow, for red-green (red-weak) colour blindness; reds look
+    /// dark to protanopes, so hot avoids them.
+    Protanopia,
+    /// Teal → pink, for blue-yellow colour blindness.
+    Tritanopia,
+    /// Dark grey → white.
+    Monochrome,
+}
+
+impl WarmthPalette {
+    pub const ALL: [WarmthPalette; 5] = [
+        WarmthPalette::Standard,
+        WarmthPalette::Deuteranopia,
+        WarmthPalette::Protanopia,
+        WarmthPalette::Tritanopia,
+        WarmthPalette::Monochrome,
+    ];
+
+    /// The palette after this one, wrapping round; the menu cycles with it.
+    pub fn next(self) -> Self {
+        let i = Self::ALL.iter().position(|&p| p == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+
+    /// Short display name, for the menu button.
+    pub fn label(self) -> &'static str {
+        match self {
+            WarmthPalette::Standard => "Standard",
+            WarmthPalette::Deuteranopia => "Deutan",
+            WarmthPalette::Protanopia => "Protan",
+            WarmthPalette::Tritanopia => "Tritan",
+            WarmthPalette::Monochrome => "Mono",
+        }
+    }
+
+    /// Short name stored in `settings.json`.
+    pub fn to_meta_str(self) -> &'static str {
+        match self {
+            WarmthPalette::Standard => "standard",
+            WarmthPalette::Deuteranopia => "deuteranopia",
+            WarmthPalette::Protanopia => "protanopia",
+            WarmthPalette::Tritanopia => "tritanopia",
+            WarmthPalette::Monochrome => "monochrome",
+        }
+    }
+
+    /// Inverse of `to_meta_str`.
+    pub fn from_meta_str(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|p| p.to_meta_str() == s)
+    }
+
+    /// Tint for warmth 0, facing away from the solution.
+    pub fn cold(self) -> [f32; 3] {
+        match self {
+            WarmthPalette::Standard => [0.2, 0.4, 0.9],
+            WarmthPalette::Deuteranopia => [0.0, 0.45, 0.7],
+            WarmthPalette::Protanopia => [0.2, 0.3, 0.75],
+            WarmthPalette::Tritanopia => [0.0, 0.35, 0.4],
+            WarmthPalette::Monochrome => [0.15, 0.15, 0.15],
+        }
+    }
+
+    /// Tint for warmth 1, facing the solution.
+    pub fn hot(self) -> [f32; 3] {
+        match self {
+            WarmthPalette::Standard => [0.9, 0.2, 0.1],
+            WarmthPalette::Deuteranopia => [0.9, 0.6, 0.0],
+            WarmthPalette::Protanopia => [1.0, 0.85, 0.15],
+            WarmthPalette::Tritanopia => [1.0, 0.45, 0.5],
+            WarmthPalette::Monochrome => [1.0, 1.0, 1.0],
+        }
+    }
+
+    /// Colour of a [0,1] warmth value.  The standard palette passes through
+    /// white half way; the others blend straight from cold to hot.
+    pub fn color(self, warmth: f32) -> [f32; 3] {
+        let w = warmth.clamp(0.0, 1.0);
+        let (cold, hot) = (self.cold(), self.hot());
+        match self {
+            WarmthPalette::Standard if w < 0.5 => lerp(cold, [1.0; 3], w * 2.0),
+            WarmthPalette::Standard => lerp([1.0; 3], hot, (w - 0.5) * 2.0),
+            _ => lerp(cold, hot, w),
+        }
+    }
+}
+
+/// Relative luminance (Rec. 709 weights) of an RGB colour.
+pub fn luminance(rgb: [f32; 3]) -> f32 {
+    0.2126 * rgb[0] + 0.7152 * rgb[1] + 0.0722 * rgb[2]
+}
+
+/// Warmth as a whole percentage, for the HUD.
+pub fn warmth_percent(warmth: f32) -> u32 {
+    (warmth.clamp(0.0, 1.0) * 100.0).round() as u32
+}
+
+/// Warmth as a bar of `cells` characters, `#` filled and `-` empty, so it
+/// reads without colour: `[###--]`.
+pub fn warmth_bar(warmth: f32, cells: usize) -> String {
+    let filled = (warmth.clamp(0.0, 1.0) * cells as f32).round() as usize;
+    format!("[{}{}]", "#".repeat(filled), "-".repeat(cells - filled))
+}
+
+fn lerp(a: [f32; 3], b: [f32; 3], t: f32) -> [f32; 3] {
+    [
+        a[0] + (b[0] - a[0]) * t,
+        a[1] + (b[1] - a[1]) * t,
+        a[2] + (b[2] - a[2]) * t,
+    ]
+}
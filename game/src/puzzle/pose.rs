@@ -0,0 +1,348 @@
+//! Posed scrambling for skinned models.
+//!
+//! The vertex shader skins `pos + uScrambleT * scrambleOffset`, so offsets
+//! computed in bind-pose model space are bent by whatever pose the bones
+//! are in: a scramble that lines up in the bind pose falls apart once the
+//! model is animated.  A skinned model is therefore scrambled in one
+//! `Pose` — a clip and a time into it — evaluated on the CPU
+//! (`AnimationPlayer::evaluate`, `Skeleton::compute_final_matrices`).
+//!
+//! `ModelPose` holds the posed positions, which the analysis, the scramble
+//! and the solve check all use, in each mesh's own space like unskinned
+//! positions.  It also holds per vertex the inverse of the pose's skin,
+//! which turns a posed offset into the bind-pose offset that the GPU
+//! skins back onto it.  Held in the pose (`hold_pose`), the model draws
+//! exactly the posed scramble.  In any other pose each offset moves with
+//! its own vertex's bones, so fragments stay with the limb they came from.
+//! `PoseLoop` uses that to play the clip during the puzzle and settle it
+//! into the pose as the view lines up.
+//!
+//! The pose is the start of the clip the solve plays (`solve_clip`), so the
+//! solved model runs straight on into its animation.
+
+use cgmath::{Matrix3, Matrix4, SquareMatrix, Vector3, Vector4, Zero};
+
+use formosaic_engine::architecture::models::animation::AnimationClip;
+use formosaic_engine::architecture::models::animation_player::AnimationPlayer;
+use formosaic_engine::architecture::models::model::Model;
+use formosaic_engine::architecture::models::model_loader::ModelLoadData;
+use formosaic_engine::architecture::models::simple_model::SimpleModel;
+use formosaic_engine::architecture::models::skeleton::Skeleton;
+
+use super::scramble_math::unweld;
+
+/// The clip a solved model plays: the most idle-looking one that does not
+/// move the model about.  `None` without animations.
+pub fn solve_clip(animations: &[AnimationClip]) -> Option<usize> {
+    let prefer = [
+        "idle", "interact", "pose", "stand", "celebrat", "victory", "win", "success",
+    ];
+    let avoid = [
+        "run", "walk", "jump", "fall", "hit", "punch", "attack", "roll", "hurt",
+    ];
+    if animations.is_empty() {
+        return None;
+    }
+    let idx = animations
+        .iter()
+        .enumerate()
+        .filter(|(_, clip)| {
+            let n = clip.name.to_lowercase();
+            !avoid.iter().any(|k| n.contains(k))
+        })
+        .max_by_key(|(_, clip)| {
+            let n = clip.name.to_lowercase();
+            prefer
+                .iter()
+                .position(|k| n.contains(k))
+                .map(|i| 100 - i as i32)
+                .unwrap_or(0)
+        })
+        .map(|(i, _)| i)
+        .unwrap_or(0);
+    Some(idx)
+}
+
+/// A skeleton pose: a time into one of the model's clips.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Pose {
+    /// Index into the model's animations; `None` = the bind pose.
+    pub clip: Option<usize>,
+    /// Time into the clip, in seconds.
+    pub time_secs: f32,
+}
+
+impl Pose {
+    /// The pose a model is scrambled in: the start of its `solve_clip`, or
+    /// the bind pose without animations.
+    pub fn for_clips(animations: &[AnimationClip]) -> Self {
+        Self {
+            clip: solve_clip(animations),
+            time_secs: 0.0,
+        }
+    }
+
+    /// Final skinning matrices of each of `mesh_count` meshes in this pose,
+    /// as `SimpleModel` computes them for the GPU.
+    pub fn bone_matrices(
+        &self,
+        skeleton: &Skeleton,
+        animations: &[AnimationClip],
+        mesh_count: usize,
+    ) -> Vec<Vec<Matrix4<f32>>> {
+        let mut skeleton = skeleton.clone();
+        let mut player = AnimationPlayer::new();
+        if let Some(clip) = self.clip.and_then(|i| animations.get(i)) {
+            player.clip = Some(clip.clone());
+            player.local_time_sec = self.time_secs as f64;
+        }
+        (0..mesh_count)
+            .map(|mesh_idx| {
+                let mesh_off = mesh_idx.min(skeleton.mesh_count.saturating_sub(1));
+                player.evaluate(&mut skeleton, mesh_off)
+            })
+            .collect()
+    }
+}
+
+/// One mesh's triangle-soup positions and the bone influences of each of
+/// its vertices (empty for an unskinned mesh).
+pub struct SkinnedMesh<'a> {
+    pub positions: &'a [f32],
+    pub bone_indices: &'a [[i32; 4]],
+    pub bone_weights: &'a [[f32; 4]],
+}
+
+/// A model's meshes evaluated in one pose.
+#[derive(Debug, Clone)]
+pub struct ModelPose {
+    pub pose: Pose,
+    /// Bind-pose positions per mesh, as the GPU is given them.
+    bind: Vec<Vec<f32>>,
+    /// Positions per mesh as the pose draws them, in the mesh's own space.
+    posed: Vec<Vec<f32>>,
+    /// Per mesh, per vertex: the map from a posed offset to the bind-pose
+    /// offset the GPU skins onto it.  Empty for an unskinned mesh.
+    unskin: Vec<Vec<Matrix3<f32>>>,
+}
+
+impl ModelPose {
+    /// Pose `meshes` with their final skinning matrices `bones` (per mesh,
+    /// see `Pose::bone_matrices`).  `mesh_transforms` are the meshes' node
+    /// transforms, which the shader leaves out for a skinned mesh.
+    pub fn new(
+        pose: Pose,
+        meshes: &[SkinnedMesh<'_>],
+        bones: &[Vec<Matrix4<f32>>],
+        mesh_transforms: &[Matrix4<f32>],
+    ) -> Self {
+        let identity = Matrix4::from_scale(1.0);
+        let mut posed = Vec::with_capacity(meshes.len());
+        let mut unskin = Vec::with_capacity(meshes.len());
+        for (mesh_idx, mesh) in meshes.iter().enumerate() {
+            let bones = bones.get(mesh_idx).map_or(&[][..], Vec::as_slice);
+            let skinned = mesh.bone_indices.iter().any(|b| b.iter().any(|&i| i >= 0));
+            if !skinned || bones.is_empty() {
+                posed.push(mesh.positions.to_vec());
+                unskin.push(Vec::new());
+                continue;
+            }
+            let transform = mesh_transforms.get(mesh_idx).copied().unwrap_or(identity);
+            let to_mesh = transform.invert().unwrap_or(identity);
+            let transform_linear = linear(transform);
+            let mut mesh_posed = Vec::with_capacity(mesh.positions.len());
+            let mut mesh_unskin = Vec::with_capacity(mesh.positions.len() / 3);
+            for (v, p) in mesh.positions.chunks_exact(3).enumerate() {
+                let skin = vertex_skin(bones, mesh.bone_indices.get(v), mesh.bone_weights.get(v));
+                let q = to_mesh * skin * Vector4::new(p[0], p[1], p[2], 1.0);
+                mesh_posed.extend_from_slice(&[q.x, q.y, q.z]);
+                let inverse = linear(skin).invert().unwrap_or_else(Matrix3::identity);
+                mesh_unskin.push(inverse * transform_linear);
+            }
+            posed.push(mesh_posed);
+            unskin.push(mesh_unskin);
+        }
+        Self {
+            pose,
+            bind: meshes.iter().map(|m| m.positions.to_vec()).collect(),
+            posed,
+            unskin,
+        }
+    }
+
+    /// Freshly parsed model data in `Pose::for_clips`, meshes unwelded
+    /// as the built model draws them.  `None` without a skinned mesh.
+    pub fn of_load_data(data: &ModelLoadData) -> Option<Self> {
+        let skeleton = data.skeleton.as_ref()?;
+        let mut soups = Vec::with_capacity(data.meshes.len());
+        for m in &data.meshes {
+            let tri_indices = &m.indices[..m.indices.len() / 3 * 3];
+            let bone_indices: Vec<[i32; 4]> = tri_indices
+                .iter()
+                .map(|&i| m.bone_indices.get(i as usize).copied().unwrap_or([-1; 4]))
+                .collect();
+            let bone_weights: Vec<[f32; 4]> = tri_indices
+                .iter()
+                .map(|&i| m.bone_weights.get(i as usize).copied().unwrap_or([0.0; 4]))
+                .collect();
+            soups.push((unweld(&m.positions, &m.indices), bone_indices, bone_weights));
+        }
+        let meshes: Vec<SkinnedMesh<'_>> = soups
+            .iter()
+            .map(|(positions, bone_indices, bone_weights)| SkinnedMesh {
+                positions,
+                bone_indices,
+                bone_weights,
+            })
+            .collect();
+        Self::posed_model(skeleton, &data.animations, &meshes, &data.mesh_transforms)
+    }
+
+    /// A built model in `Pose::for_clips`.  Call before anything is
+    /// uploaded over its bind-pose positions.  `None` without a skinned
+    /// mesh.
+    pub fn of_model(model: &SimpleModel) -> Option<Self> {
+        let skeleton = model.skeleton.as_ref()?;
+        let meshes: Vec<SkinnedMesh<'_>> = model
+            .meshes()
+            .iter()
+            .map(|m| SkinnedMesh {
+                positions: m.positions(),
+                bone_indices: m.bone_indices(),
+                bone_weights: m.bone_weights(),
+            })
+            .collect();
+        let transforms: Vec<Matrix4<f32>> = (0..meshes.len())
+            .map(|i| {
+                model
+                    .mesh_transform(i)
+                    .unwrap_or_else(|| Matrix4::from_scale(1.0))
+            })
+            .collect();
+        Self::posed_model(skeleton, model.animations(), &meshes, &transforms)
+    }
+
+    fn posed_model(
+        skeleton: &Skeleton,
+        animations: &[AnimationClip],
+        meshes: &[SkinnedMesh<'_>],
+        mesh_transforms: &[Matrix4<f32>],
+    ) -> Option<Self> {
+        let pose = Pose::for_clips(animations);
+        let bones = pose.bone_matrices(skeleton, animations, meshes.len());
+        let posed = Self::new(pose, meshes, &bones, mesh_transforms);
+        posed.unskin.iter().any(|u| !u.is_empty()).then_some(posed)
+    }
+
+    /// Posed positions per mesh: the model as the scramble sees it.
+    pub fn posed_positions(&self) -> &[Vec<f32>] {
+        &self.posed
+    }
+
+    pub fn into_posed_positions(self) -> Vec<Vec<f32>> {
+        self.posed
+    }
+
+    /// The bind-pose offsets that draw posed `offsets` for a mesh.
+    pub fn bind_offsets(&self, mesh_idx: usize, offsets: &[f32]) -> Vec<f32> {
+        let unskin = &self.unskin[mesh_idx];
+        if unskin.is_empty() {
+            return offsets.to_vec();
+        }
+        offsets
+            .chunks_exact(3)
+            .zip(unskin)
+            .flat_map(|(o, m)| {
+                let b = *m * Vector3::new(o[0], o[1], o[2]);
+                [b.x, b.y, b.z]
+            })
+            .collect()
+    }
+
+    /// The bind-pose positions that draw posed `positions` for a mesh.
+    pub fn bind_positions(&self, mesh_idx: usize, positions: &[f32]) -> Vec<f32> {
+        let moved: Vec<f32> = positions
+            .iter()
+            .zip(&self.posed[mesh_idx])
+            .map(|(p, q)| p - q)
+            .collect();
+        self.bind_offsets(mesh_idx, &moved)
+            .iter()
+            .zip(&self.bind[mesh_idx])
+            .map(|(o, b)| b + o)
+            .collect()
+    }
+}
+
+/// Upper-left 3×3 of a transform: how it moves offsets.
+fn linear(m: Matrix4<f32>) -> Matrix3<f32> {
+    Matrix3::from_cols(m.x.truncate(), m.y.truncate(), m.z.truncate())
+}
+
+/// A vertex's blended skinning matrix, as the vertex shader forms it; the
+/// identity for a vertex without influences.
+fn vertex_skin(
+    bones: &[Matrix4<f32>],
+    indices: Option<&[i32; 4]>,
+    weights: Option<&[f32; 4]>,
+) -> Matrix4<f32> {
+    let mut blended = Matrix4::zero();
+    let mut has_influence = false;
+    if let (Some(indices), Some(weights)) = (indices, weights) {
+        for (&bone_idx, &weight) in indices.iter().zip(weights) {
+            if bone_idx >= 0 && weight > 0.0 {
+                if let Some(bone) = bones.get(bone_idx as usize) {
+                    blended += *bone * weight;
+                    has_influence = true;
+                }
+            }
+        }
+    }
+    if has_influence {
+        blended
+    } else {
+        Matrix4::from_scale(1.0)
+    }
+}
+
+/// Hold `model` in `pose`: the pose's clip is paused at its time, without
+/// a crossfade, until something else is played.
+pub fn hold_pose(model: &mut SimpleModel, pose: Pose) {
+    match pose.clip {
+        Some(clip) => model.play_animation(clip),
+        None => model.player.clip = None,
+    }
+    model.player.pause();
+    model.player.local_time_sec = pose.time_secs as f64;
+    model.player.blend_elapsed = model.player.blend_duration;
+}
+
+/// A posed puzzle's clip played during the puzzle.  While the model is
+/// scrambled the clip loops in full; as the view lines up (the scramble
+/// `t` falls to 0) its time is drawn back to the pose's, where the
+/// scramble is exact.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PoseLoop {
+    pose_secs: f32,
+    duration_secs: f32,
+    clock: f32,
+}
+
+impl PoseLoop {
+    /// `None` for the bind pose or a clip without length.
+    pub fn new(pose: Pose, animations: &[AnimationClip]) -> Option<Self> {
+        let duration_secs = animations.get(pose.clip?)?.duration_seconds() as f32;
+        (duration_secs > 0.0).then_some(Self {
+            pose_secs: pose.time_secs,
+            duration_secs,
+            clock: pose.time_secs,
+        })
+    }
+
+    /// Advance the loop by `dt` seconds and return the clip time to show
+    /// at scramble `t`.
+    pub fn advance(&mut self, dt: f32, t: f32) -> f32 {
+        self.clock = (self.clock + dt).rem_euclid(self.duration_secs);
+        self.pose_secs + (self.clock - self.pose_secs) * t.clamp(0.0, 1.0)
+    }
+}
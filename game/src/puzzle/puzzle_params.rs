@@ -3,6 +3,57 @@ use cgmath::{InnerSpace, Matrix4, Vector3, Vector4};
 use formosaic_engine::architecture::models::model::Model;
 use formosaic_engine::architecture::models::simple_model::SimpleModel;
 
+/// Which way fragments are displaced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScrambleProjection {
+    /// Along one shared axis.  Exact under orthographic projection; the
+    /// perspective camera still sees faint parallax seams when solved.
+    #[default]
+    Parallel,
+    /// Along the rays from one eye point, scaled so every fragment projects
+    /// to the same place from there.  Solved by reaching that point.
+    Anamorphic,
+}
+
+/// How triangles are grouped into rigid units before being displaced.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScrambleStrategy {
+    /// Every triangle gets its own displacement (the classic scramble).
+    #[default]
+    PerTriangle,
+    /// Connected components move as rigid units.  Components larger than
+    /// `max_triangles` are split into patches (0 = no limit).
+    PerCluster { max_triangles: usize },
+    /// Each mesh moves as one rigid unit.
+    PerMesh,
+}
+
+impl ScrambleStrategy {
+    /// Short name stored in `meta.json`: `triangle`, `cluster`, `cluster:N`
+    /// or `mesh`.
+    pub fn to_meta_str(self) -> String {
+        match self {
+            ScrambleStrategy::PerTriangle => "triangle".to_string(),
+            ScrambleStrategy::PerCluster { max_triangles: 0 } => "cluster".to_string(),
+            ScrambleStrategy::PerCluster { max_triangles } => format!("cluster:{}", max_triangles),
+            ScrambleStrategy::PerMesh => "mesh".to_string(),
+        }
+    }
+
+    /// Inverse of `to_meta_str`.
+    pub fn from_meta_str(s: &str) -> Option<Self> {
+        match s {
+            "triangle" => Some(ScrambleStrategy::PerTriangle),
+            "cluster" => Some(ScrambleStrategy::PerCluster { max_triangles: 0 }),
+            "mesh" => Some(ScrambleStrategy::PerMesh),
+            _ => {
+                let n = s.strip_prefix("cluster:")?.parse().ok()?;
+                Some(ScrambleStrategy::PerCluster { max_triangles: n })
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
 pub struct PuzzleParams {
     pub entity_scale: f32,
@@ -10,6 +61,18 @@ pub struct PuzzleParams {
     pub min_disp: f32,
     pub max_disp: f32,
     pub model_space_radius: f32,
+    /// Number of scramble stages (axes).  1 = classic single-view puzzle.
+    pub stage_count: usize,
+    /// How triangles are grouped before displacement.
+    pub strategy: ScrambleStrategy,
+    /// Parallel (direction) or anamorphic (eye point) scramble.
+    pub projection: ScrambleProjection,
+    /// Largest fragment rotation (radians) about the view axis.  0 = no
+    /// rotation scramble, and the camera roll does not matter.
+    pub max_roll: f32,
+    /// Roll-locked level: the solve also needs the camera upright relative
+    /// to the model's up axis (+Y), so upside-down views do not count.
+    pub upright: bool,
 }
 
 impl PuzzleParams {
@@ -87,9 +150,51 @@ impl PuzzleParams {
             min_disp: model_radius * 0.02,
             max_disp: model_radius * 0.12,
             model_space_radius: model_radius,
+            stage_count: 1,
+            strategy: ScrambleStrategy::PerTriangle,
+            projection: ScrambleProjection::Parallel,
+            max_roll: 0.0,
+            upright: false,
+        }
+    }
+
+    /// Same geometry, split into `stages` groups each with its own axis.
+    pub fn with_stages(self, stages: usize) -> Self {
+        Self {
+            stage_count: stages.max(1),
+            ..self
+        }
+    }
+
+    /// Same geometry, displaced with `strategy`.
+    pub fn with_strategy(self, strategy: ScrambleStrategy) -> Self {
+        Self { strategy, ..self }
+    }
+
+    /// Same geometry, displaced with `projection`.
+    pub fn with_projection(self, projection: ScrambleProjection) -> Self {
+        Self { projection, ..self }
+    }
+
+    /// Same geometry, with fragments also rotated by up to `max_roll`.
+    pub fn with_roll(self, max_roll: f32) -> Self {
+        Self {
+            max_roll: max_roll.max(0.0),
+            ..self
         }
     }
 
+    /// Same geometry, with the upright constraint on or off.
+    pub fn with_upright(self, upright: bool) -> Self {
+        Self { upright, ..self }
+    }
+
+    /// Orbit distance in model-space units — how far an anamorphic eye sits
+    /// from the model's centre.
+    pub fn eye_distance(&self) -> f32 {
+        self.orbit_distance / self.entity_scale
+    }
+
     pub fn default_for(target_world_radius: f32) -> Self {
         Self {
             entity_scale: 0.005,
@@ -97,6 +202,11 @@ impl PuzzleParams {
             min_disp: 3.0,
             max_disp: 15.0,
             model_space_radius: 1.0,
+            stage_count: 1,
+            strategy: ScrambleStrategy::PerTriangle,
+            projection: ScrambleProjection::Parallel,
+            max_roll: 0.0,
+            upright: false,
         }
     }
 }
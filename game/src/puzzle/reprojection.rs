@@ -0,0 +1,215 @@
+//! Screen-space solve metric.
+//!
+//! The solve used to fire on a fixed cosine between the camera and the
+//! solution axis, which means something very different for a large model
+//! with a few big fragments than for a small one with thousands, and on a
+//! phone than on a 4K monitor.  This module instead measures what the
+//! player actually sees: how many pixels the current stage's fragments
+//! still sit away from where they belong on screen.
+//!
+//! The metric runs every frame, so it does not visit every triangle:
+//! `SeamSamples` picks at most `MAX_SAMPLES` of the stage's displaced
+//! triangles, spread evenly over the model, once per stage, and each frame
+//! projects only those.  Only the part of a fragment's offset that runs along
+//! its solution ray counts as translation — that is the part that vanishes
+//! from the solved view; the rotation scramble's in-plane turn is measured
+//! separately from the camera's roll error instead, as the distance the
+//! triangle's corners are turned about its centroid.
+//!
+//! Anamorphic stages are projected through the real perspective camera, so
+//! the error is exactly zero at the eye.  A parallel stage can never be
+//! seam-free under perspective, so it is projected orthographically along
+//! the view direction, scaled to pixels at the orbit target's depth.
+
+use cgmath::{InnerSpace, Matrix4, Vector2, Vector3};
+
+use super::scrambler::{roll_alignment, ScrambleStage};
+
+/// The stage snaps into place once its RMS misalignment drops to this.
+pub const SNAP_TOLERANCE_PX: f32 = 1.5;
+
+/// Most triangles sampled per stage each frame.
+pub const MAX_SAMPLES: usize = 256;
+
+/// The camera the metric is measured from.
+#[derive(Debug, Clone, Copy)]
+pub struct ScreenView {
+    pub position: Vector3<f32>,
+    pub forward: Vector3<f32>,
+    pub up: Vector3<f32>,
+    /// World point the camera orbits; sets the scale of a parallel stage.
+    pub target: Vector3<f32>,
+    /// Focal length in pixels: half the viewport height over `tan(fov / 2)`.
+    pub focal_px: f32,
+}
+
+impl ScreenView {
+    /// `fov_y` is the vertical field of view in radians, `viewport_height`
+    /// in pixels.
+    pub fn new(
+        position: Vector3<f32>,
+        forward: Vector3<f32>,
+        up: Vector3<f32>,
+        target: Vector3<f32>,
+        fov_y: f32,
+        viewport_height: f32,
+    ) -> Self {
+        let forward = forward.normalize();
+        let right = forward.cross(up).normalize();
+        Self {
+            position,
+            forward,
+            up: right.cross(forward),
+            target,
+            focal_px: viewport_height * 0.5 / (fov_y * 0.5).tan().max(1e-6),
+        }
+    }
+
+    fn right(&self) -> Vector3<f32> {
+        self.forward.cross(self.up)
+    }
+
+    /// Pixel position of `p` relative to the screen centre.
+    fn project(&self, p: Vector3<f32>, perspective: bool) -> Vector2<f32> {
+        let v = p - self.position;
+        let scale = if perspective {
+            self.focal_px / v.dot(self.forward).max(1e-4)
+        } else {
+            self.focal_px / (self.target - self.position).dot(self.forward).max(1e-4)
+        };
+        Vector2::new(v.dot(self.right()), v.dot(self.up)) * scale
+    }
+}
+
+/// How far the current stage's fragments are from lining up on screen.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Misalignment {
+    /// Worst fragment, in pixels.
+    pub max_px: f32,
+    /// Root mean square over all sampled fragments, in pixels.
+    pub rms_px: f32,
+}
+
+impl Misalignment {
+    /// Whether the stage is close enough to snap into place.
+    pub fn is_solved(&self) -> bool {
+        self.rms_px <= SNAP_TOLERANCE_PX
+    }
+}
+
+/// The displaced triangles of a stage the metric is measured on.  Built
+/// once per stage, since offsets never change while it is solved.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SeamSamples {
+    /// `(mesh, triangle)` indices.
+    triangles: Vec<(usize, usize)>,
+}
+
+impl SeamSamples {
+    /// Up to `MAX_SAMPLES` triangles `stage` moves, strided evenly over all
+    /// of them.  Triangles the stage does not move are never picked.
+    pub fn of_stage(stage: &ScrambleStage, original_positions: &[Vec<f32>]) -> Self {
+        let displaced: Vec<(usize, usize)> = original_positions
+            .iter()
+            .zip(&stage.scramble_offsets)
+            .enumerate()
+            .flat_map(|(mesh, (positions, offsets))| {
+                let tri_count = positions.len().min(offsets.len()) / 9;
+                (0..tri_count)
+                    .filter(move |&tri| offsets[tri * 9..tri * 9 + 9].iter().any(|&o| o != 0.0))
+                    .map(move |tri| (mesh, tri))
+            })
+            .collect();
+        let stride = displaced.len().div_ceil(MAX_SAMPLES).max(1);
+        Self {
+            triangles: displaced.into_iter().step_by(stride).collect(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.triangles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.triangles.is_empty()
+    }
+}
+
+/// Misalignment of `stage` seen from `view`.  `original_positions` are the
+/// rest positions per mesh and `model_matrices` map each mesh into world
+/// space (entity matrix times mesh transform).  Picks the samples afresh;
+/// a caller measuring every frame keeps a `SeamSamples` and calls
+/// `sampled_misalignment` instead.
+pub fn misalignment(
+    stage: &ScrambleStage,
+    original_positions: &[Vec<f32>],
+    model_matrices: &[Matrix4<f32>],
+    view: &ScreenView,
+) -> Misalignment {
+    let samples = SeamSamples::of_stage(stage, original_positions);
+    sampled_misalignment(stage, &samples, original_positions, model_matrices, view)
+}
+
+/// Misalignment of `stage` over `samples` (built for this stage).  A stage
+/// with no samples is aligned.
+pub fn sampled_misalignment(
+    stage: &ScrambleStage,
+    samples: &SeamSamples,
+    original_positions: &[Vec<f32>],
+    model_matrices: &[Matrix4<f32>],
+    view: &ScreenView,
+) -> Misalignment {
+    let perspective = stage.solution_eye.is_some();
+    let roll_chord = stage.solution_up.map_or(0.0, |up| {
+        let cos = roll_alignment(view.forward, view.up, up);
+        2.0 * (cos.acos() * 0.5).sin()
+    });
+
+    let mut max_px = 0.0f32;
+    let mut sum_sq = 0.0f32;
+    let mut count = 0usize;
+    for &(mesh, tri) in &samples.triangles {
+        let (Some(positions), Some(offsets), Some(&matrix)) = (
+            original_positions.get(mesh),
+            stage.scramble_offsets.get(mesh),
+            model_matrices.get(mesh),
+        ) else {
+            continue;
+        };
+        let world =
+            |v: &[f32], w: f32| (matrix * Vector3::new(v[0], v[1], v[2]).extend(w)).truncate();
+        let corners = [0, 3, 6].map(|c| world(&positions[tri * 9 + c..], 1.0));
+        let centroid = (corners[0] + corners[1] + corners[2]) / 3.0;
+        let offset = [0, 3, 6]
+            .map(|c| world(&offsets[tri * 9 + c..], 0.0))
+            .iter()
+            .fold(Vector3::new(0.0, 0.0, 0.0), |a, &b| a + b)
+            / 3.0;
+
+        let ray = match stage.solution_eye {
+            Some(eye) => (centroid - eye).normalize(),
+            None => stage.solution_dir.normalize(),
+        };
+        let along = ray * offset.dot(ray);
+        let at = view.project(centroid, perspective);
+        let shift = (view.project(centroid + along, perspective) - at).magnitude();
+        let turn = corners
+            .iter()
+            .map(|&c| (view.project(c, perspective) - at).magnitude())
+            .fold(0.0f32, f32::max)
+            * roll_chord;
+
+        let error = (shift * shift + turn * turn).sqrt();
+        max_px = max_px.max(error);
+        sum_sq += error * error;
+        count += 1;
+    }
+
+    if count == 0 {
+        return Misalignment::default();
+    }
+    Misalignment {
+        max_px,
+        rms_px: (sum_sq / count as f32).sqrt(),
+    }
+}
@@ -0,0 +1,87 @@
+//! Solve score and 1–3 star rating.
+//!
+//! A solve is worth `BASE_POINTS`, scaled up to double for the hardest
+//! levels, then cut down by two factors:
+//!
+//! * **Time** — full marks up to the level's par time (`par_secs`, longer for
+//!   harder levels and for every extra stage), then in proportion:
+//!   twice par keeps half.
+//! * **Hints** — every hint tier reached costs a fixed fraction
+//!   (`TIER_COST`), larger for the tiers that give more away.  Costs add
+//!   up, so climbing to the ghost snap pays for every tier on the way.
+//!
+//! Stars come from the product of the two factors alone, so a perfect
+//! solve earns three stars on any level.  A ghost snap does most of the
+//! work for the player and caps the rating at one star.
+
+use super::hints::HintTier;
+
+/// Points for a perfect solve of a difficulty-0 level.
+pub const BASE_POINTS: f32 = 1000.0;
+
+/// Par time of a single stage at difficulty 0, and the extra at difficulty 1.
+pub const PAR_BASE_SECS: f32 = 45.0;
+pub const PAR_DIFFICULTY_SECS: f32 = 135.0;
+
+/// Score fraction lost each time a tier is reached, indexed by `HintTier`.
+pub const TIER_COST: [f32; HintTier::COUNT] = [0.0, 0.05, 0.10, 0.15, 0.40];
+
+/// Hints never take more than this fraction of the score.
+const MAX_HINT_PENALTY: f32 = 0.9;
+
+/// Fraction of a perfect score needed for three and for two stars.
+const THREE_STARS: f32 = 0.8;
+const TWO_STARS: f32 = 0.45;
+
+/// Result of a solve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Score {
+    pub points: u32,
+    /// 1–3.
+    pub stars: u8,
+}
+
+/// Par time for a puzzle of `stages` stages at `difficulty` ∈ [0,1].
+pub fn par_secs(difficulty: f32, stages: usize) -> f32 {
+    let per_stage = PAR_BASE_SECS + PAR_DIFFICULTY_SECS * difficulty.clamp(0.0, 1.0);
+    per_stage * stages.max(1) as f32
+}
+
+/// Score a solve taking `time_secs`, with `tier_uses[t]` the number of times
+/// hint tier `t` was reached (`HintSystem::tier_uses`).
+pub fn score(
+    time_secs: f32,
+    tier_uses: &[u32; HintTier::COUNT],
+    difficulty: f32,
+    stages: usize,
+) -> Score {
+    let difficulty = difficulty.clamp(0.0, 1.0);
+
+    let par = par_secs(difficulty, stages);
+    let time_factor = if time_secs.is_finite() && time_secs > par {
+        par / time_secs
+    } else {
+        1.0
+    };
+
+    let penalty: f32 = tier_uses
+        .iter()
+        .zip(TIER_COST)
+        .map(|(&uses, cost)| uses as f32 * cost)
+        .sum();
+    let hint_factor = 1.0 - penalty.min(MAX_HINT_PENALTY);
+
+    let fraction = time_factor * hint_factor;
+    let points = (BASE_POINTS * (1.0 + difficulty) * fraction).round() as u32;
+
+    let stars = if tier_uses[HintTier::GhostSnap as usize] > 0 {
+        1
+    } else if fraction >= THREE_STARS {
+        3
+    } else if fraction >= TWO_STARS {
+        2
+    } else {
+        1
+    };
+    Score { points, stars }
+}
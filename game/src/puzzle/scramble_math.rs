@@ -1,11 +1,34 @@
-use cgmath::{InnerSpace, Matrix3, Matrix4, SquareMatrix, Vector3};
+use cgmath::{InnerSpace, Matrix3, Matrix4, Quaternion, Rad, Rotation3, SquareMatrix, Vector3};
 
+use super::scrambler::ScrambleTarget;
+use super::seed::PuzzleSeed;
+
+/// Triangle soup of an indexed mesh: each face's three vertices copied out
+/// in index order, 9 floats per face — the layout `Mesh::from_raw` draws.
+/// Every function here that walks positions or offsets 9 floats at a time
+/// expects this layout; the loader's `PreparedMesh::positions` are welded
+/// vertices, shared between faces, and have to go through here first.
+/// A trailing partial face is dropped, as the renderer drops it.
+pub fn unweld(positions: &[f32], indices: &[u32]) -> Vec<f32> {
+    let face_count = indices.len() / 3;
+    let mut soup = Vec::with_capacity(face_count * 9);
+    for &i in &indices[..face_count * 3] {
+        let v = i as usize * 3;
+        soup.extend_from_slice(&positions[v..v + 3]);
+    }
+    soup
+}
+
+/// One random amount along `axis` per triangle of a triangle soup of
+/// `vertex_count` floats (see `unweld`); all three corners of a triangle
+/// share it, so every face moves rigidly.
 pub fn compute_scramble_offsets(
     vertex_count: usize,
     axis: Vector3<f32>,
     min_disp: f32,
     max_disp: f32,
     mesh_transform: Matrix4<f32>,
+    seed: PuzzleSeed,
 ) -> Vec<f32> {
     use rand::Rng;
 
@@ -14,19 +37,8 @@ pub fn compute_scramble_offsets(
     }
 
     let axis = axis.normalize();
-    let basis = Matrix3::new(
-        mesh_transform.x.x,
-        mesh_transform.x.y,
-        mesh_transform.x.z,
-        mesh_transform.y.x,
-        mesh_transform.y.y,
-        mesh_transform.y.z,
-        mesh_transform.z.x,
-        mesh_transform.z.y,
-        mesh_transform.z.z,
-    );
-    let inv_basis = basis.invert().unwrap_or(Matrix3::from_scale(1.0));
-    let mut rng = rand::rng();
+    let inv_basis = inverse_basis(mesh_transform);
+    let mut rng = seed.rng();
     let mut offsets = vec![0.0f32; vertex_count];
 
     let tri_count = vertex_count / 9;
@@ -44,6 +56,203 @@ pub fn compute_scramble_offsets(
     offsets
 }
 
+/// Like `compute_scramble_offsets`, but every triangle of a cluster shares
+/// one displacement so the cluster moves as a rigid unit.  `clusters[t]` is
+/// the dense cluster id of triangle `t`; amounts are drawn in id order, so
+/// identity clusters reproduce `compute_scramble_offsets` exactly.
+pub fn compute_cluster_offsets(
+    vertex_count: usize,
+    clusters: &[usize],
+    axis: Vector3<f32>,
+    min_disp: f32,
+    max_disp: f32,
+    mesh_transform: Matrix4<f32>,
+    seed: PuzzleSeed,
+) -> Vec<f32> {
+    use rand::Rng;
+
+    if vertex_count == 0 {
+        return vec![];
+    }
+
+    let axis = axis.normalize();
+    let inv_basis = inverse_basis(mesh_transform);
+    let mut rng = seed.rng();
+    let cluster_count = clusters.iter().max().map_or(0, |&m| m + 1);
+    let amounts: Vec<f32> = (0..cluster_count)
+        .map(|_| rng.random_range(min_disp..max_disp))
+        .collect();
+
+    let mut offsets = vec![0.0f32; vertex_count];
+    let tri_count = (vertex_count / 9).min(clusters.len());
+    for tri in 0..tri_count {
+        let disp = inv_basis * (axis * amounts[clusters[tri]]);
+        let base = tri * 9;
+        for corner in 0..3 {
+            let v = base + corner * 3;
+            offsets[v] = disp.x;
+            offsets[v + 1] = disp.y;
+            offsets[v + 2] = disp.z;
+        }
+    }
+    offsets
+}
+
+/// Anamorphic counterpart of `compute_cluster_offsets`.  Each cluster is
+/// scaled about `eye` (model space) by `1 + amount / distance`, where
+/// `distance` is from the eye to the cluster's centroid: the cluster slides
+/// `amount` further along the rays from the eye while its projection from
+/// the eye stays exactly where it was.  Any `t` in `lerp_positions` keeps
+/// that property, so the camera-driven un-scramble never shows seams at the
+/// eye either.  Amounts are drawn exactly like `compute_cluster_offsets`.
+pub fn compute_anamorphic_offsets(
+    positions: &[f32],
+    clusters: &[usize],
+    eye: Vector3<f32>,
+    min_disp: f32,
+    max_disp: f32,
+    mesh_transform: Matrix4<f32>,
+    seed: PuzzleSeed,
+) -> Vec<f32> {
+    use rand::Rng;
+
+    if positions.is_empty() {
+        return vec![];
+    }
+
+    let eye_mesh = mesh_transform
+        .invert()
+        .map(|inv| (inv * eye.extend(1.0)).truncate())
+        .unwrap_or(eye);
+    let mut rng = seed.rng();
+    let cluster_count = clusters.iter().max().map_or(0, |&m| m + 1);
+    let amounts: Vec<f32> = (0..cluster_count)
+        .map(|_| rng.random_range(min_disp..max_disp))
+        .collect();
+
+    let tri_count = (positions.len() / 9).min(clusters.len());
+    let growth: Vec<f32> = cluster_centroids(positions, clusters, cluster_count)
+        .iter()
+        .zip(&amounts)
+        .map(|(&centroid, &amount)| {
+            let world = (mesh_transform * centroid.extend(1.0)).truncate();
+            amount / (world - eye).magnitude().max(1e-6)
+        })
+        .collect();
+
+    let mut offsets = vec![0.0f32; positions.len()];
+    for tri in 0..tri_count {
+        let k = growth[clusters[tri]];
+        let base = tri * 9;
+        for corner in 0..3 {
+            let v = base + corner * 3;
+            let p = Vector3::new(positions[v], positions[v + 1], positions[v + 2]);
+            let disp = (p - eye_mesh) * k;
+            offsets[v] = disp.x;
+            offsets[v + 1] = disp.y;
+            offsets[v + 2] = disp.z;
+        }
+    }
+    offsets
+}
+
+/// Rotation part of a rotation scramble: every cluster turns about its own
+/// centroid, around the direction it is viewed along from the solution —
+/// `target`'s axis, or the ray from its eye (both model space).  Angles are
+/// drawn from `[max_roll / 4, max_roll)` with a random sign, one per
+/// cluster.  The offset is the chord to the rotated position, so
+/// `lerp_positions` is exact at `t = 0` and `t = 1` and shrinks a fragment
+/// slightly in between.
+pub fn compute_roll_offsets(
+    positions: &[f32],
+    clusters: &[usize],
+    target: ScrambleTarget,
+    max_roll: f32,
+    mesh_transform: Matrix4<f32>,
+    seed: PuzzleSeed,
+) -> Vec<f32> {
+    use rand::Rng;
+
+    if positions.is_empty() || max_roll <= 0.0 {
+        return vec![0.0; positions.len()];
+    }
+
+    let inv_basis = inverse_basis(mesh_transform);
+    let mut rng = seed.rng();
+    let cluster_count = clusters.iter().max().map_or(0, |&m| m + 1);
+    let centroids = cluster_centroids(positions, clusters, cluster_count);
+    let rotations: Vec<Quaternion<f32>> = centroids
+        .iter()
+        .map(|&centroid| {
+            let angle: f32 = rng.random_range(max_roll * 0.25..max_roll);
+            let sign = if rng.random_bool(0.5) { 1.0 } else { -1.0 };
+            let axis = match target {
+                ScrambleTarget::Axis(axis) => axis,
+                ScrambleTarget::Eye(eye) => {
+                    (mesh_transform * centroid.extend(1.0)).truncate() - eye
+                }
+            };
+            Quaternion::from_axis_angle(axis.normalize(), Rad(angle * sign))
+        })
+        .collect();
+
+    let mut offsets = vec![0.0f32; positions.len()];
+    let tri_count = positions.len() / 9;
+    for (tri, &c) in clusters.iter().enumerate().take(tri_count) {
+        let base = tri * 9;
+        for corner in 0..3 {
+            let v = base + corner * 3;
+            let p = Vector3::new(positions[v], positions[v + 1], positions[v + 2]);
+            let arm = (mesh_transform * (p - centroids[c]).extend(0.0)).truncate();
+            let disp = inv_basis * (rotations[c] * arm - arm);
+            offsets[v] = disp.x;
+            offsets[v + 1] = disp.y;
+            offsets[v + 2] = disp.z;
+        }
+    }
+    offsets
+}
+
+/// Mean triangle centroid of every cluster, in mesh space.
+fn cluster_centroids(
+    positions: &[f32],
+    clusters: &[usize],
+    cluster_count: usize,
+) -> Vec<Vector3<f32>> {
+    let tri_count = (positions.len() / 9).min(clusters.len());
+    let mut sums = vec![(Vector3::new(0.0f32, 0.0, 0.0), 0usize); cluster_count];
+    for tri in 0..tri_count {
+        let b = tri * 9;
+        let c = Vector3::new(
+            positions[b] + positions[b + 3] + positions[b + 6],
+            positions[b + 1] + positions[b + 4] + positions[b + 7],
+            positions[b + 2] + positions[b + 5] + positions[b + 8],
+        ) / 3.0;
+        sums[clusters[tri]].0 += c;
+        sums[clusters[tri]].1 += 1;
+    }
+    sums.into_iter()
+        .map(|(sum, count)| sum / count.max(1) as f32)
+        .collect()
+}
+
+/// Inverse of the mesh transform's linear part — maps a world-space
+/// displacement back into mesh space.
+fn inverse_basis(mesh_transform: Matrix4<f32>) -> Matrix3<f32> {
+    let basis = Matrix3::new(
+        mesh_transform.x.x,
+        mesh_transform.x.y,
+        mesh_transform.x.z,
+        mesh_transform.y.x,
+        mesh_transform.y.y,
+        mesh_transform.y.z,
+        mesh_transform.z.x,
+        mesh_transform.z.y,
+        mesh_transform.z.z,
+    );
+    basis.invert().unwrap_or(Matrix3::from_scale(1.0))
+}
+
 pub fn lerp_positions(positions: &[f32], offsets: &[f32], t: f32) -> Vec<f32> {
     let n = positions.len().min(offsets.len());
     let mut data = vec![0.0f32; n];
@@ -4,10 +4,47 @@
 //! along that axis by a random amount derived from the model's own geometry.
 //! The camera starts at a random position far from that axis.
 //!
+//! Every random draw comes from a `PuzzleSeed`, so the same seed always
+//! produces the same axis, offsets and camera start.
+//!
+//! The `ScrambleStrategy` in `PuzzleParams` decides what moves as one piece:
+//! single triangles, connected patches (see `clusters`), or whole meshes.
+//! A cluster always shares one displacement and one stage.
+//!
+//! With `ScrambleProjection::Anamorphic` the fragments slide along the rays
+//! from an eye point on the orbit sphere instead of along one axis, so the
+//! solved view has no parallax seams under the perspective camera.  The
+//! puzzle is then solved by reaching that point, not just a direction.
+//!
+//! A rotation scramble (`PuzzleParams::max_roll` > 0) also turns every
+//! fragment about the view axis through its own centroid, and gives each
+//! stage a solution roll: the model only reads correctly once the camera's
+//! roll matches as well as its direction (see `roll_alignment`).
+//!
+//! An upright level (`PuzzleParams::upright`) also needs the camera's roll
+//! within `UPRIGHT_TOLERANCE_RAD` of upright relative to the model's +Y
+//! axis, so the pole-flipped and upside-down solutions stop counting (see
+//! `ScrambleState::is_upright`).
+//!
+//! In multi-stage mode the triangles are split into spatial bands, each with
+//! its own axis.  Finding a stage's view snaps its band into place and the
+//! player moves on to the next; only the last stage completes the puzzle.
+//! A composite scene (see `level::scene`) splits by model instead, so each
+//! model is a stage of its own (`StageSplit`).
+//!
+//! A skinned model is scrambled in an animation pose rather than its bind
+//! pose (see `pose`); `upload_stage` turns its offsets into bind-pose ones
+//! for the shader to skin.
+//!
+//! The displacement itself runs on the GPU: `upload_stage` sends the current
+//! stage's offsets once as a vertex attribute, and `apply_displacement` only
+//! sets the `uScrambleT` uniform, so the camera-driven un-scramble, ghost
+//! snap and solve restore cost nothing per vertex.
+//!
 //! All parameters (displacement range, orbit distance) are computed from the
 //! model's bounding geometry so the puzzle scales correctly to any model size.
 
-use cgmath::{InnerSpace, Matrix4, Vector3};
+use cgmath::{InnerSpace, Matrix4, Quaternion, Rad, Rotation3, Vector3};
 use rand::Rng;
 use std::cell::RefCell;
 use std::f32::consts::PI;
@@ -17,18 +54,182 @@ use formosaic_engine::architecture::models::model::Model;
 use formosaic_engine::architecture::models::simple_model::SimpleModel;
 use formosaic_engine::rendering::instances::camera::orbit_controller::OrbitController;
 
-use super::puzzle_params::PuzzleParams;
-use super::scramble_math::compute_scramble_offsets;
+use super::clusters::{cluster_count, triangle_clusters};
+use super::pose::ModelPose;
+use super::puzzle_params::{PuzzleParams, ScrambleProjection, ScrambleStrategy};
+use super::reprojection::{self, Misalignment, ScreenView, SeamSamples};
+use super::scramble_math::{
+    compute_anamorphic_offsets, compute_cluster_offsets, compute_roll_offsets,
+};
+use super::seed::{PuzzleSeed, SeedStream};
 
-pub struct ScrambleState {
-    /// The camera must look along this direction (or its opposite) to solve.
+/// Largest camera roll (radians) away from upright that still counts as
+/// upright on an upright level.
+pub const UPRIGHT_TOLERANCE_RAD: f32 = 30.0 * PI / 180.0;
+
+/// What one stage's offsets line up with, in model space.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScrambleTarget {
+    /// Parallel scramble along an axis: solved from either pole, at any
+    /// distance.
+    Axis(Vector3<f32>),
+    /// Anamorphic scramble along the rays from an eye point: solved from
+    /// that point only.
+    Eye(Vector3<f32>),
+}
+
+/// One axis of a scramble.  Single-stage puzzles have exactly one.
+pub struct ScrambleStage {
+    /// The camera must look along this direction (or its opposite) to solve
+    /// this stage.  For an anamorphic stage this points from the eye to the
+    /// orbit target.
     pub solution_dir: Vector3<f32>,
+    /// World-space eye point of an anamorphic stage; `None` for a parallel
+    /// one.
+    pub solution_eye: Option<Vector3<f32>>,
+    /// World-space camera up vector of the solved view for a rotation
+    /// scramble (perpendicular to `solution_dir`); `None` when any roll
+    /// solves.
+    pub solution_up: Option<Vector3<f32>>,
+    /// Per-vertex scramble offsets per mesh.  Zero for triangles that belong
+    /// to other stages.
+    pub scramble_offsets: Vec<Vec<f32>>,
+}
+
+pub struct ScrambleState {
+    pub stages: Vec<ScrambleStage>,
+    /// Index of the stage being solved.  Earlier stages are at rest.
+    pub current_stage: usize,
     /// Puzzle parameters computed from this model's geometry.
     pub params: PuzzleParams,
-    /// Original vertex positions per mesh (before displacement).
+    /// Original vertex positions per mesh (before displacement).  A posed
+    /// model's are its posed positions.
     pub original_positions: Vec<Vec<f32>>,
-    /// Per-vertex scramble offsets per mesh.
-    pub scramble_offsets: Vec<Vec<f32>>,
+    /// Seed the offsets (and camera start) were drawn from.
+    pub seed: PuzzleSeed,
+    /// The pose a skinned model was scrambled in (see `pose`); `None` when
+    /// positions and offsets go to the GPU as they are.
+    pub pose: Option<ModelPose>,
+}
+
+impl ScrambleState {
+    pub fn current(&self) -> &ScrambleStage {
+        &self.stages[self.current_stage.min(self.stages.len() - 1)]
+    }
+
+    /// Solution direction of the stage being solved.
+    pub fn solution_dir(&self) -> Vector3<f32> {
+        self.current().solution_dir
+    }
+
+    /// Eye point of the stage being solved, if it is anamorphic.
+    pub fn solution_eye(&self) -> Option<Vector3<f32>> {
+        self.current().solution_eye
+    }
+
+    /// Solved-view camera up of the stage being solved, if roll matters.
+    pub fn solution_up(&self) -> Option<Vector3<f32>> {
+        self.current().solution_up
+    }
+
+    /// How well the camera lines up with the current stage, as a cosine:
+    /// 1 = solved view.  Parallel stages compare the view direction with the
+    /// axis (either pole); anamorphic stages use `point_alignment`.  A stage
+    /// with a solution roll reports the worse of that and `roll_alignment`.
+    pub fn alignment(
+        &self,
+        camera_pos: Vector3<f32>,
+        camera_fwd: Vector3<f32>,
+        camera_up: Vector3<f32>,
+        target: Vector3<f32>,
+    ) -> f32 {
+        let view = match self.solution_eye() {
+            Some(eye) => point_alignment(camera_pos, eye, target),
+            None => camera_fwd
+                .normalize()
+                .dot(self.solution_dir().normalize())
+                .abs(),
+        };
+        match self.solution_up() {
+            Some(up) => view.min(roll_alignment(camera_fwd, camera_up, up)),
+            None => view,
+        }
+    }
+
+    /// Whether the camera is upright enough for this level: always true
+    /// unless `params.upright` is set, in which case its roll relative to
+    /// the model's up axis has to be within `UPRIGHT_TOLERANCE_RAD`.
+    pub fn is_upright(&self, camera_fwd: Vector3<f32>, camera_up: Vector3<f32>) -> bool {
+        self.upright_roll(camera_fwd, camera_up)
+            .is_none_or(|roll| roll.abs() <= UPRIGHT_TOLERANCE_RAD)
+    }
+
+    /// Camera roll away from upright (`camera_roll`) on an upright level;
+    /// `None` when roll does not matter for the solve.
+    pub fn upright_roll(&self, camera_fwd: Vector3<f32>, camera_up: Vector3<f32>) -> Option<f32> {
+        self.params
+            .upright
+            .then(|| camera_roll(camera_fwd, camera_up))
+    }
+
+    /// Triangles the current stage's misalignment is measured on; build
+    /// once per stage and pass to `misalignment`.
+    pub fn seam_samples(&self) -> SeamSamples {
+        SeamSamples::of_stage(self.current(), &self.original_positions)
+    }
+
+    /// Screen-space misalignment of the stage being solved — the solve
+    /// criterion (see `reprojection`).  `samples` come from `seam_samples`
+    /// for this stage.
+    pub fn misalignment(
+        &self,
+        samples: &SeamSamples,
+        model_matrices: &[Matrix4<f32>],
+        view: &ScreenView,
+    ) -> Misalignment {
+        reprojection::sampled_misalignment(
+            self.current(),
+            samples,
+            &self.original_positions,
+            model_matrices,
+            view,
+        )
+    }
+
+    pub fn stage_count(&self) -> usize {
+        self.stages.len()
+    }
+
+    pub fn is_final_stage(&self) -> bool {
+        self.current_stage + 1 >= self.stages.len()
+    }
+
+    /// Lock the current stage in place and move on to the next one.  Returns
+    /// `false` and does nothing on the final stage — that is a full solve.
+    pub fn advance_stage(&mut self) -> bool {
+        if self.is_final_stage() {
+            return false;
+        }
+        self.current_stage += 1;
+        true
+    }
+
+    /// Vertex positions for one mesh: solved stages at rest, the current
+    /// stage at scramble `t`, later stages fully scrambled.
+    pub fn displaced_positions(&self, mesh_idx: usize, t: f32) -> Vec<f32> {
+        let mut positions = self.original_positions[mesh_idx].clone();
+        for (k, stage) in self.stages.iter().enumerate() {
+            let w = match k.cmp(&self.current_stage) {
+                std::cmp::Ordering::Less => continue,
+                std::cmp::Ordering::Equal => t,
+                std::cmp::Ordering::Greater => 1.0,
+            };
+            for (p, o) in positions.iter_mut().zip(&stage.scramble_offsets[mesh_idx]) {
+                *p += o * w;
+            }
+        }
+        positions
+    }
 }
 
 /// Analyse the model, choose puzzle parameters, scramble, and return state.
@@ -37,6 +238,7 @@ pub fn scramble(
     model: &Rc<RefCell<SimpleModel>>,
     target_world_radius: f32,
     fov_radians: f32,
+    seed: PuzzleSeed,
 ) -> ScrambleState {
     let params = PuzzleParams::from_model(&model.borrow(), target_world_radius, fov_radians);
 
@@ -51,17 +253,18 @@ pub fn scramble(
     );
 
     const MAX_ELEV: f32 = 55.0 * PI / 180.0;
-    let mut rng = rand::rng();
+    let mut rng = seed.stream(SeedStream::Axis).rng();
     let theta: f32 = rng.random_range(0.0..2.0 * PI);
     let phi: f32 = rng.random_range(-MAX_ELEV..MAX_ELEV);
     let solution_dir =
         Vector3::new(phi.cos() * theta.cos(), phi.sin(), phi.cos() * theta.sin()).normalize();
 
     log::info!(
-        "[Scrambler] solution direction: ({:.3}, {:.3}, {:.3})",
+        "[Scrambler] solution direction: ({:.3}, {:.3}, {:.3})  seed={}",
         solution_dir.x,
         solution_dir.y,
-        solution_dir.z
+        solution_dir.z,
+        seed
     );
 
     let original_positions: Vec<Vec<f32>> = model
@@ -70,66 +273,415 @@ pub fn scramble(
         .iter()
         .map(|m| m.positions().to_vec())
         .collect();
-    let scramble_offsets = compute_model_offsets(
-        &model.borrow(),
-        solution_dir,
-        params.min_disp,
-        params.max_disp,
-    );
+    let scramble_offsets = compute_model_offsets(&model.borrow(), solution_dir, &params, seed);
 
-    for (mesh_idx, offsets) in scramble_offsets.iter().enumerate() {
-        let orig = &original_positions[mesh_idx];
-        let displaced: Vec<f32> = orig
-            .iter()
-            .zip(offsets.iter())
-            .map(|(p, o)| p + o)
-            .collect();
-        model.borrow_mut().upload_mesh_positions(mesh_idx, displaced);
-    }
-
-    ScrambleState {
-        solution_dir,
+    let state = ScrambleState {
+        stages: vec![ScrambleStage {
+            solution_dir,
+            solution_eye: None,
+            solution_up: None,
+            scramble_offsets,
+        }],
+        current_stage: 0,
         params,
         original_positions,
-        scramble_offsets,
-    }
+        seed,
+        pose: None,
+    };
+    upload_stage(model, &state);
+    apply_displacement(model, 1.0);
+    state
 }
 
-/// Compute per-mesh scramble offsets for a model without touching GPU state.
-/// Apply displacement to a model: positions = original + offset * t for each vertex.
-/// `t = 0.0` = solved (original positions), `t = 1.0` = fully scrambled.
-pub fn apply_displacement(
-    model: &Rc<RefCell<SimpleModel>>,
-    state: &ScrambleState,
-    t: f32,
-) {
-    for (mesh_idx, offsets) in state.scramble_offsets.iter().enumerate() {
-        let orig = &state.original_positions[mesh_idx];
-        let displaced: Vec<f32> = orig
-            .iter()
-            .zip(offsets.iter())
-            .map(|(p, o)| p + o * t)
-            .collect();
-        model.borrow_mut().upload_mesh_positions(mesh_idx, displaced);
+/// Upload the current stage to the GPU: positions with every later stage
+/// fully displaced (earlier stages at rest), plus the current stage's
+/// offsets for the vertex shader to lerp along.  Together they draw
+/// `ScrambleState::displaced_positions` for any `t`, through the skin of a
+/// posed model held in its pose.  Call when a scramble starts and whenever
+/// the stage changes.
+pub fn upload_stage(model: &Rc<RefCell<SimpleModel>>, state: &ScrambleState) {
+    let mut model = model.borrow_mut();
+    for mesh_idx in 0..state.original_positions.len() {
+        let positions = state.displaced_positions(mesh_idx, 0.0);
+        let offsets = &state.current().scramble_offsets[mesh_idx];
+        match &state.pose {
+            Some(pose) => {
+                model.upload_mesh_positions(mesh_idx, pose.bind_positions(mesh_idx, &positions));
+                model.upload_scramble_offsets(mesh_idx, pose.bind_offsets(mesh_idx, offsets));
+            }
+            None => {
+                model.upload_mesh_positions(mesh_idx, positions);
+                model.upload_scramble_offsets(mesh_idx, offsets.clone());
+            }
+        }
     }
 }
 
+/// Set how far the current stage is displaced: `t = 0.0` = solved,
+/// `t = 1.0` = fully scrambled.  Only sets the `uScrambleT` uniform; the
+/// stage must have been uploaded with `upload_stage`.
+pub fn apply_displacement(model: &Rc<RefCell<SimpleModel>>, t: f32) {
+    model.borrow_mut().set_scramble_t(t);
+}
+
 pub fn compute_model_offsets(
     model: &SimpleModel,
     axis: Vector3<f32>,
+    params: &PuzzleParams,
+    seed: PuzzleSeed,
+) -> Vec<Vec<f32>> {
+    let mesh_positions: Vec<&[f32]> = model.get_meshes().iter().map(|m| m.positions()).collect();
+    let mesh_transforms: Vec<Matrix4<f32>> = (0..mesh_positions.len())
+        .map(|i| {
+            model
+                .mesh_transform(i)
+                .unwrap_or_else(|| Matrix4::from_scale(1.0))
+        })
+        .collect();
+    compute_raw_offsets(
+        &mesh_positions,
+        &mesh_transforms,
+        axis,
+        params.min_disp,
+        params.max_disp,
+        params.strategy,
+        seed,
+    )
+}
+
+/// Per-mesh scramble offsets from raw position data — no `SimpleModel` or GPU
+/// context required.  Each mesh draws from its own sub-seed so adding or
+/// removing a mesh never shifts the offsets of the others.
+pub fn compute_raw_offsets(
+    mesh_positions: &[&[f32]],
+    mesh_transforms: &[Matrix4<f32>],
+    axis: Vector3<f32>,
+    min_disp: f32,
+    max_disp: f32,
+    strategy: ScrambleStrategy,
+    seed: PuzzleSeed,
+) -> Vec<Vec<f32>> {
+    let disp_seed = seed.stream(SeedStream::Displacement);
+    mesh_positions
+        .iter()
+        .enumerate()
+        .map(|(mesh_idx, pos)| {
+            let transform = mesh_transforms
+                .get(mesh_idx)
+                .copied()
+                .unwrap_or_else(|| Matrix4::from_scale(1.0));
+            compute_cluster_offsets(
+                pos.len(),
+                &triangle_clusters(pos, strategy),
+                axis,
+                min_disp,
+                max_disp,
+                transform,
+                disp_seed.derive(mesh_idx as u64),
+            )
+        })
+        .collect()
+}
+
+/// Anamorphic counterpart of `compute_raw_offsets`: fragments slide along the
+/// rays from `eye` (model space).  Uses the same per-mesh sub-seeds.
+pub fn compute_raw_anamorphic_offsets(
+    mesh_positions: &[&[f32]],
+    mesh_transforms: &[Matrix4<f32>],
+    eye: Vector3<f32>,
     min_disp: f32,
     max_disp: f32,
+    strategy: ScrambleStrategy,
+    seed: PuzzleSeed,
 ) -> Vec<Vec<f32>> {
-    let mut per_mesh = Vec::new();
-    for (mesh_idx, mesh) in model.get_meshes().iter().enumerate() {
-        let transform = model
-            .mesh_transform(mesh_idx)
+    let disp_seed = seed.stream(SeedStream::Displacement);
+    mesh_positions
+        .iter()
+        .enumerate()
+        .map(|(mesh_idx, pos)| {
+            let transform = mesh_transforms
+                .get(mesh_idx)
+                .copied()
+                .unwrap_or_else(|| Matrix4::from_scale(1.0));
+            compute_anamorphic_offsets(
+                pos,
+                &triangle_clusters(pos, strategy),
+                eye,
+                min_disp,
+                max_disp,
+                transform,
+                disp_seed.derive(mesh_idx as u64),
+            )
+        })
+        .collect()
+}
+
+/// Rotation part of a rotation scramble per mesh (see
+/// `compute_roll_offsets`), drawn from the `Roll` stream with the same
+/// per-mesh sub-seeds as `compute_raw_offsets`.
+pub fn compute_raw_roll_offsets(
+    mesh_positions: &[&[f32]],
+    mesh_transforms: &[Matrix4<f32>],
+    target: ScrambleTarget,
+    max_roll: f32,
+    strategy: ScrambleStrategy,
+    seed: PuzzleSeed,
+) -> Vec<Vec<f32>> {
+    let roll_seed = seed.stream(SeedStream::Roll);
+    mesh_positions
+        .iter()
+        .enumerate()
+        .map(|(mesh_idx, pos)| {
+            let transform = mesh_transforms
+                .get(mesh_idx)
+                .copied()
+                .unwrap_or_else(|| Matrix4::from_scale(1.0));
+            compute_roll_offsets(
+                pos,
+                &triangle_clusters(pos, strategy),
+                target,
+                max_roll,
+                transform,
+                roll_seed.derive(mesh_idx as u64),
+            )
+        })
+        .collect()
+}
+
+/// Eye point (model space) for an anamorphic stage whose entropy-searched
+/// axis is `axis`: on the orbit sphere around `center`, so the orbit camera
+/// can reach it exactly.
+pub fn anamorphic_eye(
+    center: Vector3<f32>,
+    axis: Vector3<f32>,
+    params: &PuzzleParams,
+) -> Vector3<f32> {
+    center + axis.normalize() * params.eye_distance()
+}
+
+/// Split the triangles into `stage_count` spatial bands.  Returns the stage
+/// index of every triangle, per mesh.
+///
+/// Clusters (per `strategy`) are ordered by the projection of their
+/// (mesh-transformed) centroid onto a seeded direction and cut into
+/// equal-sized bands, so each stage is a coherent slab of the model rather
+/// than scattered noise, and a cluster never straddles two stages.  With
+/// fewer clusters than stages the trailing stages are empty.
+pub fn assign_stage_groups(
+    mesh_positions: &[&[f32]],
+    mesh_transforms: &[Matrix4<f32>],
+    stage_count: usize,
+    strategy: ScrambleStrategy,
+    seed: PuzzleSeed,
+) -> Vec<Vec<usize>> {
+    let mut groups: Vec<Vec<usize>> = mesh_positions
+        .iter()
+        .map(|pos| vec![0; pos.len() / 9])
+        .collect();
+    if stage_count <= 1 {
+        return groups;
+    }
+
+    let mut rng = seed.stream(SeedStream::Groups).rng();
+    let theta: f32 = rng.random_range(0.0..2.0 * PI);
+    let y: f32 = rng.random_range(-1.0..1.0);
+    let r = (1.0 - y * y).sqrt();
+    let split_dir = Vector3::new(r * theta.cos(), y, r * theta.sin());
+
+    let mesh_clusters: Vec<Vec<usize>> = mesh_positions
+        .iter()
+        .map(|pos| triangle_clusters(pos, strategy))
+        .collect();
+
+    // (projected centroid, mesh, cluster) for every cluster of every mesh.
+    let mut keyed: Vec<(f32, usize, usize)> = Vec::new();
+    for (mesh_idx, (pos, clusters)) in mesh_positions.iter().zip(&mesh_clusters).enumerate() {
+        let transform = mesh_transforms
+            .get(mesh_idx)
+            .copied()
             .unwrap_or_else(|| Matrix4::from_scale(1.0));
-        let offsets =
-            compute_scramble_offsets(mesh.positions().len(), axis, min_disp, max_disp, transform);
-        per_mesh.push(offsets);
+        let mut sums = vec![(Vector3::new(0.0f32, 0.0, 0.0), 0usize); cluster_count(clusters)];
+        for (tri, &c) in clusters.iter().enumerate() {
+            let b = tri * 9;
+            sums[c].0 += Vector3::new(
+                (pos[b] + pos[b + 3] + pos[b + 6]) / 3.0,
+                (pos[b + 1] + pos[b + 4] + pos[b + 7]) / 3.0,
+                (pos[b + 2] + pos[b + 5] + pos[b + 8]) / 3.0,
+            );
+            sums[c].1 += 1;
+        }
+        for (c, (sum, count)) in sums.into_iter().enumerate() {
+            let w = transform * (sum / count as f32).extend(1.0);
+            keyed.push((w.truncate().dot(split_dir), mesh_idx, c));
+        }
+    }
+    keyed.sort_by(|a, b| a.0.total_cmp(&b.0));
+
+    let n = keyed.len();
+    let mut cluster_stage: Vec<Vec<usize>> = mesh_clusters
+        .iter()
+        .map(|c| vec![0; cluster_count(c)])
+        .collect();
+    for (rank, &(_, mesh_idx, c)) in keyed.iter().enumerate() {
+        cluster_stage[mesh_idx][c] = rank * stage_count / n;
+    }
+    for (mesh_idx, clusters) in mesh_clusters.iter().enumerate() {
+        for (tri, &c) in clusters.iter().enumerate() {
+            groups[mesh_idx][tri] = cluster_stage[mesh_idx][c];
+        }
+    }
+    groups
+}
+
+/// How a puzzle's triangles are split between its stages.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StageSplit {
+    /// This many seeded spatial bands (`assign_stage_groups`).
+    Bands(usize),
+    /// One stage per model of a composite scene: the model (part) index of
+    /// every mesh.
+    Parts(Vec<usize>),
+}
+
+impl StageSplit {
+    /// Number of stages the split makes.
+    pub fn stage_count(&self) -> usize {
+        match self {
+            StageSplit::Bands(n) => (*n).max(1),
+            StageSplit::Parts(mesh_parts) => mesh_parts.iter().max().map_or(1, |&p| p + 1),
+        }
+    }
+
+    /// Stage index of every triangle, per mesh, as `assign_stage_groups`.
+    pub fn groups(
+        &self,
+        mesh_positions: &[&[f32]],
+        mesh_transforms: &[Matrix4<f32>],
+        strategy: ScrambleStrategy,
+        seed: PuzzleSeed,
+    ) -> Vec<Vec<usize>> {
+        match self {
+            StageSplit::Bands(n) => {
+                assign_stage_groups(mesh_positions, mesh_transforms, *n, strategy, seed)
+            }
+            StageSplit::Parts(mesh_parts) => mesh_positions
+                .iter()
+                .enumerate()
+                .map(|(i, pos)| vec![mesh_parts.get(i).copied().unwrap_or(0); pos.len() / 9])
+                .collect(),
+        }
     }
-    per_mesh
+}
+
+/// Per-stage, per-mesh offsets.  Stage `k` displaces only its own triangles,
+/// towards `targets[k]`, with amounts drawn from `seed.stage(k)` — so stage 0
+/// of a one-stage puzzle matches `compute_raw_offsets` (or
+/// `compute_raw_anamorphic_offsets`) exactly.  With `params.max_roll` > 0
+/// the `compute_raw_roll_offsets` rotation is added on top.
+pub fn compute_staged_offsets(
+    mesh_positions: &[&[f32]],
+    mesh_transforms: &[Matrix4<f32>],
+    targets: &[ScrambleTarget],
+    groups: &[Vec<usize>],
+    params: &PuzzleParams,
+    seed: PuzzleSeed,
+) -> Vec<Vec<Vec<f32>>> {
+    targets
+        .iter()
+        .enumerate()
+        .map(|(k, &target)| {
+            let mut offsets = match target {
+                ScrambleTarget::Axis(axis) => compute_raw_offsets(
+                    mesh_positions,
+                    mesh_transforms,
+                    axis,
+                    params.min_disp,
+                    params.max_disp,
+                    params.strategy,
+                    seed.stage(k),
+                ),
+                ScrambleTarget::Eye(eye) => compute_raw_anamorphic_offsets(
+                    mesh_positions,
+                    mesh_transforms,
+                    eye,
+                    params.min_disp,
+                    params.max_disp,
+                    params.strategy,
+                    seed.stage(k),
+                ),
+            };
+            if params.max_roll > 0.0 {
+                let roll = compute_raw_roll_offsets(
+                    mesh_positions,
+                    mesh_transforms,
+                    target,
+                    params.max_roll,
+                    params.strategy,
+                    seed.stage(k),
+                );
+                for (mesh_offsets, mesh_roll) in offsets.iter_mut().zip(roll) {
+                    for (o, r) in mesh_offsets.iter_mut().zip(mesh_roll) {
+                        *o += r;
+                    }
+                }
+            }
+            if targets.len() > 1 {
+                for (mesh_offsets, mesh_groups) in offsets.iter_mut().zip(groups) {
+                    for (tri, &g) in mesh_groups.iter().enumerate() {
+                        if g != k {
+                            mesh_offsets[tri * 9..tri * 9 + 9].fill(0.0);
+                        }
+                    }
+                }
+            }
+            offsets
+        })
+        .collect()
+}
+
+/// `split` groups + `compute_staged_offsets` for a built model's
+/// `positions` (its own, or its posed ones), one stage per axis.
+/// Anamorphic params turn each axis into an eye via `anamorphic_eye`
+/// around the model's visual centre.  Returns `(groups, per-stage
+/// offsets)`.
+pub fn compute_model_stages(
+    model: &SimpleModel,
+    positions: &[Vec<f32>],
+    axes: &[Vector3<f32>],
+    split: &StageSplit,
+    params: &PuzzleParams,
+    seed: PuzzleSeed,
+) -> (Vec<Vec<usize>>, Vec<Vec<Vec<f32>>>) {
+    let mesh_positions: Vec<&[f32]> = positions.iter().map(Vec::as_slice).collect();
+    let mesh_transforms: Vec<Matrix4<f32>> = (0..mesh_positions.len())
+        .map(|i| {
+            model
+                .mesh_transform(i)
+                .unwrap_or_else(|| Matrix4::from_scale(1.0))
+        })
+        .collect();
+    let groups = split.groups(&mesh_positions, &mesh_transforms, params.strategy, seed);
+    let center = model
+        .visual_center()
+        .unwrap_or_else(|| Vector3::new(0.0, 0.0, 0.0));
+    let targets: Vec<ScrambleTarget> = axes
+        .iter()
+        .map(|&axis| match params.projection {
+            ScrambleProjection::Parallel => ScrambleTarget::Axis(axis),
+            ScrambleProjection::Anamorphic => {
+                ScrambleTarget::Eye(anamorphic_eye(center, axis, params))
+            }
+        })
+        .collect();
+    let offsets = compute_staged_offsets(
+        &mesh_positions,
+        &mesh_transforms,
+        &targets,
+        &groups,
+        params,
+        seed,
+    );
+    (groups, offsets)
 }
 
 /// Build an OrbitController starting ≥60° from the solution axis.
@@ -138,20 +690,46 @@ pub fn make_scrambled_orbit(
     target: Vector3<f32>,
     distance: f32,
     solution_dir: Vector3<f32>,
+    seed: PuzzleSeed,
 ) -> (OrbitController, Vector3<f32>) {
-    let mut rng = rand::rng();
+    // Must be ≥60° from solution (neither pole of the solution axis).
+    scrambled_orbit(target, distance, seed, |dir| {
+        dir.dot(solution_dir).abs() < (PI / 3.0_f32).cos()
+    })
+}
 
-    // Camera start: sample from a comfortable elevation band (±70°) and at
-    // least 60° away from the solution direction so the puzzle is non-trivial.
+/// `make_scrambled_orbit` for an anamorphic stage: the camera starts ≥60°
+/// around the orbit from the eye point.  Only the eye itself solves the
+/// puzzle, so the opposite pole is an acceptable start.
+pub fn make_scrambled_orbit_from_eye(
+    target: Vector3<f32>,
+    distance: f32,
+    eye: Vector3<f32>,
+    seed: PuzzleSeed,
+) -> (OrbitController, Vector3<f32>) {
+    let eye_dir = (eye - target).normalize();
+    scrambled_orbit(target, distance, seed, |dir| {
+        dir.dot(eye_dir) < (PI / 3.0_f32).cos()
+    })
+}
+
+fn scrambled_orbit(
+    target: Vector3<f32>,
+    distance: f32,
+    seed: PuzzleSeed,
+    accept: impl Fn(Vector3<f32>) -> bool,
+) -> (OrbitController, Vector3<f32>) {
+    let mut rng = seed.stream(SeedStream::Camera).rng();
+
+    // Camera start: sample from a comfortable elevation band (±70°) and far
+    // enough from the solution that the puzzle is non-trivial.
     const CAM_MAX_ELEV: f32 = 70.0 * PI / 180.0;
     let start_dir = loop {
         let theta: f32 = rng.random_range(0.0..2.0 * PI);
         let phi: f32 = rng.random_range(-CAM_MAX_ELEV..CAM_MAX_ELEV);
         let candidate =
             Vector3::new(phi.cos() * theta.cos(), phi.sin(), phi.cos() * theta.sin()).normalize();
-
-        // Must be ≥60° from solution (neither pole of the solution axis).
-        if candidate.dot(solution_dir).abs() < (PI / 3.0_f32).cos() {
+        if accept(candidate) {
             break candidate;
         }
     };
@@ -161,3 +739,72 @@ pub fn make_scrambled_orbit(
     ctrl.set_initial_position(camera_pos);
     (ctrl, camera_pos)
 }
+
+/// Alignment of a camera at `camera_pos` with an anamorphic `eye`, on the
+/// same cosine scale as a parallel stage's `|fwd · axis|`.  The distance to
+/// the eye is read as a chord of the orbit sphere through the eye, so on the
+/// orbit this is exactly the cosine of the angle between the camera and eye
+/// directions — and the parallel snap threshold means the same in both modes.
+pub fn point_alignment(camera_pos: Vector3<f32>, eye: Vector3<f32>, target: Vector3<f32>) -> f32 {
+    let radius = (eye - target).magnitude().max(1e-6);
+    let half_chord = ((camera_pos - eye).magnitude() / (2.0 * radius)).min(1.0);
+    // cos(2·asin(x)) = 1 − 2x²
+    1.0 - 2.0 * half_chord * half_chord
+}
+
+/// Solved-view camera up for a rotation-scramble stage looking along
+/// `solution_dir`: the upright up vector turned about the view axis by a
+/// seeded roll of 30°–180° either way, so the player always has to twist.
+pub fn solution_roll_up(solution_dir: Vector3<f32>, seed: PuzzleSeed) -> Vector3<f32> {
+    const MIN_SOLUTION_ROLL: f32 = PI / 6.0;
+    let mut rng = seed.stream(SeedStream::Roll).rng();
+    let angle: f32 = rng.random_range(MIN_SOLUTION_ROLL..PI);
+    let sign = if rng.random_bool(0.5) { 1.0 } else { -1.0 };
+    let fwd = solution_dir.normalize();
+    Quaternion::from_axis_angle(fwd, Rad(-angle * sign)) * upright_up(fwd)
+}
+
+/// Camera up vector of an upright camera looking along `solution_dir`:
+/// world +Y with its component along the view removed.  Upright levels use
+/// it as the solved-view up of a rotation scramble.
+pub fn upright_up(solution_dir: Vector3<f32>) -> Vector3<f32> {
+    let fwd = solution_dir.normalize();
+    let upright = Vector3::unit_y() - fwd * fwd.y;
+    if upright.magnitude2() > 1e-6 {
+        upright.normalize()
+    } else {
+        Vector3::unit_x()
+    }
+}
+
+/// Roll alignment of a camera with a stage's `solution_up`, as a cosine:
+/// 1 = matching roll, -1 = upside down.  Only the components perpendicular
+/// to the view axis count, so it is independent of the view direction.
+pub fn roll_alignment(
+    camera_fwd: Vector3<f32>,
+    camera_up: Vector3<f32>,
+    solution_up: Vector3<f32>,
+) -> f32 {
+    let fwd = camera_fwd.normalize();
+    let flat = |v: Vector3<f32>| v - fwd * v.dot(fwd);
+    let (a, b) = (flat(camera_up), flat(solution_up));
+    let len = (a.magnitude2() * b.magnitude2()).sqrt();
+    if len < 1e-12 {
+        return 1.0;
+    }
+    (a.dot(b) / len).clamp(-1.0, 1.0)
+}
+
+/// Roll of a camera (radians, the `OrbitController` convention: positive
+/// turns the scene clockwise) relative to an upright camera looking the
+/// same way.
+pub fn camera_roll(camera_fwd: Vector3<f32>, camera_up: Vector3<f32>) -> f32 {
+    let fwd = camera_fwd.normalize();
+    let upright = Vector3::unit_y() - fwd * fwd.y;
+    if upright.magnitude2() < 1e-12 {
+        return 0.0;
+    }
+    let upright = upright.normalize();
+    let right = fwd.cross(upright);
+    (-camera_up.dot(right)).atan2(camera_up.dot(upright))
+}
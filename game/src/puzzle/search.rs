@@ -0,0 +1,136 @@
+//! Worker pool for the entropy axis search.
+//!
+//! Candidate axes are scored independently, so they are spread over scoped
+//! worker threads that pull candidate indices from a shared counter.
+//! Results come back in candidate order and the caller reduces them
+//! serially, so the chosen axis never depends on the thread count or on
+//! scheduling — a one-thread search and a sixteen-thread search agree bit
+//! for bit.
+//!
+//! A `SearchHandle` is shared with whoever started the search.  It cancels
+//! the search between candidates (a load the player has walked away from
+//! stops burning cores) and counts finished candidates for the loading bar.
+
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use super::entropy::AnalysisBackend;
+
+/// Cancellation and progress of a running search.  Clones share state, so
+/// the main thread keeps one and hands another to the load thread.
+#[derive(Debug, Clone, Default)]
+pub struct SearchHandle {
+    cancelled: Arc<AtomicBool>,
+    done: Arc<AtomicUsize>,
+    total: Arc<AtomicUsize>,
+}
+
+impl SearchHandle {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Stop the search at the next candidate boundary.
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Fraction of the announced candidates evaluated so far, in [0,1];
+    /// 0 until the search has started.
+    pub fn progress(&self) -> f32 {
+        let total = self.total.load(Ordering::Relaxed);
+        if total == 0 {
+            return 0.0;
+        }
+        (self.done.load(Ordering::Relaxed) as f32 / total as f32).min(1.0)
+    }
+
+    /// Announce `count` more candidates to come.
+    pub fn add_work(&self, count: usize) {
+        self.total.fetch_add(count, Ordering::Relaxed);
+    }
+
+    fn finish_one(&self) {
+        self.done.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// How a search runs.
+#[derive(Debug, Clone, Default)]
+pub struct SearchOptions {
+    pub backend: AnalysisBackend,
+    /// Worker threads; 0 = one per available core.
+    pub threads: usize,
+    pub handle: SearchHandle,
+}
+
+impl SearchOptions {
+    /// Single-threaded, never cancelled.
+    pub fn serial() -> Self {
+        Self {
+            threads: 1,
+            ..Self::default()
+        }
+    }
+
+    /// Evaluate `f(0)..f(count)` on the worker pool, returning the results
+    /// in index order.  `None` if the handle was cancelled before every
+    /// index ran.
+    pub fn map<T, F>(&self, count: usize, f: F) -> Option<Vec<T>>
+    where
+        T: Send,
+        F: Fn(usize) -> T + Sync,
+    {
+        let workers = self.worker_count(count);
+        let handle = &self.handle;
+        let mut slots: Vec<Option<T>> = (0..count).map(|_| None).collect();
+
+        if workers <= 1 {
+            for (i, slot) in slots.iter_mut().enumerate() {
+                if handle.is_cancelled() {
+                    return None;
+                }
+                *slot = Some(f(i));
+                handle.finish_one();
+            }
+        } else {
+            let next = AtomicUsize::new(0);
+            std::thread::scope(|s| {
+                let workers: Vec<_> = (0..workers)
+                    .map(|_| {
+                        s.spawn(|| {
+                            let mut out = Vec::new();
+                            while !handle.is_cancelled() {
+                                let i = next.fetch_add(1, Ordering::Relaxed);
+                                if i >= count {
+                                    break;
+                                }
+                                out.push((i, f(i)));
+                                handle.finish_one();
+                            }
+                            out
+                        })
+                    })
+                    .collect();
+                for worker in workers {
+                    for (i, value) in worker.join().expect("search worker panicked") {
+                        slots[i] = Some(value);
+                    }
+                }
+            });
+        }
+        slots.into_iter().collect()
+    }
+
+    fn worker_count(&self, jobs: usize) -> usize {
+        let threads = match self.threads {
+            0 => std::thread::available_parallelism().map_or(1, |n| n.get()),
+            n => n,
+        };
+        threads.min(jobs)
+    }
+}
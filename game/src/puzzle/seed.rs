@@ -0,0 +1,115 @@
+//! Reproducible puzzle seeds.
+//!
+//! Every random choice in the scramble pipeline — the entropy search's axis
+//! candidates, the per-triangle displacement amounts and the camera start —
+//! draws from a `PuzzleSeed`.  Given the same `(level_id, seed)` pair the
+//! pipeline produces bit-identical offsets, axis, `EntropyReport` and start
+//! position, so a puzzle can be reproduced from a bug report or a test.
+//!
+//! Each stage draws from its own `SeedStream` so that, for example, changing
+//! the number of entropy candidates does not change the displacement amounts.
+
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::fmt;
+
+/// Independent random streams used by the scramble pipeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SeedStream {
+    /// Solution-axis sampling (entropy candidates, direct scramble axis).
+    Axis,
+    /// Per-triangle displacement amounts.
+    Displacement,
+    /// Camera start position.
+    Camera,
+    /// Triangle-to-stage assignment for multi-stage puzzles.
+    Groups,
+    /// Fragment rotations and the solution roll of a rotation scramble.
+    Roll,
+    /// Fragment hues and release order of the mosaic tint.
+    Tint,
+}
+
+impl SeedStream {
+    fn salt(self) -> u64 {
+        match self {
+            SeedStream::Axis => 0x41_58_49_53,         // "AXIS"
+            SeedStream::Displacement => 0x44_49_53_50, // "DISP"
+            SeedStream::Camera => 0x43_41_4D_45,       // "CAME"
+            SeedStream::Groups => 0x47_52_50_53,       // "GRPS"
+            SeedStream::Roll => 0x52_4F_4C_4C,         // "ROLL"
+            SeedStream::Tint => 0x54_49_4E_54,         // "TINT"
+        }
+    }
+}
+
+/// Seed for one reproducible scramble.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct PuzzleSeed(u64);
+
+impl PuzzleSeed {
+    pub const fn new(value: u64) -> Self {
+        Self(value)
+    }
+
+    /// A fresh seed from the thread RNG — used when no seed was requested.
+    pub fn random() -> Self {
+        Self(rand::rng().random())
+    }
+
+    /// Combine a level id with a raw seed.  Different levels played with the
+    /// same raw seed get unrelated scrambles.
+    pub fn for_level(level_id: &str, seed: u64) -> Self {
+        // FNV-1a over the id bytes: stable across platforms and Rust versions,
+        // unlike `DefaultHasher`.
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        for b in level_id.bytes() {
+            hash ^= b as u64;
+            hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        }
+        Self(mix(hash ^ mix(seed)))
+    }
+
+    pub fn value(self) -> u64 {
+        self.0
+    }
+
+    /// Sub-seed for one stage of the pipeline.
+    pub fn stream(self, stream: SeedStream) -> Self {
+        Self(mix(self.0 ^ stream.salt()))
+    }
+
+    /// Sub-seed for the `index`-th item of a stage (mesh, candidate, …).
+    pub fn derive(self, index: u64) -> Self {
+        Self(mix(self.0.wrapping_add(mix(index.wrapping_add(1)))))
+    }
+
+    /// Seed for stage `k` of a multi-stage scramble.  Stage 0 is the seed
+    /// itself, so a single-stage puzzle is identical to a plain scramble.
+    pub fn stage(self, k: usize) -> Self {
+        if k == 0 {
+            self
+        } else {
+            self.derive(k as u64)
+        }
+    }
+
+    /// Deterministic RNG seeded from this value.
+    pub fn rng(self) -> StdRng {
+        StdRng::seed_from_u64(self.0)
+    }
+}
+
+impl fmt::Display for PuzzleSeed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:016x}", self.0)
+    }
+}
+
+/// SplitMix64 finaliser — spreads every input bit over the whole output.
+fn mix(mut z: u64) -> u64 {
+    z = z.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
@@ -0,0 +1,336 @@
+//! CPU silhouette rasteriser for puzzle analysis.
+//!
+//! `entropy::alignment_score` only looks at the offsets; it cannot tell
+//! whether the solved view is recognisable or whether a wrong view happens
+//! to look coherent.  This backend renders the model instead — solved and
+//! scrambled, orthographically along each viewpoint, depth-tested, at
+//! `RASTER_SIZE`² — and compares the two images:
+//!
+//! * **Silhouette IoU** — covered pixels in both over covered in either.
+//! * **Edge coherence** — F-measure of the edges of the two images, with
+//!   one pixel of slack.  Every pixel remembers the rest-pose depth of the
+//!   surface visible there, so a fragment that only slid along the view
+//!   shows exactly the edges of the solved model, while misplaced fragments
+//!   add seams and drop outlines.
+//!
+//! A viewpoint's score is the product of the two; 1 means the scrambled
+//! model is indistinguishable from the solved one from there.
+//!
+//! Everything is plain arithmetic on position slices, so it runs on the
+//! background load thread with no GL context.
+
+use cgmath::{InnerSpace, Vector3};
+
+/// Width and height of every rendered view, in pixels.
+pub const RASTER_SIZE: usize = 48;
+
+/// Rest-depth jump between neighbouring pixels, in pixel widths, that
+/// counts as an edge.
+const EDGE_STEP_PX: f32 = 3.0;
+
+/// How the scrambled model compares with the solved one from a viewpoint.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ViewScore {
+    /// Silhouette intersection over union, in [0,1].
+    pub iou: f32,
+    /// Edge F-measure, in [0,1].
+    pub edge_coherence: f32,
+}
+
+impl ViewScore {
+    /// Combined score in [0,1]: 1 = looks solved.
+    pub fn score(&self) -> f32 {
+        self.iou * self.edge_coherence
+    }
+}
+
+/// Solved renders of a model from a fixed set of viewpoints, reused for
+/// every candidate scramble of an axis search.
+pub struct SolvedViews<'a> {
+    positions: &'a [f32],
+    center: Vector3<f32>,
+    radius: f32,
+    frames: Vec<Frame>,
+    solved: Vec<Raster>,
+}
+
+impl<'a> SolvedViews<'a> {
+    /// Render `positions` (flat, triangle order) along each of `directions`.
+    /// The views frame the model's bounding sphere grown by `margin`, so
+    /// fragments displaced by up to `margin` stay in the picture.
+    pub fn new(positions: &'a [f32], directions: &[Vector3<f32>], margin: f32) -> Self {
+        let (center, radius) = bounding_sphere(positions);
+        let radius = radius + margin.max(0.0);
+        let frames: Vec<Frame> = directions
+            .iter()
+            .map(|&d| Frame::new(d, center, radius))
+            .collect();
+        let solved = frames
+            .iter()
+            .map(|f| rasterize(positions, &[], f))
+            .collect();
+        Self {
+            positions,
+            center,
+            radius,
+            frames,
+            solved,
+        }
+    }
+
+    /// Score one extra viewpoint, framed like the others.  Nothing is
+    /// cached, so both views are rendered.
+    pub fn view_score(&self, offsets: &[f32], dir: Vector3<f32>) -> ViewScore {
+        let frame = Frame::new(dir, self.center, self.radius);
+        compare(
+            &rasterize(self.positions, &[], &frame),
+            &rasterize(self.positions, offsets, &frame),
+        )
+    }
+
+    /// Score the model scrambled by `offsets` (per vertex, like the game's
+    /// scramble offsets) from every viewpoint, in `directions` order.
+    pub fn view_scores(&self, offsets: &[f32]) -> Vec<ViewScore> {
+        self.frames
+            .iter()
+            .zip(&self.solved)
+            .map(|(frame, solved)| compare(solved, &rasterize(self.positions, offsets, frame)))
+            .collect()
+    }
+}
+
+// ─── Rasteriser ───────────────────────────────────────────────────────────────
+
+/// Orthographic view along `forward`, `px_per_unit` pixels per world unit,
+/// centred on `center`.
+#[derive(Debug, Clone, Copy)]
+struct Frame {
+    center: Vector3<f32>,
+    right: Vector3<f32>,
+    up: Vector3<f32>,
+    forward: Vector3<f32>,
+    px_per_unit: f32,
+}
+
+impl Frame {
+    fn new(dir: Vector3<f32>, center: Vector3<f32>, radius: f32) -> Self {
+        let forward = dir.normalize();
+        let helper = if forward.y.abs() < 0.9 {
+            Vector3::unit_y()
+        } else {
+            Vector3::unit_x()
+        };
+        let right = forward.cross(helper).normalize();
+        Self {
+            center,
+            right,
+            up: right.cross(forward),
+            forward,
+            px_per_unit: RASTER_SIZE as f32 / (2.0 * radius.max(1e-6)),
+        }
+    }
+
+    /// Pixel x, pixel y and depth of `p`.
+    fn project(&self, p: Vector3<f32>) -> Vector3<f32> {
+        let v = p - self.center;
+        let half = RASTER_SIZE as f32 * 0.5;
+        Vector3::new(
+            v.dot(self.right) * self.px_per_unit + half,
+            half - v.dot(self.up) * self.px_per_unit,
+            v.dot(self.forward),
+        )
+    }
+}
+
+/// One rendered view.  `depth` is infinite where nothing is drawn;
+/// `surface` is the rest-pose depth of the visible surface.
+struct Raster {
+    depth: Vec<f32>,
+    surface: Vec<f32>,
+    /// World size of one pixel.
+    pixel: f32,
+}
+
+impl Raster {
+    fn covered(&self, i: usize) -> bool {
+        self.depth[i].is_finite()
+    }
+
+    /// Outline and crease pixels: covered pixels next to an empty one, or
+    /// next to a surface more than `EDGE_STEP_PX` pixels away in depth.
+    fn edges(&self) -> Vec<bool> {
+        let n = RASTER_SIZE;
+        let step = EDGE_STEP_PX * self.pixel;
+        let mut edges = vec![false; n * n];
+        for y in 0..n {
+            for x in 0..n {
+                let i = y * n + x;
+                if !self.covered(i) {
+                    continue;
+                }
+                let neighbours = [
+                    (x > 0).then(|| i - 1),
+                    (x + 1 < n).then(|| i + 1),
+                    (y > 0).then(|| i - n),
+                    (y + 1 < n).then(|| i + n),
+                ];
+                edges[i] = neighbours.iter().any(|&j| match j {
+                    Some(j) if self.covered(j) => (self.surface[j] - self.surface[i]).abs() > step,
+                    _ => true,
+                });
+            }
+        }
+        edges
+    }
+}
+
+/// Depth-tested triangle coverage of `positions` displaced by `offsets`
+/// (ignored unless it covers every position).
+fn rasterize(positions: &[f32], offsets: &[f32], frame: &Frame) -> Raster {
+    let n = RASTER_SIZE;
+    let mut depth = vec![f32::INFINITY; n * n];
+    let mut surface = vec![0.0f32; n * n];
+    let displaced = offsets.len() >= positions.len();
+    let at = |v: &[f32], i: usize| Vector3::new(v[i], v[i + 1], v[i + 2]);
+
+    for tri in 0..positions.len() / 9 {
+        let base = tri * 9;
+        let mut screen = [Vector3::new(0.0f32, 0.0, 0.0); 3];
+        let mut rest = [0.0f32; 3];
+        for k in 0..3 {
+            let p = at(positions, base + k * 3);
+            let moved = if displaced {
+                p + at(offsets, base + k * 3)
+            } else {
+                p
+            };
+            screen[k] = frame.project(moved);
+            rest[k] = (p - frame.center).dot(frame.forward);
+        }
+        let [a, b, c] = screen;
+        let area = edge(a, b, c);
+        if area.abs() < 1e-9 {
+            continue;
+        }
+        let clamp = |v: f32| (v.max(0.0) as usize).min(n - 1);
+        let (x0, x1) = (clamp(a.x.min(b.x).min(c.x)), clamp(a.x.max(b.x).max(c.x)));
+        let (y0, y1) = (clamp(a.y.min(b.y).min(c.y)), clamp(a.y.max(b.y).max(c.y)));
+        for y in y0..=y1 {
+            for x in x0..=x1 {
+                let p = Vector3::new(x as f32 + 0.5, y as f32 + 0.5, 0.0);
+                let w = [
+                    edge(b, c, p) / area,
+                    edge(c, a, p) / area,
+                    edge(a, b, p) / area,
+                ];
+                if w.iter().any(|&w| w < 0.0) {
+                    continue;
+                }
+                let z = w[0] * a.z + w[1] * b.z + w[2] * c.z;
+                let i = y * n + x;
+                if z < depth[i] {
+                    depth[i] = z;
+                    surface[i] = w[0] * rest[0] + w[1] * rest[1] + w[2] * rest[2];
+                }
+            }
+        }
+    }
+
+    Raster {
+        depth,
+        surface,
+        pixel: 1.0 / frame.px_per_unit,
+    }
+}
+
+/// Twice the signed area of the screen-space triangle `a b p`.
+fn edge(a: Vector3<f32>, b: Vector3<f32>, p: Vector3<f32>) -> f32 {
+    (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x)
+}
+
+// ─── Scoring ──────────────────────────────────────────────────────────────────
+
+fn compare(solved: &Raster, scrambled: &Raster) -> ViewScore {
+    let count = RASTER_SIZE * RASTER_SIZE;
+    let (mut both, mut either) = (0usize, 0usize);
+    for i in 0..count {
+        let (s, m) = (solved.covered(i), scrambled.covered(i));
+        both += (s && m) as usize;
+        either += (s || m) as usize;
+    }
+    let iou = if either == 0 {
+        1.0
+    } else {
+        both as f32 / either as f32
+    };
+
+    let (e0, e1) = (solved.edges(), scrambled.edges());
+    let (d0, d1) = (dilate(&e0), dilate(&e1));
+    let matched = |e: &[bool], d: &[bool]| e.iter().zip(d).filter(|&(&e, &d)| e && d).count();
+    let (n0, n1) = (
+        e0.iter().filter(|&&e| e).count(),
+        e1.iter().filter(|&&e| e).count(),
+    );
+    let edge_coherence = match (n0, n1) {
+        (0, 0) => 1.0,
+        (0, _) | (_, 0) => 0.0,
+        _ => {
+            let precision = matched(&e1, &d0) as f32 / n1 as f32;
+            let recall = matched(&e0, &d1) as f32 / n0 as f32;
+            if precision + recall > 0.0 {
+                2.0 * precision * recall / (precision + recall)
+            } else {
+                0.0
+            }
+        }
+    };
+
+    ViewScore {
+        iou,
+        edge_coherence,
+    }
+}
+
+/// 3×3 dilation of an edge mask.
+fn dilate(mask: &[bool]) -> Vec<bool> {
+    let n = RASTER_SIZE as isize;
+    let mut out = vec![false; mask.len()];
+    for y in 0..n {
+        for x in 0..n {
+            if !mask[(y * n + x) as usize] {
+                continue;
+            }
+            for dy in -1..=1 {
+                for dx in -1..=1 {
+                    let (nx, ny) = (x + dx, y + dy);
+                    if (0..n).contains(&nx) && (0..n).contains(&ny) {
+                        out[(ny * n + nx) as usize] = true;
+                    }
+                }
+            }
+        }
+    }
+    out
+}
+
+/// Centre of the bounding box and the largest distance from it.
+fn bounding_sphere(positions: &[f32]) -> (Vector3<f32>, f32) {
+    let points = positions
+        .chunks_exact(3)
+        .map(|p| Vector3::new(p[0], p[1], p[2]));
+    let (lo, hi) = points.clone().fold(
+        (Vector3::from([f32::MAX; 3]), Vector3::from([f32::MIN; 3])),
+        |(lo, hi), p| {
+            (
+                Vector3::new(lo.x.min(p.x), lo.y.min(p.y), lo.z.min(p.z)),
+                Vector3::new(hi.x.max(p.x), hi.y.max(p.y), hi.z.max(p.z)),
+            )
+        },
+    );
+    if lo.x > hi.x {
+        return (Vector3::new(0.0, 0.0, 0.0), 1.0);
+    }
+    let center = (lo + hi) * 0.5;
+    let radius = points.map(|p| (p - center).magnitude()).fold(0.0, f32::max);
+    (center, radius)
+}
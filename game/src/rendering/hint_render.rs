@@ -26,6 +26,8 @@ const DEFAULT_FRAG: &str = include_str!("../../assets/shaders/hint.frag.glsl");
 
 struct FrameState {
     warmth_color: Vector3<f32>,
+    cold_color: Vector3<f32>,
+    hot_color: Vector3<f32>,
     warmth: f32,
     hint_tier: f32,
     time: f32,
@@ -57,6 +59,8 @@ impl HintRenderer {
 
         let frame = Rc::new(RefCell::new(FrameState {
             warmth_color: Vector3::new(1.0, 1.0, 1.0),
+            cold_color: Vector3::new(0.2, 0.4, 0.9),
+            hot_color: Vector3::new(0.9, 0.2, 0.1),
             warmth: 0.0,
             hint_tier: 0.0,
             time: 0.0,
@@ -73,6 +77,20 @@ impl HintRenderer {
                 }),
             }));
         }
+        {
+            let f = Rc::clone(&frame);
+            shader.add_per_render_uniform(Box::new(UniformAdapter {
+                uniform: UniformVec3::new("uColdColor"),
+                extractor: Box::new(move |_: &RenderState<NoopProcessable>| f.borrow().cold_color),
+            }));
+        }
+        {
+            let f = Rc::clone(&frame);
+            shader.add_per_render_uniform(Box::new(UniformAdapter {
+                uniform: UniformVec3::new("uHotColor"),
+                extractor: Box::new(move |_: &RenderState<NoopProcessable>| f.borrow().hot_color),
+            }));
+        }
         {
             let f = Rc::clone(&frame);
             shader.add_per_render_uniform(Box::new(UniformAdapter {
@@ -122,6 +140,8 @@ impl IRenderer for HintRenderer {
                 hints.warmth_color[1],
                 hints.warmth_color[2],
             );
+            f.cold_color = Vector3::from(hints.cold_color);
+            f.hot_color = Vector3::from(hints.hot_color);
             f.warmth = hints.warmth;
             f.hint_tier = hints.tier as f32;
             f.time = hints.time;
@@ -6,8 +6,13 @@
 
 pub mod hint_render;
 pub mod menu_render;
+pub mod outline_render;
 pub mod shine_render;
 
+use std::rc::Rc;
+
+use crate::puzzle::outline::Segment;
+
 // ─── Per-frame game render data ───────────────────────────────────────────────
 //
 // Written by the game layer into `SceneContext::game_render_data` each frame.
@@ -19,14 +24,29 @@ pub mod shine_render;
 pub struct HintRenderState {
     pub warmth: f32,
     pub warmth_color: [f32; 3],
+    /// Ends of the player's warmth palette (`WarmthPalette::cold`/`hot`).
+    pub cold_color: [f32; 3],
+    pub hot_color: [f32; 3],
     pub tier: u8,
     pub time: f32,
 }
 
+/// Silhouette outline hint for the current frame.
+#[derive(Clone)]
+pub struct OutlineRenderState {
+    /// World-space contour edges of the solved model.
+    pub segments: Rc<Vec<Segment>>,
+    pub color: [f32; 3],
+    pub time: f32,
+}
+
 /// All game-specific data passed from the game layer to game-side renderers
 /// via `SceneContext::game_render_data`.
 pub struct GameRenderData {
     pub hints: Option<HintRenderState>,
+    pub outline: Option<OutlineRenderState>,
     /// Seconds since solve; `None` means not yet solved.
     pub solved_timer: Option<f32>,
+    /// Camera frustum of a raced best solve, drawn like the outline.
+    pub ghost: Option<OutlineRenderState>,
 }
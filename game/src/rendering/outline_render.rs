@@ -0,0 +1,180 @@
+//! Silhouette outline hint renderer (Tier 3+) — Overlay pass.
+//!
+//! Draws the solved model's contour edges (`puzzle::outline`) as screen-space
+//! lines over the finished frame.  The edges are projected through the live
+//! camera on the CPU each frame, so the shader only has to colour them.
+//!
+//! A second instance (`OutlineRenderer::ghost`) draws the ghost camera of a
+//! raced best solve the same way.
+//!
+//! Uses ShaderProgram<NoopProcessable> + UniformAdapter — same pattern as
+//! HintRenderer.
+
+use crate::puzzle::outline::project_segments;
+use crate::rendering::{GameRenderData, OutlineRenderState};
+use cgmath::Vector3;
+use formosaic_engine::{
+    architecture::scene::scene_context::SceneContext,
+    opengl::{
+        constants::{data_type::DataType, vbo_target::VboTarget, vbo_usage::VboUsage},
+        objects::{attribute::Attribute, vao::Vao, vbo::Vbo},
+        shaders::{
+            uniform::{UniformAdapter, UniformFloat, UniformVec3},
+            RenderState, ShaderProgram,
+        },
+    },
+    rendering::abstracted::{
+        irenderer::{IRenderer, RenderPass},
+        processable::NoopProcessable,
+    },
+};
+use std::{cell::RefCell, rc::Rc};
+
+const DEFAULT_VERT: &str = include_str!("../../assets/shaders/outline.vert.glsl");
+const DEFAULT_FRAG: &str = include_str!("../../assets/shaders/outline.frag.glsl");
+
+/// Each vertex: NDC (x, y).
+const FLOATS_PER_VERT: usize = 2;
+/// Initial buffer size; grown on demand.
+const INITIAL_VERTS: usize = 4096;
+
+struct FrameState {
+    color: Vector3<f32>,
+    time: f32,
+}
+
+/// Picks the edges an `OutlineRenderer` draws out of the frame's data.
+type Source = fn(&GameRenderData) -> Option<&OutlineRenderState>;
+
+pub struct OutlineRenderer {
+    source: Source,
+    shader: ShaderProgram<NoopProcessable>,
+    frame: Rc<RefCell<FrameState>>,
+    vao: Vao,
+    vbo: Vbo,
+    /// Vertices the VBO currently has room for.
+    capacity: usize,
+}
+
+impl OutlineRenderer {
+    pub fn new() -> Result<Self, Box<dyn std::error::Error>> {
+        Self::with_shaders(DEFAULT_VERT, DEFAULT_FRAG)
+    }
+
+    /// A renderer for the ghost camera instead of the outline hint.
+    pub fn ghost() -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(Self {
+            source: |d| d.ghost.as_ref(),
+            ..Self::new()?
+        })
+    }
+
+    pub fn with_shaders(vert: &str, frag: &str) -> Result<Self, Box<dyn std::error::Error>> {
+        let frame = Rc::new(RefCell::new(FrameState {
+            color: Vector3::new(1.0, 1.0, 1.0),
+            time: 0.0,
+        }));
+
+        let mut shader = ShaderProgram::<NoopProcessable>::from_sources(vert, frag)?;
+        {
+            let f = Rc::clone(&frame);
+            shader.add_per_render_uniform(Box::new(UniformAdapter {
+                uniform: UniformVec3::new("uColor"),
+                extractor: Box::new(move |_: &RenderState<NoopProcessable>| f.borrow().color),
+            }));
+        }
+        {
+            let f = Rc::clone(&frame);
+            shader.add_per_render_uniform(Box::new(UniformAdapter {
+                uniform: UniformFloat::new("uTime"),
+                extractor: Box::new(move |_: &RenderState<NoopProcessable>| f.borrow().time),
+            }));
+        }
+
+        let stride = (FLOATS_PER_VERT * std::mem::size_of::<f32>()) as i32;
+        let mut vbo = Vbo::create(VboTarget::ArrayBuffer, VboUsage::DynamicDraw);
+        let vao = Vao::create(); // creates and auto-binds
+
+        vbo.bind();
+        vbo.allocate_float(INITIAL_VERTS * FLOATS_PER_VERT);
+        let a_pos = Attribute::of(0, 2, DataType::Float, false);
+        a_pos.link(stride, 0);
+        Attribute::enable_index(0);
+
+        vao.unbind();
+        vbo.unbind();
+
+        Ok(Self {
+            source: |d| d.outline.as_ref(),
+            shader,
+            frame,
+            vao,
+            vbo,
+            capacity: INITIAL_VERTS,
+        })
+    }
+}
+
+impl IRenderer for OutlineRenderer {
+    fn pass(&self) -> RenderPass {
+        RenderPass::Overlay
+    }
+
+    fn render(&mut self, context: &SceneContext) {
+        let outline: &OutlineRenderState = match context
+            .game_render_data
+            .as_ref()
+            .and_then(|d| d.downcast_ref::<GameRenderData>())
+            .and_then(self.source)
+        {
+            Some(o) => o,
+            None => return,
+        };
+
+        let view_proj = *context.get_camera().borrow().get_projection_view_matrix();
+        let ndc = project_segments(&outline.segments, view_proj);
+        if ndc.is_empty() {
+            return;
+        }
+        let count = ndc.len() / FLOATS_PER_VERT;
+
+        {
+            let mut f = self.frame.borrow_mut();
+            f.color = Vector3::from(outline.color);
+            f.time = outline.time;
+        }
+
+        unsafe {
+            gl::Disable(gl::DEPTH_TEST);
+            gl::Disable(gl::CULL_FACE);
+            gl::Enable(gl::BLEND);
+            gl::BlendFunc(gl::SRC_ALPHA, gl::ONE_MINUS_SRC_ALPHA);
+        }
+
+        self.shader.bind();
+        let state = RenderState::new_screenspace(self);
+        self.shader.update_per_render_uniforms(&state);
+
+        self.vao.bind();
+        self.vbo.bind();
+        if count > self.capacity {
+            self.capacity = count.next_power_of_two();
+            self.vbo.allocate_float(self.capacity * FLOATS_PER_VERT);
+        }
+        self.vbo.store_float(0, &ndc);
+        unsafe {
+            gl::DrawArrays(gl::LINES, 0, count as i32);
+        }
+        self.vbo.unbind();
+        self.vao.unbind();
+        self.shader.unbind();
+
+        unsafe {
+            gl::Enable(gl::DEPTH_TEST);
+            gl::Enable(gl::CULL_FACE);
+            gl::Disable(gl::BLEND);
+        }
+    }
+
+    fn finish(&mut self) {}
+}
@@ -1,5 +1,6 @@
 use super::util::{self as util, Scale};
 use crate::formosaic::UiState;
+use crate::level::session::{LeaderboardEntry, SessionKind};
 use crate::ui::state_machine::{UiInput, UiScreen};
 use formosaic_engine::architecture::scene::node::{scenegraph::Scenegraph, ui_node::UiNode};
 use imgui::*;
@@ -8,7 +9,19 @@ use std::{cell::RefCell, rc::Rc};
 pub fn register(scene: &Scenegraph, state: Rc<RefCell<UiState>>) {
     let credits = UiNode::new("credits", move |ui, w, h, ctx| {
         let s = state.borrow();
-        if s.screen != UiScreen::Credits || !s.is_solved {
+        if s.screen != UiScreen::Credits {
+            return;
+        }
+        if let Some((entry, rank)) = s.session_result.clone() {
+            let scale = Scale::from_screen(w, h, s.is_touch);
+            let board = s.leaderboard.clone();
+            drop(s);
+            if session_panel(ui, &scale, w, h, &entry, rank, &board) {
+                ctx.push_ui_action(UiInput::BackToMenuPressed);
+            }
+            return;
+        }
+        if !s.is_solved {
             return;
         }
         let Some(level) = &s.current_level else {
@@ -19,9 +32,16 @@ pub fn register(scene: &Scenegraph, state: Rc<RefCell<UiState>>) {
         let level_author = level.author.clone();
         let level_license = level.license.clone();
         let level_source = level.source_url.clone();
+        let best_score = level.best_score;
+        let score = s.score;
+        let new_best = s.new_best;
+        let has_replay = s.has_replay;
+        let mut share_code = s.share_code.clone();
         drop(s);
         let mut open_link = false;
         let mut go_menu = false;
+        let mut watch = false;
+        let mut race = false;
 
         let pw = (w * 0.60).clamp(scale.su(320.0), scale.su(480.0));
         let inner_w = pw - scale.pad_w() * 2.0;
@@ -37,14 +57,40 @@ pub fn register(scene: &Scenegraph, state: Rc<RefCell<UiState>>) {
             .position_pivot([0.5, 0.5])
             .build(|| {
                 util::title(ui, "Level Complete");
+                util::gap(ui, scale.gap_sm());
+                if let Some(score) = score {
+                    util::stars(ui, score.stars);
+                    ui.same_line_with_spacing(0.0, scale.gap_sm());
+                    util::text_body(ui, &format!("{} pts", score.points));
+                    if new_best {
+                        ui.same_line_with_spacing(0.0, scale.gap_sm());
+                        ui.text_colored([0.95, 0.78, 0.25, 1.0], "New best!");
+                    } else if let Some(best) = best_score {
+                        util::text_muted(ui, &format!("Best: {} pts", best));
+                    }
+                }
                 util::gap(ui, scale.gap_md());
                 util::text_body(ui, &level_name);
                 util::text_muted(ui, &format!("by {}", level_author));
                 util::gap(ui, scale.gap_xxs());
                 util::text_muted(ui, &level_license);
+                if let Some(code) = &mut share_code {
+                    util::gap(ui, scale.gap_md());
+                    util::text_muted(ui, "Share this puzzle:");
+                    // Read-only, but selectable for copying.
+                    ui.set_next_item_width(inner_w);
+                    ui.input_text("##share", code).read_only(true).build();
+                }
                 util::gap(ui, scale.gap_md());
                 util::separator_dim(ui);
                 util::gap(ui, scale.gap_sm());
+                if has_replay {
+                    let half_w = (inner_w - scale.gap_sm()) * 0.5;
+                    watch = ui.button_with_size("Watch Best Solve", [half_w, scale.btn_h()]);
+                    ui.same_line_with_spacing(0.0, scale.gap_sm());
+                    race = ui.button_with_size("Race Best Solve", [half_w, scale.btn_h()]);
+                    util::gap(ui, scale.gap_sm());
+                }
                 scale.btn_full(ui, "Open Artist Link", inner_w, &mut open_link);
                 util::gap(ui, scale.gap_sm());
                 scale.btn_full(ui, "Back to Main Menu", inner_w, &mut go_menu);
@@ -57,6 +103,84 @@ pub fn register(scene: &Scenegraph, state: Rc<RefCell<UiState>>) {
         if go_menu {
             ctx.push_ui_action(UiInput::BackToMenuPressed);
         }
+        if watch {
+            ctx.push_ui_action(UiInput::WatchReplay);
+        }
+        if race {
+            ctx.push_ui_action(UiInput::RaceReplay);
+        }
     });
     scene.add_node(Rc::new(RefCell::new(credits)));
 }
+
+/// Results of a finished marathon or time attack with its mode's
+/// leaderboard.  Returns whether "Back to Main Menu" was pressed.
+fn session_panel(
+    ui: &Ui,
+    scale: &Scale,
+    w: f32,
+    h: f32,
+    entry: &LeaderboardEntry,
+    rank: Option<usize>,
+    board: &[LeaderboardEntry],
+) -> bool {
+    let mut go_menu = false;
+    let pw = (w * 0.60).clamp(scale.su(320.0), scale.su(480.0));
+    let inner_w = pw - scale.pad_w() * 2.0;
+    let (title, summary) = match entry.kind {
+        SessionKind::Marathon { levels } => (
+            "Marathon Complete",
+            format!("{levels} puzzles in {:.1}s", entry.time_secs),
+        ),
+        SessionKind::TimeAttack { budget_secs } => (
+            "Time Up",
+            format!("{} solved in {:.0}s", entry.solved, budget_secs),
+        ),
+    };
+    let rank_text = match rank {
+        Some(1) => "New #1!".to_string(),
+        Some(r) => format!("Rank #{r}"),
+        None => "Not on the leaderboard".to_string(),
+    };
+
+    let _wp = ui.push_style_var(imgui::StyleVar::WindowPadding([
+        scale.pad_w(),
+        scale.pad_w(),
+    ]));
+    let _win_bg = ui.push_style_color(imgui::StyleColor::WindowBg, [0.03, 0.04, 0.06, 0.92]);
+    ui.window("##session_results")
+        .flags(util::popup_flags())
+        .position([w * 0.5, h * 0.5], Condition::Always)
+        .position_pivot([0.5, 0.5])
+        .build(|| {
+            util::title(ui, title);
+            util::gap(ui, scale.gap_sm());
+            util::text_body(ui, &summary);
+            if rank.is_some() {
+                ui.text_colored([0.95, 0.78, 0.25, 1.0], &rank_text);
+            } else {
+                util::text_muted(ui, &rank_text);
+            }
+            util::gap(ui, scale.gap_md());
+            util::separator_dim(ui);
+            util::gap(ui, scale.gap_sm());
+            for (i, e) in board.iter().enumerate() {
+                let line = match e.kind {
+                    SessionKind::Marathon { .. } => {
+                        format!("{:>2}.  {:.1}s   {}", i + 1, e.time_secs, e.date)
+                    }
+                    SessionKind::TimeAttack { .. } => {
+                        format!("{:>2}.  {} solved   {}", i + 1, e.solved, e.date)
+                    }
+                };
+                if rank == Some(i + 1) {
+                    ui.text_colored([0.95, 0.78, 0.25, 1.0], &line);
+                } else {
+                    util::text_muted(ui, &line);
+                }
+            }
+            util::gap(ui, scale.gap_md());
+            scale.btn_full(ui, "Back to Main Menu", inner_w, &mut go_menu);
+        });
+    go_menu
+}
@@ -0,0 +1,244 @@
+use super::util::{self as util, Scale};
+use crate::formosaic::{EditorInfo, UiState};
+use crate::puzzle::authoring::{direction_at, map_position};
+use crate::puzzle::entropy::difficulty_label;
+use crate::ui::state_machine::{EditorEdit, UiInput, UiScreen};
+use cgmath::Vector3;
+use formosaic_engine::architecture::scene::node::{scenegraph::Scenegraph, ui_node::UiNode};
+use imgui::*;
+use std::{cell::RefCell, rc::Rc};
+
+const AXIS_COLOR: [f32; 4] = [0.95, 0.78, 0.25, 1.0];
+const VIEW_COLOR: [f32; 4] = [0.55, 0.75, 1.0, 1.0];
+const COLD: [f32; 3] = [0.04, 0.06, 0.16];
+const HOT: [f32; 3] = [0.95, 0.78, 0.25];
+
+pub fn register(scene: &Scenegraph, state: Rc<RefCell<UiState>>) {
+    let editor = UiNode::new("editor", move |ui, w, h, ctx| {
+        let s = state.borrow();
+        if s.screen != UiScreen::Editor || s.is_loading {
+            return;
+        }
+        let Some(info) = s.editor.clone() else {
+            return;
+        };
+        let scale = Scale::from_screen(w, h, s.is_touch);
+        let level_name = s
+            .current_level
+            .as_ref()
+            .map(|l| l.name.clone())
+            .unwrap_or_default();
+        drop(s);
+
+        let pw = (w * 0.32).clamp(scale.su(300.0), scale.su(420.0));
+        let inner_w = pw - scale.pad_w() * 2.0;
+        let mut actions: Vec<UiInput> = Vec::new();
+
+        let _wp = ui.push_style_var(imgui::StyleVar::WindowPadding([
+            scale.pad_w(),
+            scale.pad_w(),
+        ]));
+        let _win_bg = ui.push_style_color(imgui::StyleColor::WindowBg, [0.03, 0.04, 0.06, 0.92]);
+        ui.window("##editor")
+            .flags(util::popup_flags())
+            .position([scale.pad_w(), scale.pad_w()], Condition::Always)
+            .size([pw, 0.0], Condition::Always)
+            .build(|| {
+                util::title(ui, "Puzzle Editor");
+                util::text_body(ui, &util::truncate(&level_name, 36));
+                util::gap(ui, scale.gap_sm());
+                util::text_muted(ui, "Axes - brighter is a better puzzle");
+                if let Some(axis) = axis_map(ui, &scale, &info, inner_w) {
+                    actions.push(UiInput::Edit(EditorEdit::Axis(axis)));
+                }
+                if info.heatmap_pending {
+                    util::text_dim(ui, "Mapping axes...");
+                } else {
+                    util::text_dim(ui, "Click or drag to set the axis");
+                }
+                if let (Some(report), Some(difficulty)) = (info.report, info.difficulty) {
+                    util::text_body(
+                        ui,
+                        &format!(
+                            "{}  |  {:.2} bits  |  isolation {:.0} deg",
+                            difficulty_label(difficulty),
+                            report.entropy_bits,
+                            report.solution_isolation_rad.to_degrees()
+                        ),
+                    );
+                } else {
+                    util::text_dim(ui, "Scoring...");
+                }
+
+                util::gap(ui, scale.gap_md());
+                let (mut min, mut max) = (info.min_disp, info.max_disp);
+                ui.set_next_item_width(inner_w * 0.55);
+                let min_changed = ui
+                    .slider_config("Min displacement", 0.0, info.disp_limit)
+                    .display_format("%.3f")
+                    .build(&mut min);
+                ui.set_next_item_width(inner_w * 0.55);
+                let max_changed = ui
+                    .slider_config("Max displacement", 0.0, info.disp_limit)
+                    .display_format("%.3f")
+                    .build(&mut max);
+                // Dragging one past the other takes the other along.
+                if min_changed {
+                    max = max.max(min);
+                }
+                if max_changed {
+                    min = min.min(max);
+                }
+                if min_changed || max_changed {
+                    actions.push(UiInput::Edit(EditorEdit::Displacement { min, max }));
+                }
+                let mut t = info.preview_t;
+                ui.set_next_item_width(inner_w * 0.55);
+                if ui
+                    .slider_config("Scramble", 0.0, 1.0)
+                    .display_format("%.2f")
+                    .build(&mut t)
+                {
+                    actions.push(UiInput::Edit(EditorEdit::Preview(t)));
+                }
+                util::gap(ui, scale.gap_sm());
+                let half_w = (inner_w - scale.gap_sm()) * 0.5;
+                if ui.button_with_size("View Along Axis", [half_w, scale.btn_h()]) {
+                    actions.push(UiInput::Edit(EditorEdit::ViewAxis));
+                }
+                ui.same_line_with_spacing(0.0, scale.gap_sm());
+                if ui.button_with_size("New Displacements", [half_w, scale.btn_h()]) {
+                    actions.push(UiInput::Edit(EditorEdit::Reseed));
+                }
+
+                util::gap(ui, scale.gap_md());
+                util::separator_dim(ui);
+                util::gap(ui, scale.gap_sm());
+                if info.dirty {
+                    ui.text_colored(AXIS_COLOR, "Unsaved changes");
+                } else if info.authored {
+                    util::text_muted(ui, "Saved - plays use this puzzle");
+                } else {
+                    util::text_muted(ui, "Not saved - plays search for an axis");
+                }
+                util::gap(ui, scale.gap_xs());
+                let mut save = false;
+                let mut clear = false;
+                let mut go_menu = false;
+                scale.btn_full(ui, "Save Puzzle", inner_w, &mut save);
+                if info.authored {
+                    util::gap(ui, scale.gap_sm());
+                    scale.btn_full(ui, "Use Searched Puzzles", inner_w, &mut clear);
+                }
+                util::gap(ui, scale.gap_sm());
+                scale.btn_full(ui, "Back to Main Menu", inner_w, &mut go_menu);
+                if save {
+                    actions.push(UiInput::SaveAuthored);
+                }
+                if clear {
+                    actions.push(UiInput::ClearAuthored);
+                }
+                if go_menu {
+                    actions.push(UiInput::BackToMenuPressed);
+                }
+            });
+        drop(_win_bg);
+        drop(_wp);
+
+        if !scale.is_touch {
+            ui.window("##editor_keys")
+                .flags(util::hud_flags())
+                .position([w * 0.5, h - scale.pad_w()], Condition::Always)
+                .position_pivot([0.5, 1.0])
+                .build(|| {
+                    util::text_muted(ui, "ESC  Menu    Drag  Orbit");
+                });
+        }
+        for action in actions {
+            ctx.push_ui_action(action);
+        }
+    });
+    scene.add_node(Rc::new(RefCell::new(editor)));
+}
+
+/// The direction sphere as an equirectangular heatmap, with the axis (and
+/// its opposite end) and the camera's view marked.  Returns the axis under
+/// the pointer while the map is clicked or dragged.
+fn axis_map(ui: &Ui, scale: &Scale, info: &EditorInfo, width: f32) -> Option<Vector3<f32>> {
+    let origin = ui.cursor_screen_pos();
+    let size = [width, width * 0.5];
+    let end = [origin[0] + size[0], origin[1] + size[1]];
+    ui.invisible_button("##axes", size);
+    let io = ui.io();
+    let picked = (ui.is_item_active() && (ui.is_item_activated() || io.mouse_delta != [0.0, 0.0]))
+        .then(|| {
+            direction_at(
+                ((io.mouse_pos[0] - origin[0]) / size[0]).clamp(0.0, 1.0),
+                ((io.mouse_pos[1] - origin[1]) / size[1]).clamp(0.0, 1.0),
+            )
+        });
+
+    let draw = ui.get_window_draw_list();
+    match &info.heatmap {
+        Some(map) if map.cols > 0 && map.rows > 0 => {
+            let cell = [size[0] / map.cols as f32, size[1] / map.rows as f32];
+            for (i, &score) in map.scores.iter().enumerate() {
+                let p = [
+                    origin[0] + (i % map.cols) as f32 * cell[0],
+                    origin[1] + (i / map.cols) as f32 * cell[1],
+                ];
+                // Overlap by half a pixel so no seams show between cells.
+                draw.add_rect(p, [p[0] + cell[0] + 0.5, p[1] + cell[1] + 0.5], heat(score))
+                    .filled(true)
+                    .build();
+            }
+        }
+        _ => {
+            draw.add_rect(origin, end, [COLD[0], COLD[1], COLD[2], 1.0])
+                .filled(true)
+                .build();
+        }
+    }
+    let horizon = origin[1] + size[1] * 0.5;
+    draw.add_line(
+        [origin[0], horizon],
+        [end[0], horizon],
+        [1.0, 1.0, 1.0, 0.15],
+    )
+    .build();
+    draw.add_rect(origin, end, [0.20, 0.30, 0.45, 0.6]).build();
+
+    let at = |d: Vector3<f32>| {
+        let (u, v) = map_position(d);
+        [origin[0] + u * size[0], origin[1] + v * size[1]]
+    };
+    draw.add_circle(at(info.axis), scale.su(5.0), AXIS_COLOR)
+        .filled(true)
+        .build();
+    draw.add_circle(at(-info.axis), scale.su(5.0), AXIS_COLOR)
+        .thickness(scale.su(1.5))
+        .build();
+    if let Some(view) = info.view_dir {
+        let c = at(view);
+        let r = scale.su(4.0);
+        draw.add_line([c[0] - r, c[1] - r], [c[0] + r, c[1] + r], VIEW_COLOR)
+            .thickness(scale.su(1.5))
+            .build();
+        draw.add_line([c[0] - r, c[1] + r], [c[0] + r, c[1] - r], VIEW_COLOR)
+            .thickness(scale.su(1.5))
+            .build();
+    }
+    picked
+}
+
+/// Heatmap colour for a relative score in [0,1]; squared so only axes
+/// close to the best light up.
+fn heat(score: f32) -> [f32; 4] {
+    let t = score.clamp(0.0, 1.0).powi(2);
+    [
+        COLD[0] + (HOT[0] - COLD[0]) * t,
+        COLD[1] + (HOT[1] - COLD[1]) * t,
+        COLD[2] + (HOT[2] - COLD[2]) * t,
+        1.0,
+    ]
+}
@@ -1,6 +1,7 @@
 use super::util::{self as util, Scale};
 use crate::formosaic::UiState;
 use crate::puzzle::hints::HintTier;
+use crate::puzzle::palette::{warmth_bar, warmth_percent};
 use crate::ui::state_machine::UiScreen;
 use formosaic_engine::architecture::scene::node::{scenegraph::Scenegraph, ui_node::UiNode};
 use imgui::*;
@@ -20,20 +21,14 @@ pub fn register(scene: &Scenegraph, state: Rc<RefCell<UiState>>) {
         ui.window("##hints")
             .flags(util::hud_flags())
             .position(
-                [scale.pad_w(), h - scale.su(60.0) - scale.pad_w()],
+                [scale.pad_w(), h - scale.su(118.0) - scale.pad_w()],
                 Condition::Always,
             )
-            .size([scale.su(180.0), scale.su(60.0)], Condition::Always)
+            .size([scale.su(180.0), scale.su(118.0)], Condition::Always)
             .build(|| {
                 let w = s.hint_warmth;
-                let cold = [0.2_f32, 0.4, 0.9, 1.0];
-                let hot = [0.9_f32, 0.2, 0.1, 1.0];
-                let col = [
-                    cold[0] + (hot[0] - cold[0]) * w,
-                    cold[1] + (hot[1] - cold[1]) * w,
-                    cold[2] + (hot[2] - cold[2]) * w,
-                    1.0,
-                ];
+                let [r, g, b] = s.warmth_palette.color(w);
+                let col = [r, g, b, 1.0];
                 let label = if w > 0.92 {
                     "HOT"
                 } else if w > 0.65 {
@@ -41,7 +36,24 @@ pub fn register(scene: &Scenegraph, state: Rc<RefCell<UiState>>) {
                 } else {
                     "COLD"
                 };
-                ui.text_colored(col, format!(">> {} <<", label));
+                ui.text_colored(col, format!(">> {} <<  {}%", label, warmth_percent(w)));
+                // The bar and number carry the warmth without relying on
+                // colour, whatever the palette.
+                ui.text_colored(col, warmth_bar(w, 10));
+                if let Some(px) = s.misalignment_px {
+                    util::text_muted(ui, &format!("Seams: {:.1} px", px));
+                }
+                if let Some(roll) = s.roll_cue {
+                    // Q lowers the camera roll, E raises it.
+                    let cue = if s.is_touch {
+                        "Twist to turn upright"
+                    } else if roll > 0.0 {
+                        "Turn upright: Q"
+                    } else {
+                        "Turn upright: E"
+                    };
+                    ui.text_colored([0.45, 0.75, 0.95, 1.0], cue);
+                }
                 util::text_muted(ui, &format!("Hint: {}", util::hint_tier_label(s.hint_tier)));
             });
     });
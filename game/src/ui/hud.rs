@@ -1,5 +1,6 @@
 use super::util::{self as util, Scale};
 use crate::formosaic::UiState;
+use crate::level::session::SessionKind;
 use crate::puzzle::entropy::difficulty_label;
 use crate::ui::state_machine::UiScreen;
 use formosaic_engine::architecture::scene::node::{scenegraph::Scenegraph, ui_node::UiNode};
@@ -13,6 +14,11 @@ pub fn register(scene: &Scenegraph, state: Rc<RefCell<UiState>>) {
             return;
         }
         let scale = Scale::from_screen(w, h, s.is_touch);
+        let hud_h = if s.session.is_some() || s.replay.is_some() {
+            120.0
+        } else {
+            100.0
+        };
 
         ui.window("##hud")
             .flags(util::hud_flags())
@@ -20,14 +26,67 @@ pub fn register(scene: &Scenegraph, state: Rc<RefCell<UiState>>) {
                 [w - scale.su(210.0) - scale.pad_w(), scale.pad_w()],
                 Condition::Always,
             )
-            .size([scale.su(210.0), scale.su(100.0)], Condition::Always)
+            .size([scale.su(210.0), scale.su(hud_h)], Condition::Always)
             .build(|| {
+                match s.session {
+                    Some(SessionKind::Marathon { levels }) => {
+                        let level = (s.session_solved as usize + 1).min(levels);
+                        let label = format!("Marathon {level}/{levels}  |  {:.1}s", s.session_secs);
+                        ui.text_colored([0.95, 0.78, 0.25, 1.0], label);
+                    }
+                    Some(SessionKind::TimeAttack { budget_secs }) => {
+                        let left = (budget_secs - s.session_secs).max(0.0).ceil() as u32;
+                        let label = format!(
+                            "{}:{:02} left  |  {} solved",
+                            left / 60,
+                            left % 60,
+                            s.session_solved
+                        );
+                        ui.text_colored([0.95, 0.78, 0.25, 1.0], label);
+                    }
+                    None => {}
+                }
                 if let Some(diff) = s.difficulty {
                     util::text_body(
                         ui,
                         &format!("{:.1}s  |  {}", s.elapsed_secs, difficulty_label(diff)),
                     );
                 }
+                if let Some(replay) = s.replay {
+                    let label = if replay.watching {
+                        format!("Replay  |  best {:.1}s", replay.time_secs)
+                    } else {
+                        format!("Racing best  |  {:.1}s", replay.time_secs)
+                    };
+                    ui.text_colored([0.55, 0.75, 1.0, 1.0], label);
+                }
+                if s.scene {
+                    // One mark per model: found, being solved, or still
+                    // scrambled.
+                    util::text_muted(ui, "Models");
+                    for i in 0..s.stage_count {
+                        ui.same_line_with_spacing(0.0, scale.gap_xs());
+                        let color = if s.is_solved || i < s.stage {
+                            [0.2, 0.9, 0.5, 1.0]
+                        } else if i == s.stage {
+                            [0.95, 0.78, 0.25, 1.0]
+                        } else {
+                            [0.28, 0.32, 0.42, 0.7]
+                        };
+                        ui.text_colored(color, (i + 1).to_string());
+                    }
+                } else if s.stage_count > 1 {
+                    let label = match s.stage_difficulty {
+                        Some(d) => format!(
+                            "Stage {}/{}  |  {}",
+                            s.stage + 1,
+                            s.stage_count,
+                            difficulty_label(d)
+                        ),
+                        None => format!("Stage {}/{}", s.stage + 1, s.stage_count),
+                    };
+                    util::text_muted(ui, &label);
+                }
                 if s.hint_count > 0 {
                     let label = format!(
                         "{} {}",
@@ -37,7 +96,12 @@ pub fn register(scene: &Scenegraph, state: Rc<RefCell<UiState>>) {
                     ui.text_colored([0.9, 0.6, 0.2, 0.8], label);
                 }
                 if s.is_solved {
-                    ui.text_colored([0.2, 0.9, 0.5, 1.0], "SOLVED!");
+                    let label = if s.scene {
+                        format!("SOLVED!  All {} models", s.stage_count)
+                    } else {
+                        "SOLVED!".to_string()
+                    };
+                    ui.text_colored([0.2, 0.9, 0.5, 1.0], label);
                 }
             });
 
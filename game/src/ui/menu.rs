@@ -1,14 +1,20 @@
 use super::util::{self as util, Scale};
 use crate::formosaic::UiState;
+use crate::level::share::ShareCode;
 use crate::ui::state_machine::{UiInput, UiScreen};
 use formosaic_engine::architecture::scene::node::{scenegraph::Scenegraph, ui_node::UiNode};
 use imgui::*;
 use std::{cell::RefCell, rc::Rc};
 
 pub fn register(scene: &Scenegraph, state: Rc<RefCell<UiState>>) {
+    let mut code_entry = CodeEntry::default();
+    // The mode toggles live in a settings window rather than the header,
+    // which has no room for them on a phone.
+    let mut settings_open = false;
     let menu = UiNode::new("menu", move |ui, w, h, ctx| {
         let s = state.borrow();
         if s.screen != UiScreen::MainMenu {
+            settings_open = false;
             return;
         }
         let scale = Scale::from_screen(w, h, s.is_touch);
@@ -16,6 +22,32 @@ pub fn register(scene: &Scenegraph, state: Rc<RefCell<UiState>>) {
         let levels = s.levels.clone();
         let _is_dl = s.is_downloading;
         let _is_loading = s.is_loading;
+        let streak = s.daily_streak;
+        let can_continue = s.has_saved_puzzle;
+        let share_error = s.share_error.clone();
+        let stages_label = format!("Stages: {}", s.stage_mode);
+        let view_label = if s.anamorphic_mode {
+            "View: Point"
+        } else {
+            "View: Axis"
+        };
+        let roll_label = if s.roll_mode { "Roll: On" } else { "Roll: Off" };
+        let tint_label = if s.mosaic_mode {
+            "Tint: On"
+        } else {
+            "Tint: Off"
+        };
+        let motion_label = if s.motion_mode {
+            "Motion: On"
+        } else {
+            "Motion: Off"
+        };
+        let palette_label = format!("Hue: {}", s.warmth_palette.label());
+        let daily_label = if s.daily_done {
+            "Daily (done)"
+        } else {
+            "Daily"
+        };
 
         let menu_flags = WindowFlags::NO_DECORATION
             | WindowFlags::NO_MOVE
@@ -40,7 +72,8 @@ pub fn register(scene: &Scenegraph, state: Rc<RefCell<UiState>>) {
                     let btn_h = (h * 0.08).max(scale.btn_h());
                     let title_h = (h * 0.05).max(scale.su(32.0));
                     let btns_y = h - btn_h - scale.su(80.0);
-                    let list_h = (btns_y - title_h - pad).max(0.0);
+                    let session_y = btns_y - btn_h - pad;
+                    let list_h = (session_y - title_h - pad).max(0.0);
                     let row_h = (h * 0.12).max(scale.su(80.0));
                     let m = pad;
                     let row_w = w - m * 2.0;
@@ -54,6 +87,16 @@ pub fn register(scene: &Scenegraph, state: Rc<RefCell<UiState>>) {
                         .build(|| {
                             ui.set_cursor_pos([pad, (title_h - 16.0) * 0.5]);
                             ui.text_colored([0.85, 0.62, 0.18, 1.0], "FORMOSAIC");
+                            if streak > 0 {
+                                ui.same_line_with_spacing(0.0, pad);
+                                util::text_muted(ui, &streak_label(streak));
+                            }
+                            let st_w = scale.su(110.0);
+                            let st_h = title_h - scale.gap_sm();
+                            ui.set_cursor_pos([w - st_w - pad, (title_h - st_h) * 0.5]);
+                            if ui.button_with_size("Settings", [st_w, st_h]) {
+                                settings_open = true;
+                            }
                         });
                     drop(_tok);
 
@@ -94,6 +137,10 @@ pub fn register(scene: &Scenegraph, state: Rc<RefCell<UiState>>) {
                                                 dc,
                                                 util::diff_label_str(level.difficulty),
                                             );
+                                            if level.upright {
+                                                ui.same_line_with_spacing(0.0, ip * 0.5);
+                                                util::upright_badge(ui);
+                                            }
                                             if let Some(t) = level.best_time_secs {
                                                 ui.same_line_with_spacing(0.0, ip * 0.5);
                                                 ui.text_colored(
@@ -101,6 +148,10 @@ pub fn register(scene: &Scenegraph, state: Rc<RefCell<UiState>>) {
                                                     format!("{:.1}s", t),
                                                 );
                                             }
+                                            if level.stars > 0 {
+                                                ui.same_line_with_spacing(0.0, ip * 0.5);
+                                                util::stars(ui, level.stars);
+                                            }
                                             ui.set_cursor_pos([ip, ip + scale.su(22.0)]);
                                             util::text_dim(ui, &util::truncate(&level.author, 22));
                                             ui.set_cursor_pos([
@@ -121,31 +172,64 @@ pub fn register(scene: &Scenegraph, state: Rc<RefCell<UiState>>) {
                         });
                     drop(_tok2);
 
-                    let half = (w - pad * 3.0) * 0.5;
-                    ui.set_cursor_pos([pad, btns_y]);
+                    let third = (w - pad * 4.0) / 3.0;
+                    let half = (w - pad * 3.0) / 2.0;
                     let busy = _is_dl || _is_loading;
                     let _dis = ui.begin_disabled(busy);
-                    if ui.button_with_size("+ Fetch Online", [half, btn_h]) {
+                    // Continue shares the session row when there is a
+                    // puzzle to continue.
+                    let session_w = if can_continue { third } else { half };
+                    ui.set_cursor_pos([pad, session_y]);
+                    if can_continue {
+                        if ui.button_with_size("Continue", [session_w, btn_h]) {
+                            ctx.push_ui_action(UiInput::Continue);
+                        }
+                        ui.same_line_with_spacing(0.0, pad);
+                    }
+                    if ui.button_with_size("Marathon", [session_w, btn_h]) {
+                        ctx.push_ui_action(UiInput::PlayMarathon);
+                    }
+                    ui.same_line_with_spacing(0.0, pad);
+                    if ui.button_with_size("Time Attack", [session_w, btn_h]) {
+                        ctx.push_ui_action(UiInput::PlayTimeAttack);
+                    }
+                    ui.set_cursor_pos([pad, btns_y]);
+                    if ui.button_with_size(daily_label, [third, btn_h]) {
+                        ctx.push_ui_action(UiInput::PlayDaily);
+                    }
+                    ui.same_line_with_spacing(0.0, pad);
+                    if ui.button_with_size("+ Fetch Online", [third, btn_h]) {
                         ctx.push_ui_action(UiInput::FetchOnline);
                     }
                     ui.same_line_with_spacing(0.0, pad);
-                    if ui.button_with_size("Random", [half, btn_h]) {
+                    if ui.button_with_size("Random", [third, btn_h]) {
                         ctx.push_ui_action(UiInput::RandomSaved);
                     }
+                    ui.set_cursor_pos([pad, btns_y + btn_h + pad]);
+                    let code_btn = [third, ui.frame_height()];
+                    if let Some(code) = code_entry.draw(ui, w - third - pad * 3.0, code_btn, pad) {
+                        ctx.push_ui_action(UiInput::PlayCode(code));
+                    }
                     drop(_dis);
+                    if let Some(err) = code_entry.message(share_error.as_deref()) {
+                        ui.set_cursor_pos([pad, btns_y + btn_h + pad * 2.0 + ui.frame_height()]);
+                        ui.text_colored(ERROR_COLOR, err);
+                    }
                     ui.dummy([0.0, pad]);
                 } else {
                     let bar_h = scale.su(28.0);
                     let hdr_h = scale.su(20.0);
                     let row_h = scale.su(24.0);
-                    let footer_h = scale.su(16.0);
+                    let footer_h = scale.su(26.0);
 
                     let cx_name = pad;
-                    let cx_auth = w * 0.30;
-                    let cx_diff = w * 0.52;
-                    let cx_best = w * 0.64;
-                    let cx_play = w * 0.75;
-                    let play_w = w - cx_play - pad;
+                    let cx_auth = w * 0.28;
+                    let cx_diff = w * 0.46;
+                    let cx_best = w * 0.56;
+                    let cx_score = w * 0.65;
+                    let cx_play = w * 0.78;
+                    let edit_w = scale.su(44.0);
+                    let play_w = w - cx_play - pad - edit_w - scale.gap_xs();
 
                     let _tok =
                         ui.push_style_color(imgui::StyleColor::ChildBg, [0.03, 0.04, 0.06, 0.95]);
@@ -160,11 +244,22 @@ pub fn register(scene: &Scenegraph, state: Rc<RefCell<UiState>>) {
                             let gap = scale.gap_xs();
                             let ver_w = scale.su(32.0);
 
+                            let d_w = scale.su(90.0);
                             let r_x = w - ver_w - pad - gap - r_w;
                             let n_x = r_x - gap - n_w;
+                            let d_x = n_x - gap - d_w;
+                            let s_w = scale.su(90.0);
+                            let m_x = d_x - gap - s_w;
+                            let a_x = m_x - gap - s_w;
+                            let s_x = a_x - gap - s_w;
+                            let c_x = s_x - gap - s_w;
 
                             ui.set_cursor_pos([pad, (bar_h - 14.0) * 0.5]);
                             ui.text_colored([0.85, 0.62, 0.18, 1.0], "FORMOSAIC");
+                            if streak > 0 {
+                                ui.same_line_with_spacing(0.0, pad);
+                                util::text_muted(ui, &streak_label(streak));
+                            }
                             ui.set_cursor_pos([w - ver_w - pad, (bar_h - 13.0) * 0.5]);
                             ui.text_colored([0.28, 0.34, 0.46, 0.6], "v0.1");
                             ui.set_cursor_pos([r_x, scale.su(2.0)]);
@@ -176,7 +271,30 @@ pub fn register(scene: &Scenegraph, state: Rc<RefCell<UiState>>) {
                             if ui.button_with_size("[N] Fetch Online", [n_w, btn_h_bar]) {
                                 ctx.push_ui_action(UiInput::FetchOnline);
                             }
+                            ui.set_cursor_pos([d_x, scale.su(2.0)]);
+                            if ui.button_with_size(daily_label, [d_w, btn_h_bar]) {
+                                ctx.push_ui_action(UiInput::PlayDaily);
+                            }
+                            ui.set_cursor_pos([m_x, scale.su(2.0)]);
+                            if ui.button_with_size("Marathon", [s_w, btn_h_bar]) {
+                                ctx.push_ui_action(UiInput::PlayMarathon);
+                            }
+                            ui.set_cursor_pos([a_x, scale.su(2.0)]);
+                            if ui.button_with_size("Time Attack", [s_w, btn_h_bar]) {
+                                ctx.push_ui_action(UiInput::PlayTimeAttack);
+                            }
                             drop(_dis2);
+                            ui.set_cursor_pos([s_x, scale.su(2.0)]);
+                            if ui.button_with_size("Settings", [s_w, btn_h_bar]) {
+                                settings_open = true;
+                            }
+                            if can_continue {
+                                ui.set_cursor_pos([c_x, scale.su(2.0)]);
+                                let _dis3 = ui.begin_disabled(_is_dl || _is_loading);
+                                if ui.button_with_size("Continue", [s_w, btn_h_bar]) {
+                                    ctx.push_ui_action(UiInput::Continue);
+                                }
+                            }
                         });
                     drop(_tok);
 
@@ -196,6 +314,8 @@ pub fn register(scene: &Scenegraph, state: Rc<RefCell<UiState>>) {
                             util::text_dim(ui, "Difficulty");
                             ui.set_cursor_pos([cx_best, vy]);
                             util::text_dim(ui, "Best");
+                            ui.set_cursor_pos([cx_score, vy]);
+                            util::text_dim(ui, "Score");
                         });
                     drop(_tok_hdr);
 
@@ -230,6 +350,10 @@ pub fn register(scene: &Scenegraph, state: Rc<RefCell<UiState>>) {
                                             let vy = (row_h - 13.0) * 0.5;
                                             ui.set_cursor_pos([cx_name, vy]);
                                             util::text_body(ui, &util::truncate(&level.name, 26));
+                                            if level.model_count() > 1 {
+                                                ui.same_line_with_spacing(0.0, scale.gap_xs());
+                                                util::scene_badge(ui, level.model_count());
+                                            }
                                             ui.set_cursor_pos([cx_auth, vy]);
                                             ui.text_colored(
                                                 [0.50, 0.56, 0.68, 0.85],
@@ -240,6 +364,10 @@ pub fn register(scene: &Scenegraph, state: Rc<RefCell<UiState>>) {
                                                 dc,
                                                 util::diff_label_str(level.difficulty),
                                             );
+                                            if level.upright {
+                                                ui.same_line_with_spacing(0.0, scale.gap_xs());
+                                                util::upright_badge(ui);
+                                            }
                                             ui.set_cursor_pos([cx_best, vy]);
                                             if let Some(t) = level.best_time_secs {
                                                 ui.text_colored(
@@ -252,6 +380,20 @@ pub fn register(scene: &Scenegraph, state: Rc<RefCell<UiState>>) {
                                                     "\u{2014}",
                                                 );
                                             }
+                                            ui.set_cursor_pos([cx_score, vy]);
+                                            if let Some(points) = level.best_score {
+                                                util::stars(ui, level.stars);
+                                                ui.same_line_with_spacing(0.0, scale.gap_xs());
+                                                ui.text_colored(
+                                                    [0.60, 0.65, 0.75, 0.85],
+                                                    points.to_string(),
+                                                );
+                                            } else {
+                                                ui.text_colored(
+                                                    [0.28, 0.32, 0.42, 0.5],
+                                                    "\u{2014}",
+                                                );
+                                            }
                                             ui.set_cursor_pos([cx_play, scale.su(1.0)]);
                                             if ui.button_with_size(
                                                 format!("Play##{}", level.id),
@@ -261,6 +403,19 @@ pub fn register(scene: &Scenegraph, state: Rc<RefCell<UiState>>) {
                                                     level.id.clone(),
                                                 ));
                                             }
+                                            // An authored puzzle has one axis, so
+                                            // composite scenes are not editable.
+                                            ui.same_line_with_spacing(0.0, scale.gap_xs());
+                                            if level.parts.is_empty()
+                                                && ui.button_with_size(
+                                                    format!("Edit##{}", level.id),
+                                                    [edit_w, row_h - scale.su(2.0)],
+                                                )
+                                            {
+                                                ctx.push_ui_action(UiInput::EditLevel(
+                                                    level.id.clone(),
+                                                ));
+                                            }
                                         });
                                     drop(_t3);
                                 }
@@ -268,17 +423,114 @@ pub fn register(scene: &Scenegraph, state: Rc<RefCell<UiState>>) {
                         });
                     drop(_tok2);
 
-                    ui.set_cursor_pos([pad, h - footer_h]);
+                    let footer_y = h - footer_h + (footer_h - ui.frame_height()) * 0.5;
+                    ui.set_cursor_pos([pad, footer_y + scale.su(3.0)]);
                     ui.text_colored(
                         [0.28, 0.34, 0.46, 0.45],
                         "Models via Poly Pizza (poly.pizza) CC-BY  |  Cactus by SoyMaria",
                     );
+                    if let Some(err) = code_entry.message(share_error.as_deref()) {
+                        ui.same_line_with_spacing(0.0, pad);
+                        ui.text_colored(ERROR_COLOR, err);
+                    }
+                    let code_w = scale.su(260.0);
+                    let code_btn = [scale.su(90.0), ui.frame_height()];
+                    let gap = scale.gap_xs();
+                    ui.set_cursor_pos([w - pad - code_btn[0] - gap - code_w, footer_y]);
+                    let _dis = ui.begin_disabled(_is_dl || _is_loading);
+                    if let Some(code) = code_entry.draw(ui, code_w, code_btn, gap) {
+                        ctx.push_ui_action(UiInput::PlayCode(code));
+                    }
                 }
 
                 drop(_ip2);
             });
         drop(_wp);
         drop(_ip);
+
+        if settings_open {
+            let toggles = [
+                (stages_label.as_str(), UiInput::CycleStages),
+                (view_label, UiInput::ToggleProjection),
+                (roll_label, UiInput::ToggleRoll),
+                (tint_label, UiInput::ToggleMosaic),
+                (motion_label, UiInput::ToggleMotion),
+                (palette_label.as_str(), UiInput::CyclePalette),
+            ];
+            let btn = [scale.su(220.0), scale.btn_h()];
+            let _wp = ui.push_style_var(imgui::StyleVar::WindowPadding([
+                scale.pad_w(),
+                scale.pad_w(),
+            ]));
+            let _win_bg =
+                ui.push_style_color(imgui::StyleColor::WindowBg, [0.03, 0.04, 0.06, 0.96]);
+            ui.window("##menu_settings")
+                .flags(util::popup_flags() | WindowFlags::ALWAYS_AUTO_RESIZE)
+                .position([w * 0.5, h * 0.5], Condition::Always)
+                .position_pivot([0.5, 0.5])
+                .focused(true)
+                .build(|| {
+                    util::title(ui, "Settings");
+                    util::gap(ui, scale.gap_sm());
+                    for (label, input) in toggles {
+                        if ui.button_with_size(label, btn) {
+                            ctx.push_ui_action(input);
+                        }
+                    }
+                    util::gap(ui, scale.gap_sm());
+                    if ui.button_with_size("Done", btn) {
+                        settings_open = false;
+                    }
+                });
+        }
     });
     scene.add_node(Rc::new(RefCell::new(menu)));
 }
+
+const ERROR_COLOR: [f32; 4] = [0.90, 0.40, 0.35, 1.0];
+
+/// Text box and button for playing a share code.
+#[derive(Default)]
+struct CodeEntry {
+    text: String,
+    /// Whether the last code submitted failed to decode.
+    invalid: bool,
+}
+
+impl CodeEntry {
+    /// Draw at the cursor.  Returns the code once a valid one is submitted
+    /// with the button or Enter.
+    fn draw(&mut self, ui: &Ui, input_w: f32, btn: [f32; 2], gap: f32) -> Option<ShareCode> {
+        ui.set_next_item_width(input_w);
+        let entered = ui
+            .input_text("##share_code", &mut self.text)
+            .hint("Share code")
+            .enter_returns_true(true)
+            .build();
+        ui.same_line_with_spacing(0.0, gap);
+        let pressed = ui.button_with_size("Play Code", btn);
+        if !(entered || pressed) || self.text.trim().is_empty() {
+            return None;
+        }
+        let code = ShareCode::decode(&self.text);
+        self.invalid = code.is_none();
+        if code.is_some() {
+            self.text.clear();
+        }
+        code
+    }
+
+    /// What to tell the player about the last code: a typo first, then
+    /// `share_error` from playing it.
+    fn message<'a>(&self, share_error: Option<&'a str>) -> Option<&'a str> {
+        if self.invalid {
+            Some("Not a valid share code")
+        } else {
+            share_error
+        }
+    }
+}
+
+fn streak_label(days: u32) -> String {
+    format!("{days} day streak")
+}
@@ -1,4 +1,5 @@
 pub mod credits;
+pub mod editor;
 pub mod hint_warmth;
 pub mod hud;
 pub mod loading;
@@ -1,20 +1,67 @@
+use cgmath::Vector3;
+
+use crate::level::share::ShareCode;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum UiScreen {
     MainMenu,
     Game,
     Credits,
+    /// The puzzle editor, over the level being edited.
+    Editor,
+}
+
+/// A change made in the puzzle editor.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EditorEdit {
+    /// New scramble axis, in model space.
+    Axis(Vector3<f32>),
+    /// New displacement range, in model units.
+    Displacement { min: f32, max: f32 },
+    /// How far the preview is scrambled, 0 = solved.
+    Preview(f32),
+    /// Draw new displacements for the same axis and range.
+    Reseed,
+    /// Move the camera onto the axis.
+    ViewAxis,
 }
 
 #[derive(Debug, Clone)]
 pub enum UiInput {
     PlayLevel(String),
+    PlayDaily,
+    PlayMarathon,
+    PlayTimeAttack,
     FetchOnline,
     RandomSaved,
+    /// Resume the puzzle left unfinished last time.
+    Continue,
+    /// Play the puzzle a share code names.
+    PlayCode(ShareCode),
+    CycleStages,
+    ToggleProjection,
+    ToggleRoll,
+    ToggleMosaic,
+    ToggleMotion,
+    CyclePalette,
     Hint,
     EscapePressed,
     MenuPressed,
     BackToMenuPressed,
     ArtistLinkPressed(String),
+    /// A marathon or time attack finished; show its results.
+    SessionOver,
+    /// Watch the level's best solve.
+    WatchReplay,
+    /// Play the level's best-solve puzzle again against its ghost.
+    RaceReplay,
+    /// Open a saved level in the puzzle editor.
+    EditLevel(String),
+    Edit(EditorEdit),
+    /// Store the edited puzzle in the level.
+    SaveAuthored,
+    /// Drop the level's authored puzzle; plays search for an axis again.
+    ClearAuthored,
 }
 
 #[derive(Debug, Clone)]
@@ -22,10 +69,27 @@ pub enum UiTransition {
     ShowMainMenu,
     ShowCredits,
     StartLevel(String),
+    StartDaily,
+    StartMarathon,
+    StartTimeAttack,
     FetchOnline,
     RandomSaved,
+    Continue,
+    PlayCode(ShareCode),
+    CycleStages,
+    ToggleProjection,
+    ToggleRoll,
+    ToggleMosaic,
+    ToggleMotion,
+    CyclePalette,
     AdvanceHint,
     OpenArtistLink(String),
+    WatchReplay,
+    RaceReplay,
+    StartEditor(String),
+    Edit(EditorEdit),
+    SaveAuthored,
+    ClearAuthored,
 }
 
 /// Context passed to the state machine for guard evaluation.
@@ -65,6 +129,30 @@ impl UiStateMachine {
                     self.screen = UiScreen::Game;
                     vec![UiTransition::StartLevel(id)]
                 }
+                UiInput::PlayDaily => {
+                    if !ctx.is_downloading && !ctx.is_loading {
+                        self.screen = UiScreen::Game;
+                        vec![UiTransition::StartDaily]
+                    } else {
+                        Vec::new()
+                    }
+                }
+                UiInput::PlayMarathon => {
+                    if !ctx.is_downloading && !ctx.is_loading {
+                        self.screen = UiScreen::Game;
+                        vec![UiTransition::StartMarathon]
+                    } else {
+                        Vec::new()
+                    }
+                }
+                UiInput::PlayTimeAttack => {
+                    if !ctx.is_downloading && !ctx.is_loading {
+                        self.screen = UiScreen::Game;
+                        vec![UiTransition::StartTimeAttack]
+                    } else {
+                        Vec::new()
+                    }
+                }
                 UiInput::FetchOnline => {
                     if !ctx.is_downloading && !ctx.is_loading {
                         self.screen = UiScreen::Game;
@@ -81,6 +169,36 @@ impl UiStateMachine {
                         Vec::new()
                     }
                 }
+                UiInput::Continue => {
+                    if !ctx.is_downloading && !ctx.is_loading {
+                        self.screen = UiScreen::Game;
+                        vec![UiTransition::Continue]
+                    } else {
+                        Vec::new()
+                    }
+                }
+                UiInput::PlayCode(code) => {
+                    if !ctx.is_downloading && !ctx.is_loading {
+                        self.screen = UiScreen::Game;
+                        vec![UiTransition::PlayCode(code)]
+                    } else {
+                        Vec::new()
+                    }
+                }
+                UiInput::EditLevel(id) => {
+                    if !ctx.is_downloading && !ctx.is_loading {
+                        self.screen = UiScreen::Editor;
+                        vec![UiTransition::StartEditor(id)]
+                    } else {
+                        Vec::new()
+                    }
+                }
+                UiInput::CycleStages => vec![UiTransition::CycleStages],
+                UiInput::ToggleProjection => vec![UiTransition::ToggleProjection],
+                UiInput::ToggleRoll => vec![UiTransition::ToggleRoll],
+                UiInput::ToggleMosaic => vec![UiTransition::ToggleMosaic],
+                UiInput::ToggleMotion => vec![UiTransition::ToggleMotion],
+                UiInput::CyclePalette => vec![UiTransition::CyclePalette],
                 _ => Vec::new(),
             },
             UiScreen::Game => match input {
@@ -103,6 +221,10 @@ impl UiStateMachine {
                         vec![UiTransition::ShowMainMenu]
                     }
                 }
+                UiInput::SessionOver => {
+                    self.screen = UiScreen::Credits;
+                    vec![UiTransition::ShowCredits]
+                }
                 _ => Vec::new(),
             },
             UiScreen::Credits => match input {
@@ -111,6 +233,28 @@ impl UiStateMachine {
                     vec![UiTransition::ShowMainMenu]
                 }
                 UiInput::ArtistLinkPressed(url) => vec![UiTransition::OpenArtistLink(url)],
+                UiInput::WatchReplay => {
+                    self.screen = UiScreen::Game;
+                    vec![UiTransition::WatchReplay]
+                }
+                UiInput::RaceReplay => {
+                    self.screen = UiScreen::Game;
+                    vec![UiTransition::RaceReplay]
+                }
+                _ => Vec::new(),
+            },
+            UiScreen::Editor => match input {
+                UiInput::Edit(edit) if !ctx.is_loading => vec![UiTransition::Edit(edit)],
+                UiInput::SaveAuthored if !ctx.is_loading => vec![UiTransition::SaveAuthored],
+                UiInput::ClearAuthored if !ctx.is_loading => vec![UiTransition::ClearAuthored],
+                UiInput::BackToMenuPressed | UiInput::EscapePressed | UiInput::MenuPressed => {
+                    if ctx.is_loading {
+                        Vec::new()
+                    } else {
+                        self.screen = UiScreen::MainMenu;
+                        vec![UiTransition::ShowMainMenu]
+                    }
+                }
                 _ => Vec::new(),
             },
         }
@@ -151,6 +151,32 @@ pub fn diff_label_str(d: f32) -> &'static str {
     }
 }
 
+/// Short tag for a level that only an upright camera solves.
+pub fn upright_badge(ui: &Ui) {
+    ui.text_colored([0.45, 0.75, 0.95, 0.9], "UP");
+}
+
+/// Short tag for a composite scene of `models` models.
+pub fn scene_badge(ui: &Ui, models: usize) {
+    ui.text_colored([0.55, 0.85, 0.60, 0.9], format!("x{models}"));
+}
+
+/// Three stars, the first `stars` lit.  ASCII, as the default font has no
+/// star glyph.
+pub fn stars(ui: &Ui, stars: u8) {
+    for i in 0..3 {
+        if i > 0 {
+            ui.same_line_with_spacing(0.0, 2.0);
+        }
+        let color = if i < stars {
+            [0.95, 0.78, 0.25, 1.0]
+        } else {
+            [0.28, 0.32, 0.42, 0.5]
+        };
+        ui.text_colored(color, "*");
+    }
+}
+
 pub fn truncate(s: &str, max: usize) -> String {
     if s.len() > max {
         format!("{}..", &s[..max])
@@ -164,6 +190,7 @@ pub fn hint_tier_label(tier: HintTier) -> &'static str {
         HintTier::None => "None",
         HintTier::WarmCold => "Warm/Cold",
         HintTier::AxisPlane => "Axis Plane",
+        HintTier::Outline => "Outline",
         HintTier::GhostSnap => "Ghost Snap",
     }
 }
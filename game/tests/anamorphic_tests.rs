@@ -0,0 +1,209 @@
+mod common;
+
+use cgmath::{InnerSpace, Matrix4, SquareMatrix, Vector3};
+use common::{cloud, vertex};
+use formosaic::puzzle::hints::HintSystem;
+use formosaic::puzzle::puzzle_params::{PuzzleParams, ScrambleStrategy};
+use formosaic::puzzle::scramble_math::{compute_anamorphic_offsets, lerp_positions};
+use formosaic::puzzle::scrambler::{
+    compute_raw_anamorphic_offsets, compute_staged_offsets, make_scrambled_orbit_from_eye,
+    point_alignment, ScrambleStage, ScrambleState, ScrambleTarget,
+};
+use formosaic::puzzle::seed::PuzzleSeed;
+
+const SEED: PuzzleSeed = PuzzleSeed::new(0xa4a);
+
+/// Every vertex of `moved` lies on the ray from `eye` through the matching
+/// vertex of `orig`, on the far side.
+fn assert_on_rays(orig: &[f32], moved: &[f32], eye: Vector3<f32>) {
+    for i in 0..orig.len() / 3 {
+        let a = (vertex(orig, i) - eye).normalize();
+        let b = (vertex(moved, i) - eye).normalize();
+        assert!(a.dot(b) > 1.0 - 1e-5, "vertex {i} left its ray");
+        assert!(
+            (vertex(moved, i) - eye).magnitude() >= (vertex(orig, i) - eye).magnitude() - 1e-5,
+            "vertex {i} moved towards the eye"
+        );
+    }
+}
+
+// ─── Offsets ─────────────────────────────────────────────────────────────────
+
+#[test]
+fn projection_from_eye_is_unchanged_at_any_t() {
+    let verts = cloud(32);
+    let eye = Vector3::new(1.5, 2.0, 12.0);
+    let clusters: Vec<usize> = (0..32).collect();
+    let offsets =
+        compute_anamorphic_offsets(&verts, &clusters, eye, 0.1, 0.5, Matrix4::identity(), SEED);
+    for t in [0.25, 0.5, 1.0] {
+        assert_on_rays(&verts, &lerp_positions(&verts, &offsets, t), eye);
+    }
+}
+
+#[test]
+fn centroid_slides_by_the_drawn_amount() {
+    let verts = cloud(16);
+    let eye = Vector3::new(0.0, 0.0, -10.0);
+    let clusters: Vec<usize> = (0..16).collect();
+    let offsets =
+        compute_anamorphic_offsets(&verts, &clusters, eye, 0.1, 0.5, Matrix4::identity(), SEED);
+    let moved = lerp_positions(&verts, &offsets, 1.0);
+    for tri in 0..16 {
+        let c =
+            (vertex(&verts, tri * 3) + vertex(&verts, tri * 3 + 1) + vertex(&verts, tri * 3 + 2))
+                / 3.0;
+        let m =
+            (vertex(&moved, tri * 3) + vertex(&moved, tri * 3 + 1) + vertex(&moved, tri * 3 + 2))
+                / 3.0;
+        let slide = (m - eye).magnitude() - (c - eye).magnitude();
+        assert!(
+            (0.1 - 1e-4..=0.5 + 1e-4).contains(&slide),
+            "tri {tri} slid {slide}"
+        );
+    }
+}
+
+#[test]
+fn mesh_transform_keeps_rays_in_model_space() {
+    let verts = cloud(8);
+    let transform =
+        Matrix4::from_translation(Vector3::new(3.0, -1.0, 2.0)) * Matrix4::from_scale(2.0);
+    let eye = Vector3::new(0.0, 5.0, 20.0);
+    let offsets = compute_raw_anamorphic_offsets(
+        &[verts.as_slice()],
+        &[transform],
+        eye,
+        0.1,
+        0.5,
+        ScrambleStrategy::PerTriangle,
+        SEED,
+    );
+    let to_model = |v: &[f32]| -> Vec<f32> {
+        v.chunks_exact(3)
+            .flat_map(|p| {
+                let w = transform * Vector3::new(p[0], p[1], p[2]).extend(1.0);
+                [w.x, w.y, w.z]
+            })
+            .collect()
+    };
+    let moved = lerp_positions(&verts, &offsets[0], 1.0);
+    assert_on_rays(&to_model(&verts), &to_model(&moved), eye);
+}
+
+#[test]
+fn clusters_scale_as_one_piece() {
+    // Two triangles sharing an edge stay joined when they share a cluster.
+    let verts = vec![
+        0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 1.0, 0.0, //
+        0.0, 0.0, 0.0, 1.0, 1.0, 0.0, 0.0, 1.0, 0.0,
+    ];
+    let offsets = compute_anamorphic_offsets(
+        &verts,
+        &[0, 0],
+        Vector3::new(0.2, 0.4, 8.0),
+        0.1,
+        0.5,
+        Matrix4::identity(),
+        SEED,
+    );
+    let moved = lerp_positions(&verts, &offsets, 1.0);
+    assert_eq!(vertex(&moved, 0), vertex(&moved, 3));
+    assert_eq!(vertex(&moved, 2), vertex(&moved, 4));
+}
+
+#[test]
+fn eye_target_stages_use_anamorphic_offsets() {
+    let verts = cloud(12);
+    let eye = Vector3::new(1.0, 1.0, 9.0);
+    let params = PuzzleParams {
+        min_disp: 0.1,
+        max_disp: 0.5,
+        ..PuzzleParams::default_for(1.0)
+    };
+    let staged = compute_staged_offsets(
+        &[verts.as_slice()],
+        &[Matrix4::identity()],
+        &[ScrambleTarget::Eye(eye)],
+        &[vec![0; 12]],
+        &params,
+        SEED,
+    );
+    let raw = compute_raw_anamorphic_offsets(
+        &[verts.as_slice()],
+        &[Matrix4::identity()],
+        eye,
+        0.1,
+        0.5,
+        ScrambleStrategy::PerTriangle,
+        SEED,
+    );
+    assert_eq!(staged, vec![raw]);
+}
+
+// ─── Solve detection ─────────────────────────────────────────────────────────
+
+#[test]
+fn point_alignment_matches_orbit_angle() {
+    let target = Vector3::new(0.0, 0.0, 0.0);
+    let eye = Vector3::new(0.0, 0.0, 5.0);
+    assert!((point_alignment(eye, eye, target) - 1.0).abs() < 1e-6);
+    for deg in [5.0f32, 30.0, 90.0, 180.0] {
+        let a = deg.to_radians();
+        let cam = Vector3::new(a.sin(), 0.0, a.cos()) * 5.0;
+        assert!(
+            (point_alignment(cam, eye, target) - a.cos()).abs() < 1e-4,
+            "{deg}° off"
+        );
+    }
+}
+
+#[test]
+fn eye_stage_is_solved_from_one_point_only() {
+    let target = Vector3::new(0.0, 0.0, 0.0);
+    let eye = Vector3::new(0.0, 0.0, 5.0);
+    let state = ScrambleState {
+        stages: vec![ScrambleStage {
+            solution_dir: -Vector3::unit_z(),
+            solution_eye: Some(eye),
+            solution_up: None,
+            scramble_offsets: vec![vec![]],
+        }],
+        current_stage: 0,
+        params: PuzzleParams::default_for(1.0),
+        original_positions: vec![vec![]],
+        seed: SEED,
+        pose: None,
+    };
+    let up = Vector3::unit_y();
+    let at_eye = state.alignment(eye, -Vector3::unit_z(), up, target);
+    let opposite = state.alignment(-eye, Vector3::unit_z(), up, target);
+    assert!(at_eye > 0.999);
+    assert!(
+        opposite < -0.999,
+        "the far pole must not solve a point puzzle"
+    );
+}
+
+#[test]
+fn orbit_from_eye_starts_far_from_the_eye() {
+    let target = Vector3::new(1.0, 0.0, 0.0);
+    let eye = target + Vector3::new(0.0, 0.6, 0.8) * 4.0;
+    for s in 0..16 {
+        let (_, start) = make_scrambled_orbit_from_eye(target, 4.0, eye, PuzzleSeed::new(s));
+        assert!(((start - target).magnitude() - 4.0).abs() < 1e-4);
+        assert!(point_alignment(start, eye, target) < 0.5 + 1e-6);
+    }
+}
+
+// ─── Hints ───────────────────────────────────────────────────────────────────
+
+#[test]
+fn point_warmth_only_warms_towards_the_eye() {
+    let mut hints = HintSystem::new();
+    let dir = -Vector3::unit_z();
+    assert!(hints.update_point(0.1, 1.0, dir).warmth > 0.99);
+    assert!(hints.update_point(0.1, -1.0, dir).warmth < 0.01);
+    // An axis puzzle keeps the opposite pole warm.
+    assert!(hints.update(0.1, -dir, dir).warmth > 0.99);
+}
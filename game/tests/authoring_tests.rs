@@ -0,0 +1,209 @@
+mod common;
+
+use cgmath::{InnerSpace, Vector3};
+use common::cloud;
+use formosaic::level::storage::LevelMeta;
+use formosaic::puzzle::authoring::{direction_at, map_position, AuthoredPuzzle, AxisHeatmap};
+use formosaic::puzzle::entropy::{analyse_fixed_axes, analyse_fixed_axis, AnalysisBackend};
+use formosaic::puzzle::puzzle_params::{PuzzleParams, ScrambleStrategy};
+use formosaic::puzzle::search::{SearchHandle, SearchOptions};
+use formosaic::puzzle::seed::PuzzleSeed;
+
+fn per_triangle(positions: &[f32]) -> Vec<usize> {
+    (0..positions.len() / 9).flat_map(|t| [t; 3]).collect()
+}
+
+fn puzzle() -> AuthoredPuzzle {
+    AuthoredPuzzle {
+        axis: Vector3::new(0.6, 0.0, 0.8),
+        min_disp: 0.02,
+        max_disp: 0.12,
+        seed: PuzzleSeed::new(0x9e37_79b9_7f4a_7c15),
+    }
+}
+
+fn meta(authored: Option<AuthoredPuzzle>) -> LevelMeta {
+    LevelMeta {
+        id: "abc".into(),
+        name: "Abc".into(),
+        author: "A".into(),
+        license: "CC0".into(),
+        source_url: "https://example.com".into(),
+        model_file: "model.glb".into(),
+        best_time_secs: None,
+        best_score: None,
+        stars: 0,
+        play_count: 0,
+        difficulty: 0.5,
+        scramble_strategy: ScrambleStrategy::PerTriangle,
+        upright: false,
+        authored,
+        parts: Vec::new(),
+    }
+}
+
+// ─── Authored puzzle ─────────────────────────────────────────────────────────
+
+#[test]
+fn meta_str_round_trips_exactly() {
+    let p = puzzle().with_axis(Vector3::new(0.3, -0.7, 0.2));
+    assert_eq!(AuthoredPuzzle::from_meta_str(&p.to_meta_str()), Some(p));
+    assert_eq!(
+        puzzle().to_meta_str(),
+        "0.6,0,0.8/0.02,0.12/9e3779b97f4a7c15"
+    );
+}
+
+#[test]
+fn meta_str_normalises_the_axis() {
+    let p = AuthoredPuzzle::from_meta_str("0,2,0/0.1,0.2/ff").unwrap();
+    assert_eq!(p.axis, Vector3::unit_y());
+    assert_eq!(p.seed, PuzzleSeed::new(0xff));
+}
+
+#[test]
+fn bad_meta_strs_are_rejected() {
+    for s in [
+        "",
+        "0,0,0/0.1,0.2/ff",
+        "1,0,0/0.2,0.2/ff",
+        "1,0,0/0.3,0.2/ff",
+        "1,0,0/-0.1,0.2/ff",
+        "1,0/0.1,0.2/ff",
+        "1,0,0/0.1,0.2",
+        "1,0,0/0.1,0.2/zz",
+        "1,0,0/0.1,0.2/ff/1",
+        "1,NaN,0/0.1,0.2/ff",
+        "1,0,0/0.1,inf/ff",
+    ] {
+        assert_eq!(AuthoredPuzzle::from_meta_str(s), None, "{s:?}");
+    }
+}
+
+#[test]
+fn with_disp_keeps_a_valid_range() {
+    let p = puzzle().with_disp(-0.5, 0.2);
+    assert_eq!((p.min_disp, p.max_disp), (0.0, 0.2));
+    let p = puzzle().with_disp(0.3, 0.1);
+    assert_eq!(p.min_disp, 0.3);
+    assert!(p.max_disp > p.min_disp);
+    assert!(AuthoredPuzzle::from_meta_str(&p.to_meta_str()).is_some());
+}
+
+#[test]
+fn with_axis_ignores_a_zero_axis() {
+    assert_eq!(puzzle().with_axis(Vector3::new(0.0, 0.0, 0.0)), puzzle());
+    let p = puzzle().with_axis(Vector3::new(0.0, 0.0, -3.0));
+    assert_eq!(p.axis, -Vector3::unit_z());
+}
+
+#[test]
+fn apply_sets_one_stage_and_the_range() {
+    let params = PuzzleParams::default_for(1.0).with_stages(3);
+    let applied = puzzle().apply(params);
+    assert_eq!(applied.stage_count, 1);
+    assert_eq!((applied.min_disp, applied.max_disp), (0.02, 0.12));
+}
+
+#[test]
+fn level_meta_round_trips_the_authored_puzzle() {
+    let back = LevelMeta::from_json(&meta(Some(puzzle())).to_json()).unwrap();
+    assert_eq!(back.authored, Some(puzzle()));
+    let back = LevelMeta::from_json(&meta(None).to_json()).unwrap();
+    assert_eq!(back.authored, None);
+
+    let old = r#"{"id":"abc","name":"Abc","author":"A","license":"CC0","source_url":"u","model_file":"m.glb","best_time_secs":null,"play_count":0,"difficulty":0.5}"#;
+    assert_eq!(LevelMeta::from_json(old).unwrap().authored, None);
+}
+
+// ─── Map ─────────────────────────────────────────────────────────────────────
+
+#[test]
+fn map_positions_round_trip() {
+    for (u, v) in [(0.0, 0.5), (0.25, 0.25), (0.6, 0.8), (0.99, 0.1)] {
+        let d = direction_at(u, v);
+        assert!((d.magnitude() - 1.0).abs() < 1e-5);
+        let (u2, v2) = map_position(d);
+        assert!((u - u2).abs() < 1e-4 && (v - v2).abs() < 1e-4, "{u},{v}");
+    }
+    assert!((direction_at(0.0, 0.5) - Vector3::unit_x()).magnitude() < 1e-5);
+    assert!((direction_at(0.3, 0.0) - Vector3::unit_y()).magnitude() < 1e-5);
+    assert!((direction_at(0.3, 1.0) + Vector3::unit_y()).magnitude() < 1e-5);
+}
+
+#[test]
+fn heatmap_is_normalised_and_deterministic() {
+    let positions = cloud(32);
+    let clusters = per_triangle(&positions);
+    let params = PuzzleParams::default_for(1.0);
+    let map = |p: &AuthoredPuzzle| {
+        AxisHeatmap::new(
+            &positions,
+            &clusters,
+            p,
+            &params,
+            12,
+            6,
+            &SearchOptions::serial(),
+        )
+        .unwrap()
+    };
+    let a = map(&puzzle());
+    assert_eq!(a.scores.len(), 12 * 6);
+    assert!(a.scores.iter().all(|s| (0.0..=1.0).contains(s)));
+    assert!(a.scores.contains(&1.0));
+    // The edited axis plays no part; only the displacements do.
+    let b = map(&puzzle().with_axis(Vector3::unit_y()));
+    assert_eq!(a.scores, b.scores);
+    assert_eq!(a.at(0.0, 0.0), a.scores[0]);
+    assert_eq!(a.at(1.0, 1.0), a.scores[12 * 6 - 1]);
+}
+
+#[test]
+fn cancelled_heatmap_is_none() {
+    let positions = cloud(8);
+    let handle = SearchHandle::new();
+    handle.cancel();
+    let options = SearchOptions {
+        handle,
+        ..SearchOptions::serial()
+    };
+    let map = AxisHeatmap::new(
+        &positions,
+        &per_triangle(&positions),
+        &puzzle(),
+        &PuzzleParams::default_for(1.0),
+        4,
+        2,
+        &options,
+    );
+    assert!(map.is_none());
+}
+
+// ─── Fixed-axis analysis ─────────────────────────────────────────────────────
+
+#[test]
+fn fixed_axes_agree_with_a_single_fixed_axis() {
+    let positions = cloud(32);
+    let clusters = per_triangle(&positions);
+    let params = puzzle().apply(PuzzleParams::default_for(1.0));
+    let seed = puzzle().seed;
+    let axes = [Vector3::unit_x(), Vector3::new(0.0, 2.0, 1.0)];
+    let options = SearchOptions {
+        threads: 2,
+        ..SearchOptions::default()
+    };
+    let many = analyse_fixed_axes(&positions, &clusters, &axes, &params, seed, &options).unwrap();
+    for (axis, result) in axes.iter().zip(&many) {
+        let one = analyse_fixed_axis(
+            &positions,
+            &clusters,
+            *axis,
+            &params,
+            seed,
+            AnalysisBackend::Heuristic,
+        );
+        assert_eq!(one.report, result.report);
+        assert!((one.axis - axis.normalize()).magnitude() < 1e-6);
+    }
+}
@@ -0,0 +1,138 @@
+use formosaic::level::outcomes::{load_calibration, save_calibration, OutcomeLog, PlayOutcome};
+use formosaic::puzzle::calibration::{
+    observed_difficulty, DifficultyFeatures, DifficultyModel, MIN_OUTCOMES, REFERENCE_SECS,
+};
+use formosaic::puzzle::entropy::analyse_axis;
+
+fn features(heuristic: f32, entropy: f32, extra_stages: f32) -> DifficultyFeatures {
+    DifficultyFeatures {
+        heuristic,
+        entropy,
+        weak_peak: 0.1,
+        crowding: 0.5,
+        roll_sensitivity: 0.0,
+        extra_stages,
+    }
+}
+
+fn outcome(id: &str, time_secs: f32, hints: u32, gave_up: bool) -> PlayOutcome {
+    PlayOutcome {
+        level_id: id.to_string(),
+        time_secs,
+        hints,
+        gave_up,
+        features: features(0.4, 0.55, 1.0),
+    }
+}
+
+// ─── Observed difficulty ─────────────────────────────────────────────────────
+
+#[test]
+fn observed_difficulty_grows_with_effort() {
+    assert_eq!(observed_difficulty(0.0, 0, false), 0.0);
+    assert!((observed_difficulty(REFERENCE_SECS, 0, false) - 0.5).abs() < 1e-6);
+    assert!(observed_difficulty(60.0, 2, false) > observed_difficulty(60.0, 0, false));
+    assert!(observed_difficulty(600.0, 5, false) < 1.0);
+    assert_eq!(observed_difficulty(5.0, 0, true), 1.0);
+}
+
+// ─── Model ───────────────────────────────────────────────────────────────────
+
+#[test]
+fn default_model_reproduces_the_heuristic() {
+    let offsets: Vec<f32> = (0..90).map(|i| (i % 7) as f32 * 0.05).collect();
+    let report = analyse_axis(&offsets, cgmath::Vector3::unit_z());
+    let f = DifficultyFeatures::from_report(&report, 1);
+    assert_eq!(DifficultyModel::default().difficulty(&f), report.difficulty);
+    assert!((0.0..=1.0).contains(&f.entropy));
+    assert_eq!(f.extra_stages, 0.0);
+}
+
+#[test]
+fn fit_needs_enough_outcomes() {
+    let samples = vec![(features(0.5, 0.5, 0.0), 0.5); MIN_OUTCOMES - 1];
+    assert!(DifficultyModel::fit(&samples).is_none());
+}
+
+#[test]
+fn fit_follows_the_data() {
+    // Players find everything harder than estimated, and extra stages much
+    // harder still.
+    let mut samples = Vec::new();
+    for i in 0..200 {
+        let h = (i % 10) as f32 / 10.0;
+        let e = ((i * 7) % 11) as f32 / 11.0;
+        let stages = (i % 3) as f32;
+        let y = (0.15 + 0.6 * h + 0.1 * stages).min(1.0);
+        samples.push((features(h, e, stages), y));
+    }
+    let model = DifficultyModel::fit(&samples).unwrap();
+    assert_eq!(model.samples, 200);
+    let default = DifficultyModel::default();
+    assert!(model.rms_error(&samples) < 0.05, "{model:?}");
+    assert!(model.rms_error(&samples) < default.rms_error(&samples) * 0.5);
+    assert!(model.extra_stages > 0.05);
+}
+
+#[test]
+fn few_outcomes_only_nudge_the_default() {
+    let samples: Vec<_> = (0..MIN_OUTCOMES)
+        .map(|i| (features(i as f32 / 10.0, 0.5, 0.0), 1.0))
+        .collect();
+    let model = DifficultyModel::fit(&samples).unwrap();
+    let f = features(0.2, 0.5, 0.0);
+    let d = model.difficulty(&f);
+    assert!(d > 0.2 && d < 1.0, "{d}");
+}
+
+#[test]
+fn model_round_trips_through_json() {
+    let model = DifficultyModel {
+        bias: 0.125,
+        heuristic: 0.75,
+        entropy: -0.25,
+        weak_peak: 0.5,
+        crowding: 0.0625,
+        roll_sensitivity: 0.3,
+        extra_stages: 0.1,
+        samples: 42,
+    };
+    assert_eq!(DifficultyModel::from_json(&model.to_json()), Some(model));
+    assert_eq!(DifficultyModel::from_json("{}"), None);
+}
+
+// ─── Outcome log ─────────────────────────────────────────────────────────────
+
+#[test]
+fn outcome_round_trips_through_json() {
+    for o in [
+        outcome("cactus_builtin", 42.17, 1, false),
+        outcome("a1", 310.5, 0, true),
+    ] {
+        assert_eq!(PlayOutcome::from_json(&o.to_json()), Some(o));
+    }
+}
+
+#[test]
+fn log_persists_and_calibrates() {
+    let dir = std::env::temp_dir().join(format!("formosaic_outcomes_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let mut log = OutcomeLog::load(&dir);
+    assert!(log.outcomes.is_empty());
+    assert!(log.calibrate().is_none());
+    for i in 0..MIN_OUTCOMES {
+        log.record(outcome("a1", 30.0 * i as f32, 0, i == 0));
+    }
+    let reloaded = OutcomeLog::load(&dir);
+    assert_eq!(reloaded.outcomes, log.outcomes);
+
+    let model = reloaded.calibrate().unwrap();
+    assert!(load_calibration(&dir).is_none());
+    save_calibration(&dir, &model);
+    let loaded = load_calibration(&dir).unwrap();
+    assert_eq!(loaded.samples, model.samples);
+    assert!((loaded.bias - model.bias).abs() < 1e-5);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,205 @@
+use cgmath::{Matrix4, SquareMatrix, Vector3};
+use formosaic::level::storage::LevelMeta;
+use formosaic::puzzle::clusters::{cluster_count, flat_clusters, triangle_clusters};
+use formosaic::puzzle::entropy::best_stage_axes_from_offsets;
+use formosaic::puzzle::puzzle_params::{PuzzleParams, ScrambleStrategy};
+use formosaic::puzzle::scramble_math::{compute_cluster_offsets, compute_scramble_offsets};
+use formosaic::puzzle::scrambler::{assign_stage_groups, compute_raw_offsets};
+use formosaic::puzzle::seed::PuzzleSeed;
+
+const SEED: PuzzleSeed = PuzzleSeed::new(0xc1a57);
+
+/// A unit quad (two triangles sharing an edge) at `x`.
+fn quad(x: f32) -> Vec<f32> {
+    let (a, b, c, d) = (
+        [x, 0.0, 0.0],
+        [x + 1.0, 0.0, 0.0],
+        [x + 1.0, 1.0, 0.0],
+        [x, 1.0, 0.0],
+    );
+    [a, b, c, a, c, d].concat()
+}
+
+/// `n` triangles in a connected strip; neighbours share an edge.
+fn strip(n: usize) -> Vec<f32> {
+    let mut v = Vec::with_capacity(n * 9);
+    for i in 0..n {
+        let x = (i / 2) as f32;
+        if i % 2 == 0 {
+            v.extend_from_slice(&[x, 0.0, 0.0, x + 1.0, 0.0, 0.0, x, 1.0, 0.0]);
+        } else {
+            v.extend_from_slice(&[x + 1.0, 0.0, 0.0, x + 1.0, 1.0, 0.0, x, 1.0, 0.0]);
+        }
+    }
+    v
+}
+
+fn params(strategy: ScrambleStrategy, stages: usize) -> PuzzleParams {
+    PuzzleParams {
+        min_disp: 0.1,
+        max_disp: 0.5,
+        ..PuzzleParams::default_for(1.0)
+    }
+    .with_strategy(strategy)
+    .with_stages(stages)
+}
+
+// ─── Clustering ──────────────────────────────────────────────────────────────
+
+#[test]
+fn components_follow_connectivity() {
+    let mut verts = quad(0.0);
+    verts.extend(quad(5.0));
+    verts.extend(quad(0.0).iter().map(|c| c + 1e-7)); // welds onto the first quad
+    let clusters = triangle_clusters(&verts, ScrambleStrategy::PerCluster { max_triangles: 0 });
+    assert_eq!(clusters, vec![0, 0, 1, 1, 0, 0]);
+}
+
+#[test]
+fn patches_respect_size_bound() {
+    let verts = strip(20);
+    let clusters = triangle_clusters(&verts, ScrambleStrategy::PerCluster { max_triangles: 4 });
+    assert_eq!(clusters.len(), 20);
+    let n = cluster_count(&clusters);
+    assert!(
+        n >= 5,
+        "20 triangles in patches of ≤4 need ≥5 clusters, got {n}"
+    );
+    for c in 0..n {
+        let size = clusters.iter().filter(|&&x| x == c).count();
+        assert!((1..=4).contains(&size), "cluster {c} has {size} triangles");
+    }
+}
+
+#[test]
+fn per_triangle_and_per_mesh_are_trivial() {
+    let verts = strip(6);
+    assert_eq!(
+        triangle_clusters(&verts, ScrambleStrategy::PerTriangle),
+        (0..6).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        triangle_clusters(&verts, ScrambleStrategy::PerMesh),
+        vec![0; 6]
+    );
+}
+
+#[test]
+fn flat_clusters_never_share_ids_across_meshes() {
+    let a = quad(0.0);
+    let b = quad(0.0);
+    let flat = flat_clusters(&[a.as_slice(), b.as_slice()], ScrambleStrategy::PerMesh);
+    assert_eq!(flat, vec![0, 0, 1, 1]);
+}
+
+// ─── Offsets ─────────────────────────────────────────────────────────────────
+
+#[test]
+fn identity_clusters_match_classic_scramble() {
+    let verts = strip(10);
+    let axis = Vector3::new(0.2, 0.9, 0.1);
+    let clustered = compute_cluster_offsets(
+        verts.len(),
+        &(0..10).collect::<Vec<_>>(),
+        axis,
+        0.1,
+        0.5,
+        Matrix4::identity(),
+        SEED,
+    );
+    let classic = compute_scramble_offsets(verts.len(), axis, 0.1, 0.5, Matrix4::identity(), SEED);
+    assert_eq!(clustered, classic);
+}
+
+#[test]
+fn clusters_move_rigidly() {
+    let mut verts = quad(0.0);
+    verts.extend(quad(5.0));
+    let strategy = ScrambleStrategy::PerCluster { max_triangles: 0 };
+    let offsets = compute_raw_offsets(
+        &[verts.as_slice()],
+        &[Matrix4::identity()],
+        Vector3::unit_z(),
+        0.1,
+        0.5,
+        strategy,
+        SEED,
+    );
+    let tri = |t: usize| offsets[0][t * 9..t * 9 + 9].to_vec();
+    assert_eq!(tri(0), tri(1));
+    assert_eq!(tri(2), tri(3));
+    assert_ne!(tri(0), tri(2));
+}
+
+#[test]
+fn stage_groups_never_split_a_cluster() {
+    let verts = strip(40);
+    let strategy = ScrambleStrategy::PerCluster { max_triangles: 5 };
+    let clusters = triangle_clusters(&verts, strategy);
+    let groups = assign_stage_groups(
+        &[verts.as_slice()],
+        &[Matrix4::identity()],
+        2,
+        strategy,
+        SEED,
+    );
+    for (t, &c) in clusters.iter().enumerate() {
+        let first = clusters.iter().position(|&x| x == c).unwrap();
+        assert_eq!(groups[0][t], groups[0][first], "cluster {c} spans stages");
+    }
+    assert!(groups[0].contains(&0) && groups[0].contains(&1));
+}
+
+// ─── Entropy ─────────────────────────────────────────────────────────────────
+
+#[test]
+fn entropy_models_the_strategy() {
+    let verts = strip(24);
+    let groups = vec![0; 24];
+    let per_tri: Vec<usize> = (0..24).collect();
+    let tri = best_stage_axes_from_offsets(
+        &verts,
+        &groups,
+        &per_tri,
+        &params(ScrambleStrategy::PerTriangle, 1),
+        6,
+        SEED,
+    );
+    let mesh = best_stage_axes_from_offsets(
+        &verts,
+        &groups,
+        &[0; 24],
+        &params(ScrambleStrategy::PerMesh, 1),
+        6,
+        SEED,
+    );
+    // A rigidly moved mesh looks whole from everywhere: no distinctive view.
+    assert!(mesh.combined.entropy_bits > tri.combined.entropy_bits);
+    assert!((mesh.combined.entropy_bits - 64f32.log2()).abs() < 1e-3);
+}
+
+// ─── Metadata ────────────────────────────────────────────────────────────────
+
+#[test]
+fn strategy_meta_round_trip() {
+    for s in [
+        ScrambleStrategy::PerTriangle,
+        ScrambleStrategy::PerCluster { max_triangles: 0 },
+        ScrambleStrategy::PerCluster { max_triangles: 64 },
+        ScrambleStrategy::PerMesh,
+    ] {
+        assert_eq!(ScrambleStrategy::from_meta_str(&s.to_meta_str()), Some(s));
+    }
+    assert_eq!(ScrambleStrategy::from_meta_str("cluster:x"), None);
+}
+
+#[test]
+fn level_meta_without_strategy_scrambles_per_triangle() {
+    let old = r#"{"id":"a","name":"A","author":"b","license":"CC0","source_url":"","model_file":"model.glb","best_time_secs":null,"play_count":0,"difficulty":0.5000}"#;
+    let mut meta = LevelMeta::from_json(old).unwrap();
+    assert_eq!(meta.scramble_strategy, ScrambleStrategy::PerTriangle);
+
+    meta.scramble_strategy = ScrambleStrategy::PerCluster { max_triangles: 32 };
+    let back = LevelMeta::from_json(&meta.to_json()).unwrap();
+    assert_eq!(back.scramble_strategy, meta.scramble_strategy);
+}
@@ -0,0 +1,32 @@
+//! Fixtures shared by the integration tests.  Each test crate uses only
+//! some of them.
+#![allow(dead_code)]
+
+use cgmath::Vector3;
+
+/// `n` small triangles spread over a cube.
+pub fn cloud(n: usize) -> Vec<f32> {
+    let mut v = Vec::with_capacity(n * 9);
+    for i in 0..n {
+        let x = (i % 4) as f32;
+        let y = ((i / 4) % 4) as f32;
+        let z = (i / 16) as f32;
+        v.extend_from_slice(&[x, y, z, x + 0.3, y, z, x, y + 0.3, z + 0.1]);
+    }
+    v
+}
+
+/// `n` separate small triangles in a row.
+pub fn strip(n: usize) -> Vec<f32> {
+    let mut v = Vec::with_capacity(n * 9);
+    for i in 0..n {
+        let x = i as f32 * 2.0;
+        v.extend_from_slice(&[x, 0.0, 0.0, x + 0.5, 0.0, 0.0, x, 0.5, 0.0]);
+    }
+    v
+}
+
+/// Vertex `i` of the flat positions `v`.
+pub fn vertex(v: &[f32], i: usize) -> Vector3<f32> {
+    Vector3::new(v[i * 3], v[i * 3 + 1], v[i * 3 + 2])
+}
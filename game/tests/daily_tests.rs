@@ -0,0 +1,179 @@
+use formosaic::level::daily::{DailyChallenge, DailyLog, DailyRecord, UtcDate};
+use formosaic::level::storage::LevelMeta;
+use formosaic::puzzle::puzzle_params::ScrambleStrategy;
+
+fn level(id: &str, difficulty: f32) -> LevelMeta {
+    LevelMeta {
+        id: id.to_string(),
+        name: id.to_string(),
+        author: "test".to_string(),
+        license: "CC0".to_string(),
+        source_url: String::new(),
+        model_file: "model.glb".to_string(),
+        best_time_secs: None,
+        best_score: None,
+        stars: 0,
+        play_count: 0,
+        difficulty,
+        scramble_strategy: ScrambleStrategy::PerTriangle,
+        upright: false,
+        authored: None,
+        parts: Vec::new(),
+    }
+}
+
+fn library() -> Vec<LevelMeta> {
+    vec![
+        level("cactus_builtin", 0.3),
+        level("a1", 0.8),
+        level("b2", 0.1),
+        level("c3", 0.5),
+    ]
+}
+
+#[test]
+fn epoch_day_round_trip() {
+    assert_eq!(UtcDate::from_days_since_epoch(0), UtcDate::new(1970, 1, 1));
+    assert_eq!(UtcDate::new(2000, 3, 1).days_since_epoch(), 11_017);
+    assert_eq!(
+        UtcDate::from_days_since_epoch(-1),
+        UtcDate::new(1969, 12, 31)
+    );
+    for days in (-800..30_000).step_by(7) {
+        assert_eq!(
+            UtcDate::from_days_since_epoch(days).days_since_epoch(),
+            days
+        );
+    }
+}
+
+#[test]
+fn pred_crosses_month_and_leap_day() {
+    assert_eq!(UtcDate::new(2024, 3, 1).pred(), UtcDate::new(2024, 2, 29));
+    assert_eq!(UtcDate::new(2023, 3, 1).pred(), UtcDate::new(2023, 2, 28));
+    assert_eq!(UtcDate::new(2025, 1, 1).pred(), UtcDate::new(2024, 12, 31));
+}
+
+#[test]
+fn date_display_and_parse_round_trip() {
+    let d = UtcDate::new(2025, 3, 4);
+    assert_eq!(d.to_string(), "2025-03-04");
+    assert_eq!(UtcDate::parse("2025-03-04"), Some(d));
+    assert_eq!(UtcDate::parse("2025-13-04"), None);
+    assert_eq!(UtcDate::parse("garbage"), None);
+}
+
+#[test]
+fn same_date_gives_same_challenge() {
+    let date = UtcDate::new(2025, 6, 1);
+    let a = DailyChallenge::for_date(date, &library()).unwrap();
+    let b = DailyChallenge::for_date(date, &library()).unwrap();
+    assert_eq!(a, b);
+}
+
+#[test]
+fn challenge_ignores_registry_order() {
+    // The registry sorts by difficulty, which can change after re-analysis;
+    // the daily pick must not depend on it.
+    let date = UtcDate::new(2025, 6, 1);
+    let mut shuffled = library();
+    shuffled.reverse();
+    shuffled[0].difficulty = 0.99;
+    assert_eq!(
+        DailyChallenge::for_date(date, &library()),
+        DailyChallenge::for_date(date, &shuffled)
+    );
+}
+
+#[test]
+fn challenge_changes_over_days() {
+    let levels = library();
+    let start = UtcDate::new(2025, 1, 1).days_since_epoch();
+    let picks: Vec<DailyChallenge> = (0..28)
+        .map(|i| {
+            DailyChallenge::for_date(UtcDate::from_days_since_epoch(start + i), &levels).unwrap()
+        })
+        .collect();
+    let distinct_levels = {
+        let mut ids: Vec<&str> = picks.iter().map(|c| c.level_id.as_str()).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        ids.len()
+    };
+    assert!(distinct_levels > 1, "daily always picked the same level");
+    for w in picks.windows(2) {
+        assert_ne!(w[0].seed, w[1].seed, "consecutive days share a seed");
+    }
+}
+
+#[test]
+fn builtin_alone_is_enough() {
+    let c = DailyChallenge::for_date(UtcDate::new(2025, 6, 1), &[level("cactus_builtin", 0.3)])
+        .unwrap();
+    assert_eq!(c.level_id, "cactus_builtin");
+    assert!(DailyChallenge::for_date(UtcDate::new(2025, 6, 1), &[]).is_none());
+}
+
+#[test]
+fn streak_counts_consecutive_days() {
+    let today = UtcDate::new(2025, 3, 10);
+    let mut log = DailyLog::in_memory();
+    assert_eq!(log.streak(today), 0);
+
+    log.record_completion(UtcDate::new(2025, 3, 7), "a1", 10.0, 0);
+    log.record_completion(UtcDate::new(2025, 3, 8), "a1", 10.0, 0);
+    log.record_completion(UtcDate::new(2025, 3, 9), "a1", 10.0, 0);
+    // Yesterday's streak is still alive before today's daily is played.
+    assert_eq!(log.streak(today), 3);
+
+    log.record_completion(today, "a1", 10.0, 0);
+    assert_eq!(log.streak(today), 4);
+
+    // Missing a day resets it.
+    assert_eq!(log.streak(UtcDate::new(2025, 3, 12)), 0);
+}
+
+#[test]
+fn first_completion_of_a_day_counts() {
+    let date = UtcDate::new(2025, 3, 10);
+    let mut log = DailyLog::in_memory();
+    assert!(log.record_completion(date, "a1", 30.0, 2));
+    assert!(!log.record_completion(date, "a1", 5.0, 0));
+    let r = log.record_for(date).unwrap();
+    assert_eq!(r.time_secs, 30.0);
+    assert_eq!(r.hints, 2);
+}
+
+#[test]
+fn record_json_round_trip() {
+    let r = DailyRecord {
+        date: UtcDate::new(2025, 3, 14),
+        level_id: "cactus_builtin".to_string(),
+        time_secs: 42.17,
+        hints: 1,
+    };
+    let back = DailyRecord::from_json(&r.to_json()).unwrap();
+    assert_eq!(back.date, r.date);
+    assert_eq!(back.level_id, r.level_id);
+    assert!((back.time_secs - r.time_secs).abs() < 0.01);
+    assert_eq!(back.hints, r.hints);
+}
+
+#[test]
+fn log_persists_to_data_dir() {
+    let dir = std::env::temp_dir().join(format!("formosaic_daily_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+
+    let mut log = DailyLog::load(&dir);
+    assert!(log.records.is_empty());
+    log.record_completion(UtcDate::new(2025, 3, 9), "b2", 12.5, 1);
+    log.record_completion(UtcDate::new(2025, 3, 8), "a1", 20.0, 0);
+
+    let reloaded = DailyLog::load(&dir);
+    assert_eq!(reloaded.records.len(), 2);
+    assert_eq!(reloaded.records[0].date, UtcDate::new(2025, 3, 8));
+    assert_eq!(reloaded.records[1].level_id, "b2");
+    assert_eq!(reloaded.streak(UtcDate::new(2025, 3, 9)), 2);
+
+    let _ = std::fs::remove_dir_all(&dir);
+}
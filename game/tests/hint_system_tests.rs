@@ -13,6 +13,8 @@ fn hint_tier_advances_and_caps() {
     hints.advance();
     assert_eq!(hints.tier(), HintTier::AxisPlane);
     hints.advance();
+    assert_eq!(hints.tier(), HintTier::Outline);
+    hints.advance();
     assert_eq!(hints.tier(), HintTier::GhostSnap);
     hints.advance();
     assert_eq!(hints.tier(), HintTier::GhostSnap);
@@ -34,9 +36,9 @@ fn hint_update_sets_warmth_and_disc_visibility() {
 #[test]
 fn ghost_snap_accumulates_lerp() {
     let mut hints = HintSystem::new();
-    hints.advance();
-    hints.advance();
-    hints.advance();
+    for _ in 0..4 {
+        hints.advance();
+    }
 
     let first = hints.update(1.0, Vector3::unit_z(), Vector3::unit_z());
     let second = hints.update(1.0, Vector3::unit_z(), Vector3::unit_z());
@@ -0,0 +1,128 @@
+use cgmath::{InnerSpace, Matrix4, SquareMatrix, Vector3};
+use formosaic::puzzle::clusters::triangle_clusters;
+use formosaic::puzzle::entropy::best_stage_axes_from_offsets;
+use formosaic::puzzle::puzzle_params::{PuzzleParams, ScrambleStrategy};
+use formosaic::puzzle::scramble_math::unweld;
+use formosaic::puzzle::scrambler::{compute_staged_offsets, ScrambleTarget, StageSplit};
+use formosaic::puzzle::seed::PuzzleSeed;
+
+const SEED: PuzzleSeed = PuzzleSeed::new(0x1d3e);
+
+/// A unit cube as the loader produces it: 8 welded vertices shared by 12
+/// faces.
+fn cube() -> (Vec<f32>, Vec<u32>) {
+    let mut positions = Vec::new();
+    for i in 0..8 {
+        positions.extend_from_slice(&[
+            (i & 1) as f32,
+            ((i >> 1) & 1) as f32,
+            ((i >> 2) & 1) as f32,
+        ]);
+    }
+    let indices = vec![
+        0, 2, 1, 1, 2, 3, // z = 0
+        4, 5, 6, 5, 7, 6, // z = 1
+        0, 1, 4, 1, 5, 4, // y = 0
+        2, 6, 3, 3, 6, 7, // y = 1
+        0, 4, 2, 2, 4, 6, // x = 0
+        1, 3, 5, 3, 7, 5, // x = 1
+    ];
+    (positions, indices)
+}
+
+fn corner(soup: &[f32], face: usize, c: usize) -> Vector3<f32> {
+    let b = face * 9 + c * 3;
+    Vector3::new(soup[b], soup[b + 1], soup[b + 2])
+}
+
+/// The scramble offsets of a one-stage puzzle over `soup`.
+fn offsets(soup: &[f32], strategy: ScrambleStrategy, max_roll: f32) -> Vec<f32> {
+    let params = PuzzleParams::default_for(1.0)
+        .with_strategy(strategy)
+        .with_roll(max_roll);
+    let transforms = [Matrix4::identity()];
+    let groups = StageSplit::Bands(1).groups(&[soup], &transforms, strategy, SEED);
+    let target = ScrambleTarget::Axis(Vector3::new(0.3, 0.8, -0.5).normalize());
+    let mut stages =
+        compute_staged_offsets(&[soup], &transforms, &[target], &groups, &params, SEED);
+    stages.remove(0).remove(0)
+}
+
+#[test]
+fn unweld_copies_every_face_out() {
+    let (positions, indices) = cube();
+    let soup = unweld(&positions, &indices);
+    assert_eq!(soup.len(), 12 * 9);
+    for face in 0..12 {
+        for c in 0..3 {
+            let v = indices[face * 3 + c] as usize * 3;
+            let expected = Vector3::new(positions[v], positions[v + 1], positions[v + 2]);
+            assert_eq!(corner(&soup, face, c), expected);
+        }
+    }
+}
+
+#[test]
+fn unweld_drops_a_partial_face() {
+    let (positions, mut indices) = cube();
+    indices.truncate(7);
+    assert_eq!(unweld(&positions, &indices).len(), 2 * 9);
+    assert!(unweld(&positions, &[]).is_empty());
+}
+
+#[test]
+fn every_face_moves_rigidly() {
+    let (positions, indices) = cube();
+    let soup = unweld(&positions, &indices);
+    for strategy in [ScrambleStrategy::PerTriangle, ScrambleStrategy::PerMesh] {
+        for max_roll in [0.0, 0.6] {
+            let off = offsets(&soup, strategy, max_roll);
+            assert_eq!(off.len(), soup.len());
+            let moved: Vec<f32> = soup.iter().zip(&off).map(|(p, o)| p + o).collect();
+            for face in 0..12 {
+                for (a, b) in [(0, 1), (1, 2), (2, 0)] {
+                    let before = (corner(&soup, face, a) - corner(&soup, face, b)).magnitude();
+                    let after = (corner(&moved, face, a) - corner(&moved, face, b)).magnitude();
+                    assert!(
+                        (before - after).abs() < 1e-5,
+                        "{strategy:?} roll {max_roll}: face {face} deformed"
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn faces_sharing_a_vertex_move_apart() {
+    // Welded vertex 0 is a corner of several faces; scrambled per triangle
+    // each of them takes it somewhere else, which only a soup can draw.
+    let (positions, indices) = cube();
+    let soup = unweld(&positions, &indices);
+    let off = offsets(&soup, ScrambleStrategy::PerTriangle, 0.0);
+    let sharing: Vec<Vector3<f32>> = (0..12)
+        .filter_map(|face| {
+            let c = (0..3).find(|&c| indices[face * 3 + c] == 0)?;
+            Some(corner(&off, face, c))
+        })
+        .collect();
+    assert!(sharing.len() > 2);
+    assert!(sharing.iter().any(|o| (o - sharing[0]).magnitude() > 1e-6));
+}
+
+#[test]
+fn analysis_sees_every_face() {
+    let (positions, indices) = cube();
+    let soup = unweld(&positions, &indices);
+    let clusters = triangle_clusters(&soup, ScrambleStrategy::PerTriangle);
+    assert_eq!(clusters.len(), 12);
+    // Patches are grown over shared corners, so the closed cube is one
+    // connected piece however it was indexed.
+    let patches = triangle_clusters(&soup, ScrambleStrategy::PerCluster { max_triangles: 0 });
+    assert!(patches.iter().all(|&c| c == patches[0]));
+
+    let params = PuzzleParams::default_for(1.0);
+    let search = best_stage_axes_from_offsets(&soup, &[0; 12], &clusters, &params, 6, SEED);
+    assert_eq!(search.stages.len(), 1);
+    assert!(search.combined.entropy_bits.is_finite());
+}
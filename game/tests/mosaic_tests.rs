@@ -0,0 +1,113 @@
+mod common;
+
+use cgmath::Vector3;
+use common::strip;
+use formosaic::puzzle::mosaic::{fragment_tints, mosaic_t, stage_tints, MOSAIC_FULL_TINT_RAD};
+use formosaic::puzzle::puzzle_params::{PuzzleParams, ScrambleStrategy};
+use formosaic::puzzle::scrambler::{ScrambleStage, ScrambleState};
+use formosaic::puzzle::seed::PuzzleSeed;
+
+const SEED: PuzzleSeed = PuzzleSeed::new(0x7147);
+
+/// Tint (rgba) of vertex `v`.
+fn tint(tints: &[f32], v: usize) -> [f32; 4] {
+    tints[v * 4..v * 4 + 4].try_into().unwrap()
+}
+
+fn state(stages: Vec<Vec<f32>>, current_stage: usize) -> ScrambleState {
+    ScrambleState {
+        stages: stages
+            .into_iter()
+            .map(|offsets| ScrambleStage {
+                solution_dir: Vector3::unit_z(),
+                solution_eye: None,
+                solution_up: None,
+                scramble_offsets: vec![offsets],
+            })
+            .collect(),
+        current_stage,
+        params: PuzzleParams::default_for(1.0),
+        original_positions: vec![strip(2)],
+        seed: SEED,
+        pose: None,
+    }
+}
+
+// ─── Fragment tints ──────────────────────────────────────────────────────────
+
+#[test]
+fn every_vertex_gets_a_tint() {
+    let a = strip(3);
+    let b = strip(2);
+    let tints = fragment_tints(&[&a, &b], ScrambleStrategy::PerTriangle, SEED);
+    assert_eq!(tints.len(), 2);
+    assert_eq!(tints[0].len(), 3 * 3 * 4);
+    assert_eq!(tints[1].len(), 2 * 3 * 4);
+    for mesh in &tints {
+        for rgba in mesh.chunks_exact(4) {
+            assert!(rgba[..3].iter().all(|c| (0.0..=1.0).contains(c)));
+            assert!(rgba[3] > 0.0 && rgba[3] <= 1.0, "release order {}", rgba[3]);
+        }
+    }
+}
+
+#[test]
+fn a_fragment_shares_one_tint() {
+    let positions = strip(4);
+    let tints = &fragment_tints(&[&positions], ScrambleStrategy::PerTriangle, SEED)[0];
+    for tri in 0..4 {
+        assert_eq!(tint(tints, tri * 3), tint(tints, tri * 3 + 1));
+        assert_eq!(tint(tints, tri * 3), tint(tints, tri * 3 + 2));
+    }
+    // Neighbouring fragments are told apart by hue.
+    assert_ne!(tint(tints, 0)[..3], tint(tints, 3)[..3]);
+
+    let whole = &fragment_tints(&[&positions], ScrambleStrategy::PerMesh, SEED)[0];
+    assert!(whole.chunks_exact(4).all(|t| t == tint(whole, 0)));
+}
+
+#[test]
+fn tints_follow_the_seed() {
+    let positions = strip(6);
+    let tints = |seed| fragment_tints(&[&positions], ScrambleStrategy::PerTriangle, seed);
+    assert_eq!(tints(SEED), tints(SEED));
+    assert_ne!(tints(SEED), tints(PuzzleSeed::new(0x7148)));
+}
+
+// ─── Stage tints ─────────────────────────────────────────────────────────────
+
+#[test]
+fn settled_fragments_are_untinted() {
+    let positions = strip(2);
+    let tints = &fragment_tints(&[&positions], ScrambleStrategy::PerTriangle, SEED)[0];
+    // Stage 0 moves the first triangle, stage 1 the second.
+    let mut first = vec![0.0; 18];
+    first[..9].copy_from_slice(&[0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0]);
+    let mut second = vec![0.0; 18];
+    second[9..].copy_from_slice(&[0.0, 1.0, 0.0, 0.0, 1.0, 0.0, 0.0, 1.0, 0.0]);
+
+    let stage0 = stage_tints(&state(vec![first.clone(), second.clone()], 0), 0, tints);
+    assert_eq!(&stage0, tints);
+
+    let stage1 = stage_tints(&state(vec![first, second], 1), 0, tints);
+    for v in 0..3 {
+        assert_eq!(tint(&stage1, v)[3], 0.0);
+        assert_eq!(tint(&stage1, v + 3), tint(tints, v + 3));
+    }
+}
+
+// ─── Tint strength ───────────────────────────────────────────────────────────
+
+#[test]
+fn tint_fades_out_towards_the_solution() {
+    assert_eq!(mosaic_t(1.0), 0.0);
+    assert_eq!(mosaic_t(MOSAIC_FULL_TINT_RAD.cos()), 1.0);
+    assert_eq!(mosaic_t(0.0), 1.0);
+    assert_eq!(mosaic_t(-1.0), 1.0);
+    let mut last = 0.0;
+    for deg in [5.0f32, 15.0, 30.0, 45.0, 60.0] {
+        let t = mosaic_t(deg.to_radians().cos());
+        assert!(t > last && t < 1.0, "{deg}: {t}");
+        last = t;
+    }
+}
@@ -0,0 +1,287 @@
+use cgmath::{InnerSpace, Matrix4, SquareMatrix, Vector3};
+use formosaic::puzzle::entropy::{
+    best_scramble_axis_from_offsets, best_stage_axes_from_offsets, combine_reports, EntropyReport,
+    MIN_STAGE_SEPARATION_RAD,
+};
+use formosaic::puzzle::hints::{HintSystem, HintTier};
+use formosaic::puzzle::puzzle_params::{PuzzleParams, ScrambleStrategy};
+use formosaic::puzzle::scrambler::{
+    assign_stage_groups, compute_raw_offsets, compute_staged_offsets, ScrambleStage, ScrambleState,
+    ScrambleTarget,
+};
+use formosaic::puzzle::seed::PuzzleSeed;
+
+const SEED: PuzzleSeed = PuzzleSeed::new(0x57a6e);
+const TRI: ScrambleStrategy = ScrambleStrategy::PerTriangle;
+
+/// Per-triangle params with a small displacement range.
+fn params(stages: usize) -> PuzzleParams {
+    PuzzleParams {
+        min_disp: 0.1,
+        max_disp: 0.5,
+        ..PuzzleParams::default_for(1.0)
+    }
+    .with_stages(stages)
+}
+
+/// `n` small triangles spread over a cube so any split direction separates them.
+fn cloud(n: usize) -> Vec<f32> {
+    let mut v = Vec::with_capacity(n * 9);
+    for i in 0..n {
+        let x = (i % 5) as f32;
+        let y = ((i / 5) % 5) as f32;
+        let z = (i / 25) as f32;
+        v.extend_from_slice(&[x, y, z, x + 0.1, y, z, x, y + 0.1, z]);
+    }
+    v
+}
+
+fn report(difficulty: f32) -> EntropyReport {
+    EntropyReport {
+        entropy_bits: 2.0,
+        peak_score: 0.9,
+        solution_isolation_rad: 1.0,
+        roll_sensitivity: 0.0,
+        difficulty,
+    }
+}
+
+fn two_stage_state(verts: &[f32]) -> ScrambleState {
+    let axes = [Vector3::unit_x(), Vector3::unit_y()];
+    let groups = assign_stage_groups(&[verts], &[Matrix4::identity()], 2, TRI, SEED);
+    let offsets = compute_staged_offsets(
+        &[verts],
+        &[Matrix4::identity()],
+        &axes.map(ScrambleTarget::Axis),
+        &groups,
+        &params(2),
+        SEED,
+    );
+    ScrambleState {
+        stages: axes
+            .iter()
+            .zip(offsets)
+            .map(|(&solution_dir, scramble_offsets)| ScrambleStage {
+                solution_dir,
+                solution_eye: None,
+                solution_up: None,
+                scramble_offsets,
+            })
+            .collect(),
+        current_stage: 0,
+        params: PuzzleParams::default_for(1.0).with_stages(2),
+        original_positions: vec![verts.to_vec()],
+        seed: SEED,
+        pose: None,
+    }
+}
+
+// ─── Grouping ────────────────────────────────────────────────────────────────
+
+#[test]
+fn groups_partition_triangles_evenly() {
+    let verts = cloud(60);
+    let groups = assign_stage_groups(&[verts.as_slice()], &[Matrix4::identity()], 3, TRI, SEED);
+    assert_eq!(groups.len(), 1);
+    assert_eq!(groups[0].len(), 60);
+    for k in 0..3 {
+        assert_eq!(groups[0].iter().filter(|&&g| g == k).count(), 20);
+    }
+}
+
+#[test]
+fn single_stage_puts_everything_in_group_zero() {
+    let verts = cloud(10);
+    let groups = assign_stage_groups(&[verts.as_slice()], &[Matrix4::identity()], 1, TRI, SEED);
+    assert!(groups[0].iter().all(|&g| g == 0));
+}
+
+#[test]
+fn groups_are_reproducible() {
+    let verts = cloud(40);
+    let a = assign_stage_groups(&[verts.as_slice()], &[Matrix4::identity()], 2, TRI, SEED);
+    let b = assign_stage_groups(&[verts.as_slice()], &[Matrix4::identity()], 2, TRI, SEED);
+    assert_eq!(a, b);
+}
+
+// ─── Offsets ─────────────────────────────────────────────────────────────────
+
+#[test]
+fn each_stage_moves_only_its_own_triangles_along_its_axis() {
+    let verts = cloud(30);
+    let axes = [Vector3::unit_x(), Vector3::unit_z()];
+    let groups = assign_stage_groups(&[verts.as_slice()], &[Matrix4::identity()], 2, TRI, SEED);
+    let staged = compute_staged_offsets(
+        &[verts.as_slice()],
+        &[Matrix4::identity()],
+        &axes.map(ScrambleTarget::Axis),
+        &groups,
+        &params(2),
+        SEED,
+    );
+    assert_eq!(staged.len(), 2);
+    for (k, stage) in staged.iter().enumerate() {
+        for (tri, &g) in groups[0].iter().enumerate() {
+            let o = &stage[0][tri * 9..tri * 9 + 3];
+            let v = Vector3::new(o[0], o[1], o[2]);
+            if g == k {
+                assert!(
+                    v.magnitude() > 0.09,
+                    "stage {k} left its triangle {tri} in place"
+                );
+                assert!(v.normalize().dot(axes[k]) > 0.999);
+            } else {
+                assert_eq!(
+                    v.magnitude(),
+                    0.0,
+                    "stage {k} moved triangle {tri} of stage {g}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn one_stage_matches_plain_scramble() {
+    let verts = cloud(12);
+    let axis = Vector3::new(0.3, 0.8, 0.1).normalize();
+    let groups = assign_stage_groups(&[verts.as_slice()], &[Matrix4::identity()], 1, TRI, SEED);
+    let staged = compute_staged_offsets(
+        &[verts.as_slice()],
+        &[Matrix4::identity()],
+        &[ScrambleTarget::Axis(axis)],
+        &groups,
+        &params(1),
+        SEED,
+    );
+    let plain = compute_raw_offsets(
+        &[verts.as_slice()],
+        &[Matrix4::identity()],
+        axis,
+        0.1,
+        0.5,
+        TRI,
+        SEED,
+    );
+    assert_eq!(staged, vec![plain]);
+}
+
+// ─── State ───────────────────────────────────────────────────────────────────
+
+#[test]
+fn advancing_locks_earlier_stages_in_place() {
+    let verts = cloud(20);
+    let mut state = two_stage_state(&verts);
+    assert_eq!(state.solution_dir(), Vector3::unit_x());
+    assert!(!state.is_final_stage());
+
+    // Stage 0 at rest, stage 1 still fully scrambled.
+    let at_solution = state.displaced_positions(0, 0.0);
+    let stage1 = state.stages[1].scramble_offsets[0].clone();
+    for i in 0..verts.len() {
+        assert!((at_solution[i] - (verts[i] + stage1[i])).abs() < 1e-6);
+    }
+
+    assert!(state.advance_stage());
+    assert_eq!(state.solution_dir(), Vector3::unit_y());
+    assert!(state.is_final_stage());
+    assert!(!state.advance_stage(), "final stage must not advance");
+
+    // After advancing, stage 0 stays solved even at full scramble.
+    let scrambled = state.displaced_positions(0, 1.0);
+    for i in 0..verts.len() {
+        assert!((scrambled[i] - (verts[i] + stage1[i])).abs() < 1e-6);
+    }
+    let solved = state.displaced_positions(0, 0.0);
+    assert_eq!(solved, verts);
+}
+
+// ─── Entropy ─────────────────────────────────────────────────────────────────
+
+#[test]
+fn stage_axes_are_separated() {
+    let verts = cloud(60);
+    let groups =
+        assign_stage_groups(&[verts.as_slice()], &[Matrix4::identity()], 3, TRI, SEED).concat();
+    let clusters: Vec<usize> = (0..60).collect();
+    let search = best_stage_axes_from_offsets(&verts, &groups, &clusters, &params(3), 8, SEED);
+    assert_eq!(search.stages.len(), 3);
+    let axes = search.axes();
+    for i in 0..axes.len() {
+        for j in 0..i {
+            let angle = axes[i].dot(axes[j]).abs().min(1.0).acos();
+            assert!(
+                angle >= MIN_STAGE_SEPARATION_RAD - 1e-4,
+                "stages {j} and {i} are only {:.1}° apart",
+                angle.to_degrees()
+            );
+        }
+    }
+}
+
+#[test]
+fn stage_axes_are_separated_even_when_every_draw_is_close() {
+    // One candidate on the narrow upright band: across these seeds some
+    // stage's redraws all land near an earlier axis.
+    let verts = cloud(30);
+    let groups =
+        assign_stage_groups(&[verts.as_slice()], &[Matrix4::identity()], 3, TRI, SEED).concat();
+    let clusters: Vec<usize> = (0..30).collect();
+    let params = params(3).with_upright(true);
+    for n in 0..64 {
+        let seed = PuzzleSeed::new(n);
+        let axes =
+            best_stage_axes_from_offsets(&verts, &groups, &clusters, &params, 1, seed).axes();
+        assert_eq!(axes.len(), 3);
+        for i in 0..axes.len() {
+            for j in 0..i {
+                let angle = axes[i].dot(axes[j]).abs().min(1.0).acos();
+                assert!(
+                    angle >= MIN_STAGE_SEPARATION_RAD - 1e-4,
+                    "seed {n}: stages {j} and {i} are only {:.1}° apart",
+                    angle.to_degrees()
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn one_stage_search_matches_single_axis_search() {
+    let verts = cloud(20);
+    let groups = vec![0; 20];
+    let clusters: Vec<usize> = (0..20).collect();
+    let staged = best_stage_axes_from_offsets(&verts, &groups, &clusters, &params(1), 6, SEED);
+    let single = best_scramble_axis_from_offsets(&verts, 0.1, 0.5, 6, SEED);
+    assert_eq!(staged.axes(), vec![single.axis]);
+    assert_eq!(staged.combined, single.report);
+}
+
+#[test]
+fn combined_difficulty_exceeds_each_stage() {
+    let stages = [report(0.4), report(0.3)];
+    let combined = combine_reports(&stages);
+    assert!((combined.difficulty - (1.0 - 0.6 * 0.7)).abs() < 1e-6);
+    assert!(combined.difficulty > 0.4);
+    assert!((combined.entropy_bits - 4.0).abs() < 1e-6);
+    assert_eq!(combine_reports(&[report(0.4)]), report(0.4));
+}
+
+// ─── Hints ───────────────────────────────────────────────────────────────────
+
+#[test]
+fn next_stage_resets_tier_but_keeps_count() {
+    let mut hints = HintSystem::new();
+    hints.set_stage_count(2);
+    for _ in 0..4 {
+        hints.advance();
+    }
+    let _ = hints.update(5.0, Vector3::unit_z(), Vector3::unit_z());
+
+    hints.next_stage();
+    assert_eq!(hints.tier(), HintTier::None);
+    assert_eq!(hints.hint_count(), 4);
+    let out = hints.update(0.1, Vector3::unit_z(), Vector3::unit_x());
+    assert_eq!(out.ghost_lerp, 0.0);
+    assert_eq!((out.stage, out.stage_count), (1, 2));
+}
@@ -0,0 +1,147 @@
+use cgmath::{InnerSpace, Matrix4, SquareMatrix, Vector3, Vector4};
+use formosaic::puzzle::outline::{project_segments, silhouette_edges};
+use formosaic::puzzle::scrambler::ScrambleTarget;
+
+/// Unit cube centred on the origin as triangle soup, outward winding.
+fn cube() -> Vec<f32> {
+    let faces: [[Vector3<f32>; 3]; 6] = [
+        // normal, u, v  (u × v = normal)
+        [Vector3::unit_x(), Vector3::unit_y(), Vector3::unit_z()],
+        [-Vector3::unit_x(), Vector3::unit_z(), Vector3::unit_y()],
+        [Vector3::unit_y(), Vector3::unit_z(), Vector3::unit_x()],
+        [-Vector3::unit_y(), Vector3::unit_x(), Vector3::unit_z()],
+        [Vector3::unit_z(), Vector3::unit_x(), Vector3::unit_y()],
+        [-Vector3::unit_z(), Vector3::unit_y(), Vector3::unit_x()],
+    ];
+    let mut v = Vec::new();
+    for [n, u, w] in faces {
+        let c = n * 0.5;
+        let corner = |a: f32, b: f32| c + u * a + w * b;
+        let quad = [
+            corner(-0.5, -0.5),
+            corner(0.5, -0.5),
+            corner(0.5, 0.5),
+            corner(-0.5, 0.5),
+        ];
+        for i in [0, 1, 2, 0, 2, 3] {
+            v.extend_from_slice(&[quad[i].x, quad[i].y, quad[i].z]);
+        }
+    }
+    v
+}
+
+#[test]
+fn cube_seen_along_an_axis_outlines_one_square() {
+    let edges = silhouette_edges(
+        &[cube()],
+        &[Matrix4::identity()],
+        ScrambleTarget::Axis(-Vector3::unit_z()),
+    );
+    assert_eq!(edges.len(), 4, "{edges:?}");
+    for [a, b] in &edges {
+        assert_eq!((a.z, b.z), (0.5, 0.5), "outline on the facing side");
+        assert!(((a - b).magnitude() - 1.0).abs() < 1e-6, "no diagonals");
+    }
+}
+
+#[test]
+fn convex_outline_bounds_the_projection() {
+    let dir = Vector3::new(0.3, -0.5, 0.8).normalize();
+    let tilt = Matrix4::from_angle_y(cgmath::Deg(20.0)) * Matrix4::from_angle_x(cgmath::Deg(35.0));
+    let meshes = vec![cube()];
+    let mesh = &meshes[0];
+    let edges = silhouette_edges(&meshes, &[tilt], ScrambleTarget::Axis(dir));
+    assert_eq!(
+        edges.len(),
+        6,
+        "a generic view of a cube has a hexagonal outline"
+    );
+
+    // Every vertex projects onto the inner side of every outline edge.
+    let right = dir.cross(Vector3::unit_y()).normalize();
+    let up = right.cross(dir);
+    let flat = |p: Vector3<f32>| (p.dot(right), p.dot(up));
+    let verts: Vec<Vector3<f32>> = mesh
+        .chunks_exact(3)
+        .map(|p| (tilt * Vector4::new(p[0], p[1], p[2], 1.0)).truncate())
+        .collect();
+    for [a, b] in &edges {
+        let ((ax, ay), (bx, by)) = (flat(*a), flat(*b));
+        let sides: Vec<f32> = verts
+            .iter()
+            .map(|&p| {
+                let (px, py) = flat(p);
+                (bx - ax) * (py - ay) - (by - ay) * (px - ax)
+            })
+            .collect();
+        let all_left = sides.iter().all(|&s| s >= -1e-5);
+        let all_right = sides.iter().all(|&s| s <= 1e-5);
+        assert!(all_left || all_right, "edge {a:?}-{b:?} is not on the hull");
+    }
+}
+
+#[test]
+fn meshes_are_welded_in_world_space() {
+    // The cube's faces split over two meshes, the second one placed by its
+    // transform, still outline as one cube.
+    let mesh = cube();
+    let (first, second) = mesh.split_at(9 * 6);
+    let shift = Vector3::new(2.0, 0.0, 0.0);
+    let moved: Vec<f32> = second
+        .chunks_exact(3)
+        .flat_map(|p| [p[0] - shift.x, p[1], p[2]])
+        .collect();
+    let edges = silhouette_edges(
+        &[first.to_vec(), moved],
+        &[Matrix4::identity(), Matrix4::from_translation(shift)],
+        ScrambleTarget::Axis(-Vector3::unit_z()),
+    );
+    assert_eq!(edges.len(), 4);
+}
+
+#[test]
+fn eye_point_sees_a_perspective_outline() {
+    // Close to the front face, the side faces turn away from the eye and
+    // the outline is the front square alone; from far off-axis it is not.
+    let eye = Vector3::new(0.0, 0.0, 3.0);
+    let edges = silhouette_edges(&[cube()], &[Matrix4::identity()], ScrambleTarget::Eye(eye));
+    assert_eq!(edges.len(), 4);
+    assert!(edges.iter().all(|[a, b]| a.z == 0.5 && b.z == 0.5));
+
+    let side = Vector3::new(3.0, 2.0, 3.0);
+    let edges = silhouette_edges(&[cube()], &[Matrix4::identity()], ScrambleTarget::Eye(side));
+    assert_eq!(edges.len(), 6);
+}
+
+#[test]
+fn open_mesh_keeps_its_boundary() {
+    let tri = vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0];
+    let edges = silhouette_edges(
+        &[tri],
+        &[Matrix4::identity()],
+        ScrambleTarget::Axis(Vector3::unit_z()),
+    );
+    assert_eq!(edges.len(), 3);
+    assert!(silhouette_edges(&[], &[], ScrambleTarget::Axis(Vector3::unit_z())).is_empty());
+}
+
+#[test]
+fn projection_clips_at_the_near_plane() {
+    let proj = cgmath::perspective(cgmath::Deg(60.0), 1.0, 0.1, 100.0);
+    let view = Matrix4::look_at_rh(
+        cgmath::Point3::new(0.0, 0.0, 5.0),
+        cgmath::Point3::new(0.0, 0.0, 0.0),
+        Vector3::unit_y(),
+    );
+    let segments = [
+        [Vector3::new(-1.0, 0.0, 0.0), Vector3::new(1.0, 0.0, 0.0)],
+        // Runs from in front of the camera to behind it.
+        [Vector3::new(0.0, 1.0, 0.0), Vector3::new(0.0, 1.0, 10.0)],
+        // Entirely behind.
+        [Vector3::new(0.0, 0.0, 8.0), Vector3::new(1.0, 0.0, 9.0)],
+    ];
+    let ndc = project_segments(&segments, proj * view);
+    assert_eq!(ndc.len(), 8);
+    assert!(ndc[0] < 0.0 && ndc[2] > 0.0 && ndc[1] == 0.0);
+    assert!(ndc.iter().all(|v| v.is_finite()));
+}
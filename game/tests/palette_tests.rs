@@ -0,0 +1,131 @@
+use cgmath::Vector3;
+use formosaic::level::settings::Settings;
+use formosaic::puzzle::hints::HintSystem;
+use formosaic::puzzle::palette::{luminance, warmth_bar, warmth_percent, WarmthPalette};
+
+fn close(a: [f32; 3], b: [f32; 3]) -> bool {
+    a.iter().zip(&b).all(|(x, y)| (x - y).abs() < 1e-5)
+}
+
+// ─── Palettes ────────────────────────────────────────────────────────────────
+
+#[test]
+fn palettes_run_from_cold_to_hot() {
+    for palette in WarmthPalette::ALL {
+        assert!(close(palette.color(0.0), palette.cold()), "{palette:?}");
+        assert!(close(palette.color(1.0), palette.hot()), "{palette:?}");
+        assert!(close(palette.color(-1.0), palette.cold()), "{palette:?}");
+        assert!(close(palette.color(2.0), palette.hot()), "{palette:?}");
+    }
+    assert!(close(WarmthPalette::Standard.color(0.5), [1.0; 3]));
+}
+
+#[test]
+fn accessible_palettes_warm_up_in_brightness() {
+    // Hue alone cannot be relied on, so outside the standard palette a
+    // warmer colour is never darker, and hot is clearly lighter than cold.
+    for palette in WarmthPalette::ALL
+        .into_iter()
+        .filter(|&p| p != WarmthPalette::Standard)
+    {
+        let mut last = luminance(palette.color(0.0));
+        for i in 1..=20 {
+            let l = luminance(palette.color(i as f32 / 20.0));
+            assert!(l >= last - 1e-6, "{palette:?} darkens at step {i}");
+            last = l;
+        }
+        assert!(
+            luminance(palette.hot()) - luminance(palette.cold()) > 0.2,
+            "{palette:?}"
+        );
+    }
+    let grey = WarmthPalette::Monochrome.color(0.4);
+    assert!(grey[0] == grey[1] && grey[1] == grey[2]);
+}
+
+#[test]
+fn next_cycles_through_every_palette() {
+    let mut seen = vec![WarmthPalette::default()];
+    let mut p = WarmthPalette::default().next();
+    while p != WarmthPalette::default() {
+        assert!(!seen.contains(&p));
+        seen.push(p);
+        p = p.next();
+    }
+    assert_eq!(seen.len(), WarmthPalette::ALL.len());
+}
+
+#[test]
+fn meta_strings_round_trip() {
+    for palette in WarmthPalette::ALL {
+        assert_eq!(
+            WarmthPalette::from_meta_str(palette.to_meta_str()),
+            Some(palette)
+        );
+    }
+    assert_eq!(WarmthPalette::from_meta_str("sepia"), None);
+}
+
+#[test]
+fn hint_output_uses_the_palette() {
+    let mut hints = HintSystem::new();
+    hints.advance();
+    let standard = hints.update(0.1, Vector3::unit_z(), Vector3::unit_z());
+    assert!(close(standard.warmth_color, WarmthPalette::Standard.hot()));
+
+    hints.set_palette(WarmthPalette::Tritanopia);
+    hints.reset_full();
+    assert_eq!(hints.palette(), WarmthPalette::Tritanopia);
+    let away = hints.update(0.1, -Vector3::unit_x(), Vector3::unit_z());
+    assert!(close(
+        away.warmth_color,
+        WarmthPalette::Tritanopia.color(away.warmth)
+    ));
+}
+
+// ─── Non-colour indicator ────────────────────────────────────────────────────
+
+#[test]
+fn warmth_reads_as_number_and_bar() {
+    assert_eq!(warmth_percent(0.0), 0);
+    assert_eq!(warmth_percent(0.874), 87);
+    assert_eq!(warmth_percent(1.5), 100);
+    assert_eq!(warmth_bar(0.0, 5), "[-----]");
+    assert_eq!(warmth_bar(0.6, 5), "[###--]");
+    assert_eq!(warmth_bar(1.0, 5), "[#####]");
+}
+
+// ─── Settings ────────────────────────────────────────────────────────────────
+
+#[test]
+fn settings_round_trip() {
+    let settings = Settings {
+        warmth_palette: WarmthPalette::Protanopia,
+    };
+    let json = settings.to_json();
+    assert_eq!(json, r#"{"version":1,"warmth_palette":"protanopia"}"#);
+    assert_eq!(Settings::from_json(&json), settings);
+}
+
+#[test]
+fn unknown_settings_fall_back_to_defaults() {
+    assert_eq!(Settings::from_json(""), Settings::default());
+    assert_eq!(
+        Settings::from_json(r#"{"version":2,"warmth_palette":"sepia"}"#),
+        Settings::default()
+    );
+}
+
+#[test]
+fn settings_persist_in_the_data_dir() {
+    let dir = std::env::temp_dir().join(format!("formosaic_settings_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    assert_eq!(Settings::load(&dir), Settings::default());
+
+    let settings = Settings {
+        warmth_palette: WarmthPalette::Monochrome,
+    };
+    settings.save(&dir);
+    assert_eq!(Settings::load(&dir), settings);
+    let _ = std::fs::remove_dir_all(&dir);
+}
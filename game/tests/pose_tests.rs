@@ -0,0 +1,282 @@
+use cgmath::{Deg, InnerSpace, Matrix4, Quaternion, Rotation3, SquareMatrix, Vector3, Vector4};
+use formosaic::puzzle::pose::{solve_clip, ModelPose, Pose, PoseLoop, SkinnedMesh};
+use formosaic::puzzle::puzzle_params::{PuzzleParams, ScrambleStrategy};
+use formosaic::puzzle::scrambler::{compute_staged_offsets, ScrambleTarget, StageSplit};
+use formosaic::puzzle::seed::PuzzleSeed;
+use formosaic_engine::architecture::models::animation::{
+    AnimationClip, BoneChannel, PositionKey, RotationKey, ScalingKey,
+};
+use formosaic_engine::architecture::models::skeleton::{BoneData, Skeleton};
+
+const SEED: PuzzleSeed = PuzzleSeed::new(0x905e);
+
+/// A root bone and an arm one unit along +X from it.
+fn skeleton() -> Skeleton {
+    let arm_bind = Matrix4::from_translation(Vector3::new(1.0, 0.0, 0.0));
+    let bones = vec![
+        BoneData {
+            name: "root".into(),
+            bind_local_transform: Matrix4::identity(),
+            offset_matrices: vec![Matrix4::identity()],
+            parent_index: None,
+        },
+        BoneData {
+            name: "arm".into(),
+            bind_local_transform: arm_bind,
+            offset_matrices: vec![arm_bind.invert().unwrap()],
+            parent_index: Some(0),
+        },
+    ];
+    Skeleton::new(bones, 1)
+}
+
+/// A one-second clip that swings the arm from 90° up to 0°.
+fn clip(name: &str) -> AnimationClip {
+    let key = |time: f64, deg: f32| RotationKey {
+        time,
+        value: Quaternion::from_angle_z(Deg(deg)),
+    };
+    let at = |time: f64| PositionKey {
+        time,
+        value: Vector3::new(1.0, 0.0, 0.0),
+    };
+    let unit = |time: f64| ScalingKey {
+        time,
+        value: Vector3::new(1.0, 1.0, 1.0),
+    };
+    AnimationClip {
+        name: name.into(),
+        duration_ticks: 10.0,
+        ticks_per_second: 10.0,
+        channels: vec![BoneChannel {
+            bone_name: "arm".into(),
+            position_keys: vec![at(0.0), at(10.0)],
+            rotation_keys: vec![key(0.0, 90.0), key(10.0, 0.0)],
+            scaling_keys: vec![unit(0.0), unit(10.0)],
+        }],
+    }
+}
+
+/// A triangle on the root, one on the arm and one straddling both.
+fn mesh() -> (Vec<f32>, Vec<[i32; 4]>, Vec<[f32; 4]>) {
+    let positions = vec![
+        -1.0, 0.0, 0.0, -0.5, 0.5, 0.0, -0.5, 0.0, 0.5, // root
+        1.5, 0.0, 0.0, 2.0, 0.5, 0.0, 2.0, 0.0, 0.5, // arm
+        0.5, 0.0, 0.0, 1.0, 0.5, 0.0, 1.0, 0.0, 0.5, // both
+    ];
+    let root = [0, -1, -1, -1];
+    let arm = [1, -1, -1, -1];
+    let both = [0, 1, -1, -1];
+    let bone_indices = vec![root, root, root, arm, arm, arm, both, both, both];
+    let bone_weights = vec![
+        [1.0, 0.0, 0.0, 0.0],
+        [1.0, 0.0, 0.0, 0.0],
+        [1.0, 0.0, 0.0, 0.0],
+        [1.0, 0.0, 0.0, 0.0],
+        [1.0, 0.0, 0.0, 0.0],
+        [1.0, 0.0, 0.0, 0.0],
+        [0.5, 0.5, 0.0, 0.0],
+        [0.5, 0.5, 0.0, 0.0],
+        [0.5, 0.5, 0.0, 0.0],
+    ];
+    (positions, bone_indices, bone_weights)
+}
+
+/// Skinned position of a bind-pose vertex, as the vertex shader draws it.
+fn draw(bones: &[Matrix4<f32>], indices: [i32; 4], weights: [f32; 4], p: [f32; 3]) -> Vector3<f32> {
+    let p = Vector4::new(p[0], p[1], p[2], 1.0);
+    let mut out = Vector4::new(0.0, 0.0, 0.0, 0.0);
+    for (&i, &w) in indices.iter().zip(&weights) {
+        if i >= 0 && w > 0.0 {
+            out += bones[i as usize] * p * w;
+        }
+    }
+    out.truncate()
+}
+
+fn at(flat: &[f32], v: usize) -> [f32; 3] {
+    [flat[v * 3], flat[v * 3 + 1], flat[v * 3 + 2]]
+}
+
+/// The mesh at the start of the clip, and the bones that draw it there.
+fn posed() -> (ModelPose, Vec<Matrix4<f32>>) {
+    let (positions, bone_indices, bone_weights) = mesh();
+    let pose = Pose {
+        clip: Some(0),
+        time_secs: 0.0,
+    };
+    let bones = pose.bone_matrices(&skeleton(), &[clip("Idle")], 1);
+    let meshes = [SkinnedMesh {
+        positions: &positions,
+        bone_indices: &bone_indices,
+        bone_weights: &bone_weights,
+    }];
+    (ModelPose::new(pose, &meshes, &bones, &[]), bones[0].clone())
+}
+
+// ─── Posing ──────────────────────────────────────────────────────────────────
+
+#[test]
+fn bind_pose_leaves_positions_alone() {
+    let (positions, bone_indices, bone_weights) = mesh();
+    let bones = Pose {
+        clip: None,
+        time_secs: 0.0,
+    }
+    .bone_matrices(&skeleton(), &[], 1);
+    let meshes = [SkinnedMesh {
+        positions: &positions,
+        bone_indices: &bone_indices,
+        bone_weights: &bone_weights,
+    }];
+    let pose = ModelPose::new(Pose::for_clips(&[]), &meshes, &bones, &[]);
+    for (a, b) in pose.posed_positions()[0].iter().zip(&positions) {
+        assert!((a - b).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn posed_positions_follow_the_bones() {
+    let (positions, bone_indices, bone_weights) = mesh();
+    let (pose, bones) = posed();
+    let posed = &pose.posed_positions()[0];
+    for v in 0..9 {
+        let expected = draw(&bones, bone_indices[v], bone_weights[v], at(&positions, v));
+        let got = Vector3::from(at(posed, v));
+        assert!((got - expected).magnitude() < 1e-5, "vertex {v}");
+    }
+    // The root triangle stays; the arm's is swung up about the elbow.
+    assert_eq!(at(posed, 0), [-1.0, 0.0, 0.0]);
+    let tip = Vector3::from(at(posed, 3));
+    assert!((tip - Vector3::new(1.0, 0.5, 0.0)).magnitude() < 1e-5);
+}
+
+#[test]
+fn mesh_transform_is_taken_out_of_posed_positions() {
+    let (positions, bone_indices, bone_weights) = mesh();
+    let (plain, _) = posed();
+    let transform = Matrix4::from_scale(2.0);
+    let bones: Vec<Vec<Matrix4<f32>>> = Pose {
+        clip: Some(0),
+        time_secs: 0.0,
+    }
+    .bone_matrices(&skeleton(), &[clip("Idle")], 1);
+    let meshes = [SkinnedMesh {
+        positions: &positions,
+        bone_indices: &bone_indices,
+        bone_weights: &bone_weights,
+    }];
+    let scaled = ModelPose::new(plain.pose, &meshes, &bones, &[transform]);
+    for (a, b) in scaled.posed_positions()[0]
+        .iter()
+        .zip(&plain.posed_positions()[0])
+    {
+        assert!((a * 2.0 - b).abs() < 1e-5);
+    }
+}
+
+#[test]
+fn unskinned_meshes_pass_through() {
+    let positions = vec![0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 0.0];
+    let meshes = [SkinnedMesh {
+        positions: &positions,
+        bone_indices: &[],
+        bone_weights: &[],
+    }];
+    let pose = ModelPose::new(Pose::for_clips(&[]), &meshes, &[vec![]], &[]);
+    assert_eq!(pose.posed_positions()[0], positions);
+    let offsets = vec![0.5; 9];
+    assert_eq!(pose.bind_offsets(0, &offsets), offsets);
+}
+
+// ─── Scrambling in the pose ──────────────────────────────────────────────────
+
+/// One-stage scramble offsets of `soup` along a fixed axis.
+fn scramble(soup: &[f32]) -> Vec<f32> {
+    let params = PuzzleParams::default_for(2.0).with_strategy(ScrambleStrategy::PerTriangle);
+    let transforms = [Matrix4::identity()];
+    let groups =
+        StageSplit::Bands(1).groups(&[soup], &transforms, ScrambleStrategy::PerTriangle, SEED);
+    let target = ScrambleTarget::Axis(Vector3::new(0.2, 0.4, 0.9).normalize());
+    let mut stages =
+        compute_staged_offsets(&[soup], &transforms, &[target], &groups, &params, SEED);
+    stages.remove(0).remove(0)
+}
+
+#[test]
+fn held_pose_draws_the_posed_scramble() {
+    let (positions, bone_indices, bone_weights) = mesh();
+    let (pose, bones) = posed();
+    let posed = &pose.posed_positions()[0];
+    let offsets = scramble(posed);
+    let bind_offsets = pose.bind_offsets(0, &offsets);
+    for v in 0..9 {
+        let p = at(&positions, v);
+        let o = at(&bind_offsets, v);
+        let moved = [p[0] + o[0], p[1] + o[1], p[2] + o[2]];
+        let drawn = draw(&bones, bone_indices[v], bone_weights[v], moved);
+        let expected = Vector3::from(at(posed, v)) + Vector3::from(at(&offsets, v));
+        assert!((drawn - expected).magnitude() < 1e-4, "vertex {v}");
+    }
+}
+
+#[test]
+fn bind_pose_offsets_are_bent_by_the_pose() {
+    // What the game did before: offsets of the bind pose, skinned in
+    // another pose, miss the posed scramble on the arm.
+    let (positions, bone_indices, bone_weights) = mesh();
+    let (pose, bones) = posed();
+    let offsets = scramble(&positions);
+    let v = 3;
+    let p = at(&positions, v);
+    let o = at(&offsets, v);
+    let drawn = draw(
+        &bones,
+        bone_indices[v],
+        bone_weights[v],
+        [p[0] + o[0], p[1] + o[1], p[2] + o[2]],
+    );
+    let posed = Vector3::from(at(&pose.posed_positions()[0], v));
+    assert!((drawn - (posed + Vector3::from(o))).magnitude() > 1e-2);
+}
+
+#[test]
+fn bind_positions_undo_the_pose() {
+    let (positions, _, _) = mesh();
+    let (pose, _) = posed();
+    let back = pose.bind_positions(0, &pose.posed_positions()[0]);
+    for (a, b) in back.iter().zip(&positions) {
+        assert!((a - b).abs() < 1e-5);
+    }
+}
+
+// ─── Clips ───────────────────────────────────────────────────────────────────
+
+#[test]
+fn solve_clip_prefers_idle_and_avoids_locomotion() {
+    let clips = [clip("Run"), clip("Wave"), clip("Idle_01"), clip("Walk")];
+    assert_eq!(solve_clip(&clips), Some(2));
+    assert_eq!(solve_clip(&[clip("Run")]), Some(0));
+    assert_eq!(solve_clip(&[]), None);
+    assert_eq!(Pose::for_clips(&clips).clip, Some(2));
+    assert_eq!(Pose::for_clips(&[]).clip, None);
+}
+
+#[test]
+fn pose_loop_settles_into_the_pose() {
+    let pose = Pose {
+        clip: Some(0),
+        time_secs: 0.25,
+    };
+    let mut pose_loop = PoseLoop::new(pose, &[clip("Idle")]).unwrap();
+    // Fully scrambled, the clip plays and loops.
+    assert!((pose_loop.advance(0.5, 1.0) - 0.75).abs() < 1e-5);
+    assert!((pose_loop.advance(0.5, 1.0) - 0.25).abs() < 1e-5);
+    assert!((pose_loop.advance(0.25, 1.0) - 0.5).abs() < 1e-5);
+    // Lined up it shows the pose, though the clip plays on underneath;
+    // half way, it is half way there.
+    assert!((pose_loop.advance(0.25, 0.0) - 0.25).abs() < 1e-5);
+    assert!((pose_loop.advance(0.0, 0.5) - 0.5).abs() < 1e-5);
+
+    assert!(PoseLoop::new(Pose::for_clips(&[]), &[]).is_none());
+}
@@ -0,0 +1,157 @@
+use std::cell::Cell;
+use std::rc::Rc;
+
+use cgmath::{Deg, InnerSpace, Quaternion, Rotation3, Vector3};
+use formosaic::level::replay::{
+    frustum_segments, Recorder, Recording, ReplayController, ReplayFrame, ReplayPuzzle,
+    REPLAY_VERSION, SAMPLE_INTERVAL_SECS,
+};
+use formosaic::puzzle::puzzle_params::ScrambleProjection;
+use formosaic::puzzle::seed::PuzzleSeed;
+use formosaic_engine::architecture::scene::node::transform::Transform;
+use formosaic_engine::rendering::instances::camera::camera_controller::CameraController;
+
+const PUZZLE: ReplayPuzzle = ReplayPuzzle {
+    seed: PuzzleSeed::new(0x9e57),
+    stages: 2,
+    max_roll: 0.75,
+    projection: ScrambleProjection::Anamorphic,
+};
+
+fn camera(x: f32, yaw_deg: f32) -> Transform {
+    Transform {
+        position: Vector3::new(x, 1.0, 5.0),
+        rotation: Quaternion::from_angle_y(Deg(yaw_deg)),
+        ..Transform::new()
+    }
+}
+
+/// A recording of `n` frames sliding along x and turning about y.
+fn recording(n: usize) -> Recording {
+    let mut rec = Recorder::new(PUZZLE);
+    for i in 0..n - 1 {
+        rec.tick(SAMPLE_INTERVAL_SECS, &camera(i as f32, i as f32 * 10.0));
+        if i == 2 {
+            rec.hint(1);
+        }
+    }
+    rec.finish(&camera((n - 1) as f32, (n - 1) as f32 * 10.0), 12.5)
+}
+
+fn close(a: Vector3<f32>, b: Vector3<f32>) -> bool {
+    (a - b).magnitude() < 1e-3
+}
+
+// ─── Format ──────────────────────────────────────────────────────────────────
+
+#[test]
+fn round_trips_through_bytes() {
+    let rec = recording(6);
+    let back = Recording::from_bytes(&rec.to_bytes()).unwrap();
+    assert_eq!(back.puzzle, PUZZLE);
+    assert_eq!(
+        (back.time_secs, back.events.clone()),
+        (12.5, rec.events.clone())
+    );
+    assert_eq!(back.frames.len(), rec.frames.len());
+    for (a, b) in rec.frames.iter().zip(&back.frames) {
+        assert!(close(a.position, b.position));
+        // Rotations are quantised to 16 bits per component.
+        assert!(a.rotation.dot(b.rotation).abs() > 1.0 - 1e-6);
+    }
+}
+
+#[test]
+fn is_compact() {
+    let rec = recording(100);
+    assert!(rec.to_bytes().len() < 100 * 20 + 64);
+}
+
+#[test]
+fn rejects_other_versions_and_damage() {
+    let bytes = recording(4).to_bytes();
+    assert!(Recording::from_bytes(&bytes[..bytes.len() - 1]).is_none());
+    let mut other = bytes.clone();
+    other[4] = REPLAY_VERSION + 1;
+    assert!(Recording::from_bytes(&other).is_none());
+    let mut bad_magic = bytes;
+    bad_magic[0] = b'X';
+    assert!(Recording::from_bytes(&bad_magic).is_none());
+}
+
+#[test]
+fn saves_next_to_the_level() {
+    let dir = std::env::temp_dir().join(format!("formosaic_replay_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    assert!(Recording::load(&dir).is_none());
+    let rec = recording(5);
+    rec.save(&dir).unwrap();
+    assert_eq!(Recording::load(&dir).unwrap().frames.len(), 5);
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+// ─── Recording ───────────────────────────────────────────────────────────────
+
+#[test]
+fn samples_at_a_fixed_interval() {
+    let mut rec = Recorder::new(PUZZLE);
+    rec.tick(0.016, &camera(0.0, 0.0));
+    // Quick frames add up towards the next sample without taking one.
+    for _ in 0..3 {
+        rec.tick(SAMPLE_INTERVAL_SECS * 0.2, &camera(1.0, 0.0));
+    }
+    rec.hint(2);
+    // A long frame fills in every interval it covers.
+    rec.tick(SAMPLE_INTERVAL_SECS * 3.2, &camera(2.0, 0.0));
+    let rec = rec.finish(&camera(3.0, 0.0), 1.0);
+    let xs: Vec<f32> = rec.frames.iter().map(|f| f.position.x).collect();
+    assert_eq!(xs, [0.0, 2.0, 2.0, 2.0, 3.0]);
+    assert_eq!(rec.events[0].frame, 1);
+    assert_eq!(rec.events[0].tier, 2);
+}
+
+#[test]
+fn sample_interpolates_and_holds_at_the_ends() {
+    let rec = recording(4);
+    let mid = rec.sample(SAMPLE_INTERVAL_SECS * 1.5).unwrap();
+    assert!((mid.position.x - 1.5).abs() < 1e-4);
+    let fwd = mid.rotation * -Vector3::unit_z();
+    let expected = Quaternion::from_angle_y(Deg(15.0)) * -Vector3::unit_z();
+    assert!(close(fwd, expected));
+
+    assert_eq!(rec.sample(-1.0).unwrap().position.x, 0.0);
+    assert_eq!(rec.sample(100.0).unwrap().position.x, 3.0);
+    assert_eq!(rec.hints_at(0.0), 0);
+    assert_eq!(rec.hints_at(rec.duration_secs()), 1);
+}
+
+// ─── Playback ────────────────────────────────────────────────────────────────
+
+#[test]
+fn controller_follows_the_clock() {
+    let rec = Rc::new(recording(4));
+    let clock = Rc::new(Cell::new(0.0));
+    let mut controller = ReplayController::new(rec.clone(), clock.clone());
+    let mut transform = Transform::new();
+    controller.control(&mut transform);
+    assert!(close(transform.position, rec.frames[0].position));
+    clock.set(SAMPLE_INTERVAL_SECS * 2.0);
+    controller.control(&mut transform);
+    assert!(close(transform.position, rec.frames[2].position));
+}
+
+#[test]
+fn ghost_frustum_points_where_the_camera_looks() {
+    let frame = ReplayFrame {
+        position: Vector3::new(0.0, 0.0, 5.0),
+        rotation: Quaternion::from_angle_y(Deg(0.0)),
+    };
+    let segments = frustum_segments(&frame, 60f32.to_radians(), 1.5, 2.0);
+    assert_eq!(segments.len(), 11);
+    let from_eye: Vec<_> = segments.iter().filter(|s| s[0] == frame.position).collect();
+    assert_eq!(from_eye.len(), 4);
+    for s in from_eye {
+        // Every edge from the eye runs towards -z, two units deep.
+        assert!((s[1].z - 3.0).abs() < 1e-5);
+    }
+}
@@ -0,0 +1,223 @@
+mod common;
+
+use cgmath::{InnerSpace, Matrix4, SquareMatrix, Vector3};
+use common::cloud;
+use formosaic::puzzle::hints::{HintSystem, WARMTH_HALF_PX};
+use formosaic::puzzle::puzzle_params::ScrambleStrategy;
+use formosaic::puzzle::reprojection::{
+    misalignment, sampled_misalignment, ScreenView, SeamSamples, MAX_SAMPLES, SNAP_TOLERANCE_PX,
+};
+use formosaic::puzzle::scrambler::{
+    compute_raw_anamorphic_offsets, compute_raw_offsets, compute_raw_roll_offsets, ScrambleStage,
+    ScrambleTarget,
+};
+use formosaic::puzzle::seed::PuzzleSeed;
+
+const SEED: PuzzleSeed = PuzzleSeed::new(0x5e4);
+const FOV: f32 = 75.0 * std::f32::consts::PI / 180.0;
+const HEIGHT: f32 = 1080.0;
+
+fn target() -> Vector3<f32> {
+    Vector3::new(1.5, 1.5, 0.5)
+}
+
+/// Camera `dist` from the target, looking at it from `dir`.
+fn view_from(dir: Vector3<f32>, dist: f32, height: f32) -> ScreenView {
+    let dir = dir.normalize();
+    let up = if dir.y.abs() > 0.9 {
+        Vector3::unit_z()
+    } else {
+        Vector3::unit_y()
+    };
+    ScreenView::new(target() + dir * dist, -dir, up, target(), FOV, height)
+}
+
+fn parallel_stage(verts: &[f32], axis: Vector3<f32>) -> ScrambleStage {
+    ScrambleStage {
+        solution_dir: axis,
+        solution_eye: None,
+        solution_up: None,
+        scramble_offsets: compute_raw_offsets(
+            &[verts],
+            &[Matrix4::identity()],
+            axis,
+            0.1,
+            0.5,
+            ScrambleStrategy::PerTriangle,
+            SEED,
+        ),
+    }
+}
+
+// ─── Parallel stages ─────────────────────────────────────────────────────────
+
+#[test]
+fn parallel_stage_is_aligned_from_either_pole() {
+    let rest = vec![cloud(32)];
+    let verts = &rest[0];
+    let axis = Vector3::new(0.2, 0.3, 0.9).normalize();
+    let stage = parallel_stage(verts, axis);
+    let mats = [Matrix4::identity()];
+    for pole in [axis, -axis] {
+        let m = misalignment(&stage, &rest, &mats, &view_from(pole, 8.0, HEIGHT));
+        assert!(m.max_px < 1e-3, "pole {pole:?}: {m:?}");
+        assert!(m.is_solved());
+    }
+}
+
+#[test]
+fn error_grows_with_the_angle_off_axis() {
+    let rest = vec![cloud(32)];
+    let verts = &rest[0];
+    let axis = Vector3::unit_z();
+    let stage = parallel_stage(verts, axis);
+    let mats = [Matrix4::identity()];
+    let mut prev = 0.0;
+    for deg in [2.0f32, 10.0, 30.0, 60.0] {
+        let a = deg.to_radians();
+        let dir = Vector3::new(a.sin(), 0.0, a.cos());
+        let m = misalignment(&stage, &rest, &mats, &view_from(dir, 8.0, HEIGHT));
+        assert!(m.rms_px > prev, "{deg}°: {m:?}");
+        assert!(m.max_px >= m.rms_px);
+        prev = m.rms_px;
+    }
+    assert!(prev > SNAP_TOLERANCE_PX);
+}
+
+#[test]
+fn error_scales_with_resolution_and_model_size() {
+    let rest = vec![cloud(32)];
+    let verts = &rest[0];
+    let stage = parallel_stage(verts, Vector3::unit_z());
+    let dir = Vector3::new(0.3, 0.0, 1.0);
+    let one = [Matrix4::identity()];
+    let base = misalignment(&stage, &rest, &one, &view_from(dir, 8.0, HEIGHT));
+    let hi_res = misalignment(&stage, &rest, &one, &view_from(dir, 8.0, HEIGHT * 2.0));
+    assert!((hi_res.rms_px / base.rms_px - 2.0).abs() < 1e-3);
+
+    // Scaling the model about the target scales its on-screen error.
+    let grow = Matrix4::from_translation(target())
+        * Matrix4::from_scale(2.0)
+        * Matrix4::from_translation(-target());
+    let big = misalignment(&stage, &rest, &[grow], &view_from(dir, 8.0, HEIGHT));
+    assert!((big.rms_px / base.rms_px - 2.0).abs() < 1e-3);
+}
+
+#[test]
+fn untouched_triangles_are_skipped() {
+    let rest = vec![cloud(16)];
+    let verts = &rest[0];
+    let mut stage = parallel_stage(verts, Vector3::unit_z());
+    let view = view_from(Vector3::new(0.4, 0.0, 1.0), 8.0, HEIGHT);
+    let mats = [Matrix4::identity()];
+    let full = misalignment(&stage, &rest, &mats, &view);
+    // Another stage owns every second triangle.
+    for tri in (1..16).step_by(2) {
+        stage.scramble_offsets[0][tri * 9..tri * 9 + 9].fill(0.0);
+    }
+    let half = misalignment(&stage, &rest, &mats, &view);
+    assert!(half.rms_px > full.rms_px * 0.5, "zeros diluted the RMS");
+    stage.scramble_offsets[0].fill(0.0);
+    let none = misalignment(&stage, &rest, &mats, &view);
+    assert_eq!(none.max_px, 0.0);
+    assert!(none.is_solved());
+}
+
+#[test]
+fn samples_are_capped_and_kept_for_the_stage() {
+    let rest = vec![cloud(MAX_SAMPLES * 3 + 5)];
+    let stage = parallel_stage(&rest[0], Vector3::unit_z());
+    let samples = SeamSamples::of_stage(&stage, &rest);
+    assert!(samples.len() <= MAX_SAMPLES);
+    assert!(samples.len() > MAX_SAMPLES / 2);
+
+    // Measured on the kept samples, the error still tracks the camera.
+    let mats = [Matrix4::identity()];
+    let near = view_from(Vector3::new(0.05, 0.0, 1.0), 8.0, HEIGHT);
+    let far = view_from(Vector3::new(0.6, 0.0, 1.0), 8.0, HEIGHT);
+    let m_near = sampled_misalignment(&stage, &samples, &rest, &mats, &near);
+    let m_far = sampled_misalignment(&stage, &samples, &rest, &mats, &far);
+    assert!(m_far.rms_px > m_near.rms_px);
+    assert_eq!(m_far, misalignment(&stage, &rest, &mats, &far));
+}
+
+// ─── Anamorphic and rotation stages ──────────────────────────────────────────
+
+#[test]
+fn eye_stage_is_aligned_at_the_eye_only() {
+    let rest = vec![cloud(32)];
+    let verts = &rest[0];
+    let dir = Vector3::new(0.3, 0.4, 1.0).normalize();
+    let eye = target() + dir * 6.0;
+    let stage = ScrambleStage {
+        solution_dir: -dir,
+        solution_eye: Some(eye),
+        solution_up: None,
+        scramble_offsets: compute_raw_anamorphic_offsets(
+            &[verts.as_slice()],
+            &[Matrix4::identity()],
+            eye,
+            0.1,
+            0.5,
+            ScrambleStrategy::PerTriangle,
+            SEED,
+        ),
+    };
+    let mats = [Matrix4::identity()];
+    let at_eye = misalignment(&stage, &rest, &mats, &view_from(dir, 6.0, HEIGHT));
+    assert!(at_eye.max_px < 1e-2, "{at_eye:?}");
+    let opposite = misalignment(&stage, &rest, &mats, &view_from(-dir, 6.0, HEIGHT));
+    assert!(
+        !opposite.is_solved(),
+        "the far pole must not solve a point puzzle"
+    );
+}
+
+#[test]
+fn roll_stage_needs_the_roll_as_well() {
+    let rest = vec![cloud(16)];
+    let verts = &rest[0];
+    let axis = Vector3::unit_z();
+    let mut stage = parallel_stage(verts, axis);
+    let roll = compute_raw_roll_offsets(
+        &[verts.as_slice()],
+        &[Matrix4::identity()],
+        ScrambleTarget::Axis(axis),
+        1.0,
+        ScrambleStrategy::PerTriangle,
+        SEED,
+    );
+    for (o, r) in stage.scramble_offsets[0].iter_mut().zip(&roll[0]) {
+        *o += r;
+    }
+    stage.solution_up = Some(Vector3::unit_x());
+    let mats = [Matrix4::identity()];
+    let cam = target() + axis * 8.0;
+    let upright = ScreenView::new(cam, -axis, Vector3::unit_y(), target(), FOV, HEIGHT);
+    let rolled = ScreenView::new(cam, -axis, Vector3::unit_x(), target(), FOV, HEIGHT);
+    assert!(!misalignment(&stage, &rest, &mats, &upright).is_solved());
+    let m = misalignment(&stage, &rest, &mats, &rolled);
+    assert!(
+        m.max_px < 1e-3,
+        "in-plane turn counted as translation: {m:?}"
+    );
+}
+
+// ─── Hints ───────────────────────────────────────────────────────────────────
+
+#[test]
+fn warmth_follows_the_pixel_error() {
+    let mut hints = HintSystem::new();
+    let dir = Vector3::unit_z();
+    assert!(hints.update_error(0.1, 0.0, dir).warmth > 0.999);
+    assert!(hints.update_error(0.1, SNAP_TOLERANCE_PX, dir).warmth > 0.999);
+    let half = hints.update_error(0.1, SNAP_TOLERANCE_PX + WARMTH_HALF_PX, dir);
+    assert!((half.warmth - 0.5).abs() < 1e-5);
+    let mut prev = 1.0;
+    for px in [4.0, 16.0, 64.0, 256.0] {
+        let w = hints.update_error(0.1, px, dir).warmth;
+        assert!(w < prev, "{px} px");
+        prev = w;
+    }
+    assert!(prev < 0.1);
+}
@@ -0,0 +1,80 @@
+use cgmath::Vector3;
+use formosaic::level::daily::UtcDate;
+use formosaic::level::replay::ReplayPuzzle;
+use formosaic::level::resume::SavedPuzzle;
+use formosaic::puzzle::hints::{HintSystem, HintTier};
+use formosaic::puzzle::puzzle_params::ScrambleProjection;
+use formosaic::puzzle::seed::PuzzleSeed;
+
+fn saved() -> SavedPuzzle {
+    SavedPuzzle {
+        level_id: "fox_01".to_string(),
+        puzzle: ReplayPuzzle {
+            // The full 64 bits must survive; f32 parsing would not keep them.
+            seed: PuzzleSeed::new(0x9e37_79b9_7f4a_7c15),
+            stages: 3,
+            max_roll: 0.75,
+            projection: ScrambleProjection::Anamorphic,
+        },
+        stage: 1,
+        camera_position: Vector3::new(0.5, -1.25, 3.1),
+        camera_roll: -0.3,
+        elapsed_secs: 42.5,
+        hint_tier: HintTier::AxisPlane,
+        hint_count: 3,
+        tier_uses: [0, 2, 1, 0, 0],
+        daily: Some(UtcDate::new(2025, 3, 14)),
+    }
+}
+
+#[test]
+fn round_trips_through_json() {
+    let s = saved();
+    assert_eq!(SavedPuzzle::from_json(&s.to_json()), Some(s.clone()));
+    let not_daily = SavedPuzzle { daily: None, ..s };
+    assert_eq!(
+        SavedPuzzle::from_json(&not_daily.to_json()),
+        Some(not_daily)
+    );
+}
+
+#[test]
+fn rejects_other_versions_and_bad_fields() {
+    let json = saved().to_json();
+    assert!(SavedPuzzle::from_json(&json.replace(r#""version":1"#, r#""version":2"#)).is_none());
+    assert!(
+        SavedPuzzle::from_json(&json.replace(r#""hint_tier":2"#, r#""hint_tier":9"#)).is_none()
+    );
+    assert!(SavedPuzzle::from_json(&json.replace("[0,2,1,0,0]", "[0,2]")).is_none());
+    assert!(SavedPuzzle::from_json("{}").is_none());
+}
+
+#[test]
+fn persists_and_clears() {
+    let dir = std::env::temp_dir().join(format!("formosaic_resume_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    assert!(!SavedPuzzle::exists(&dir));
+    assert!(SavedPuzzle::load(&dir).is_none());
+    saved().save(&dir);
+    assert!(SavedPuzzle::exists(&dir));
+    assert_eq!(SavedPuzzle::load(&dir), Some(saved()));
+    SavedPuzzle::clear(&dir);
+    assert!(!SavedPuzzle::exists(&dir));
+    let _ = std::fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn hints_pick_up_where_they_left_off() {
+    let s = saved();
+    let mut hints = HintSystem::new();
+    hints.set_stage_count(s.puzzle.stages);
+    hints.restore(s.stage, s.hint_tier, s.hint_count, s.tier_uses);
+    assert_eq!(hints.stage(), 1);
+    assert_eq!(hints.tier(), HintTier::AxisPlane);
+    assert_eq!(hints.hint_count(), 3);
+    hints.advance();
+    assert_eq!(hints.tier_uses(), [0, 2, 1, 1, 0]);
+    for v in 0..HintTier::COUNT as u8 {
+        assert_eq!(HintTier::from_u8(v).map(HintTier::as_u8), Some(v));
+    }
+}
@@ -0,0 +1,207 @@
+mod common;
+
+use cgmath::{InnerSpace, Matrix4, SquareMatrix, Vector3};
+use common::{cloud, vertex};
+use formosaic::puzzle::entropy::best_stage_axes_from_offsets;
+use formosaic::puzzle::puzzle_params::{PuzzleParams, ScrambleStrategy};
+use formosaic::puzzle::scramble_math::{compute_roll_offsets, lerp_positions};
+use formosaic::puzzle::scrambler::{
+    camera_roll, compute_raw_roll_offsets, compute_staged_offsets, roll_alignment,
+    solution_roll_up, ScrambleStage, ScrambleState, ScrambleTarget,
+};
+use formosaic::puzzle::seed::PuzzleSeed;
+use formosaic_engine::architecture::scene::node::transform::Transform;
+use formosaic_engine::rendering::instances::camera::camera_controller::CameraController;
+use formosaic_engine::rendering::instances::camera::orbit_controller::OrbitController;
+
+const SEED: PuzzleSeed = PuzzleSeed::new(0x7011);
+const ROLL: f32 = 1.0;
+
+fn centroid(v: &[f32], tri: usize) -> Vector3<f32> {
+    (vertex(v, tri * 3) + vertex(v, tri * 3 + 1) + vertex(v, tri * 3 + 2)) / 3.0
+}
+
+fn params(max_roll: f32) -> PuzzleParams {
+    PuzzleParams {
+        min_disp: 0.1,
+        max_disp: 0.5,
+        ..PuzzleParams::default_for(1.0)
+    }
+    .with_roll(max_roll)
+}
+
+// ─── Offsets ─────────────────────────────────────────────────────────────────
+
+#[test]
+fn fragments_turn_rigidly_about_the_axis() {
+    let verts = cloud(16);
+    let axis = Vector3::new(0.3, 0.9, 0.2).normalize();
+    let clusters: Vec<usize> = (0..16).collect();
+    let offsets = compute_roll_offsets(
+        &verts,
+        &clusters,
+        ScrambleTarget::Axis(axis),
+        ROLL,
+        Matrix4::identity(),
+        SEED,
+    );
+    let turned = lerp_positions(&verts, &offsets, 1.0);
+    for tri in 0..16 {
+        let (c, m) = (centroid(&verts, tri), centroid(&turned, tri));
+        assert!((c - m).magnitude() < 1e-5, "tri {tri} drifted");
+        for i in tri * 3..tri * 3 + 3 {
+            let (a, b) = (vertex(&verts, i) - c, vertex(&turned, i) - m);
+            assert!((a.magnitude() - b.magnitude()).abs() < 1e-5);
+            assert!((a.dot(axis) - b.dot(axis)).abs() < 1e-5, "left its plane");
+        }
+        let flat = |v: Vector3<f32>| (v - axis * v.dot(axis)).normalize();
+        let turn = flat(vertex(&verts, tri * 3) - c).dot(flat(vertex(&turned, tri * 3) - m));
+        assert!(turn < (ROLL * 0.25).cos() + 1e-4, "tri {tri} barely turned");
+    }
+}
+
+#[test]
+fn mesh_transform_turns_about_the_world_axis() {
+    let verts = cloud(8);
+    let transform = Matrix4::from_translation(Vector3::new(1.0, 2.0, 3.0))
+        * Matrix4::from_angle_x(cgmath::Deg(40.0));
+    let axis = Vector3::unit_z();
+    let offsets = compute_raw_roll_offsets(
+        &[verts.as_slice()],
+        &[transform],
+        ScrambleTarget::Axis(axis),
+        ROLL,
+        ScrambleStrategy::PerTriangle,
+        SEED,
+    );
+    let turned = lerp_positions(&verts, &offsets[0], 1.0);
+    let world = |p: Vector3<f32>| (transform * p.extend(1.0)).truncate();
+    for i in 0..verts.len() / 3 {
+        let depth = |v: &[f32]| world(vertex(v, i)).dot(axis);
+        assert!((depth(&verts) - depth(&turned)).abs() < 1e-4);
+    }
+}
+
+#[test]
+fn roll_adds_on_top_of_the_translation() {
+    let verts = cloud(12);
+    let axis = Vector3::unit_y();
+    let stage = |p: &PuzzleParams| {
+        compute_staged_offsets(
+            &[verts.as_slice()],
+            &[Matrix4::identity()],
+            &[ScrambleTarget::Axis(axis)],
+            &[vec![0; 12]],
+            p,
+            SEED,
+        )
+    };
+    let plain = stage(&params(0.0));
+    let rolled = stage(&params(ROLL));
+    let roll = compute_raw_roll_offsets(
+        &[verts.as_slice()],
+        &[Matrix4::identity()],
+        ScrambleTarget::Axis(axis),
+        ROLL,
+        ScrambleStrategy::PerTriangle,
+        SEED,
+    );
+    for i in 0..verts.len() {
+        assert!((rolled[0][0][i] - (plain[0][0][i] + roll[0][i])).abs() < 1e-6);
+    }
+    assert!(compute_roll_offsets(
+        &verts,
+        &[0; 12],
+        ScrambleTarget::Axis(axis),
+        0.0,
+        Matrix4::identity(),
+        SEED
+    )
+    .iter()
+    .all(|&o| o == 0.0));
+}
+
+// ─── Solve detection ─────────────────────────────────────────────────────────
+
+#[test]
+fn solution_roll_is_perpendicular_and_needs_a_twist() {
+    let dir = Vector3::new(0.4, -0.3, -0.8).normalize();
+    for s in 0..16 {
+        let up = solution_roll_up(dir, PuzzleSeed::new(s));
+        assert!(up.dot(dir).abs() < 1e-5);
+        assert!((up.magnitude() - 1.0).abs() < 1e-5);
+        let roll = camera_roll(dir, up).abs();
+        assert!(
+            roll >= 30f32.to_radians() - 1e-4,
+            "seed {s}: only {roll} rad"
+        );
+    }
+}
+
+#[test]
+fn camera_roll_matches_orbit_controller() {
+    let mut ctrl = OrbitController::new(Vector3::new(0.0, 0.0, 0.0), 5.0);
+    let mut transform = Transform::new();
+    transform.position = Vector3::new(3.0, 2.0, 4.0);
+    ctrl.set_initial_position(transform.position);
+    for roll in [-2.5f32, -0.4, 0.0, 0.7, 3.0] {
+        ctrl.set_roll(roll);
+        CameraController::control(&mut ctrl, &mut transform);
+        let measured = camera_roll(transform.forward(), transform.up());
+        assert!(
+            (measured - roll).abs() < 1e-4,
+            "{roll} read back as {measured}"
+        );
+    }
+}
+
+#[test]
+fn roll_alignment_is_the_roll_cosine() {
+    let fwd = -Vector3::unit_z();
+    let up = Vector3::unit_y();
+    assert!((roll_alignment(fwd, up, up) - 1.0).abs() < 1e-6);
+    assert!((roll_alignment(fwd, up, -up) + 1.0).abs() < 1e-6);
+    let tilted = Vector3::new(-(0.5f32).sin(), 0.5f32.cos(), 0.0);
+    assert!((roll_alignment(fwd, up, tilted) - 0.5f32.cos()).abs() < 1e-5);
+}
+
+#[test]
+fn roll_stage_needs_direction_and_roll() {
+    let dir = -Vector3::unit_z();
+    let solution_up = Vector3::unit_x();
+    let state = ScrambleState {
+        stages: vec![ScrambleStage {
+            solution_dir: dir,
+            solution_eye: None,
+            solution_up: Some(solution_up),
+            scramble_offsets: vec![vec![]],
+        }],
+        current_stage: 0,
+        params: params(ROLL),
+        original_positions: vec![vec![]],
+        seed: SEED,
+        pose: None,
+    };
+    let origin = Vector3::new(0.0, 0.0, 0.0);
+    let upright = state.alignment(origin, dir, Vector3::unit_y(), origin);
+    let rolled = state.alignment(origin, dir, solution_up, origin);
+    assert!(upright.abs() < 1e-5, "direction alone must not solve");
+    assert!(rolled > 0.999);
+}
+
+// ─── Entropy ─────────────────────────────────────────────────────────────────
+
+#[test]
+fn roll_raises_difficulty_but_not_entropy() {
+    let verts = cloud(24);
+    let groups = vec![0; 24];
+    let clusters: Vec<usize> = (0..24).collect();
+    let plain = best_stage_axes_from_offsets(&verts, &groups, &clusters, &params(0.0), 6, SEED);
+    let rolled = best_stage_axes_from_offsets(&verts, &groups, &clusters, &params(ROLL), 6, SEED);
+    assert_eq!(plain.combined.roll_sensitivity, 0.0);
+    assert!(rolled.combined.roll_sensitivity > 0.0);
+    assert!(rolled.combined.roll_sensitivity < 1.0);
+    assert_eq!(rolled.axes(), plain.axes());
+    assert_eq!(rolled.combined.entropy_bits, plain.combined.entropy_bits);
+    assert!(rolled.combined.difficulty > plain.combined.difficulty);
+}
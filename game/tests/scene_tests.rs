@@ -0,0 +1,169 @@
+mod common;
+
+use cgmath::{InnerSpace, Matrix4, Vector3};
+use common::strip;
+use formosaic::level::scene::{
+    part_mesh_transforms, parts_from_meta_str, parts_to_meta_str, side_by_side, Bounds, ScenePart,
+    MAX_SCENE_MODELS,
+};
+use formosaic::level::storage::{LevelMeta, LevelRegistry};
+use formosaic::puzzle::puzzle_params::ScrambleStrategy;
+use formosaic::puzzle::scrambler::{assign_stage_groups, StageSplit};
+use formosaic::puzzle::seed::PuzzleSeed;
+
+const SEED: PuzzleSeed = PuzzleSeed::new(0x5ce7e);
+
+fn part(file: &str, x: f32, scale: f32) -> ScenePart {
+    ScenePart {
+        model_file: file.into(),
+        offset: Vector3::new(x, 0.25, -0.5),
+        scale,
+    }
+}
+
+fn meta(parts: Vec<ScenePart>) -> LevelMeta {
+    LevelMeta {
+        id: "garden".into(),
+        name: "Garden".into(),
+        author: "A".into(),
+        license: "CC0".into(),
+        source_url: "https://example.com".into(),
+        model_file: "model.glb".into(),
+        best_time_secs: None,
+        best_score: None,
+        stars: 0,
+        play_count: 0,
+        difficulty: 0.5,
+        scramble_strategy: ScrambleStrategy::PerTriangle,
+        upright: false,
+        authored: None,
+        parts,
+    }
+}
+
+// ─── Meta strings ────────────────────────────────────────────────────────────
+
+#[test]
+fn parts_round_trip() {
+    let parts = vec![part("chair.glb", 1.5, 0.8), part("lamp.fbx", -1.25, 0.5)];
+    let s = parts_to_meta_str(&parts);
+    assert_eq!(
+        s,
+        "chair.glb@1.5,0.25,-0.5*0.8;lamp.fbx@-1.25,0.25,-0.5*0.5"
+    );
+    assert_eq!(parts_from_meta_str(&s), Some(parts));
+    assert_eq!(parts_from_meta_str(""), Some(Vec::new()));
+}
+
+#[test]
+fn bad_parts_are_rejected() {
+    for s in [
+        "chair.glb",
+        "@1,0,0*1",
+        "chair.glb@1,0*1",
+        "chair.glb@1,0,0",
+        "chair.glb@1,0,0*0",
+        "chair.glb@1,0,0*-1",
+        "chair.glb@1,NaN,0*1",
+        "chair.glb@1,0,0*inf",
+        "chair.glb@1,0,0*1;",
+    ] {
+        assert_eq!(parts_from_meta_str(s), None, "{s:?}");
+    }
+    let too_many = vec![part("a.glb", 1.0, 1.0); MAX_SCENE_MODELS];
+    assert_eq!(parts_from_meta_str(&parts_to_meta_str(&too_many)), None);
+}
+
+#[test]
+fn level_meta_round_trips_the_parts() {
+    let parts = side_by_side(&["part1.glb", "part2.glb"]);
+    let back = LevelMeta::from_json(&meta(parts.clone()).to_json()).unwrap();
+    assert_eq!(back.parts, parts);
+    assert_eq!(back.model_count(), 3);
+
+    let single = meta(Vec::new()).to_json();
+    assert!(!single.contains("parts"));
+    assert_eq!(LevelMeta::from_json(&single).unwrap().model_count(), 1);
+
+    let old = r#"{"id":"abc","name":"Abc","author":"A","license":"CC0","source_url":"u","model_file":"m.glb","best_time_secs":null,"play_count":0,"difficulty":0.5}"#;
+    assert!(LevelMeta::from_json(old).unwrap().parts.is_empty());
+}
+
+// ─── Placement ───────────────────────────────────────────────────────────────
+
+#[test]
+fn placement_centres_and_scales_the_part() {
+    let main_pos = strip(3);
+    let main = Bounds::of(&[&main_pos], &[Matrix4::from_scale(2.0)]).unwrap();
+    // The part is ten times smaller and far from the origin.
+    let part_pos: Vec<f32> = strip(2).iter().map(|c| c * 0.1 + 7.0).collect();
+    let part_bounds = Bounds::of(&[&part_pos], &[]).unwrap();
+
+    let p = part("p.glb", 1.5, 0.5);
+    let placement = p.placement(main, part_bounds);
+    let placed = Bounds::of(&[&part_pos], &[placement]).unwrap();
+    let expected = main.center + p.offset * main.radius;
+    assert!((placed.center - expected).magnitude() < 1e-4);
+    assert!((placed.radius - 0.5 * main.radius).abs() < 1e-4);
+}
+
+#[test]
+fn part_mesh_transforms_fill_missing_ones() {
+    let placement = Matrix4::from_translation(Vector3::new(1.0, 2.0, 3.0));
+    let own = Matrix4::from_scale(2.0);
+    let transforms = part_mesh_transforms(&[own], 3, placement);
+    assert_eq!(transforms, vec![placement * own, placement, placement]);
+}
+
+#[test]
+fn empty_meshes_have_no_bounds() {
+    assert_eq!(Bounds::of(&[&[]], &[]), None);
+}
+
+// ─── Stage split ─────────────────────────────────────────────────────────────
+
+#[test]
+fn parts_split_one_stage_per_model() {
+    let (a, b, c) = (strip(2), strip(3), strip(1));
+    let split = StageSplit::Parts(vec![0, 0, 1]);
+    assert_eq!(split.stage_count(), 2);
+    let groups = split.groups(&[&a, &b, &c], &[], ScrambleStrategy::PerTriangle, SEED);
+    assert_eq!(groups, vec![vec![0, 0], vec![0, 0, 0], vec![1]]);
+}
+
+#[test]
+fn bands_split_matches_assign_stage_groups() {
+    let verts = strip(12);
+    let split = StageSplit::Bands(3);
+    assert_eq!(split.stage_count(), 3);
+    assert_eq!(
+        split.groups(&[&verts], &[], ScrambleStrategy::PerTriangle, SEED),
+        assign_stage_groups(&[&verts], &[], 3, ScrambleStrategy::PerTriangle, SEED)
+    );
+    assert_eq!(StageSplit::Bands(0).stage_count(), 1);
+}
+
+// ─── Registry ────────────────────────────────────────────────────────────────
+
+#[test]
+fn registry_saves_every_model_of_a_scene() {
+    let dir = std::env::temp_dir().join(format!("formosaic_scene_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let mut registry = LevelRegistry::load(&dir);
+    let scene = meta(side_by_side(&["part1.glb", "part2.glb"]));
+
+    assert!(registry
+        .save_scene(scene.clone(), b"main", &[b"one"])
+        .is_err());
+    registry
+        .save_scene(scene.clone(), b"main", &[b"one", b"two"])
+        .unwrap();
+    let paths = registry.part_paths(&scene);
+    assert_eq!(std::fs::read(&paths[0]).unwrap(), b"one");
+    assert_eq!(std::fs::read(&paths[1]).unwrap(), b"two");
+    assert_eq!(std::fs::read(registry.model_path(&scene)).unwrap(), b"main");
+
+    let reloaded = LevelRegistry::load(&dir);
+    assert_eq!(reloaded.levels[0].parts, scene.parts);
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,169 @@
+use formosaic::level::storage::LevelMeta;
+use formosaic::puzzle::hints::{HintSystem, HintTier};
+use formosaic::puzzle::puzzle_params::ScrambleStrategy;
+use formosaic::puzzle::scoring::{par_secs, score, Score, BASE_POINTS};
+
+const NO_HINTS: [u32; HintTier::COUNT] = [0; HintTier::COUNT];
+
+fn uses(tiers: &[HintTier]) -> [u32; HintTier::COUNT] {
+    let mut out = NO_HINTS;
+    for &t in tiers {
+        out[t as usize] += 1;
+    }
+    out
+}
+
+#[test]
+fn perfect_solve_earns_three_stars_and_full_points() {
+    let s = score(10.0, &NO_HINTS, 0.0, 1);
+    assert_eq!(s.points, BASE_POINTS as u32);
+    assert_eq!(s.stars, 3);
+
+    let hard = score(10.0, &NO_HINTS, 1.0, 1);
+    assert_eq!(hard.points, 2 * BASE_POINTS as u32);
+    assert_eq!(hard.stars, 3, "stars do not depend on difficulty");
+}
+
+#[test]
+fn time_over_par_costs_points() {
+    let par = par_secs(0.5, 1);
+    assert_eq!(score(par, &NO_HINTS, 0.5, 1), score(1.0, &NO_HINTS, 0.5, 1));
+    let slow = score(par * 2.0, &NO_HINTS, 0.5, 1);
+    let fast = score(par, &NO_HINTS, 0.5, 1);
+    assert_eq!(slow.points, fast.points / 2);
+    assert!(slow.stars < fast.stars);
+    assert_eq!(score(par * 10.0, &NO_HINTS, 0.5, 1).stars, 1);
+}
+
+#[test]
+fn par_grows_with_difficulty_and_stages() {
+    assert!(par_secs(1.0, 1) > par_secs(0.0, 1));
+    assert_eq!(par_secs(0.3, 3), 3.0 * par_secs(0.3, 1));
+    assert_eq!(par_secs(0.3, 0), par_secs(0.3, 1));
+}
+
+#[test]
+fn deeper_hints_cost_more() {
+    let warm = score(10.0, &uses(&[HintTier::WarmCold]), 0.5, 1);
+    let disc = score(
+        10.0,
+        &uses(&[HintTier::WarmCold, HintTier::AxisPlane]),
+        0.5,
+        1,
+    );
+    let outline = score(
+        10.0,
+        &uses(&[HintTier::WarmCold, HintTier::AxisPlane, HintTier::Outline]),
+        0.5,
+        1,
+    );
+    let full = score(10.0, &NO_HINTS, 0.5, 1);
+    assert!(full.points > warm.points);
+    assert!(warm.points > disc.points);
+    assert!(disc.points > outline.points);
+    assert_eq!(warm.stars, 3, "a single warm/cold hint is cheap");
+}
+
+#[test]
+fn ghost_snap_caps_at_one_star() {
+    let all = uses(&[
+        HintTier::WarmCold,
+        HintTier::AxisPlane,
+        HintTier::Outline,
+        HintTier::GhostSnap,
+    ]);
+    let s = score(1.0, &all, 1.0, 1);
+    assert_eq!(s.stars, 1);
+    assert!(s.points > 0);
+}
+
+#[test]
+fn hint_system_counts_tiers_reached() {
+    let mut hints = HintSystem::new();
+    hints.set_stage_count(2);
+    hints.advance();
+    hints.advance();
+    hints.next_stage();
+    hints.advance();
+    assert_eq!(
+        hints.tier_uses(),
+        uses(&[HintTier::WarmCold, HintTier::AxisPlane, HintTier::WarmCold])
+    );
+
+    for _ in 0..6 {
+        hints.advance();
+    }
+    assert_eq!(hints.tier_uses()[HintTier::GhostSnap as usize], 1);
+    assert_eq!(hints.hint_count(), 9);
+
+    hints.reset_full();
+    assert_eq!(hints.tier_uses(), NO_HINTS);
+}
+
+fn meta() -> LevelMeta {
+    LevelMeta {
+        id: "a1".to_string(),
+        name: "A".to_string(),
+        author: "test".to_string(),
+        license: "CC0".to_string(),
+        source_url: String::new(),
+        model_file: "model.glb".to_string(),
+        best_time_secs: None,
+        best_score: None,
+        stars: 0,
+        play_count: 0,
+        difficulty: 0.5,
+        scramble_strategy: ScrambleStrategy::PerTriangle,
+        upright: false,
+        authored: None,
+        parts: Vec::new(),
+    }
+}
+
+#[test]
+fn level_keeps_best_score_and_stars_separately() {
+    let mut m = meta();
+    assert!(m.record_score(
+        40.0,
+        Score {
+            points: 900,
+            stars: 2
+        }
+    ));
+    assert!(!m.record_score(
+        20.0,
+        Score {
+            points: 800,
+            stars: 3
+        }
+    ));
+    assert_eq!(m.best_score, Some(900));
+    assert_eq!(m.stars, 3);
+    assert_eq!(m.best_time_secs, Some(20.0));
+    assert_eq!(m.play_count, 2);
+}
+
+#[test]
+fn meta_json_round_trips_score() {
+    let mut m = meta();
+    m.record_score(
+        12.0,
+        Score {
+            points: 1234,
+            stars: 2,
+        },
+    );
+    let back = LevelMeta::from_json(&m.to_json()).unwrap();
+    assert_eq!(back.best_score, Some(1234));
+    assert_eq!(back.stars, 2);
+
+    // Levels saved before scoring existed.
+    let old = r#"{"id":"x","name":"X","author":"a","license":"l","source_url":"","model_file":"m.glb","best_time_secs":3.50,"play_count":1,"difficulty":0.4}"#;
+    let back = LevelMeta::from_json(old).unwrap();
+    assert_eq!(back.best_score, None);
+    assert_eq!(back.stars, 0);
+    assert_eq!(back.best_time_secs, Some(3.5));
+
+    let never = LevelMeta::from_json(&meta().to_json()).unwrap();
+    assert_eq!(never.best_score, None);
+}
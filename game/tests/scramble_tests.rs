@@ -1,5 +1,15 @@
+// The range checks below predate clippy's `manual_range_contains`.
+#![allow(clippy::manual_range_contains)]
+
 use cgmath::{Matrix4, SquareMatrix, Vector3};
+use formosaic::puzzle::entropy::best_scramble_axis_from_offsets;
+use formosaic::puzzle::puzzle_params::ScrambleStrategy;
 use formosaic::puzzle::scramble_math::{compute_scramble_offsets, lerp_positions};
+use formosaic::puzzle::scrambler::{compute_raw_offsets, make_scrambled_orbit};
+use formosaic::puzzle::seed::PuzzleSeed;
+
+const SEED: PuzzleSeed = PuzzleSeed::new(0x5eed);
+const TRI: ScrambleStrategy = ScrambleStrategy::PerTriangle;
 
 fn pos(x: f32, y: f32, z: f32) -> [f32; 3] {
     [x, y, z]
@@ -25,6 +35,7 @@ fn each_triangle_receives_same_offset_per_corner() {
         0.1,
         0.5,
         Matrix4::identity(),
+        SEED,
     );
     assert_eq!(off[0..3], off[3..6], "tri0 corner0 ≠ corner1");
     assert_eq!(off[0..3], off[6..9], "tri0 corner0 ≠ corner2");
@@ -41,6 +52,7 @@ fn different_triangles_can_have_different_offsets() {
         0.1,
         999.0,
         Matrix4::identity(),
+        SEED,
     );
     assert_ne!(off[0..3], off[9..12], "tri0 and tri1 have same offset");
 }
@@ -54,6 +66,7 @@ fn offset_along_axis_with_identity_transform() {
         0.1,
         0.5,
         Matrix4::identity(),
+        SEED,
     );
     for i in 0..off.len() / 3 {
         assert_eq!(off[i * 3], 0.0, "x at tri {}", i);
@@ -67,7 +80,7 @@ fn offset_along_axis_with_identity_transform() {
 fn offset_along_rotated_axis() {
     let rot = Matrix4::from_angle_z(cgmath::Deg(90.0));
     let verts = two_tri_quad();
-    let off = compute_scramble_offsets(verts.len(), Vector3::unit_y(), 0.1, 0.5, rot);
+    let off = compute_scramble_offsets(verts.len(), Vector3::unit_y(), 0.1, 0.5, rot, SEED);
     for i in 0..off.len() / 3 {
         assert!(
             off[i * 3 + 1].abs() < 1e-6,
@@ -93,7 +106,7 @@ fn offset_along_rotated_axis() {
 
 #[test]
 fn empty_mesh_returns_empty_offsets() {
-    let off = compute_scramble_offsets(0, Vector3::unit_y(), 0.1, 0.5, Matrix4::identity());
+    let off = compute_scramble_offsets(0, Vector3::unit_y(), 0.1, 0.5, Matrix4::identity(), SEED);
     assert!(off.is_empty());
 }
 
@@ -106,6 +119,7 @@ fn offset_range_is_respected() {
         0.3,
         0.301,
         Matrix4::identity(),
+        SEED,
     );
     for i in 0..off.len() / 3 {
         let y = off[i * 3 + 1];
@@ -164,6 +178,7 @@ fn compute_then_lerp_restores_original() {
         0.1,
         0.5,
         Matrix4::identity(),
+        SEED,
     );
     let scrambled = lerp_positions(&verts, &off, 1.0);
     assert_ne!(scrambled, verts);
@@ -180,6 +195,7 @@ fn compute_then_lerp_half_is_between() {
         0.1,
         0.5,
         Matrix4::identity(),
+        SEED,
     );
     let original = verts.clone();
     let half = lerp_positions(&verts, &off, 0.5);
@@ -197,3 +213,153 @@ fn compute_then_lerp_half_is_between() {
         );
     }
 }
+
+// ── Seeded reproducibility ─────────────────────────────────────
+
+/// A small "model": a strip of quads at varying heights so the entropy search
+/// has something non-degenerate to score.
+fn strip(quads: usize) -> Vec<f32> {
+    let mut v = Vec::new();
+    for q in 0..quads {
+        let x = q as f32;
+        let y = (q % 3) as f32 * 0.5;
+        v.extend_from_slice(&pos(x, y, -1.0));
+        v.extend_from_slice(&pos(x + 1.0, y, -1.0));
+        v.extend_from_slice(&pos(x + 1.0, y, 1.0));
+        v.extend_from_slice(&pos(x, y, -1.0));
+        v.extend_from_slice(&pos(x + 1.0, y, 1.0));
+        v.extend_from_slice(&pos(x, y, 1.0));
+    }
+    v
+}
+
+#[test]
+fn same_seed_gives_identical_offsets() {
+    let verts = strip(8);
+    let a = compute_scramble_offsets(
+        verts.len(),
+        Vector3::unit_y(),
+        0.1,
+        0.5,
+        Matrix4::identity(),
+        SEED,
+    );
+    let b = compute_scramble_offsets(
+        verts.len(),
+        Vector3::unit_y(),
+        0.1,
+        0.5,
+        Matrix4::identity(),
+        SEED,
+    );
+    let a_bits: Vec<u32> = a.iter().map(|f| f.to_bits()).collect();
+    let b_bits: Vec<u32> = b.iter().map(|f| f.to_bits()).collect();
+    assert_eq!(a_bits, b_bits);
+}
+
+#[test]
+fn different_seeds_give_different_offsets() {
+    let verts = strip(8);
+    let a = compute_scramble_offsets(
+        verts.len(),
+        Vector3::unit_y(),
+        0.1,
+        0.5,
+        Matrix4::identity(),
+        PuzzleSeed::new(1),
+    );
+    let b = compute_scramble_offsets(
+        verts.len(),
+        Vector3::unit_y(),
+        0.1,
+        0.5,
+        Matrix4::identity(),
+        PuzzleSeed::new(2),
+    );
+    assert_ne!(a, b);
+}
+
+#[test]
+fn level_id_is_part_of_the_seed() {
+    assert_eq!(
+        PuzzleSeed::for_level("7S5Snphkam", 42),
+        PuzzleSeed::for_level("7S5Snphkam", 42)
+    );
+    assert_ne!(
+        PuzzleSeed::for_level("7S5Snphkam", 42),
+        PuzzleSeed::for_level("cactus_builtin", 42)
+    );
+    assert_ne!(
+        PuzzleSeed::for_level("7S5Snphkam", 42),
+        PuzzleSeed::for_level("7S5Snphkam", 43)
+    );
+}
+
+#[test]
+fn raw_offsets_are_reproducible_per_mesh() {
+    let a_mesh = strip(4);
+    let b_mesh = strip(6);
+    let meshes: Vec<&[f32]> = vec![&a_mesh, &b_mesh];
+    let transforms = vec![Matrix4::identity(), Matrix4::from_scale(2.0)];
+    let seed = PuzzleSeed::for_level("cactus_builtin", 7);
+
+    let first = compute_raw_offsets(&meshes, &transforms, Vector3::unit_x(), 0.1, 0.5, TRI, seed);
+    let second = compute_raw_offsets(&meshes, &transforms, Vector3::unit_x(), 0.1, 0.5, TRI, seed);
+    assert_eq!(first, second);
+
+    // Each mesh has its own sub-seed: the shared prefix must not repeat.
+    assert_ne!(first[0][0..9], first[1][0..9]);
+}
+
+#[test]
+fn entropy_search_is_reproducible() {
+    let verts = strip(12);
+    let seed = PuzzleSeed::for_level("cactus_builtin", 99);
+    let a = best_scramble_axis_from_offsets(&verts, 0.1, 0.5, 8, seed);
+    let b = best_scramble_axis_from_offsets(&verts, 0.1, 0.5, 8, seed);
+
+    assert_eq!(a.axis.x.to_bits(), b.axis.x.to_bits());
+    assert_eq!(a.axis.y.to_bits(), b.axis.y.to_bits());
+    assert_eq!(a.axis.z.to_bits(), b.axis.z.to_bits());
+    assert_eq!(a.report, b.report);
+}
+
+#[test]
+fn entropy_search_depends_on_seed() {
+    let verts = strip(12);
+    let a = best_scramble_axis_from_offsets(&verts, 0.1, 0.5, 8, PuzzleSeed::new(1));
+    let b = best_scramble_axis_from_offsets(&verts, 0.1, 0.5, 8, PuzzleSeed::new(2));
+    assert_ne!(a.axis, b.axis);
+}
+
+#[test]
+fn camera_start_is_reproducible() {
+    let target = Vector3::new(0.0, 0.0, 0.0);
+    let seed = PuzzleSeed::for_level("cactus_builtin", 3);
+    let (_, a) = make_scrambled_orbit(target, 5.0, Vector3::unit_y(), seed);
+    let (_, b) = make_scrambled_orbit(target, 5.0, Vector3::unit_y(), seed);
+    assert_eq!(a.x.to_bits(), b.x.to_bits());
+    assert_eq!(a.y.to_bits(), b.y.to_bits());
+    assert_eq!(a.z.to_bits(), b.z.to_bits());
+}
+
+#[test]
+fn full_pipeline_is_reproducible_from_level_and_seed() {
+    let verts = strip(10);
+    let run = || {
+        let seed = PuzzleSeed::for_level("7S5Snphkam", 0xdecafbad);
+        let search = best_scramble_axis_from_offsets(&verts, 0.1, 0.5, 6, seed);
+        let offsets = compute_raw_offsets(
+            &[verts.as_slice()],
+            &[Matrix4::identity()],
+            search.axis,
+            0.1,
+            0.5,
+            ScrambleStrategy::PerTriangle,
+            seed,
+        );
+        let (_, start) = make_scrambled_orbit(Vector3::new(0.0, 0.0, 0.0), 4.0, search.axis, seed);
+        (search.axis, search.report, offsets, start)
+    };
+    assert_eq!(run(), run());
+}
@@ -1,5 +1,6 @@
 use cgmath::{InnerSpace, Vector3};
 use formosaic::puzzle::scrambler::make_scrambled_orbit;
+use formosaic::puzzle::seed::PuzzleSeed;
 
 fn angle_between(a: Vector3<f32>, b: Vector3<f32>) -> f32 {
     let dot = a.normalize().dot(b.normalize()).clamp(-1.0, 1.0);
@@ -12,7 +13,8 @@ fn scrambled_orbit_starts_far_from_solution_axis() {
     let distance = 10.0;
     let solution_dir = Vector3::unit_y();
 
-    let (_ctrl, camera_pos) = make_scrambled_orbit(target, distance, solution_dir);
+    let (_ctrl, camera_pos) =
+        make_scrambled_orbit(target, distance, solution_dir, PuzzleSeed::new(1));
     let camera_dir = (camera_pos - target).normalize();
 
     let angle = angle_between(camera_dir, solution_dir);
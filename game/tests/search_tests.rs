@@ -0,0 +1,130 @@
+mod common;
+
+use common::cloud;
+use formosaic::puzzle::entropy::{best_stage_axes_from_offsets, best_stage_axes_with};
+use formosaic::puzzle::puzzle_params::PuzzleParams;
+use formosaic::puzzle::search::{SearchHandle, SearchOptions};
+use formosaic::puzzle::seed::PuzzleSeed;
+
+const SEED: PuzzleSeed = PuzzleSeed::new(0x9001);
+const CANDIDATES: usize = 12;
+
+fn params(stages: usize) -> PuzzleParams {
+    PuzzleParams {
+        min_disp: 0.1,
+        max_disp: 0.5,
+        ..PuzzleParams::default_for(1.0)
+    }
+    .with_stages(stages)
+}
+
+fn threads(n: usize) -> SearchOptions {
+    SearchOptions {
+        threads: n,
+        ..SearchOptions::default()
+    }
+}
+
+// ─── Worker pool ─────────────────────────────────────────────────────────────
+
+#[test]
+fn map_returns_results_in_index_order() {
+    for n in [1, 3, 8] {
+        let squares = threads(n).map(20, |i| i * i).unwrap();
+        assert_eq!(squares, (0..20).map(|i| i * i).collect::<Vec<_>>());
+    }
+}
+
+#[test]
+fn result_does_not_depend_on_thread_count() {
+    let verts = cloud(48);
+    let groups: Vec<usize> = (0..48).map(|t| t % 2).collect();
+    let clusters: Vec<usize> = (0..48).collect();
+    let serial = best_stage_axes_with(
+        &verts,
+        &groups,
+        &clusters,
+        &params(2),
+        CANDIDATES,
+        SEED,
+        &SearchOptions::serial(),
+    )
+    .unwrap();
+    for n in [2, 5, 0] {
+        let pooled = best_stage_axes_with(
+            &verts,
+            &groups,
+            &clusters,
+            &params(2),
+            CANDIDATES,
+            SEED,
+            &threads(n),
+        )
+        .unwrap();
+        assert_eq!(pooled.axes(), serial.axes(), "{n} threads");
+        assert_eq!(pooled.stage_reports(), serial.stage_reports());
+        assert_eq!(pooled.combined, serial.combined);
+    }
+    let default =
+        best_stage_axes_from_offsets(&verts, &groups, &clusters, &params(2), CANDIDATES, SEED);
+    assert_eq!(default.axes(), serial.axes());
+}
+
+// ─── Cancellation and progress ───────────────────────────────────────────────
+
+#[test]
+fn cancelled_search_returns_nothing() {
+    let verts = cloud(16);
+    let options = threads(4);
+    options.handle.cancel();
+    let result = best_stage_axes_with(
+        &verts,
+        &[0; 16],
+        &(0..16).collect::<Vec<_>>(),
+        &params(1),
+        CANDIDATES,
+        SEED,
+        &options,
+    );
+    assert!(result.is_none());
+}
+
+#[test]
+fn cancelling_midway_stops_between_candidates() {
+    let options = SearchOptions::serial();
+    let ran = std::sync::atomic::AtomicUsize::new(0);
+    let result = options.map(10, |i| {
+        ran.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+        if i == 3 {
+            options.handle.cancel();
+        }
+    });
+    assert!(result.is_none());
+    assert_eq!(ran.into_inner(), 4);
+    assert!(options.handle.progress() < 1.0);
+}
+
+#[test]
+fn progress_counts_every_stage_candidate() {
+    let handle = SearchHandle::new();
+    assert_eq!(handle.progress(), 0.0);
+    let options = SearchOptions {
+        threads: 3,
+        handle: handle.clone(),
+        ..SearchOptions::default()
+    };
+    let verts = cloud(24);
+    let groups: Vec<usize> = (0..24).map(|t| t % 3).collect();
+    let clusters: Vec<usize> = (0..24).collect();
+    best_stage_axes_with(
+        &verts,
+        &groups,
+        &clusters,
+        &params(3),
+        CANDIDATES,
+        SEED,
+        &options,
+    )
+    .unwrap();
+    assert_eq!(handle.progress(), 1.0);
+}
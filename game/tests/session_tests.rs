@@ -0,0 +1,175 @@
+use std::collections::HashSet;
+
+use formosaic::level::daily::UtcDate;
+use formosaic::level::session::{
+    Leaderboard, LeaderboardEntry, Session, SessionKind, LEADERBOARD_SIZE,
+};
+use formosaic::level::storage::LevelMeta;
+use formosaic::puzzle::puzzle_params::ScrambleStrategy;
+use formosaic::puzzle::seed::PuzzleSeed;
+
+const SEED: PuzzleSeed = PuzzleSeed::new(0x5e55);
+const MARATHON: SessionKind = SessionKind::Marathon { levels: 3 };
+const TIME_ATTACK: SessionKind = SessionKind::TimeAttack { budget_secs: 120.0 };
+
+fn level(id: &str) -> LevelMeta {
+    LevelMeta {
+        id: id.to_string(),
+        name: id.to_string(),
+        author: "test".to_string(),
+        license: "CC0".to_string(),
+        source_url: String::new(),
+        model_file: "model.glb".to_string(),
+        best_time_secs: None,
+        best_score: None,
+        stars: 0,
+        play_count: 0,
+        difficulty: 0.5,
+        scramble_strategy: ScrambleStrategy::PerTriangle,
+        upright: false,
+        authored: None,
+        parts: Vec::new(),
+    }
+}
+
+fn library(n: usize) -> Vec<LevelMeta> {
+    (0..n).map(|i| level(&format!("lvl{i}"))).collect()
+}
+
+fn entry(kind: SessionKind, solved: u32, time_secs: f32) -> LeaderboardEntry {
+    LeaderboardEntry {
+        date: UtcDate::new(2025, 3, 14),
+        kind,
+        solved,
+        time_secs,
+    }
+}
+
+// ─── Session ─────────────────────────────────────────────────────────────────
+
+#[test]
+fn marathon_plays_distinct_levels_then_stops() {
+    let mut session = Session::new(MARATHON, &library(6), SEED).unwrap();
+    let mut seen = HashSet::new();
+    for _ in 0..3 {
+        let peeked = session.peek_next();
+        let id = session.next_level().unwrap();
+        assert_eq!(peeked.as_deref(), Some(id.as_str()));
+        assert!(seen.insert(id), "level repeated");
+    }
+    assert_eq!(session.peek_next(), None);
+    assert_eq!(session.next_level(), None);
+}
+
+#[test]
+fn marathon_is_cut_to_the_library() {
+    let session = Session::new(SessionKind::Marathon { levels: 10 }, &library(4), SEED).unwrap();
+    assert_eq!(session.kind, SessionKind::Marathon { levels: 4 });
+    assert!(Session::new(MARATHON, &[], SEED).is_none());
+}
+
+#[test]
+fn order_depends_only_on_seed_and_library() {
+    let mut shuffled = library(8);
+    shuffled.reverse();
+    let mut a = Session::new(TIME_ATTACK, &library(8), SEED).unwrap();
+    let mut b = Session::new(TIME_ATTACK, &shuffled, SEED).unwrap();
+    for _ in 0..20 {
+        assert_eq!(a.next_level(), b.next_level());
+    }
+}
+
+#[test]
+fn time_attack_reshuffles_without_back_to_back_repeats() {
+    let mut session = Session::new(TIME_ATTACK, &library(3), SEED).unwrap();
+    let mut prev: Option<String> = None;
+    for _ in 0..30 {
+        let peeked = session.peek_next();
+        let id = session.next_level().unwrap();
+        assert_eq!(peeked, Some(id.clone()), "peek must match across reshuffles");
+        assert_ne!(prev.as_ref(), Some(&id));
+        prev = Some(id);
+    }
+}
+
+#[test]
+fn clocks_and_end_conditions() {
+    let mut marathon = Session::new(MARATHON, &library(5), SEED).unwrap();
+    for t in [30.0, 45.0] {
+        marathon.record_solve(t);
+    }
+    assert_eq!(marathon.total_secs(10.0), 85.0);
+    assert_eq!(marathon.remaining_secs(10.0), None);
+    assert!(!marathon.is_over(1e6));
+    marathon.record_solve(20.0);
+    assert!(marathon.is_over(0.0));
+    let result = marathon.result(UtcDate::new(2025, 1, 1), 0.0);
+    assert_eq!((result.solved, result.time_secs), (3, 95.0));
+
+    let mut attack = Session::new(TIME_ATTACK, &library(5), SEED).unwrap();
+    attack.record_solve(100.0);
+    assert_eq!(attack.remaining_secs(5.0), Some(15.0));
+    assert!(!attack.is_over(19.0));
+    assert!(attack.is_over(25.0));
+    let result = attack.result(UtcDate::new(2025, 1, 1), 25.0);
+    assert_eq!((result.solved, result.time_secs), (1, 120.0));
+}
+
+// ─── Leaderboard ─────────────────────────────────────────────────────────────
+
+#[test]
+fn entries_round_trip_through_json() {
+    for e in [entry(MARATHON, 3, 312.4), entry(TIME_ATTACK, 7, 120.0)] {
+        assert_eq!(LeaderboardEntry::from_json(&e.to_json()), Some(e));
+    }
+    assert!(LeaderboardEntry::from_json(r#"{"date":"2025-03-14","mode":"zen"}"#).is_none());
+}
+
+#[test]
+fn ranks_by_solved_then_time_per_kind() {
+    let mut board = Leaderboard::in_memory();
+    assert_eq!(board.record(entry(TIME_ATTACK, 4, 120.0)), Some(1));
+    assert_eq!(board.record(entry(MARATHON, 3, 200.0)), Some(1));
+    assert_eq!(board.record(entry(MARATHON, 3, 150.0)), Some(1));
+    assert_eq!(board.record(entry(MARATHON, 3, 250.0)), Some(3));
+    assert_eq!(board.record(entry(TIME_ATTACK, 6, 120.0)), Some(1));
+    // A tie goes behind the older result.
+    assert_eq!(board.record(entry(TIME_ATTACK, 4, 120.0)), Some(3));
+
+    let times: Vec<f32> = board.top(MARATHON).iter().map(|e| e.time_secs).collect();
+    assert_eq!(times, [150.0, 200.0, 250.0]);
+    // Another marathon length is a separate board.
+    let five = SessionKind::Marathon { levels: 5 };
+    assert_eq!(board.record(entry(five, 5, 900.0)), Some(1));
+    assert_eq!(board.top(five).len(), 1);
+}
+
+#[test]
+fn keeps_only_the_best_results() {
+    let mut board = Leaderboard::in_memory();
+    for i in 0..LEADERBOARD_SIZE {
+        board.record(entry(MARATHON, 3, 100.0 + i as f32));
+    }
+    board.record(entry(TIME_ATTACK, 1, 120.0));
+    assert_eq!(board.record(entry(MARATHON, 3, 500.0)), None);
+    assert_eq!(board.record(entry(MARATHON, 3, 50.0)), Some(1));
+    let top = board.top(MARATHON);
+    assert_eq!(top.len(), LEADERBOARD_SIZE);
+    assert_eq!(top.last().unwrap().time_secs, 100.0 + (LEADERBOARD_SIZE - 2) as f32);
+    assert_eq!(board.top(TIME_ATTACK).len(), 1);
+}
+
+#[test]
+fn persists_to_the_data_dir() {
+    let dir = std::env::temp_dir().join(format!("formosaic_board_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    let mut board = Leaderboard::load(&dir);
+    assert!(board.entries.is_empty());
+    board.record(entry(MARATHON, 3, 200.0));
+    board.record(entry(TIME_ATTACK, 5, 120.0));
+    board.record(entry(MARATHON, 3, 150.0));
+
+    let reloaded = Leaderboard::load(&dir);
+    assert_eq!(reloaded.entries, board.entries);
+    let _ = std::fs::remove_dir_all(&dir);
+}
@@ -0,0 +1,197 @@
+use cgmath::Vector3;
+use formosaic::formosaic::LoadJob;
+use formosaic::level::share::{valid_level_id, ShareCode, GROUP_LEN, MAX_SHARE_STAGES};
+use formosaic::puzzle::authoring::AuthoredPuzzle;
+use formosaic::puzzle::puzzle_params::{ScrambleProjection, ScrambleStrategy};
+use formosaic::puzzle::seed::PuzzleSeed;
+
+fn code() -> ShareCode {
+    ShareCode {
+        level_id: "7S5Snphkam".to_string(),
+        seed: PuzzleSeed::new(0x9e37_79b9_7f4a_7c15),
+        stages: 3,
+        projection: ScrambleProjection::Anamorphic,
+        roll: true,
+        strategy: ScrambleStrategy::PerTriangle,
+        upright: false,
+        authored: None,
+    }
+}
+
+/// A code for an authored per-cluster upright level.
+fn authored_code() -> ShareCode {
+    let seed = PuzzleSeed::new(0x0123_4567_89ab_cdef);
+    ShareCode {
+        seed,
+        stages: 1,
+        projection: ScrambleProjection::Parallel,
+        roll: false,
+        strategy: ScrambleStrategy::PerCluster { max_triangles: 48 },
+        upright: true,
+        authored: Some(AuthoredPuzzle {
+            axis: Vector3::new(0.6, 0.0, 0.8),
+            min_disp: 0.02,
+            max_disp: 0.125,
+            seed,
+        }),
+        ..code()
+    }
+}
+
+#[test]
+fn round_trips_every_mode() {
+    for stages in [1, 2, MAX_SHARE_STAGES] {
+        for projection in [ScrambleProjection::Parallel, ScrambleProjection::Anamorphic] {
+            for roll in [false, true] {
+                let c = ShareCode {
+                    stages,
+                    projection,
+                    roll,
+                    ..code()
+                };
+                assert_eq!(ShareCode::decode(&c.encode().unwrap()), Some(c));
+            }
+        }
+    }
+}
+
+#[test]
+fn round_trips_every_strategy_and_authored_puzzle() {
+    for strategy in [
+        ScrambleStrategy::PerTriangle,
+        ScrambleStrategy::PerMesh,
+        ScrambleStrategy::PerCluster { max_triangles: 0 },
+        ScrambleStrategy::PerCluster {
+            max_triangles: u16::MAX as usize,
+        },
+    ] {
+        for upright in [false, true] {
+            let c = ShareCode {
+                strategy,
+                upright,
+                ..code()
+            };
+            assert_eq!(ShareCode::decode(&c.encode().unwrap()), Some(c));
+        }
+    }
+    let c = authored_code();
+    assert_eq!(ShareCode::decode(&c.encode().unwrap()), Some(c));
+}
+
+#[test]
+fn format_is_stable() {
+    // Codes already handed around must keep decoding to the same puzzle.
+    let text = "08S0-05BW-99ZV-JY9Q-KRVN-6DAK-DSR6-GTV1-DNY0-R";
+    assert_eq!(code().encode().as_deref(), Some(text));
+    assert_eq!(ShareCode::decode(text), Some(code()));
+    // A version 1 code did not say which strategy or authored puzzle it
+    // was played with, so it is refused rather than guessed at.
+    assert_eq!(
+        ShareCode::decode("04S1-AZ2A-FYWQ-JDWY-6X9K-AMVE-E1M6-PRBD-35W0"),
+        None
+    );
+}
+
+#[test]
+fn is_short_and_grouped() {
+    let text = code().encode().unwrap();
+    assert!(text.len() < 48, "{text}");
+    assert!(text.split('-').all(|g| g.len() <= GROUP_LEN));
+    assert!(text.bytes().all(|b| b == b'-' || b.is_ascii_alphanumeric()));
+}
+
+#[test]
+fn forgives_case_spacing_and_lookalikes() {
+    let text = code().encode().unwrap();
+    let retyped = format!("  {}  ", text.replace('-', " ").to_lowercase());
+    assert_eq!(ShareCode::decode(&retyped), Some(code()));
+    let lookalikes = text.replace('0', "O").replace('1', "l");
+    assert_eq!(ShareCode::decode(&lookalikes), Some(code()));
+}
+
+#[test]
+fn checksum_catches_typos() {
+    let text = code().encode().unwrap();
+    for (i, c) in text.char_indices().filter(|(_, c)| *c != '-') {
+        let typo = if c == 'X' { 'Y' } else { 'X' };
+        let mut bad = text.clone();
+        bad.replace_range(i..i + 1, &typo.to_string());
+        assert_eq!(ShareCode::decode(&bad), None, "{bad}");
+    }
+    // Swapped neighbours.
+    let mut chars: Vec<char> = text.chars().filter(|&c| c != '-').collect();
+    let i = chars.windows(2).position(|w| w[0] != w[1]).unwrap();
+    chars.swap(i, i + 1);
+    assert_eq!(
+        ShareCode::decode(&chars.into_iter().collect::<String>()),
+        None
+    );
+}
+
+#[test]
+fn rejects_other_versions_and_junk() {
+    let bytes = code().to_bytes().unwrap();
+    assert_eq!(ShareCode::from_bytes(&bytes), Some(code()));
+    let mut other = bytes.clone();
+    other[0] += 1;
+    assert!(ShareCode::from_bytes(&other).is_none());
+    let mut unknown_strategy = bytes;
+    unknown_strategy[2] = 9;
+    assert!(ShareCode::from_bytes(&unknown_strategy).is_none());
+
+    let text = code().encode().unwrap();
+    assert!(ShareCode::decode(&text[..text.len() - 3]).is_none());
+    assert!(ShareCode::decode("").is_none());
+    assert!(ShareCode::decode("not a code!").is_none());
+    assert!(ShareCode::decode("0000-0000").is_none());
+}
+
+#[test]
+fn refuses_ids_that_are_not_safe_paths() {
+    assert!(valid_level_id("cactus_builtin"));
+    assert!(valid_level_id("7S5Snphkam"));
+    for bad in ["", "../etc", "a/b", "a b", "ü"] {
+        assert!(!valid_level_id(bad), "{bad}");
+        let c = ShareCode {
+            level_id: bad.to_string(),
+            ..code()
+        };
+        assert!(c.encode().is_none());
+    }
+    let too_many = ShareCode {
+        stages: MAX_SHARE_STAGES + 1,
+        ..code()
+    };
+    assert!(too_many.encode().is_none());
+    let huge_clusters = ShareCode {
+        strategy: ScrambleStrategy::PerCluster {
+            max_triangles: u16::MAX as usize + 1,
+        },
+        ..code()
+    };
+    assert!(huge_clusters.encode().is_none());
+    let mut own_seed = authored_code();
+    if let Some(p) = &mut own_seed.authored {
+        p.seed = PuzzleSeed::new(1);
+    }
+    assert!(own_seed.encode().is_none());
+}
+
+#[test]
+fn authored_level_code_loads_the_same_puzzle() {
+    // The job the sharer's authored per-cluster upright level loaded with.
+    let code = authored_code();
+    let job = LoadJob {
+        request_id: 7,
+        level_id: code.level_id.clone(),
+        seed: code.seed,
+        stages: 1,
+        strategy: ScrambleStrategy::PerCluster { max_triangles: 48 },
+        upright: true,
+        max_roll: 0.0,
+        authored: code.authored,
+        parts: Vec::new(),
+    };
+    let received = ShareCode::decode(&code.encode().unwrap()).unwrap();
+    assert_eq!(LoadJob::from_share_code(7, &received, Vec::new()), job);
+}
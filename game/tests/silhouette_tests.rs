@@ -0,0 +1,163 @@
+use cgmath::{InnerSpace, Vector3};
+use formosaic::puzzle::entropy::{
+    analyse_axis, analyse_axis_with, best_stage_axes_from_offsets, best_stage_axes_with,
+    AnalysisBackend,
+};
+use formosaic::puzzle::puzzle_params::PuzzleParams;
+use formosaic::puzzle::search::SearchOptions;
+use formosaic::puzzle::seed::PuzzleSeed;
+use formosaic::puzzle::silhouette::SolvedViews;
+
+const SEED: PuzzleSeed = PuzzleSeed::new(0x5117);
+
+/// A curved `n`×`n` quad sheet, two triangles per quad, spanning [-1,1]².
+fn sheet(n: usize) -> Vec<f32> {
+    let point = |i: usize, j: usize| {
+        let (x, y) = (
+            i as f32 / n as f32 * 2.0 - 1.0,
+            j as f32 / n as f32 * 2.0 - 1.0,
+        );
+        [x, y, 0.3 * (x * 2.0).sin() * (y * 1.5).cos()]
+    };
+    let mut v = Vec::with_capacity(n * n * 18);
+    for i in 0..n {
+        for j in 0..n {
+            let (a, b, c, d) = (
+                point(i, j),
+                point(i + 1, j),
+                point(i + 1, j + 1),
+                point(i, j + 1),
+            );
+            for p in [a, b, c, a, c, d] {
+                v.extend_from_slice(&p);
+            }
+        }
+    }
+    v
+}
+
+/// Per-triangle offsets along `axis`, amounts cycling through [0.1, 0.4].
+fn offsets_along(positions: &[f32], axis: Vector3<f32>) -> Vec<f32> {
+    let mut offsets = vec![0.0; positions.len()];
+    for tri in 0..positions.len() / 9 {
+        let d = axis * (0.1 + 0.1 * (tri % 4) as f32);
+        for corner in 0..3 {
+            let v = tri * 9 + corner * 3;
+            offsets[v..v + 3].copy_from_slice(&[d.x, d.y, d.z]);
+        }
+    }
+    offsets
+}
+
+// ─── Rasterised views ────────────────────────────────────────────────────────
+
+#[test]
+fn unscrambled_model_matches_every_view() {
+    let verts = sheet(8);
+    let dirs = [
+        Vector3::unit_z(),
+        Vector3::unit_x(),
+        Vector3::new(1.0, 1.0, 1.0),
+    ];
+    let views = SolvedViews::new(&verts, &dirs, 0.5);
+    for v in views.view_scores(&vec![0.0; verts.len()]) {
+        assert_eq!(v.iou, 1.0);
+        assert_eq!(v.edge_coherence, 1.0);
+    }
+}
+
+#[test]
+fn sliding_along_the_view_keeps_silhouette_and_edges() {
+    let verts = sheet(8);
+    let axis = Vector3::new(0.1, 0.2, 1.0).normalize();
+    let offsets = offsets_along(&verts, axis);
+    let off_axis = Vector3::new(1.0, 0.3, 0.4).normalize();
+    let views = SolvedViews::new(&verts, &[axis, -axis, off_axis], 0.5);
+    let scores = views.view_scores(&offsets);
+    for v in &scores[..2] {
+        assert!(v.iou > 0.999, "{v:?}");
+        assert!(v.edge_coherence > 0.999, "{v:?}");
+    }
+    assert!(
+        scores[2].score() < 0.9,
+        "wrong view looks solved: {:?}",
+        scores[2]
+    );
+}
+
+#[test]
+fn hidden_fragments_do_not_count() {
+    // A small triangle behind a large one, moved sideways but still
+    // hidden, changes nothing on screen.
+    let mut verts = vec![
+        -1.0, -1.0, 0.0, 1.0, -1.0, 0.0, 0.0, 1.0, 0.0, //
+        -0.2, -0.2, -0.5, 0.2, -0.2, -0.5, 0.0, 0.2, -0.5,
+    ];
+    let mut offsets = vec![0.0; 18];
+    for v in 3..6 {
+        offsets[v * 3] = 0.3;
+    }
+    let views = SolvedViews::new(&verts, &[-Vector3::unit_z()], 0.5);
+    let hidden = views.view_scores(&offsets)[0];
+    assert_eq!(hidden.score(), 1.0);
+
+    // Put the small triangle in front and the same move shows.
+    for v in 3..6 {
+        verts[v * 3 + 2] = 0.5;
+    }
+    let views = SolvedViews::new(&verts, &[-Vector3::unit_z()], 0.5);
+    assert!(views.view_scores(&offsets)[0].edge_coherence < 1.0);
+}
+
+// ─── Entropy reports ─────────────────────────────────────────────────────────
+
+#[test]
+fn silhouette_report_peaks_at_the_solution() {
+    let verts = sheet(6);
+    let axis = Vector3::new(0.2, 0.1, 1.0).normalize();
+    let offsets = offsets_along(&verts, axis);
+    let report = analyse_axis_with(AnalysisBackend::Silhouette, &verts, &offsets, axis);
+    assert!(report.peak_score > 0.95, "{report:?}");
+    assert!((0.0..=1.0).contains(&report.difficulty));
+    assert!(report.entropy_bits.is_finite());
+
+    let heuristic = analyse_axis_with(AnalysisBackend::Heuristic, &verts, &offsets, axis);
+    assert_eq!(heuristic, analyse_axis(&offsets, axis));
+}
+
+#[test]
+fn backend_is_chosen_per_search() {
+    let verts = sheet(4);
+    let tris = verts.len() / 9;
+    let groups = vec![0; tris];
+    let clusters: Vec<usize> = (0..tris).collect();
+    let params = PuzzleParams {
+        min_disp: 0.1,
+        max_disp: 0.4,
+        ..PuzzleParams::default_for(1.0)
+    };
+    let default = best_stage_axes_from_offsets(&verts, &groups, &clusters, &params, 4, SEED);
+    let heuristic = best_stage_axes_with(
+        &verts,
+        &groups,
+        &clusters,
+        &params,
+        4,
+        SEED,
+        &SearchOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(default.axes(), heuristic.axes());
+    assert_eq!(default.combined, heuristic.combined);
+
+    let run = || {
+        let options = SearchOptions {
+            backend: AnalysisBackend::Silhouette,
+            ..SearchOptions::default()
+        };
+        best_stage_axes_with(&verts, &groups, &clusters, &params, 4, SEED, &options).unwrap()
+    };
+    let silhouette = run();
+    assert_eq!(silhouette.axes(), run().axes(), "not reproducible");
+    assert!(silhouette.combined.peak_score > 0.9);
+}
@@ -0,0 +1,138 @@
+mod common;
+
+use cgmath::{InnerSpace, Quaternion, Rad, Rotation3, Vector3};
+use common::cloud;
+use formosaic::level::storage::LevelMeta;
+use formosaic::puzzle::entropy::{best_stage_axes_from_offsets, UPRIGHT_AXIS_ELEVATION_RAD};
+use formosaic::puzzle::hints::HintSystem;
+use formosaic::puzzle::puzzle_params::{PuzzleParams, ScrambleStrategy};
+use formosaic::puzzle::scrambler::{
+    camera_roll, upright_up, ScrambleStage, ScrambleState, UPRIGHT_TOLERANCE_RAD,
+};
+use formosaic::puzzle::seed::PuzzleSeed;
+
+const SEED: PuzzleSeed = PuzzleSeed::new(0x0b);
+
+fn params(upright: bool) -> PuzzleParams {
+    PuzzleParams {
+        min_disp: 0.1,
+        max_disp: 0.5,
+        ..PuzzleParams::default_for(1.0)
+    }
+    .with_upright(upright)
+}
+
+fn state(upright: bool) -> ScrambleState {
+    ScrambleState {
+        stages: vec![ScrambleStage {
+            solution_dir: -Vector3::unit_z(),
+            solution_eye: None,
+            solution_up: None,
+            scramble_offsets: vec![vec![]],
+        }],
+        current_stage: 0,
+        params: params(upright),
+        original_positions: vec![vec![]],
+        seed: SEED,
+        pose: None,
+    }
+}
+
+/// Camera up looking along `fwd`, rolled by `roll` from upright.
+fn rolled_up(fwd: Vector3<f32>, roll: f32) -> Vector3<f32> {
+    Quaternion::from_axis_angle(fwd.normalize(), Rad(-roll)) * upright_up(fwd)
+}
+
+// ─── Solve check ─────────────────────────────────────────────────────────────
+
+#[test]
+fn rolled_up_reads_back_its_roll() {
+    let fwd = Vector3::new(0.3, -0.4, -0.8).normalize();
+    for roll in [-2.0f32, -0.3, 0.0, 0.5, 2.5] {
+        let measured = camera_roll(fwd, rolled_up(fwd, roll));
+        assert!((measured - roll).abs() < 1e-4, "{roll} read as {measured}");
+    }
+}
+
+#[test]
+fn upright_level_rejects_rolled_cameras() {
+    let fwd = -Vector3::unit_z();
+    let sc = state(true);
+    assert!(sc.is_upright(fwd, Vector3::unit_y()));
+    assert!(sc.is_upright(fwd, rolled_up(fwd, UPRIGHT_TOLERANCE_RAD * 0.9)));
+    assert!(!sc.is_upright(fwd, rolled_up(fwd, UPRIGHT_TOLERANCE_RAD * 1.1)));
+    assert!(!sc.is_upright(fwd, -Vector3::unit_y()), "upside down");
+    // The opposite pole, upright, still counts.
+    assert!(sc.is_upright(-fwd, Vector3::unit_y()));
+}
+
+#[test]
+fn other_levels_accept_any_roll() {
+    let fwd = -Vector3::unit_z();
+    let sc = state(false);
+    assert!(sc.is_upright(fwd, -Vector3::unit_y()));
+    assert_eq!(sc.upright_roll(fwd, -Vector3::unit_y()), None);
+}
+
+// ─── Axis choice ─────────────────────────────────────────────────────────────
+
+#[test]
+fn upright_axes_stay_near_the_horizon() {
+    let verts = cloud(24);
+    let groups: Vec<usize> = (0..24).map(|i| i % 3).collect();
+    let clusters: Vec<usize> = (0..24).collect();
+    let max_y = UPRIGHT_AXIS_ELEVATION_RAD.sin() + 1e-5;
+    for s in 0..8 {
+        let params = params(true).with_stages(3);
+        let search =
+            best_stage_axes_from_offsets(&verts, &groups, &clusters, &params, 6, SEED.derive(s));
+        for axis in search.axes() {
+            assert!(axis.y.abs() <= max_y, "seed {s}: axis {axis:?} too steep");
+        }
+    }
+}
+
+// ─── Roll cue ────────────────────────────────────────────────────────────────
+
+#[test]
+fn roll_cue_needs_a_hint_and_a_real_roll() {
+    let fwd = -Vector3::unit_z();
+    let mut hints = HintSystem::new();
+    hints.set_upright_roll(Some(2.0));
+    assert_eq!(hints.update(0.016, fwd, fwd).roll_cue, None, "no hint yet");
+
+    hints.advance();
+    assert_eq!(hints.update(0.016, fwd, fwd).roll_cue, Some(2.0));
+    hints.set_upright_roll(Some(UPRIGHT_TOLERANCE_RAD * 0.5));
+    assert_eq!(hints.update(0.016, fwd, fwd).roll_cue, None);
+    hints.set_upright_roll(None);
+    assert_eq!(hints.update(0.016, fwd, fwd).roll_cue, None);
+}
+
+// ─── Level metadata ──────────────────────────────────────────────────────────
+
+#[test]
+fn upright_flag_round_trips_and_defaults_off() {
+    let meta = LevelMeta {
+        id: "abc".into(),
+        name: "Abc".into(),
+        author: "A".into(),
+        license: "CC0".into(),
+        source_url: "https://example.com".into(),
+        model_file: "model.glb".into(),
+        best_time_secs: None,
+        best_score: None,
+        stars: 0,
+        play_count: 0,
+        difficulty: 0.5,
+        scramble_strategy: ScrambleStrategy::PerTriangle,
+        upright: true,
+        authored: None,
+        parts: Vec::new(),
+    };
+    let back = LevelMeta::from_json(&meta.to_json()).unwrap();
+    assert!(back.upright);
+
+    let old = r#"{"id":"abc","name":"Abc","author":"A","license":"CC0","source_url":"u","model_file":"m.glb","best_time_secs":null,"play_count":0,"difficulty":0.5}"#;
+    assert!(!LevelMeta::from_json(old).unwrap().upright);
+}